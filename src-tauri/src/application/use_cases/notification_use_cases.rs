@@ -45,6 +45,7 @@ impl CreateNotificationRequest {
             url: self.url,
             tags: self.tags,
             custom_data: self.custom_data,
+            ..Default::default()
         };
 
         (self.title, self.content, self.priority, metadata)
@@ -0,0 +1,175 @@
+use crate::domain::{entities::Attachment, services::AttachmentService};
+use crate::presentation::dtos::ValidationError;
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachmentDownload {
+    pub attachment: Attachment,
+    /// Base64 encoded content
+    pub data: String,
+    pub from_cache: bool,
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_attachments(
+    notification_id: String,
+    attachment_service: tauri::State<'_, Arc<dyn AttachmentService>>,
+) -> Result<Vec<Attachment>, ValidationError> {
+    attachment_service
+        .get_attachments(parse_id(&notification_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// The content of an attachment, downloaded from its service unless it is
+/// in the local cache
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_attachment(
+    notification_id: String,
+    attachment_id: String,
+    attachment_service: tauri::State<'_, Arc<dyn AttachmentService>>,
+) -> Result<AttachmentDownload, ValidationError> {
+    let content = attachment_service
+        .download_attachment(parse_id(&notification_id)?, parse_id(&attachment_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+
+    Ok(AttachmentDownload {
+        attachment: content.attachment,
+        data: BASE64_STANDARD.encode(&content.data),
+        from_cache: content.from_cache,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::{AttachmentContent, MockAttachmentService};
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_download_attachment_encodes_content() {
+        let attachment = Attachment::new(
+            "logo.png".to_string(),
+            "image/png".to_string(),
+            Some(4),
+            "https://example.com/logo.png".to_string(),
+        );
+        let attachment_id = attachment.id;
+
+        let mut service = MockAttachmentService::new();
+        service
+            .expect_download_attachment()
+            .withf(move |_, id| *id == attachment_id)
+            .returning(move |_, _| {
+                Ok(AttachmentContent {
+                    attachment: attachment.clone(),
+                    data: vec![0x89, b'P', b'N', b'G'],
+                    from_cache: true,
+                })
+            });
+        let service = Arc::new(service) as Arc<dyn AttachmentService>;
+
+        let download = download_attachment(
+            Uuid::new_v4().to_string(),
+            attachment_id.to_string(),
+            create_test_state(service.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(download.data, "iVBORw==");
+        assert!(download.from_cache);
+
+        assert!(download_attachment(
+            "not-a-uuid".to_string(),
+            attachment_id.to_string(),
+            create_test_state(service),
+        )
+        .await
+        .is_err());
+    }
+    #[tokio::test]
+    async fn test_download_attachment_through_the_service_bridge() {
+        use crate::domain::entities::{
+            Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+            ServiceType,
+        };
+        use crate::domain::repositories::DynNotificationRepository;
+        use crate::domain::services::integrations::{
+            manager::IntegrationManager, service_bridge::ServiceBridge,
+        };
+        use crate::domain::services::{
+            DefaultAttachmentService, MockIntegrationService, MockNotificationService,
+        };
+        use crate::infrastructure::repositories::{
+            FileAttachmentStore, SqliteNotificationRepository,
+        };
+
+        let attachment = Attachment::new(
+            "notes.txt".to_string(),
+            "text/plain".to_string(),
+            Some(5),
+            "messages/1/attachments/2".to_string(),
+        );
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let mut notification = Notification::new(
+            "Notes".to_string(),
+            "Attached".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Google,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                attachments: vec![attachment.clone()],
+                ..Default::default()
+            },
+        );
+        notifications.save(&mut notification).await.unwrap();
+
+        // Wired as in `run`: the bridge finds the integration of the source
+        let mut google = MockIntegrationService::new();
+        google
+            .expect_service_type()
+            .return_const(ServiceType::Google);
+        google
+            .expect_download_attachment()
+            .withf(|_, attachment| attachment.remote_ref == "messages/1/attachments/2")
+            .times(1)
+            .returning(|_, _| Ok(b"hello".to_vec()));
+        let integration_manager = Arc::new(IntegrationManager::new());
+        integration_manager.add_service(Arc::new(google)).await;
+        let bridge = Arc::new(ServiceBridge::new(
+            integration_manager,
+            Arc::new(MockNotificationService::new()),
+        ));
+        let directory = tempfile::tempdir().unwrap();
+        let service = Arc::new(
+            DefaultAttachmentService::new(
+                notifications,
+                Arc::new(FileAttachmentStore::new(directory.path()).unwrap()),
+            )
+            .with_downloader(bridge),
+        ) as Arc<dyn AttachmentService>;
+
+        let download = download_attachment(
+            notification.id.to_string(),
+            attachment.id.to_string(),
+            create_test_state(service),
+        )
+        .await
+        .unwrap();
+        assert_eq!(download.data, BASE64_STANDARD.encode(b"hello"));
+        assert!(!download.from_cache);
+        assert_eq!(download.attachment.text.as_deref(), Some("hello"));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::domain::services::{CoalescingConfig, NotificationCoalescer};
+use crate::presentation::dtos::ValidationError;
+use std::sync::Arc;
+
+#[tauri::command]
+pub async fn get_coalescing_config(
+    coalescer: tauri::State<'_, Arc<NotificationCoalescer>>,
+) -> Result<CoalescingConfig, ValidationError> {
+    Ok(coalescer.config())
+}
+
+/// Saves which notifications are merged and for how long, applied to the
+/// next notifications
+#[tauri::command]
+pub async fn save_coalescing_config(
+    config: CoalescingConfig,
+    coalescer: tauri::State<'_, Arc<NotificationCoalescer>>,
+) -> Result<CoalescingConfig, ValidationError> {
+    coalescer
+        .save_config(config)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::{
+        SqliteNotificationRepository, SqliteSettingsRepository,
+    };
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_coalescing_commands() {
+        let coalescer = Arc::new(
+            NotificationCoalescer::from_settings(
+                Arc::new(SqliteNotificationRepository::new(":memory:").unwrap()),
+                Arc::new(SqliteSettingsRepository::new(":memory:").unwrap()),
+            )
+            .await
+            .unwrap(),
+        );
+
+        let invalid = CoalescingConfig {
+            window_minutes: -5,
+            ..CoalescingConfig::default()
+        };
+        assert!(
+            save_coalescing_config(invalid, create_test_state(coalescer.clone()))
+                .await
+                .is_err()
+        );
+
+        let config = CoalescingConfig {
+            window_minutes: 10,
+            event_types: vec!["PushEvent".to_string()],
+            ..CoalescingConfig::default()
+        };
+        save_coalescing_config(config.clone(), create_test_state(coalescer.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_coalescing_config(create_test_state(coalescer))
+                .await
+                .unwrap(),
+            config
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::domain::{
+    entities::{Contact, ContactIdentity, ContactPreferences},
+    services::ContactService,
+};
+use crate::presentation::dtos::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveContactRequest {
+    /// Updates the existing contact when set, creates a new one otherwise
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub identities: Vec<ContactIdentity>,
+    #[serde(default)]
+    pub vip: bool,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub preferences: ContactPreferences,
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn get_contacts(
+    contact_service: tauri::State<'_, Arc<dyn ContactService>>,
+) -> Result<Vec<Contact>, ValidationError> {
+    contact_service
+        .get_contacts()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_contact(
+    contact_id: String,
+    contact_service: tauri::State<'_, Arc<dyn ContactService>>,
+) -> Result<Contact, ValidationError> {
+    contact_service
+        .get_contact(parse_id(&contact_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn save_contact(
+    request: SaveContactRequest,
+    contact_service: tauri::State<'_, Arc<dyn ContactService>>,
+) -> Result<Contact, ValidationError> {
+    let mut contact = match &request.id {
+        Some(id) => contact_service
+            .get_contact(parse_id(id)?)
+            .await
+            .map_err(|e| ValidationError::from_message(&e.to_string()))?,
+        None => Contact::new(String::new(), Vec::new()),
+    };
+
+    contact.name = request.name;
+    contact.identities = request.identities;
+    contact.vip = request.vip;
+    contact.notes = request.notes;
+    contact.preferences = request.preferences;
+
+    contact_service
+        .save_contact(contact)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_contact(
+    contact_id: String,
+    contact_service: tauri::State<'_, Arc<dyn ContactService>>,
+) -> Result<(), ValidationError> {
+    contact_service
+        .delete_contact(parse_id(&contact_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Links two contacts found to be the same person, keeping `target_id`
+#[tauri::command(rename_all = "snake_case")]
+pub async fn merge_contacts(
+    target_id: String,
+    other_id: String,
+    contact_service: tauri::State<'_, Arc<dyn ContactService>>,
+) -> Result<Contact, ValidationError> {
+    contact_service
+        .merge_contacts(parse_id(&target_id)?, parse_id(&other_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{entities::NotificationSource, services::MockContactService};
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_save_contact_creates_and_validates() {
+        let mut service = MockContactService::new();
+        service
+            .expect_save_contact()
+            .withf(|contact| contact.name == "Grace" && contact.vip)
+            .returning(Ok);
+        let service = Arc::new(service) as Arc<dyn ContactService>;
+
+        let contact = save_contact(
+            SaveContactRequest {
+                id: None,
+                name: "Grace".to_string(),
+                identities: vec![ContactIdentity {
+                    source: NotificationSource::Email,
+                    handle: "grace@example.com".to_string(),
+                }],
+                vip: true,
+                notes: None,
+                preferences: ContactPreferences::default(),
+            },
+            create_test_state(service.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(contact.identities.len(), 1);
+
+        assert!(merge_contacts(
+            "bad".to_string(),
+            contact.id.to_string(),
+            create_test_state(service)
+        )
+        .await
+        .is_err());
+    }
+}
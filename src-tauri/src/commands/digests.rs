@@ -0,0 +1,103 @@
+use crate::domain::{
+    entities::{Digest, DigestSettings},
+    services::DigestService,
+};
+use crate::presentation::dtos::ValidationError;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_DIGEST_LIMIT: usize = 20;
+
+#[tauri::command]
+pub async fn get_digest_settings(
+    digest_service: tauri::State<'_, Arc<dyn DigestService>>,
+) -> Result<DigestSettings, ValidationError> {
+    digest_service
+        .get_settings()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Saves the schedule, scope and length of the digests
+#[tauri::command]
+pub async fn save_digest_settings(
+    settings: DigestSettings,
+    digest_service: tauri::State<'_, Arc<dyn DigestService>>,
+) -> Result<DigestSettings, ValidationError> {
+    digest_service
+        .save_settings(settings)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Generates a digest of the configured window ending now, outside the schedule
+#[tauri::command]
+pub async fn generate_digest(
+    digest_service: tauri::State<'_, Arc<dyn DigestService>>,
+) -> Result<Digest, ValidationError> {
+    digest_service
+        .generate_digest(Utc::now())
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn get_digests(
+    limit: Option<usize>,
+    digest_service: tauri::State<'_, Arc<dyn DigestService>>,
+) -> Result<Vec<Digest>, ValidationError> {
+    digest_service
+        .get_digests(limit.unwrap_or(DEFAULT_DIGEST_LIMIT))
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_digest(
+    digest_id: String,
+    digest_service: tauri::State<'_, Arc<dyn DigestService>>,
+) -> Result<Digest, ValidationError> {
+    let id =
+        Uuid::parse_str(&digest_id).map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    digest_service
+        .get_digest(id)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{error::DomainError, services::MockDigestService};
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_digest_commands() {
+        let mut service = MockDigestService::new();
+        service
+            .expect_save_settings()
+            .returning(|_| Err(DomainError::ValidationError("Unknown timezone".into())));
+        service
+            .expect_get_digests()
+            .withf(|limit| *limit == DEFAULT_DIGEST_LIMIT)
+            .returning(|_| Ok(vec![]));
+        let service = Arc::new(service) as Arc<dyn DigestService>;
+
+        assert!(save_digest_settings(
+            DigestSettings::default(),
+            create_test_state(service.clone())
+        )
+        .await
+        .is_err());
+        assert!(get_digests(None, create_test_state(service.clone()))
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(
+            get_digest("not-a-uuid".to_string(), create_test_state(service))
+                .await
+                .is_err()
+        );
+    }
+}
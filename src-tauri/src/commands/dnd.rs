@@ -0,0 +1,113 @@
+use crate::domain::{
+    entities::DndSettings,
+    services::{DndStatus, DoNotDisturb},
+};
+use crate::presentation::dtos::ValidationError;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+
+#[tauri::command]
+pub async fn get_dnd_status(
+    dnd: tauri::State<'_, Arc<DoNotDisturb>>,
+) -> Result<DndStatus, ValidationError> {
+    dnd.status(Utc::now())
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Saves the quiet windows, timezone, threshold and VIP senders
+#[tauri::command]
+pub async fn save_dnd_settings(
+    settings: DndSettings,
+    dnd: tauri::State<'_, Arc<DoNotDisturb>>,
+) -> Result<DndStatus, ValidationError> {
+    dnd.save_settings(settings)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    get_dnd_status(dnd).await
+}
+
+/// Turns Do Not Disturb on for `duration_minutes`, until `until`, or until
+/// turned off; turning it off releases the held notifications
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_dnd_manual(
+    enabled: bool,
+    duration_minutes: Option<i64>,
+    until: Option<DateTime<Utc>>,
+    dnd: tauri::State<'_, Arc<DoNotDisturb>>,
+) -> Result<DndStatus, ValidationError> {
+    let until = match duration_minutes {
+        Some(minutes) if minutes <= 0 => {
+            return Err(ValidationError::from_message(
+                "Duration must be at least one minute",
+            ))
+        }
+        Some(minutes) => Some(Utc::now() + Duration::minutes(minutes)),
+        None => until,
+    };
+    if until.is_some_and(|until| until <= Utc::now()) {
+        return Err(ValidationError::from_message(
+            "Do Not Disturb must end in the future",
+        ));
+    }
+
+    dnd.set_manual(enabled, until)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    get_dnd_status(dnd).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::QuietWindow;
+    use crate::infrastructure::repositories::SqliteDndRepository;
+    use crate::test_utils::create_test_state;
+    use chrono::{NaiveTime, Weekday};
+
+    #[tokio::test]
+    async fn test_dnd_commands() {
+        let dnd = Arc::new(DoNotDisturb::new(Arc::new(
+            SqliteDndRepository::new(":memory:").unwrap(),
+        )));
+
+        let invalid = DndSettings {
+            timezone: "Nowhere/Special".to_string(),
+            ..DndSettings::default()
+        };
+        assert!(save_dnd_settings(invalid, create_test_state(dnd.clone()))
+            .await
+            .is_err());
+
+        let settings = DndSettings {
+            windows: vec![QuietWindow {
+                days: vec![Weekday::Sat, Weekday::Sun],
+                start: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(23, 59, 0).unwrap(),
+            }],
+            ..DndSettings::default()
+        };
+        let status = save_dnd_settings(settings, create_test_state(dnd.clone()))
+            .await
+            .unwrap();
+        assert_eq!(status.settings.windows.len(), 1);
+        assert!(!status.active);
+
+        assert!(
+            set_dnd_manual(true, Some(0), None, create_test_state(dnd.clone()))
+                .await
+                .is_err()
+        );
+        let status = set_dnd_manual(true, Some(30), None, create_test_state(dnd.clone()))
+            .await
+            .unwrap();
+        assert!(status.active && status.manual);
+        assert!(status.settings.manual_until.is_some());
+
+        let status = set_dnd_manual(false, None, None, create_test_state(dnd))
+            .await
+            .unwrap();
+        assert!(!status.manual);
+        assert!(status.settings.manual_until.is_none());
+    }
+}
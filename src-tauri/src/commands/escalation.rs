@@ -0,0 +1,159 @@
+use crate::domain::{
+    entities::{EscalationPolicy, EscalationStep, RuleCondition},
+    repositories::EscalationPolicyRepository,
+};
+use crate::presentation::dtos::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveEscalationPolicyRequest {
+    /// Updates the existing policy when set, creates a new one otherwise
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// New policies go last when omitted
+    pub position: Option<i32>,
+    #[serde(default)]
+    pub conditions: Vec<RuleCondition>,
+    pub steps: Vec<EscalationStep>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn get_escalation_policies(
+    policy_repo: tauri::State<'_, Arc<dyn EscalationPolicyRepository>>,
+) -> Result<Vec<EscalationPolicy>, ValidationError> {
+    policy_repo
+        .find_all()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn save_escalation_policy(
+    request: SaveEscalationPolicyRequest,
+    policy_repo: tauri::State<'_, Arc<dyn EscalationPolicyRepository>>,
+) -> Result<EscalationPolicy, ValidationError> {
+    let policies = policy_repo
+        .find_all()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+
+    let mut policy = match &request.id {
+        Some(id) => {
+            let id = parse_id(id)?;
+            policies
+                .iter()
+                .find(|policy| policy.id == id)
+                .cloned()
+                .ok_or_else(|| ValidationError::from_message("Escalation policy not found"))?
+        }
+        None => {
+            let mut policy = EscalationPolicy::new(String::new(), Vec::new(), Vec::new());
+            policy.position = policies.iter().map(|p| p.position + 1).max().unwrap_or(0);
+            policy
+        }
+    };
+
+    policy.name = request.name;
+    policy.enabled = request.enabled;
+    policy.conditions = request.conditions;
+    policy.steps = request.steps;
+    if let Some(position) = request.position {
+        policy.position = position;
+    }
+    policy
+        .validate()
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+
+    policy_repo
+        .save(&mut policy)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    Ok(policy)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_escalation_policy(
+    policy_id: String,
+    policy_repo: tauri::State<'_, Arc<dyn EscalationPolicyRepository>>,
+) -> Result<(), ValidationError> {
+    let id = parse_id(&policy_id)?;
+    policy_repo
+        .delete(id)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{EscalationAction, NotificationSource};
+    use crate::infrastructure::repositories::SqliteEscalationPolicyRepository;
+    use crate::test_utils::create_test_state;
+
+    fn request(name: &str) -> SaveEscalationPolicyRequest {
+        SaveEscalationPolicyRequest {
+            id: None,
+            name: name.to_string(),
+            enabled: true,
+            position: None,
+            conditions: vec![RuleCondition::Source {
+                source: NotificationSource::Jira,
+            }],
+            steps: vec![EscalationStep {
+                after_minutes: 240,
+                actions: vec![EscalationAction::RaisePriority],
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_escalation_policy_commands() {
+        let repo = Arc::new(SqliteEscalationPolicyRepository::new(":memory:").unwrap())
+            as Arc<dyn EscalationPolicyRepository>;
+
+        let mut invalid = request("No steps");
+        invalid.steps.clear();
+        assert!(
+            save_escalation_policy(invalid, create_test_state(repo.clone()))
+                .await
+                .is_err()
+        );
+
+        let first = save_escalation_policy(request("First"), create_test_state(repo.clone()))
+            .await
+            .unwrap();
+        let second = save_escalation_policy(request("Second"), create_test_state(repo.clone()))
+            .await
+            .unwrap();
+        assert_eq!((first.position, second.position), (0, 1));
+
+        let mut update = request("First, renamed");
+        update.id = Some(first.id.to_string());
+        let updated = save_escalation_policy(update, create_test_state(repo.clone()))
+            .await
+            .unwrap();
+        assert_eq!(updated.id, first.id);
+        assert_eq!(updated.position, 0);
+
+        delete_escalation_policy(second.id.to_string(), create_test_state(repo.clone()))
+            .await
+            .unwrap();
+        let policies = get_escalation_policies(create_test_state(repo))
+            .await
+            .unwrap();
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].name, "First, renamed");
+    }
+}
@@ -0,0 +1,100 @@
+use crate::domain::events::{EventBus, EventEnvelope};
+use crate::domain::repositories::EventStore;
+use crate::presentation::dtos::ValidationError;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+const DEFAULT_EVENT_LOG_LIMIT: usize = 200;
+
+/// Returns every retained event published after `sequence`, oldest first.
+///
+/// The webview calls this after (re)subscribing to the `domain-event` channel
+/// with the last sequence it has seen, so nothing emitted in between is lost.
+#[tauri::command]
+pub async fn get_events_since(
+    sequence: u64,
+    event_bus: tauri::State<'_, Arc<EventBus>>,
+) -> Result<Vec<EventEnvelope>, ValidationError> {
+    Ok(event_bus.events_since(sequence).await)
+}
+
+/// Reads the persisted event log from a point in time, for audit views.
+///
+/// Pass the last returned sequence as `after_sequence` to page forward.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_event_log(
+    from: String,
+    after_sequence: Option<u64>,
+    limit: Option<usize>,
+    event_store: tauri::State<'_, Arc<dyn EventStore>>,
+) -> Result<Vec<EventEnvelope>, ValidationError> {
+    let from = DateTime::parse_from_rfc3339(&from)
+        .map_err(|e| ValidationError::from_message(&format!("Invalid timestamp: {}", e)))?
+        .with_timezone(&Utc);
+
+    event_store
+        .find_from(
+            from,
+            after_sequence.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_EVENT_LOG_LIMIT),
+        )
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::{EventPublisher, ServiceEvent};
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_get_events_since() {
+        let bus = Arc::new(EventBus::new());
+        for count in 0..3 {
+            bus.publish(ServiceEvent::sync_completed(None, count, count, 0).into())
+                .await
+                .unwrap();
+        }
+
+        let state = create_test_state(bus);
+        let events = get_events_since(1, state).await.unwrap();
+
+        let sequences: Vec<u64> = events.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_get_event_log() {
+        use crate::infrastructure::repositories::SqliteEventStore;
+
+        let store = Arc::new(SqliteEventStore::new(":memory:").unwrap()) as Arc<dyn EventStore>;
+        let bus = EventBus::persistent(store.clone()).await.unwrap();
+        for count in 0..3 {
+            bus.publish(ServiceEvent::sync_completed(None, count, count, 0).into())
+                .await
+                .unwrap();
+        }
+
+        let from = (Utc::now() - chrono::Duration::minutes(5)).to_rfc3339();
+        let page = get_event_log(
+            from.clone(),
+            Some(1),
+            Some(1),
+            create_test_state(store.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].sequence, 2);
+
+        assert!(get_event_log(
+            "yesterday".to_string(),
+            None,
+            None,
+            create_test_state(store)
+        )
+        .await
+        .is_err());
+    }
+}
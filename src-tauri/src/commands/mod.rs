@@ -1,6 +1,47 @@
+pub mod attachments;
+pub mod coalescing;
+pub mod contacts;
+pub mod digests;
+pub mod dnd;
+pub mod escalation;
+pub mod events;
+pub mod notes;
 pub mod oauth;
+pub mod responses;
+pub mod rules;
+pub mod scoring;
+pub mod statistics;
+pub mod tags;
+pub mod threads;
+pub mod views;
+pub mod webhooks;
 
+pub use attachments::{download_attachment, get_attachments};
+pub use coalescing::{get_coalescing_config, save_coalescing_config};
+pub use contacts::{delete_contact, get_contact, get_contacts, merge_contacts, save_contact};
+pub use digests::{
+    generate_digest, get_digest, get_digest_settings, get_digests, save_digest_settings,
+};
+pub use dnd::{get_dnd_status, save_dnd_settings, set_dnd_manual};
+pub use escalation::{delete_escalation_policy, get_escalation_policies, save_escalation_policy};
+pub use events::{get_event_log, get_events_since};
+pub use notes::{delete_note, get_notes, save_note, search_notes};
 pub use oauth::{
     delete_oauth_service_config, get_service_configs, handle_oauth_callback, save_oauth_config,
     start_oauth_flow,
 };
+pub use responses::{
+    cancel_response_stream, clear_conversation_memory, get_ai_provider_calls, get_ai_router_config,
+    get_conversation_memory, save_ai_router_config, stream_response,
+};
+pub use rules::{delete_rule, get_rules, reorder_rules, save_rule, test_rules};
+pub use scoring::{get_scoring_config, save_scoring_config};
+pub use statistics::get_statistics;
+pub use tags::{delete_tag, get_tags, merge_tags, rename_tag, save_tag};
+pub use threads::{
+    archive_thread, get_thread, get_thread_notifications, get_threads, mark_thread_read,
+};
+pub use views::{delete_view, get_view_notifications, get_views, save_view};
+pub use webhooks::{
+    create_webhook, delete_webhook, get_webhook_deliveries, get_webhooks, set_webhook_enabled,
+};
@@ -0,0 +1,149 @@
+use crate::domain::{
+    entities::{Note, NoteTarget},
+    services::NoteService,
+};
+use crate::presentation::dtos::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveNoteRequest {
+    /// Updates the existing note when set, creates a new one otherwise
+    pub id: Option<String>,
+    /// The note goes on the notification when set, on the thread otherwise
+    pub notification_id: Option<String>,
+    pub thread_key: Option<String>,
+    /// Markdown
+    pub body: String,
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+fn target(
+    notification_id: Option<String>,
+    thread_key: Option<String>,
+) -> Result<NoteTarget, ValidationError> {
+    match (notification_id, thread_key) {
+        (Some(id), _) => Ok(NoteTarget::Notification {
+            notification_id: parse_id(&id)?,
+        }),
+        (None, Some(thread_key)) => Ok(NoteTarget::Thread { thread_key }),
+        (None, None) => Err(ValidationError::from_message(
+            "A note needs a notification_id or a thread_key",
+        )),
+    }
+}
+
+/// Notes on a thread, or on a notification together with the notes on its thread
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_notes(
+    notification_id: Option<String>,
+    thread_key: Option<String>,
+    note_service: tauri::State<'_, Arc<dyn NoteService>>,
+) -> Result<Vec<Note>, ValidationError> {
+    let notes = match target(notification_id, thread_key)? {
+        NoteTarget::Notification { notification_id } => {
+            note_service.get_notification_notes(notification_id).await
+        }
+        target => note_service.get_notes(target).await,
+    };
+    notes.map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn save_note(
+    request: SaveNoteRequest,
+    note_service: tauri::State<'_, Arc<dyn NoteService>>,
+) -> Result<Note, ValidationError> {
+    let target = target(request.notification_id, request.thread_key)?;
+    let note = match &request.id {
+        Some(id) => {
+            let mut note = note_service
+                .get_note(parse_id(id)?)
+                .await
+                .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+            note.target = target;
+            note.body = request.body;
+            note
+        }
+        None => Note::new(target, request.body),
+    };
+
+    note_service
+        .save_note(note)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_note(
+    note_id: String,
+    note_service: tauri::State<'_, Arc<dyn NoteService>>,
+) -> Result<(), ValidationError> {
+    note_service
+        .delete_note(parse_id(&note_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Notes containing every word of the query, newest first
+#[tauri::command]
+pub async fn search_notes(
+    query: String,
+    note_service: tauri::State<'_, Arc<dyn NoteService>>,
+) -> Result<Vec<Note>, ValidationError> {
+    note_service
+        .search_notes(&query)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::MockNoteService;
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_save_note_needs_a_target() {
+        let mut service = MockNoteService::new();
+        service
+            .expect_save_note()
+            .withf(|note| {
+                note.target
+                    == NoteTarget::Thread {
+                        thread_key: "outage".to_string(),
+                    }
+            })
+            .returning(Ok);
+        let service = Arc::new(service) as Arc<dyn NoteService>;
+
+        let note = save_note(
+            SaveNoteRequest {
+                id: None,
+                notification_id: None,
+                thread_key: Some("outage".to_string()),
+                body: "Relates to INC-42".to_string(),
+            },
+            create_test_state(service.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(note.body, "Relates to INC-42");
+
+        assert!(save_note(
+            SaveNoteRequest {
+                id: None,
+                notification_id: None,
+                thread_key: None,
+                body: "Nowhere".to_string(),
+            },
+            create_test_state(service),
+        )
+        .await
+        .is_err());
+    }
+}
@@ -0,0 +1,242 @@
+use crate::domain::entities::ConversationEntry;
+use crate::domain::services::{
+    ai::{AIRouter, AIRouterConfig, AIService, ProviderCall},
+    NotificationService,
+};
+use crate::infrastructure::services::response_streams::ResponseStreams;
+use crate::presentation::dtos::ValidationError;
+use std::sync::Arc;
+use tauri::{AppHandle, Runtime};
+use uuid::Uuid;
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Starts writing a reply to the notification. The chunks arrive as
+/// `response-stream` events carrying the returned stream id.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn stream_response<R: Runtime>(
+    app: AppHandle<R>,
+    notification_id: String,
+    notification_service: tauri::State<'_, Arc<dyn NotificationService>>,
+    response_streams: tauri::State<'_, Arc<ResponseStreams>>,
+) -> Result<String, ValidationError> {
+    let notification = notification_service
+        .get_notification(parse_id(&notification_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    let stream = notification_service
+        .generate_response_stream(&notification)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+
+    Ok(response_streams.start(app, stream).to_string())
+}
+
+/// Stops a reply being written. Returns false when it had already ended.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_response_stream(
+    stream_id: String,
+    response_streams: tauri::State<'_, Arc<ResponseStreams>>,
+) -> Result<bool, ValidationError> {
+    Ok(response_streams.cancel(parse_id(&stream_id)?))
+}
+
+/// What the AI remembers of a conversation, keyed by the thread key of its
+/// notifications (`notification:<id>` for those without a thread)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_conversation_memory(
+    conversation_key: String,
+    ai_service: tauri::State<'_, Arc<dyn AIService>>,
+) -> Result<Vec<ConversationEntry>, ValidationError> {
+    ai_service
+        .conversation_memory(&conversation_key)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Makes the AI forget a conversation, the next reply starts afresh
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_conversation_memory(
+    conversation_key: String,
+    ai_service: tauri::State<'_, Arc<dyn AIService>>,
+) -> Result<(), ValidationError> {
+    ai_service
+        .clear_conversation_memory(&conversation_key)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// The latest AI calls and the provider that served each, most recent last
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_ai_provider_calls(
+    ai_router: tauri::State<'_, Arc<AIRouter>>,
+) -> Result<Vec<ProviderCall>, ValidationError> {
+    Ok(ai_router.recent_calls())
+}
+
+#[tauri::command]
+pub async fn get_ai_router_config(
+    ai_router: tauri::State<'_, Arc<AIRouter>>,
+) -> Result<AIRouterConfig, ValidationError> {
+    Ok(ai_router.config())
+}
+
+/// Saves the AI providers, which serve the calls made from then on
+#[tauri::command]
+pub async fn save_ai_router_config(
+    config: AIRouterConfig,
+    ai_router: tauri::State<'_, Arc<AIRouter>>,
+) -> Result<AIRouterConfig, ValidationError> {
+    ai_router
+        .save_config(config)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+    };
+    use crate::domain::services::ai::{AIConfig, ConversationMemories, OllamaService};
+    use crate::domain::services::MockNotificationService;
+    use crate::infrastructure::repositories::SqliteSettingsRepository;
+    use crate::infrastructure::services::response_streams::{
+        ResponseStreamEvent, RESPONSE_STREAM_CHANNEL,
+    };
+    use crate::test_utils::create_test_state;
+    use std::sync::Mutex;
+    use tauri::Listener;
+
+    #[tokio::test]
+    async fn test_stream_response_emits_chunks() {
+        let notification = Notification::new(
+            "Lunch?".to_string(),
+            "Are you free on Friday?".to_string(),
+            NotificationPriority::Low,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+
+        let mut service = MockNotificationService::new();
+        let stored = notification.clone();
+        service
+            .expect_get_notification()
+            .returning(move |_| Ok(stored.clone()));
+        service.expect_generate_response_stream().returning(|_| {
+            Ok(Box::pin(futures::stream::iter(vec![
+                Ok("Friday ".to_string()),
+                Ok("works!".to_string()),
+            ])))
+        });
+        let service = Arc::new(service) as Arc<dyn NotificationService>;
+
+        let app = tauri::test::mock_app();
+        let (done_sender, done) = tokio::sync::oneshot::channel();
+        let done_sender = Mutex::new(Some(done_sender));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        app.listen_any(RESPONSE_STREAM_CHANNEL, move |event| {
+            let event: ResponseStreamEvent = serde_json::from_str(event.payload()).unwrap();
+            let finished = matches!(event, ResponseStreamEvent::Done { .. });
+            sink.lock().unwrap().push(event);
+            if finished {
+                if let Some(sender) = done_sender.lock().unwrap().take() {
+                    let _ = sender.send(());
+                }
+            }
+        });
+
+        let stream_id = stream_response(
+            app.handle().clone(),
+            notification.id.to_string(),
+            create_test_state(service),
+            create_test_state(Arc::new(ResponseStreams::new())),
+        )
+        .await
+        .unwrap();
+        done.await.unwrap();
+
+        let stream_id = Uuid::parse_str(&stream_id).unwrap();
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[2],
+            ResponseStreamEvent::Done {
+                stream_id,
+                response: "Friday works!".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conversation_memory_commands() {
+        let service = OllamaService::new(AIConfig::default());
+        service
+            .memory
+            .add_entry("jira:OPS-1", "User", "Is OPS-1 fixed?")
+            .await
+            .unwrap();
+        let service = Arc::new(service) as Arc<dyn AIService>;
+
+        let entries =
+            get_conversation_memory("jira:OPS-1".to_string(), create_test_state(service.clone()))
+                .await
+                .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].content, "Is OPS-1 fixed?");
+
+        clear_conversation_memory("jira:OPS-1".to_string(), create_test_state(service.clone()))
+            .await
+            .unwrap();
+        assert!(
+            get_conversation_memory("jira:OPS-1".to_string(), create_test_state(service))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ai_router_config_commands() {
+        let ai_router = Arc::new(
+            AIRouter::from_settings(
+                Arc::new(SqliteSettingsRepository::new(":memory:").unwrap()),
+                Arc::new(ConversationMemories::default()),
+            )
+            .await
+            .unwrap(),
+        );
+
+        let empty = AIRouterConfig {
+            providers: Vec::new(),
+            ..AIRouterConfig::default()
+        };
+        assert!(
+            save_ai_router_config(empty, create_test_state(ai_router.clone()))
+                .await
+                .is_err()
+        );
+
+        let mut config = AIRouterConfig::default();
+        config.providers[0].config.model = "llama3.2:3b".to_string();
+        save_ai_router_config(config.clone(), create_test_state(ai_router.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_ai_router_config(create_test_state(ai_router))
+                .await
+                .unwrap(),
+            config
+        );
+    }
+}
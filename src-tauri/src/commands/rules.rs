@@ -0,0 +1,264 @@
+use crate::domain::{
+    entities::{Rule, RuleAction, RuleCondition},
+    repositories::{NotificationRepository, RuleRepository},
+    services::{evaluate_rules, RuleEvaluation},
+};
+use crate::presentation::dtos::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveRuleRequest {
+    /// Updates the existing rule when set, creates a new one otherwise
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// New rules go last when omitted
+    pub position: Option<i32>,
+    #[serde(default)]
+    pub stop_processing: bool,
+    #[serde(default)]
+    pub conditions: Vec<RuleCondition>,
+    pub actions: Vec<RuleAction>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn get_rules(
+    rule_repo: tauri::State<'_, Arc<dyn RuleRepository>>,
+) -> Result<Vec<Rule>, ValidationError> {
+    rule_repo
+        .find_all()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn save_rule(
+    request: SaveRuleRequest,
+    rule_repo: tauri::State<'_, Arc<dyn RuleRepository>>,
+) -> Result<Rule, ValidationError> {
+    let rules = rule_repo
+        .find_all()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+
+    let mut rule = match &request.id {
+        Some(id) => {
+            let id = parse_id(id)?;
+            rules
+                .iter()
+                .find(|rule| rule.id == id)
+                .cloned()
+                .ok_or_else(|| ValidationError::from_message("Rule not found"))?
+        }
+        None => {
+            let mut rule = Rule::new(String::new(), Vec::new(), Vec::new());
+            rule.position = rules.iter().map(|r| r.position + 1).max().unwrap_or(0);
+            rule
+        }
+    };
+
+    rule.name = request.name;
+    rule.enabled = request.enabled;
+    rule.stop_processing = request.stop_processing;
+    rule.conditions = request.conditions;
+    rule.actions = request.actions;
+    if let Some(position) = request.position {
+        rule.position = position;
+    }
+    rule.validate()
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+
+    rule_repo
+        .save(&mut rule)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    Ok(rule)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_rule(
+    rule_id: String,
+    rule_repo: tauri::State<'_, Arc<dyn RuleRepository>>,
+) -> Result<(), ValidationError> {
+    let id = parse_id(&rule_id)?;
+    rule_repo
+        .delete(id)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Sets the evaluation order to the order of `rule_ids`
+#[tauri::command(rename_all = "snake_case")]
+pub async fn reorder_rules(
+    rule_ids: Vec<String>,
+    rule_repo: tauri::State<'_, Arc<dyn RuleRepository>>,
+) -> Result<Vec<Rule>, ValidationError> {
+    for (position, rule_id) in rule_ids.iter().enumerate() {
+        let id = parse_id(rule_id)?;
+        let mut rule = rule_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| ValidationError::from_message(&e.to_string()))?
+            .ok_or_else(|| ValidationError::from_message("Rule not found"))?;
+        rule.position = position as i32;
+        rule_repo
+            .save(&mut rule)
+            .await
+            .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    }
+
+    rule_repo
+        .find_all()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Shows which rules would match a notification and what they would do,
+/// without changing anything. Pass `rule` to try an unsaved rule on its own.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn test_rules(
+    notification_id: String,
+    rule: Option<Rule>,
+    rule_repo: tauri::State<'_, Arc<dyn RuleRepository>>,
+    notification_repo: tauri::State<'_, Arc<dyn NotificationRepository>>,
+) -> Result<RuleEvaluation, ValidationError> {
+    let id = parse_id(&notification_id)?;
+    let notification = notification_repo
+        .find_by_id(id)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?
+        .ok_or_else(|| ValidationError::from_message("Notification not found"))?;
+
+    let rules = match rule {
+        Some(rule) => {
+            rule.validate()
+                .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+            vec![rule]
+        }
+        None => rule_repo
+            .find_all()
+            .await
+            .map_err(|e| ValidationError::from_message(&e.to_string()))?,
+    };
+
+    Ok(evaluate_rules(&rules, &notification))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+        NotificationStatus,
+    };
+    use crate::infrastructure::repositories::{SqliteNotificationRepository, SqliteRuleRepository};
+    use crate::test_utils::create_test_state;
+
+    fn archive_request(name: &str) -> SaveRuleRequest {
+        SaveRuleRequest {
+            id: None,
+            name: name.to_string(),
+            enabled: true,
+            position: None,
+            stop_processing: false,
+            conditions: vec![RuleCondition::Source {
+                source: NotificationSource::Github,
+            }],
+            actions: vec![RuleAction::SetStatus {
+                status: NotificationStatus::Archived,
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rule_commands() {
+        let rule_repo =
+            Arc::new(SqliteRuleRepository::new(":memory:").unwrap()) as Arc<dyn RuleRepository>;
+        let notification_repo = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap())
+            as Arc<dyn NotificationRepository>;
+
+        let mut invalid = archive_request("Invalid");
+        invalid.conditions = vec![RuleCondition::Title {
+            pattern: "(".to_string(),
+        }];
+        assert!(save_rule(invalid, create_test_state(rule_repo.clone()))
+            .await
+            .is_err());
+
+        let first = save_rule(
+            archive_request("First"),
+            create_test_state(rule_repo.clone()),
+        )
+        .await
+        .unwrap();
+        let second = save_rule(
+            archive_request("Second"),
+            create_test_state(rule_repo.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!((first.position, second.position), (0, 1));
+
+        let reordered = reorder_rules(
+            vec![second.id.to_string(), first.id.to_string()],
+            create_test_state(rule_repo.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(reordered[0].id, second.id);
+
+        let mut notification = Notification::new(
+            "Build failed".to_string(),
+            "CI is red".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        notification_repo.save(&mut notification).await.unwrap();
+
+        let evaluation = test_rules(
+            notification.id.to_string(),
+            None,
+            create_test_state(rule_repo.clone()),
+            create_test_state(notification_repo.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(evaluation.matched_rules.len(), 2);
+        assert_eq!(evaluation.matched_rules[0].rule_id, second.id);
+        assert_eq!(evaluation.notification.status, NotificationStatus::Archived);
+
+        // Test mode leaves the notification untouched
+        let stored = notification_repo
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, NotificationStatus::New);
+
+        delete_rule(first.id.to_string(), create_test_state(rule_repo.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_rules(create_test_state(rule_repo)).await.unwrap().len(),
+            1
+        );
+    }
+}
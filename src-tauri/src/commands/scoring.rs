@@ -0,0 +1,23 @@
+use crate::domain::services::{PriorityScorer, ScoringConfig};
+use crate::presentation::dtos::ValidationError;
+use std::sync::Arc;
+
+#[tauri::command]
+pub async fn get_scoring_config(
+    scorer: tauri::State<'_, Arc<PriorityScorer>>,
+) -> Result<ScoringConfig, ValidationError> {
+    Ok(scorer.config())
+}
+
+/// Saves the weights, mentions and keywords of the priority score, applied
+/// from the next scoring on
+#[tauri::command]
+pub async fn save_scoring_config(
+    config: ScoringConfig,
+    scorer: tauri::State<'_, Arc<PriorityScorer>>,
+) -> Result<ScoringConfig, ValidationError> {
+    scorer
+        .save_config(config)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
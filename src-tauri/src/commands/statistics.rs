@@ -0,0 +1,55 @@
+use crate::domain::{
+    entities::{NotificationStatistics, TimeBucket},
+    services::StatisticsService,
+};
+use crate::presentation::dtos::ValidationError;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, ValidationError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| ValidationError::from_message(&format!("Invalid timestamp: {}", e)))
+}
+
+/// Statistics for the notifications received from `from` until `to`, now by
+/// default, counted per day unless another `bucket` is given
+#[tauri::command]
+pub async fn get_statistics(
+    from: String,
+    to: Option<String>,
+    bucket: Option<TimeBucket>,
+    statistics_service: tauri::State<'_, Arc<dyn StatisticsService>>,
+) -> Result<NotificationStatistics, ValidationError> {
+    let from = parse_timestamp(&from)?;
+    let to = match to {
+        Some(to) => parse_timestamp(&to)?,
+        None => Utc::now(),
+    };
+
+    statistics_service
+        .get_statistics(from, to, bucket.unwrap_or(TimeBucket::Day))
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::MockStatisticsService;
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_get_statistics_rejects_invalid_timestamps() {
+        let service = Arc::new(MockStatisticsService::new()) as Arc<dyn StatisticsService>;
+
+        let result = get_statistics(
+            "last week".to_string(),
+            None,
+            None,
+            create_test_state(service),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::domain::{
+    entities::{Tag, TagSummary},
+    services::TagService,
+};
+use crate::presentation::dtos::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveTagRequest {
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+#[tauri::command]
+pub async fn get_tags(
+    tag_service: tauri::State<'_, Arc<dyn TagService>>,
+) -> Result<Vec<TagSummary>, ValidationError> {
+    tag_service
+        .get_tags()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn save_tag(
+    request: SaveTagRequest,
+    tag_service: tauri::State<'_, Arc<dyn TagService>>,
+) -> Result<Tag, ValidationError> {
+    let mut tag = Tag::new(request.name);
+    tag.color = request.color;
+    tag.description = request.description;
+
+    tag_service
+        .save_tag(tag)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rename_tag(
+    name: String,
+    new_name: String,
+    tag_service: tauri::State<'_, Arc<dyn TagService>>,
+) -> Result<TagSummary, ValidationError> {
+    tag_service
+        .rename_tag(&name, &new_name)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Replaces `source` by `target` on every notification
+#[tauri::command]
+pub async fn merge_tags(
+    source: String,
+    target: String,
+    tag_service: tauri::State<'_, Arc<dyn TagService>>,
+) -> Result<TagSummary, ValidationError> {
+    tag_service
+        .merge_tags(&source, &target)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Removes the tag from every notification, returns how many were changed
+#[tauri::command]
+pub async fn delete_tag(
+    name: String,
+    tag_service: tauri::State<'_, Arc<dyn TagService>>,
+) -> Result<usize, ValidationError> {
+    tag_service
+        .delete_tag(&name)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::MockTagService;
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_save_tag_passes_the_display_settings() {
+        let mut service = MockTagService::new();
+        service
+            .expect_save_tag()
+            .withf(|tag| tag.name == "release" && tag.color.as_deref() == Some("#1f883d"))
+            .returning(Ok);
+        let service = Arc::new(service) as Arc<dyn TagService>;
+
+        let tag = save_tag(
+            SaveTagRequest {
+                name: "release".to_string(),
+                color: Some("#1f883d".to_string()),
+                description: Some("Shipped to production".to_string()),
+            },
+            create_test_state(service),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tag.description.as_deref(), Some("Shipped to production"));
+    }
+}
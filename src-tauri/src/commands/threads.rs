@@ -0,0 +1,62 @@
+use crate::domain::{entities::Thread, services::ThreadService};
+use crate::presentation::dtos::{NotificationError, NotificationResponse};
+use std::sync::Arc;
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_threads(
+    include_archived: Option<bool>,
+    thread_service: tauri::State<'_, Arc<dyn ThreadService>>,
+) -> Result<Vec<Thread>, NotificationError> {
+    thread_service
+        .get_threads(include_archived.unwrap_or(false))
+        .await
+        .map_err(NotificationError::from)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_thread(
+    thread_key: String,
+    thread_service: tauri::State<'_, Arc<dyn ThreadService>>,
+) -> Result<Thread, NotificationError> {
+    thread_service
+        .get_thread(&thread_key)
+        .await
+        .map_err(NotificationError::from)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_thread_notifications(
+    thread_key: String,
+    thread_service: tauri::State<'_, Arc<dyn ThreadService>>,
+) -> Result<Vec<NotificationResponse>, NotificationError> {
+    let notifications = thread_service
+        .get_thread_notifications(&thread_key)
+        .await
+        .map_err(NotificationError::from)?;
+    Ok(notifications
+        .into_iter()
+        .map(NotificationResponse::from)
+        .collect())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn archive_thread(
+    thread_key: String,
+    thread_service: tauri::State<'_, Arc<dyn ThreadService>>,
+) -> Result<(), NotificationError> {
+    thread_service
+        .archive_thread(&thread_key)
+        .await
+        .map_err(NotificationError::from)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mark_thread_read(
+    thread_key: String,
+    thread_service: tauri::State<'_, Arc<dyn ThreadService>>,
+) -> Result<(), NotificationError> {
+    thread_service
+        .mark_thread_read(&thread_key)
+        .await
+        .map_err(NotificationError::from)
+}
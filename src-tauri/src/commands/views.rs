@@ -0,0 +1,136 @@
+use crate::domain::{
+    entities::{NotificationSort, NotificationStatus, RuleCondition, SavedView, SavedViewSummary},
+    services::SavedViewService,
+};
+use crate::presentation::dtos::{NotificationResponse, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveViewRequest {
+    /// Updates the existing view when set, creates a new one otherwise
+    pub id: Option<String>,
+    pub name: String,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub position: i32,
+    #[serde(default)]
+    pub statuses: Vec<NotificationStatus>,
+    pub query: Option<String>,
+    #[serde(default)]
+    pub conditions: Vec<RuleCondition>,
+    #[serde(default)]
+    pub sort: NotificationSort,
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+/// Every view with its current total and unread counts, pinned views first
+#[tauri::command]
+pub async fn get_views(
+    view_service: tauri::State<'_, Arc<dyn SavedViewService>>,
+) -> Result<Vec<SavedViewSummary>, ValidationError> {
+    view_service
+        .get_views()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn save_view(
+    request: SaveViewRequest,
+    view_service: tauri::State<'_, Arc<dyn SavedViewService>>,
+) -> Result<SavedView, ValidationError> {
+    let mut view = match &request.id {
+        Some(id) => view_service
+            .get_view(parse_id(id)?)
+            .await
+            .map_err(|e| ValidationError::from_message(&e.to_string()))?,
+        None => SavedView::new(String::new()),
+    };
+
+    view.name = request.name;
+    view.icon = request.icon;
+    view.pinned = request.pinned;
+    view.position = request.position;
+    view.statuses = request.statuses;
+    view.query = request.query;
+    view.conditions = request.conditions;
+    view.sort = request.sort;
+
+    view_service
+        .save_view(view)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_view(
+    view_id: String,
+    view_service: tauri::State<'_, Arc<dyn SavedViewService>>,
+) -> Result<(), ValidationError> {
+    view_service
+        .delete_view(parse_id(&view_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_view_notifications(
+    view_id: String,
+    view_service: tauri::State<'_, Arc<dyn SavedViewService>>,
+) -> Result<Vec<NotificationResponse>, ValidationError> {
+    let notifications = view_service
+        .get_view_notifications(parse_id(&view_id)?)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+    Ok(notifications
+        .into_iter()
+        .map(NotificationResponse::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::MockSavedViewService;
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_save_view_updates_the_existing_view() {
+        let existing = SavedView::new("Reviews".to_string());
+        let id = existing.id;
+        let mut service = MockSavedViewService::new();
+        service
+            .expect_get_view()
+            .returning(move |_| Ok(existing.clone()));
+        service
+            .expect_save_view()
+            .withf(move |view| view.id == id && view.pinned && view.sort == NotificationSort::Score)
+            .returning(Ok);
+        let service = Arc::new(service) as Arc<dyn SavedViewService>;
+
+        let view = save_view(
+            SaveViewRequest {
+                id: Some(id.to_string()),
+                name: "VIP reviews".to_string(),
+                icon: Some("star".to_string()),
+                pinned: true,
+                position: 0,
+                statuses: vec![NotificationStatus::New],
+                query: None,
+                conditions: vec![RuleCondition::Vip],
+                sort: NotificationSort::Score,
+            },
+            create_test_state(service),
+        )
+        .await
+        .unwrap();
+        assert_eq!(view.name, "VIP reviews");
+    }
+}
@@ -0,0 +1,184 @@
+use crate::domain::{
+    entities::{WebhookDelivery, WebhookSubscription},
+    repositories::WebhookRepository,
+};
+use crate::presentation::dtos::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_DELIVERY_LIMIT: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Generated when omitted; returned once in the created subscription.
+    pub secret: Option<String>,
+}
+
+/// The created subscription with its secret, which is never returned again
+#[derive(Debug, Serialize)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ValidationError> {
+    Uuid::parse_str(id).map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command]
+pub async fn create_webhook(
+    request: CreateWebhookRequest,
+    webhook_repo: tauri::State<'_, Arc<dyn WebhookRepository>>,
+) -> Result<CreatedWebhook, ValidationError> {
+    let url = reqwest::Url::parse(&request.url)
+        .map_err(|e| ValidationError::from_message(&format!("Invalid webhook URL: {}", e)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ValidationError::from_message(
+            "Webhook URL must use http or https",
+        ));
+    }
+
+    let secret = request
+        .secret
+        .filter(|secret| !secret.is_empty())
+        .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+
+    let mut subscription =
+        WebhookSubscription::new(request.url, request.event_types, secret.clone());
+    webhook_repo
+        .save(&mut subscription)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?;
+
+    Ok(CreatedWebhook {
+        subscription,
+        secret,
+    })
+}
+
+#[tauri::command]
+pub async fn get_webhooks(
+    webhook_repo: tauri::State<'_, Arc<dyn WebhookRepository>>,
+) -> Result<Vec<WebhookSubscription>, ValidationError> {
+    webhook_repo
+        .find_all()
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_webhook_enabled(
+    webhook_id: String,
+    enabled: bool,
+    webhook_repo: tauri::State<'_, Arc<dyn WebhookRepository>>,
+) -> Result<(), ValidationError> {
+    let id = parse_id(&webhook_id)?;
+    let mut subscription = webhook_repo
+        .find_by_id(id)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))?
+        .ok_or_else(|| ValidationError::from_message("Webhook not found"))?;
+
+    subscription.set_enabled(enabled);
+    webhook_repo
+        .save(&mut subscription)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_webhook(
+    webhook_id: String,
+    webhook_repo: tauri::State<'_, Arc<dyn WebhookRepository>>,
+) -> Result<(), ValidationError> {
+    let id = parse_id(&webhook_id)?;
+    webhook_repo
+        .delete(id)
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_webhook_deliveries(
+    webhook_id: String,
+    limit: Option<usize>,
+    webhook_repo: tauri::State<'_, Arc<dyn WebhookRepository>>,
+) -> Result<Vec<WebhookDelivery>, ValidationError> {
+    let id = parse_id(&webhook_id)?;
+    webhook_repo
+        .find_deliveries(id, limit.unwrap_or(DEFAULT_DELIVERY_LIMIT))
+        .await
+        .map_err(|e| ValidationError::from_message(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::SqliteWebhookRepository;
+    use crate::test_utils::create_test_state;
+
+    #[tokio::test]
+    async fn test_webhook_commands() {
+        let repo = Arc::new(SqliteWebhookRepository::new(":memory:").unwrap())
+            as Arc<dyn WebhookRepository>;
+
+        let invalid = create_webhook(
+            CreateWebhookRequest {
+                url: "ftp://example.com/hook".to_string(),
+                event_types: vec![],
+                secret: None,
+            },
+            create_test_state(repo.clone()),
+        )
+        .await;
+        assert!(invalid.is_err());
+
+        let created = create_webhook(
+            CreateWebhookRequest {
+                url: "https://example.com/hook".to_string(),
+                event_types: vec!["NotificationCreated".to_string()],
+                secret: None,
+            },
+            create_test_state(repo.clone()),
+        )
+        .await
+        .unwrap();
+        assert!(!created.secret.is_empty());
+        assert_eq!(
+            serde_json::to_value(&created).unwrap()["secret"],
+            created.secret
+        );
+        let created = created.subscription;
+        assert!(created.enabled);
+
+        set_webhook_enabled(
+            created.id.to_string(),
+            false,
+            create_test_state(repo.clone()),
+        )
+        .await
+        .unwrap();
+        let webhooks = get_webhooks(create_test_state(repo.clone())).await.unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert!(!webhooks[0].enabled);
+        assert!(serde_json::to_value(&webhooks).unwrap()[0]
+            .get("secret")
+            .is_none());
+
+        let deliveries =
+            get_webhook_deliveries(created.id.to_string(), None, create_test_state(repo.clone()))
+                .await
+                .unwrap();
+        assert!(deliveries.is_empty());
+
+        delete_webhook(created.id.to_string(), create_test_state(repo.clone()))
+            .await
+            .unwrap();
+        assert!(get_webhooks(create_test_state(repo)).await.unwrap().is_empty());
+    }
+}
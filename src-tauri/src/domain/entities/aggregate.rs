@@ -0,0 +1,124 @@
+use super::notification::Notification;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A burst of similar notifications merged into a single one.
+///
+/// The notification carrying the aggregate keeps the title of the first item
+/// with a count appended, and the content and url of the latest one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationAggregate {
+    /// Grouping key shared by every merged item
+    pub key: String,
+    /// Title of the first item, before the count was appended
+    pub base_title: String,
+    pub count: usize,
+    pub first_at: DateTime<Utc>,
+    pub last_at: DateTime<Utc>,
+    /// Every merged item, oldest first
+    pub children: Vec<AggregateChild>,
+}
+
+/// Reference to one of the items merged into an aggregate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AggregateChild {
+    pub external_id: Option<String>,
+    pub title: String,
+    pub url: Option<String>,
+    pub received_at: DateTime<Utc>,
+}
+
+impl AggregateChild {
+    fn of(notification: &Notification) -> Self {
+        Self {
+            external_id: notification.metadata.external_id.clone(),
+            title: notification.title.clone(),
+            url: notification.metadata.url.clone(),
+            received_at: notification.created_at,
+        }
+    }
+}
+
+impl Notification {
+    /// Turns this notification into the first item of an aggregate
+    pub fn start_aggregate(&mut self, key: String) {
+        self.metadata.aggregate = Some(NotificationAggregate {
+            key,
+            base_title: self.title.clone(),
+            count: 1,
+            first_at: self.created_at,
+            last_at: self.created_at,
+            children: vec![AggregateChild::of(self)],
+        });
+    }
+
+    /// Merges another item into this aggregate, starting one if needed
+    pub fn absorb(&mut self, other: &Notification) {
+        if self.metadata.aggregate.is_none() {
+            let key = other
+                .metadata
+                .aggregate
+                .as_ref()
+                .map(|aggregate| aggregate.key.clone())
+                .unwrap_or_default();
+            self.start_aggregate(key);
+        }
+        let Some(aggregate) = self.metadata.aggregate.as_mut() else {
+            return;
+        };
+
+        aggregate.count += 1;
+        aggregate.last_at = aggregate.last_at.max(other.created_at);
+        aggregate.children.push(AggregateChild::of(other));
+        self.title = format!("{} ({} events)", aggregate.base_title, aggregate.count);
+        self.content = other.content.clone();
+        self.metadata.body = other.metadata.body.clone();
+        if other.metadata.url.is_some() {
+            self.metadata.url = other.metadata.url.clone();
+        }
+        self.updated_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationMetadata, NotificationPriority, NotificationSource};
+
+    fn push(id: &str) -> Notification {
+        Notification::new(
+            format!("Push to octo/repo {}", id),
+            format!("Commit {}", id),
+            NotificationPriority::Low,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: Some(id.to_string()),
+                url: Some(format!("https://github.com/octo/repo/commit/{}", id)),
+                tags: vec!["PushEvent".to_string()],
+                custom_data: None,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_absorb_updates_the_aggregate_in_place() {
+        let mut first = push("1");
+        first.start_aggregate("github:octo/repo:PushEvent".to_string());
+        first.absorb(&push("2"));
+        first.absorb(&push("3"));
+
+        let aggregate = first.metadata.aggregate.as_ref().unwrap();
+        assert_eq!(aggregate.count, 3);
+        assert_eq!(aggregate.children.len(), 3);
+        assert_eq!(aggregate.children[2].external_id.as_deref(), Some("3"));
+        assert_eq!(first.title, "Push to octo/repo 1 (3 events)");
+        assert_eq!(first.content, "Commit 3");
+        assert_eq!(
+            first.metadata.url.as_deref(),
+            Some("https://github.com/octo/repo/commit/3")
+        );
+        // The first item's identity is kept
+        assert_eq!(first.metadata.external_id.as_deref(), Some("1"));
+    }
+}
@@ -0,0 +1,102 @@
+use super::notification::Notification;
+use super::rich_content::RichContent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Extracted text kept per attachment, enough for search and the AI
+pub const MAX_ATTACHMENT_TEXT_CHARS: usize = 20_000;
+
+/// A file attached to an email, issue or chat message. Only its reference is
+/// stored until it is downloaded, the content then stays in a local cache.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub name: String,
+    pub mime_type: String,
+    /// In bytes, when the service tells
+    pub size: Option<u64>,
+    /// URL or service path the integration downloads the content from
+    pub remote_ref: String,
+    /// Set once the content has been downloaded into the cache
+    pub cached: Option<CachedBlob>,
+    /// Text of text, Markdown and HTML attachments, once downloaded
+    pub text: Option<String>,
+}
+
+/// Content of an attachment in the local cache, addressed by its hash
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedBlob {
+    /// SHA-256 of the content, hex encoded
+    pub hash: String,
+    pub size: u64,
+    pub cached_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    pub fn new(name: String, mime_type: String, size: Option<u64>, remote_ref: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            mime_type,
+            size,
+            remote_ref,
+            cached: None,
+            text: None,
+        }
+    }
+
+    /// The text of the content when the mime type is a text format
+    pub fn extract_text(mime_type: &str, data: &[u8]) -> Option<String> {
+        let mime_type = mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        let content = String::from_utf8_lossy(data);
+        let text = match mime_type.as_str() {
+            "text/html" => RichContent::html(content).text,
+            "text/markdown" => RichContent::markdown(content).text,
+            "application/json" | "application/xml" => content.into_owned(),
+            text if text.starts_with("text/") => content.into_owned(),
+            _ => return None,
+        };
+        Some(text.chars().take(MAX_ATTACHMENT_TEXT_CHARS).collect())
+    }
+}
+
+impl Notification {
+    pub fn attachment(&self, id: Uuid) -> Option<&Attachment> {
+        self.metadata
+            .attachments
+            .iter()
+            .find(|attachment| attachment.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_is_extracted_from_text_formats_only() {
+        assert_eq!(
+            Attachment::extract_text("text/plain; charset=utf-8", b"Build log\nok").as_deref(),
+            Some("Build log\nok")
+        );
+        assert_eq!(
+            Attachment::extract_text("TEXT/HTML", b"<p>Q3 <b>report</b></p>").as_deref(),
+            Some("Q3 report")
+        );
+        assert_eq!(Attachment::extract_text("image/png", &[0x89, 0x50]), None);
+
+        let long = "a".repeat(MAX_ATTACHMENT_TEXT_CHARS + 10);
+        assert_eq!(
+            Attachment::extract_text("text/csv", long.as_bytes())
+                .unwrap()
+                .len(),
+            MAX_ATTACHMENT_TEXT_CHARS
+        );
+    }
+}
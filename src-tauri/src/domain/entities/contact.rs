@@ -0,0 +1,166 @@
+use super::notification::{Notification, NotificationSource};
+use crate::domain::error::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A person, known by one or more identities across the connected services.
+///
+/// Incoming notifications are linked to the contact owning their sender's
+/// identity, which makes the contact's VIP flag and preferences available to
+/// filters, rules, Do Not Disturb and the AI.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Contact {
+    pub id: Uuid,
+    pub name: String,
+    pub identities: Vec<ContactIdentity>,
+    pub vip: bool,
+    pub notes: Option<String>,
+    pub preferences: ContactPreferences,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// How a contact appears in one service: an email address, a GitHub login,
+/// a LinkedIn profile id, ...
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContactIdentity {
+    pub source: NotificationSource,
+    /// Compared ignoring case
+    pub handle: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContactPreferences {
+    /// Tone the AI should use when replying, e.g. "formal" or "friendly"
+    #[serde(default)]
+    pub tone: Option<String>,
+    /// Rules may send automatic replies to this contact
+    #[serde(default = "default_true")]
+    pub auto_reply: bool,
+}
+
+/// The contact a notification was sent by, kept up to date by the contact repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SenderContact {
+    pub contact_id: Uuid,
+    pub name: String,
+    pub vip: bool,
+    pub auto_reply: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ContactPreferences {
+    fn default() -> Self {
+        Self {
+            tone: None,
+            auto_reply: true,
+        }
+    }
+}
+
+impl Contact {
+    pub fn new(name: String, identities: Vec<ContactIdentity>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            identities,
+            vip: false,
+            notes: None,
+            preferences: ContactPreferences::default(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Contact name cannot be empty".to_string(),
+            ));
+        }
+        if self
+            .identities
+            .iter()
+            .any(|identity| identity.handle.trim().is_empty())
+        {
+            return Err(DomainError::ValidationError(
+                "Contact identities cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn has_identity(&self, source: &NotificationSource, handle: &str) -> bool {
+        self.identities
+            .iter()
+            .any(|identity| identity.matches(source, handle))
+    }
+
+    pub fn as_sender(&self) -> SenderContact {
+        SenderContact {
+            contact_id: self.id,
+            name: self.name.clone(),
+            vip: self.vip,
+            auto_reply: self.preferences.auto_reply,
+        }
+    }
+}
+
+impl ContactIdentity {
+    pub fn matches(&self, source: &NotificationSource, handle: &str) -> bool {
+        &self.source == source && self.handle.trim().eq_ignore_ascii_case(handle.trim())
+    }
+}
+
+impl Notification {
+    /// Whether the sender was resolved to a VIP contact
+    pub fn is_from_vip(&self) -> bool {
+        self.metadata.contact.as_ref().is_some_and(|c| c.vip)
+    }
+
+    /// Whether rules may reply automatically to the sender
+    pub fn allows_auto_reply(&self) -> bool {
+        self.metadata.contact.as_ref().is_none_or(|c| c.auto_reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identities_match_per_source_ignoring_case() {
+        let mut contact = Contact::new(
+            "Ada Lovelace".to_string(),
+            vec![
+                ContactIdentity {
+                    source: NotificationSource::Microsoft,
+                    handle: "Ada@Example.com".to_string(),
+                },
+                ContactIdentity {
+                    source: NotificationSource::Github,
+                    handle: "ada".to_string(),
+                },
+            ],
+        );
+        assert!(contact.validate().is_ok());
+
+        assert!(contact.has_identity(&NotificationSource::Microsoft, "ada@example.com"));
+        assert!(contact.has_identity(&NotificationSource::Github, "ADA"));
+        assert!(!contact.has_identity(&NotificationSource::Gitlab, "ada"));
+
+        contact.vip = true;
+        contact.preferences.auto_reply = false;
+        let sender = contact.as_sender();
+        assert!(sender.vip);
+        assert!(!sender.auto_reply);
+
+        contact.name = " ".to_string();
+        assert!(contact.validate().is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConversationEntry {
+    pub timestamp: DateTime<Utc>,
+    pub role: String,
+    pub content: String,
+}
+
+/// The latest turns of one conversation, what the AI remembers of it.
+///
+/// The oldest turns are forgotten first, once there are more than
+/// `max_entries` of them or they add up to more than `max_tokens`.
+#[derive(Debug, Clone)]
+pub struct ConversationMemory {
+    pub entries: VecDeque<ConversationEntry>,
+    pub max_entries: usize,
+    pub max_tokens: usize,
+}
+
+impl ConversationMemory {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(max_entries),
+            max_entries,
+            max_tokens: usize::MAX,
+        }
+    }
+
+    pub fn with_token_budget(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens;
+        self.forget_over_budget();
+        self
+    }
+
+    /// Restores remembered entries, oldest first
+    pub fn with_entries(mut self, entries: impl IntoIterator<Item = ConversationEntry>) -> Self {
+        self.entries.extend(entries);
+        self.forget_over_budget();
+        self
+    }
+
+    pub fn add_entry(&mut self, role: String, content: String) {
+        self.entries.push_back(ConversationEntry {
+            timestamp: Utc::now(),
+            role,
+            content,
+        });
+        self.forget_over_budget();
+    }
+
+    /// Rough token count of the remembered turns, about four characters a token
+    pub fn estimated_tokens(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| entry.content.chars().count().div_ceil(4))
+            .sum()
+    }
+
+    /// The latest entry is always kept, however long it is
+    fn forget_over_budget(&mut self) {
+        while self.entries.len() > 1
+            && (self.entries.len() > self.max_entries || self.estimated_tokens() > self.max_tokens)
+        {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn get_context(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("{}({}): {}", entry.role, entry.timestamp, entry.content))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_turns_are_forgotten_over_budget() {
+        let mut memory = ConversationMemory::new(10).with_token_budget(4);
+        memory.add_entry("User".to_string(), "Ship it?".to_string());
+        memory.add_entry("Assistant".to_string(), "Yes".to_string());
+        assert_eq!(memory.entries.len(), 2);
+        assert_eq!(memory.estimated_tokens(), 3);
+
+        memory.add_entry("User".to_string(), "Today?".to_string());
+        assert_eq!(
+            memory
+                .entries
+                .iter()
+                .map(|e| e.content.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Yes", "Today?"]
+        );
+
+        // A single turn over budget is still remembered
+        memory.add_entry("User".to_string(), "x".repeat(100));
+        assert_eq!(memory.entries.len(), 1);
+    }
+}
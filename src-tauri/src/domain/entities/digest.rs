@@ -0,0 +1,234 @@
+use super::notification::{Notification, NotificationPriority, NotificationSource};
+use crate::domain::{
+    error::{DomainError, DomainResult},
+    services::snooze::parse_timezone,
+};
+use chrono::{DateTime, Datelike, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Source of the notifications that surface digests
+pub const DIGEST_SOURCE: &str = "Digest";
+
+/// How notifications are gathered before being summarized
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestGrouping {
+    Source,
+    Thread,
+}
+
+/// When digests are generated and what goes into them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DigestSettings {
+    pub enabled: bool,
+    /// IANA timezone `time` is expressed in
+    pub timezone: String,
+    /// Local time of day the digest is generated at, on each of `days`
+    pub time: NaiveTime,
+    pub days: Vec<Weekday>,
+    /// How far back each digest looks
+    pub window_hours: u32,
+    pub group_by: DigestGrouping,
+    /// Only these sources, every source when empty
+    pub sources: Vec<NotificationSource>,
+    /// Only notifications with one of these tags, every notification when empty
+    pub tags: Vec<String>,
+    /// Groups beyond this are counted but not summarized
+    pub max_groups: usize,
+    /// Notifications listed per group in the prompt and the fallback summary
+    pub max_items_per_group: usize,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A summary of the notifications received over a period, most urgent first
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Digest {
+    pub id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub group_by: DigestGrouping,
+    pub sections: Vec<DigestSection>,
+    pub notification_count: usize,
+    /// Groups left out by `max_groups`
+    pub omitted_groups: usize,
+    /// Some groups were summarized without the AI because it was unavailable
+    pub used_fallback: bool,
+    /// The notification surfacing this digest
+    pub notification_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DigestSection {
+    pub urgency: NotificationPriority,
+    pub groups: Vec<DigestGroup>,
+}
+
+/// The notifications of one source or thread, summarized together
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DigestGroup {
+    /// Source name or thread key
+    pub key: String,
+    pub title: String,
+    pub summary: String,
+    pub suggested_actions: Vec<String>,
+    pub notification_ids: Vec<Uuid>,
+    pub ai_generated: bool,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timezone: "UTC".to_string(),
+            time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            window_hours: 24,
+            group_by: DigestGrouping::Source,
+            sources: Vec::new(),
+            tags: Vec::new(),
+            max_groups: 10,
+            max_items_per_group: 5,
+            last_run_at: None,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl DigestSettings {
+    pub fn validate(&self) -> DomainResult<()> {
+        parse_timezone(&self.timezone)?;
+        if self.window_hours == 0 {
+            return Err(DomainError::ValidationError(
+                "Digest window must be at least one hour".to_string(),
+            ));
+        }
+        if self.max_groups == 0 || self.max_items_per_group == 0 {
+            return Err(DomainError::ValidationError(
+                "Digest length limits must be at least one".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether today's digest is due and has not been generated yet
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let Ok(timezone) = parse_timezone(&self.timezone) else {
+            return false;
+        };
+
+        let local = now.with_timezone(&timezone);
+        if !self.days.contains(&local.weekday()) {
+            return false;
+        }
+        let Some(scheduled) = timezone
+            .from_local_datetime(&local.date_naive().and_time(self.time))
+            .earliest()
+            .map(|time| time.with_timezone(&Utc))
+        else {
+            return false;
+        };
+
+        now >= scheduled && self.last_run_at.is_none_or(|last| last < scheduled)
+    }
+
+    /// Whether the notification belongs in a digest with this scope
+    pub fn includes(&self, notification: &Notification) -> bool {
+        if notification.is_digest() {
+            return false;
+        }
+        let source_matches =
+            self.sources.is_empty() || self.sources.contains(&notification.metadata.source);
+        let tag_matches = self.tags.is_empty()
+            || notification
+                .metadata
+                .tags
+                .iter()
+                .any(|tag| self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+        source_matches && tag_matches
+    }
+}
+
+impl Notification {
+    pub fn is_digest(&self) -> bool {
+        matches!(&self.metadata.source, NotificationSource::Custom(source) if source == DIGEST_SOURCE)
+    }
+}
+
+impl Digest {
+    /// Plain text rendering used as the content of the digest notification
+    pub fn render(&self) -> String {
+        let mut text = format!(
+            "{} notifications between {} and {}\n",
+            self.notification_count,
+            self.period_start.format("%Y-%m-%d %H:%M UTC"),
+            self.period_end.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        for section in &self.sections {
+            text.push_str(&format!("\n## {:?}\n", section.urgency));
+            for group in &section.groups {
+                text.push_str(&format!(
+                    "\n### {} ({})\n{}\n",
+                    group.title,
+                    group.notification_ids.len(),
+                    group.summary
+                ));
+                for action in &group.suggested_actions {
+                    text.push_str(&format!("- {}\n", action));
+                }
+            }
+        }
+
+        if self.omitted_groups > 0 {
+            text.push_str(&format!("\nand {} more groups\n", self.omitted_groups));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_due_once_per_scheduled_day() {
+        let mut settings = DigestSettings {
+            enabled: true,
+            timezone: "Europe/Paris".to_string(),
+            ..DigestSettings::default()
+        };
+        assert!(settings.validate().is_ok());
+
+        // Friday 2025-01-10, 07:59 and 08:00 in Paris
+        assert!(!settings.is_due(utc("2025-01-10T06:59:00Z")));
+        assert!(settings.is_due(utc("2025-01-10T07:00:00Z")));
+
+        settings.last_run_at = Some(utc("2025-01-10T07:00:00Z"));
+        assert!(!settings.is_due(utc("2025-01-10T15:00:00Z")));
+        // Saturday is not a digest day, Monday is
+        assert!(!settings.is_due(utc("2025-01-11T09:00:00Z")));
+        assert!(settings.is_due(utc("2025-01-13T07:30:00Z")));
+
+        settings.enabled = false;
+        assert!(!settings.is_due(utc("2025-01-13T07:30:00Z")));
+
+        settings.max_groups = 0;
+        assert!(settings.validate().is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use super::notification::{Notification, NotificationPriority};
+use crate::domain::{
+    error::{DomainError, DomainResult},
+    services::snooze::parse_timezone,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Do Not Disturb settings: recurring quiet windows plus a manual switch.
+///
+/// While DND is active, notifications below `threshold` are held: they raise
+/// no alert and trigger no auto-response until DND ends. Critical
+/// notifications and VIP senders always get through.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DndSettings {
+    /// Turns the weekly windows on, the manual switch works either way
+    pub enabled: bool,
+    /// IANA timezone the windows are expressed in, e.g. "Europe/Paris"
+    pub timezone: String,
+    pub windows: Vec<QuietWindow>,
+    pub manual_enabled: bool,
+    /// The manual switch turns itself off at this time, never when unset
+    pub manual_until: Option<DateTime<Utc>>,
+    /// Lowest priority that still gets through
+    pub threshold: NotificationPriority,
+    /// Senders that always get through, compared ignoring case. VIP
+    /// contacts get through too.
+    pub vip_senders: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A weekly quiet period. A window ending before it starts runs past
+/// midnight, so `days` are the days it starts on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuietWindow {
+    pub days: Vec<Weekday>,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl Default for DndSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timezone: "UTC".to_string(),
+            windows: Vec::new(),
+            manual_enabled: false,
+            manual_until: None,
+            threshold: NotificationPriority::High,
+            vip_senders: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl DndSettings {
+    pub fn validate(&self) -> DomainResult<()> {
+        parse_timezone(&self.timezone)?;
+        for window in &self.windows {
+            if window.days.is_empty() {
+                return Err(DomainError::ValidationError(
+                    "Quiet window must have at least one day".to_string(),
+                ));
+            }
+            if window.start == window.end {
+                return Err(DomainError::ValidationError(
+                    "Quiet window cannot start and end at the same time".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_manual_active(&self, now: DateTime<Utc>) -> bool {
+        self.manual_enabled && self.manual_until.is_none_or(|until| now < until)
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        if self.is_manual_active(now) {
+            return true;
+        }
+        if !self.enabled {
+            return false;
+        }
+        // Unknown timezones are rejected when saving
+        let Ok(timezone) = parse_timezone(&self.timezone) else {
+            return false;
+        };
+
+        let local = now.with_timezone(&timezone);
+        let (today, time) = (local.weekday(), local.time());
+        let yesterday = (local.date_naive() - Duration::days(1)).weekday();
+        self.windows.iter().any(|window| {
+            if window.start < window.end {
+                window.days.contains(&today) && window.start <= time && time < window.end
+            } else {
+                (window.days.contains(&today) && time >= window.start)
+                    || (window.days.contains(&yesterday) && time < window.end)
+            }
+        })
+    }
+
+    pub fn bypasses(&self, notification: &Notification) -> bool {
+        // Critical ranks highest, so it always reaches the threshold
+        if notification.priority.rank() >= self.threshold.rank() || notification.is_from_vip() {
+            return true;
+        }
+        notification.sender().is_some_and(|sender| {
+            self.vip_senders
+                .iter()
+                .any(|vip| vip.eq_ignore_ascii_case(&sender))
+        })
+    }
+
+    /// Whether the notification should be held if it arrived at `now`
+    pub fn holds(&self, notification: &Notification, now: DateTime<Utc>) -> bool {
+        self.is_active(now) && !self.bypasses(notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationMetadata, NotificationSource, SenderContact};
+    use serde_json::json;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn nights() -> DndSettings {
+        DndSettings {
+            enabled: true,
+            timezone: "Europe/Paris".to_string(),
+            windows: vec![QuietWindow {
+                days: vec![Weekday::Fri],
+                start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+            }],
+            vip_senders: vec!["Boss@Example.com".to_string()],
+            ..DndSettings::default()
+        }
+    }
+
+    fn email(priority: NotificationPriority, from: &str) -> Notification {
+        Notification::new(
+            "Hello".to_string(),
+            "Content".to_string(),
+            priority,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: Some(json!({ "from": from })),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_windows_cross_midnight_in_the_timezone() {
+        let settings = nights();
+        assert!(settings.validate().is_ok());
+
+        // Friday 2025-01-10, 21:30 and 23:00 in Paris
+        assert!(!settings.is_active(utc("2025-01-10T20:30:00Z")));
+        assert!(settings.is_active(utc("2025-01-10T22:00:00Z")));
+        // Saturday 06:59 and 07:00 in Paris
+        assert!(settings.is_active(utc("2025-01-11T05:59:00Z")));
+        assert!(!settings.is_active(utc("2025-01-11T06:00:00Z")));
+        // Saturday night is not a quiet night
+        assert!(!settings.is_active(utc("2025-01-11T22:00:00Z")));
+
+        let disabled = DndSettings {
+            enabled: false,
+            ..nights()
+        };
+        assert!(!disabled.is_active(utc("2025-01-10T22:00:00Z")));
+    }
+
+    #[test]
+    fn test_manual_switch_expires() {
+        let now = Utc::now();
+        let mut settings = DndSettings {
+            manual_enabled: true,
+            manual_until: Some(now + Duration::hours(1)),
+            ..DndSettings::default()
+        };
+        assert!(settings.is_active(now));
+        assert!(!settings.is_active(now + Duration::hours(2)));
+
+        settings.manual_until = None;
+        assert!(settings.is_active(now + Duration::days(30)));
+    }
+
+    #[test]
+    fn test_critical_and_vip_bypass() {
+        let quiet = utc("2025-01-10T23:00:00Z");
+        let settings = nights();
+
+        assert!(settings.holds(&email(NotificationPriority::Medium, "a@example.com"), quiet));
+        assert!(!settings.holds(&email(NotificationPriority::High, "a@example.com"), quiet));
+        assert!(!settings.holds(
+            &email(NotificationPriority::Critical, "a@example.com"),
+            quiet
+        ));
+        assert!(!settings.holds(&email(NotificationPriority::Low, "boss@example.com"), quiet));
+        let mut from_vip = email(NotificationPriority::Low, "ceo@example.com");
+        from_vip.metadata.contact = Some(SenderContact {
+            contact_id: uuid::Uuid::new_v4(),
+            name: "CEO".to_string(),
+            vip: true,
+            auto_reply: true,
+        });
+        assert!(!settings.holds(&from_vip, quiet));
+
+        let strict = DndSettings {
+            threshold: NotificationPriority::Critical,
+            ..nights()
+        };
+        assert!(strict.holds(&email(NotificationPriority::High, "a@example.com"), quiet));
+        assert!(!strict.holds(
+            &email(NotificationPriority::Critical, "a@example.com"),
+            quiet
+        ));
+
+        let invalid = DndSettings {
+            timezone: "Mars/Olympus".to_string(),
+            ..nights()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
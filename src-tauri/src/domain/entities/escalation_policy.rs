@@ -0,0 +1,233 @@
+use super::notification::Notification;
+use super::rule::RuleCondition;
+use crate::domain::error::{DomainError, DomainResult};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What to do with notifications left `ActionRequired` for too long, such as
+/// "after 4h raise the priority and alert again, after 24h forward it".
+///
+/// A notification follows the first enabled policy, in ascending `position`,
+/// whose conditions all match. A policy without conditions matches every
+/// notification.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EscalationPolicy {
+    pub id: Uuid,
+    pub name: String,
+    pub enabled: bool,
+    pub position: i32,
+    pub conditions: Vec<RuleCondition>,
+    /// Ordered by `after_minutes`
+    pub steps: Vec<EscalationStep>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EscalationStep {
+    /// Time spent `ActionRequired` before the step runs
+    pub after_minutes: u32,
+    pub actions: Vec<EscalationAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EscalationAction {
+    /// Moves the priority one level up, Critical stays Critical
+    RaisePriority,
+    /// Alerts the user again, unless Do Not Disturb holds the notification
+    Realert,
+    Forward {
+        to: String,
+    },
+}
+
+/// How far a notification went through its escalation policy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EscalationState {
+    /// When the notification became `ActionRequired`
+    pub since: DateTime<Utc>,
+    /// The policy followed, chosen at the first step
+    pub policy_id: Option<Uuid>,
+    pub steps_taken: usize,
+    /// Recipients the next step was already forwarded to, so a step retried
+    /// after a failed forward does not reach them twice
+    #[serde(default)]
+    pub forwarded: Vec<String>,
+    /// Failed forward attempts of the next step, which is taken without the
+    /// failing recipients once they reach the limit
+    #[serde(default)]
+    pub forward_failures: u32,
+}
+
+impl EscalationState {
+    pub fn started(since: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            policy_id: None,
+            steps_taken: 0,
+            forwarded: Vec::new(),
+            forward_failures: 0,
+        }
+    }
+}
+
+impl EscalationPolicy {
+    pub fn new(name: String, conditions: Vec<RuleCondition>, steps: Vec<EscalationStep>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            enabled: true,
+            position: 0,
+            conditions,
+            steps,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Checks the policy can run: it has a name and steps in increasing
+    /// delays, each with actions, and its patterns compile
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Escalation policy name cannot be empty".to_string(),
+            ));
+        }
+        if self.steps.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Escalation policy must have at least one step".to_string(),
+            ));
+        }
+
+        for condition in &self.conditions {
+            condition.validate()?;
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            if step.actions.is_empty() {
+                return Err(DomainError::ValidationError(format!(
+                    "Escalation step {} has no actions",
+                    index + 1
+                )));
+            }
+            if index > 0 && step.after_minutes <= self.steps[index - 1].after_minutes {
+                return Err(DomainError::ValidationError(
+                    "Escalation steps must have increasing delays".to_string(),
+                ));
+            }
+            for action in &step.actions {
+                if let EscalationAction::Forward { to } = action {
+                    if to.trim().is_empty() {
+                        return Err(DomainError::ValidationError(
+                            "Forward recipient cannot be empty".to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, notification: &Notification) -> bool {
+        self.enabled
+            && self
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(notification))
+    }
+
+    /// Indexes of the steps not taken yet whose delay has passed at `now`
+    pub fn due_steps(&self, state: &EscalationState, now: DateTime<Utc>) -> std::ops::Range<usize> {
+        let start = state.steps_taken.min(self.steps.len());
+        let end = start
+            + self.steps[start..]
+                .iter()
+                .take_while(|step| {
+                    state.since + Duration::minutes(i64::from(step.after_minutes)) <= now
+                })
+                .count();
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationPriority, NotificationSource};
+
+    fn policy() -> EscalationPolicy {
+        EscalationPolicy::new(
+            "Unanswered email".to_string(),
+            vec![RuleCondition::Source {
+                source: NotificationSource::Email,
+            }],
+            vec![
+                EscalationStep {
+                    after_minutes: 4 * 60,
+                    actions: vec![EscalationAction::RaisePriority, EscalationAction::Realert],
+                },
+                EscalationStep {
+                    after_minutes: 24 * 60,
+                    actions: vec![EscalationAction::Forward {
+                        to: "backup@example.com".to_string(),
+                    }],
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_due_steps_follow_the_time_spent_action_required() {
+        let policy = policy();
+        assert!(policy.validate().is_ok());
+
+        let since = Utc::now();
+        let mut state = EscalationState::started(since);
+        assert_eq!(policy.due_steps(&state, since + Duration::hours(3)), 0..0);
+        assert_eq!(policy.due_steps(&state, since + Duration::hours(5)), 0..1);
+        // Steps missed while the app was closed all run
+        assert_eq!(policy.due_steps(&state, since + Duration::days(2)), 0..2);
+
+        state.steps_taken = 1;
+        assert_eq!(policy.due_steps(&state, since + Duration::hours(5)), 1..1);
+        state.steps_taken = 2;
+        assert_eq!(policy.due_steps(&state, since + Duration::days(2)), 2..2);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut unordered = policy();
+        unordered.steps.reverse();
+        assert!(unordered.validate().is_err());
+
+        let mut empty_step = policy();
+        empty_step.steps[0].actions.clear();
+        assert!(empty_step.validate().is_err());
+
+        let mut no_recipient = policy();
+        no_recipient.steps[1].actions = vec![EscalationAction::Forward {
+            to: " ".to_string(),
+        }];
+        assert!(no_recipient.validate().is_err());
+
+        let mut no_steps = policy();
+        no_steps.steps.clear();
+        assert!(no_steps.validate().is_err());
+
+        assert_eq!(
+            serde_json::to_value(EscalationAction::RaisePriority).unwrap(),
+            serde_json::json!({ "type": "raise_priority" })
+        );
+        assert_eq!(
+            NotificationPriority::Critical.raised(),
+            NotificationPriority::Critical
+        );
+        assert_eq!(
+            NotificationPriority::Low.raised(),
+            NotificationPriority::Medium
+        );
+    }
+}
@@ -1,12 +1,63 @@
+pub mod aggregate;
+pub mod attachment;
+pub mod contact;
+pub mod conversation_memory;
+pub mod digest;
+pub mod dnd;
+pub mod escalation_policy;
+pub mod note;
 pub mod notification;
+pub mod priority_score;
+pub mod rich_content;
+pub mod rule;
+pub mod saved_view;
 pub mod service_config;
+pub mod statistics;
+pub mod tag;
+pub mod thread;
+pub mod webhook;
+
+pub use aggregate::{AggregateChild, NotificationAggregate};
+
+pub use attachment::{Attachment, CachedBlob, MAX_ATTACHMENT_TEXT_CHARS};
+
+pub use contact::{Contact, ContactIdentity, ContactPreferences, SenderContact};
+
+pub use conversation_memory::{ConversationEntry, ConversationMemory};
+
+pub use digest::{Digest, DigestGroup, DigestGrouping, DigestSection, DigestSettings};
+
+pub use dnd::{DndSettings, QuietWindow};
+
+pub use escalation_policy::{EscalationAction, EscalationPolicy, EscalationState, EscalationStep};
+
+pub use note::{Note, NoteTarget};
 
 pub use notification::{
     Notification, NotificationMetadata, NotificationPriority, NotificationSource,
     NotificationStatus,
 };
 
+pub use priority_score::{PriorityScore, ScoreFactor, ScoreFactorKind};
+
+pub use rich_content::{BodyFormat, RichContent};
+
+pub use rule::{Rule, RuleAction, RuleCondition};
+
+pub use saved_view::{
+    NotificationSort, SavedView, SavedViewSummary, NEEDS_REPLY_VIEW_ID, SNOOZED_VIEW_ID,
+    WAITING_ON_OTHERS_VIEW_ID, WAITING_TAG,
+};
+
 pub use service_config::{
     ApiKeyConfig, AuthConfig, AuthType, BasicAuthConfig, CustomAuthConfig, OAuth2Config,
     ServiceConfig, ServiceEndpoints, ServiceType,
 };
+
+pub use statistics::{AiStatistics, BucketStatistics, NotificationStatistics, TimeBucket};
+
+pub use tag::{Tag, TagSummary};
+
+pub use thread::Thread;
+
+pub use webhook::{WebhookDelivery, WebhookSubscription};
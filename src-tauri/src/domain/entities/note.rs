@@ -0,0 +1,113 @@
+use super::notification::Notification;
+use crate::domain::error::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A private note the user keeps on a notification or a whole thread, such as
+/// "waiting on Alice" or "relates to INC-42"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Note {
+    pub id: Uuid,
+    pub target: NoteTarget,
+    /// Markdown
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What a note is attached to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NoteTarget {
+    Notification { notification_id: Uuid },
+    Thread { thread_key: String },
+}
+
+impl Note {
+    pub fn new(target: NoteTarget, body: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            target,
+            body,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.body.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Note cannot be empty".to_string(),
+            ));
+        }
+        if let NoteTarget::Thread { thread_key } = &self.target {
+            if thread_key.trim().is_empty() {
+                return Err(DomainError::ValidationError(
+                    "Thread key cannot be empty".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the note is on the notification or on its thread
+    pub fn applies_to(&self, notification: &Notification) -> bool {
+        match &self.target {
+            NoteTarget::Notification { notification_id } => *notification_id == notification.id,
+            NoteTarget::Thread { thread_key } => *thread_key == notification.thread_key(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationMetadata, NotificationPriority, NotificationSource};
+
+    #[test]
+    fn test_notes_apply_to_their_notification_and_thread() {
+        let notification = Notification::new(
+            "Re: outage".to_string(),
+            "Still failing".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                thread_key: Some("outage".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let on_thread = Note::new(
+            NoteTarget::Thread {
+                thread_key: "outage".to_string(),
+            },
+            "Relates to INC-42".to_string(),
+        );
+        let elsewhere = Note::new(
+            NoteTarget::Notification {
+                notification_id: Uuid::new_v4(),
+            },
+            "Waiting on Alice".to_string(),
+        );
+        assert!(on_thread.applies_to(&notification));
+        assert!(!elsewhere.applies_to(&notification));
+
+        assert!(Note::new(on_thread.target.clone(), " ".to_string())
+            .validate()
+            .is_err());
+        assert!(Note::new(
+            NoteTarget::Thread {
+                thread_key: String::new()
+            },
+            "Text".to_string()
+        )
+        .validate()
+        .is_err());
+    }
+}
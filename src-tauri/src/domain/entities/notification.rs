@@ -1,3 +1,9 @@
+use super::aggregate::NotificationAggregate;
+use super::attachment::Attachment;
+use super::contact::SenderContact;
+use super::escalation_policy::EscalationState;
+use super::priority_score::PriorityScore;
+use super::rich_content::RichContent;
 use crate::infrastructure::repositories::cached_repository::CachedEntity;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -12,6 +18,29 @@ pub enum NotificationPriority {
     Critical,
 }
 
+impl NotificationPriority {
+    /// Position in the Low < Medium < High < Critical ordering
+    pub fn rank(&self) -> u8 {
+        match self {
+            NotificationPriority::Low => 0,
+            NotificationPriority::Medium => 1,
+            NotificationPriority::High => 2,
+            NotificationPriority::Critical => 3,
+        }
+    }
+
+    /// The next priority up, Critical stays Critical
+    pub fn raised(&self) -> Self {
+        match self {
+            NotificationPriority::Low => NotificationPriority::Medium,
+            NotificationPriority::Medium => NotificationPriority::High,
+            NotificationPriority::High | NotificationPriority::Critical => {
+                NotificationPriority::Critical
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum NotificationStatus {
     New,
@@ -20,10 +49,12 @@ pub enum NotificationStatus {
     ActionRequired,
     ActionTaken,
     Deleted,
+    Snoozed,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum NotificationSource {
+    #[default]
     Email,
     Github,
     Gitlab,
@@ -49,13 +80,30 @@ impl fmt::Display for NotificationSource {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NotificationMetadata {
     pub source: NotificationSource,
     pub external_id: Option<String>,
     pub url: Option<String>,
     pub tags: Vec<String>,
     pub custom_data: Option<serde_json::Value>,
+    /// Groups messages of the same conversation, e.g. "github:owner/repo#42"
+    #[serde(default)]
+    pub thread_key: Option<String>,
+    /// Set when similar notifications are merged into this one
+    #[serde(default)]
+    pub aggregate: Option<NotificationAggregate>,
+    /// Contact the sender was resolved to when the notification arrived
+    #[serde(default)]
+    pub contact: Option<SenderContact>,
+    /// Ranking computed by the priority scorer
+    #[serde(default)]
+    pub score: Option<PriorityScore>,
+    /// The body as the service wrote it, absent for notifications created locally
+    #[serde(default)]
+    pub body: Option<RichContent>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +118,15 @@ pub struct Notification {
     pub updated_at: DateTime<Utc>,
     pub read_at: Option<DateTime<Utc>>,
     pub action_taken_at: Option<DateTime<Utc>>,
+    /// When a snoozed notification comes back
+    #[serde(default)]
+    pub snoozed_until: Option<DateTime<Utc>>,
+    /// Status to restore when the snooze ends
+    #[serde(default)]
+    pub snoozed_from: Option<NotificationStatus>,
+    /// Progress of the escalation while the notification is `ActionRequired`
+    #[serde(default)]
+    pub escalation: Option<EscalationState>,
 }
 
 impl CachedEntity for Notification {
@@ -97,6 +154,9 @@ impl Notification {
             updated_at: now,
             read_at: None,
             action_taken_at: None,
+            snoozed_until: None,
+            snoozed_from: None,
+            escalation: None,
         }
     }
 
@@ -112,9 +172,14 @@ impl Notification {
         self.updated_at = Utc::now();
     }
 
+    /// Starts the escalation clock, unless the notification already waits
     pub fn mark_action_required(&mut self) {
+        let now = Utc::now();
+        if self.status != NotificationStatus::ActionRequired {
+            self.escalation = Some(EscalationState::started(now));
+        }
         self.status = NotificationStatus::ActionRequired;
-        self.updated_at = Utc::now();
+        self.updated_at = now;
     }
 
     pub fn mark_action_taken(&mut self) {
@@ -127,4 +192,65 @@ impl Notification {
         self.status = NotificationStatus::Deleted;
         self.updated_at = Utc::now();
     }
+
+    /// Hides the notification until `until`. Snoozing again only moves the
+    /// wake-up time and keeps the original status to restore.
+    pub fn snooze(&mut self, until: DateTime<Utc>) {
+        if self.status != NotificationStatus::Snoozed {
+            self.snoozed_from = Some(self.status.clone());
+        }
+        self.status = NotificationStatus::Snoozed;
+        self.snoozed_until = Some(until);
+        self.updated_at = Utc::now();
+    }
+
+    /// Restores the status the notification had before it was snoozed
+    pub fn unsnooze(&mut self) {
+        let now = Utc::now();
+        if self.status == NotificationStatus::Snoozed {
+            self.status = self.snoozed_from.take().unwrap_or(NotificationStatus::New);
+            // Time spent snoozed does not count towards escalation
+            if self.status == NotificationStatus::ActionRequired {
+                self.escalation = Some(EscalationState::started(now));
+            }
+        }
+        self.snoozed_until = None;
+        self.snoozed_from = None;
+        self.updated_at = now;
+    }
+
+    /// Who sent the notification, as found in the integration's custom data.
+    /// Addresses written as `Name <address>` are reduced to the address.
+    pub fn sender(&self) -> Option<String> {
+        const SENDER_POINTERS: [&str; 9] = [
+            "/from_address",
+            "/from",
+            "/sender",
+            "/sender/login",
+            "/author/username",
+            "/actor/login",
+            "/user/emailAddress",
+            "/user/name",
+            "/messageDetails/sender/id",
+        ];
+
+        let data = self.metadata.custom_data.as_ref()?;
+        SENDER_POINTERS
+            .iter()
+            .filter_map(|pointer| data.pointer(pointer)?.as_str())
+            .map(|sender| {
+                sender
+                    .rsplit_once('<')
+                    .and_then(|(_, address)| address.strip_suffix('>'))
+                    .unwrap_or(sender)
+                    .trim()
+            })
+            .find(|sender| !sender.is_empty())
+            .map(str::to_string)
+    }
+
+    pub fn is_snooze_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == NotificationStatus::Snoozed
+            && self.snoozed_until.is_none_or(|until| until <= now)
+    }
 }
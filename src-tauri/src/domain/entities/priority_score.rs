@@ -0,0 +1,101 @@
+use super::notification::{Notification, NotificationPriority};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How urgent a notification is, from 0 to 100, with the factors it was
+/// computed from so the ranking can be explained to the user.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PriorityScore {
+    pub score: f64,
+    pub factors: Vec<ScoreFactor>,
+    /// Priority suggested by the AI analysis, kept so the score can be
+    /// refreshed as the notification ages without analysing it again
+    #[serde(default)]
+    pub ai_priority: Option<NotificationPriority>,
+    pub scored_at: DateTime<Utc>,
+}
+
+/// One contribution to a priority score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoreFactor {
+    pub kind: ScoreFactorKind,
+    pub points: f64,
+    /// Human readable reason, e.g. "Mentions @octocat"
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreFactorKind {
+    SourcePriority,
+    AiPriority,
+    Vip,
+    Mention,
+    Keyword,
+    Age,
+    Deadline,
+}
+
+impl PriorityScore {
+    /// Sums the factors, clamped to the 0-100 range
+    pub fn new(
+        factors: Vec<ScoreFactor>,
+        ai_priority: Option<NotificationPriority>,
+        scored_at: DateTime<Utc>,
+    ) -> Self {
+        let score = factors
+            .iter()
+            .map(|factor| factor.points)
+            .sum::<f64>()
+            .clamp(0.0, 100.0);
+        Self {
+            score,
+            factors,
+            ai_priority,
+            scored_at,
+        }
+    }
+}
+
+impl Notification {
+    /// The priority score, 0 when the notification was never scored
+    pub fn score(&self) -> f64 {
+        self.metadata.score.as_ref().map_or(0.0, |s| s.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn factor(kind: ScoreFactorKind, points: f64) -> ScoreFactor {
+        ScoreFactor {
+            kind,
+            points,
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_score_is_the_clamped_sum_of_its_factors() {
+        let score = PriorityScore::new(
+            vec![
+                factor(ScoreFactorKind::SourcePriority, 20.0),
+                factor(ScoreFactorKind::Vip, 15.0),
+            ],
+            None,
+            Utc::now(),
+        );
+        assert_eq!(score.score, 35.0);
+
+        let capped = PriorityScore::new(
+            vec![
+                factor(ScoreFactorKind::SourcePriority, 80.0),
+                factor(ScoreFactorKind::Deadline, 40.0),
+            ],
+            Some(NotificationPriority::Critical),
+            Utc::now(),
+        );
+        assert_eq!(capped.score, 100.0);
+    }
+}
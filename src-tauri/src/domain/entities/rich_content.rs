@@ -0,0 +1,269 @@
+use super::notification::Notification;
+use lazy_static::lazy_static;
+use pulldown_cmark::{html, Options, Parser};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How the service wrote the body of a notification
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyFormat {
+    Plain,
+    Markdown,
+    Html,
+    /// Atlassian Document Format, the JSON documents of Jira and Confluence
+    Adf,
+}
+
+/// The body of a notification in the format its service sent it, with a
+/// rendering safe to display and the text the AI and search work on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RichContent {
+    pub format: BodyFormat,
+    /// The body as received, ADF documents as JSON
+    pub original: String,
+    /// Rendered HTML without scripts, styles, event handlers or unsafe links
+    pub html: String,
+    /// Plain text with normalized whitespace
+    pub text: String,
+}
+
+lazy_static! {
+    static ref LINE_BREAK: Regex = Regex::new(r"(?i)<br\s*/?>").unwrap();
+    static ref LIST_ITEM: Regex = Regex::new(r"(?i)\s*<li(\s[^>]*)?>").unwrap();
+    static ref BLOCK: Regex =
+        Regex::new(r"(?i)</?(p|div|h[1-6]|tr|blockquote|pre|ul|ol|table)(\s[^>]*)?>|<hr\s*/?>")
+            .unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref ENTITY: Regex = Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap();
+    static ref SPACES: Regex = Regex::new(r"[ \t\u{a0}]+").unwrap();
+    static ref BLANK_LINES: Regex = Regex::new(r"\n{3,}").unwrap();
+}
+
+impl RichContent {
+    pub fn new(format: BodyFormat, original: String) -> Self {
+        let html = match format {
+            BodyFormat::Plain => plain_to_html(&original),
+            BodyFormat::Markdown => markdown_to_html(&original),
+            BodyFormat::Html => original.clone(),
+            BodyFormat::Adf => match serde_json::from_str::<Value>(&original) {
+                Ok(document) => adf_to_html(&document),
+                Err(_) => plain_to_html(&original),
+            },
+        };
+        let html = ammonia::clean(&html);
+        let text = match format {
+            BodyFormat::Plain => normalize_text(&original),
+            _ => html_to_text(&html),
+        };
+
+        Self {
+            format,
+            original,
+            html,
+            text,
+        }
+    }
+
+    pub fn plain(body: impl Into<String>) -> Self {
+        Self::new(BodyFormat::Plain, body.into())
+    }
+
+    pub fn markdown(body: impl Into<String>) -> Self {
+        Self::new(BodyFormat::Markdown, body.into())
+    }
+
+    pub fn html(body: impl Into<String>) -> Self {
+        Self::new(BodyFormat::Html, body.into())
+    }
+
+    pub fn adf(document: &Value) -> Self {
+        Self::new(BodyFormat::Adf, document.to_string())
+    }
+}
+
+impl Notification {
+    /// The plain text of the body, or the content when there is no rich body
+    pub fn text(&self) -> &str {
+        self.metadata
+            .body
+            .as_ref()
+            .map_or(&self.content, |body| &body.text)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Paragraphs at blank lines, line breaks elsewhere
+fn plain_to_html(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| format!("<p>{}</p>", escape(paragraph).replace('\n', "<br>")))
+        .collect()
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let parser = Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+    );
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, parser);
+    rendered
+}
+
+fn adf_children(node: &Value) -> String {
+    node["content"]
+        .as_array()
+        .map(|children| children.iter().map(adf_to_html).collect())
+        .unwrap_or_default()
+}
+
+fn adf_to_html(node: &Value) -> String {
+    let wrap = |tag: &str| format!("<{tag}>{}</{tag}>", adf_children(node));
+    match node["type"].as_str().unwrap_or_default() {
+        "paragraph" => wrap("p"),
+        "heading" => {
+            let level = node["attrs"]["level"].as_u64().unwrap_or(1).clamp(1, 6);
+            wrap(&format!("h{}", level))
+        }
+        "bulletList" => wrap("ul"),
+        "orderedList" => wrap("ol"),
+        "listItem" => wrap("li"),
+        "blockquote" => wrap("blockquote"),
+        "codeBlock" => format!("<pre><code>{}</code></pre>", adf_children(node)),
+        "table" => wrap("table"),
+        "tableRow" => wrap("tr"),
+        "tableHeader" => wrap("th"),
+        "tableCell" => wrap("td"),
+        "panel" => wrap("div"),
+        "rule" => "<hr>".to_string(),
+        "hardBreak" => "<br>".to_string(),
+        "mention" | "emoji" => escape(node["attrs"]["text"].as_str().unwrap_or_default()),
+        "inlineCard" | "blockCard" => {
+            let url = escape(node["attrs"]["url"].as_str().unwrap_or_default());
+            format!("<a href=\"{url}\">{url}</a>")
+        }
+        "text" => {
+            let mut text = escape(node["text"].as_str().unwrap_or_default());
+            for mark in node["marks"].as_array().into_iter().flatten() {
+                text = match mark["type"].as_str().unwrap_or_default() {
+                    "strong" => format!("<strong>{}</strong>", text),
+                    "em" => format!("<em>{}</em>", text),
+                    "code" => format!("<code>{}</code>", text),
+                    "strike" => format!("<del>{}</del>", text),
+                    "underline" => format!("<u>{}</u>", text),
+                    "link" => format!(
+                        "<a href=\"{}\">{}</a>",
+                        escape(mark["attrs"]["href"].as_str().unwrap_or_default()),
+                        text
+                    ),
+                    _ => text,
+                };
+            }
+            text
+        }
+        _ => adf_children(node),
+    }
+}
+
+fn decode_entity(entity: &str) -> Option<String> {
+    let decoded = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        _ => {
+            let code = match entity.strip_prefix('#')? {
+                hex if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok()?,
+                decimal => decimal.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some(decoded.to_string())
+}
+
+fn html_to_text(html: &str) -> String {
+    let text = LINE_BREAK.replace_all(html, "\n");
+    let text = LIST_ITEM.replace_all(&text, "\n- ");
+    let text = BLOCK.replace_all(&text, "\n\n");
+    let text = TAG.replace_all(&text, "");
+    let text = ENTITY.replace_all(&text, |captures: &regex::Captures| {
+        decode_entity(&captures[1]).unwrap_or_else(|| captures[0].to_string())
+    });
+    normalize_text(&text)
+}
+
+/// Single spaces inside lines, at most one blank line between paragraphs
+fn normalize_text(text: &str) -> String {
+    let lines: Vec<String> = text
+        .replace("\r\n", "\n")
+        .lines()
+        .map(|line| SPACES.replace_all(line, " ").trim().to_string())
+        .collect();
+    BLANK_LINES
+        .replace_all(&lines.join("\n"), "\n\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_formats_render_safe_html_and_plain_text() {
+        let plain = RichContent::plain("Hello   <team>,\r\n\n\n\nSee you  ");
+        assert_eq!(plain.text, "Hello <team>,\n\nSee you");
+        assert_eq!(plain.html, "<p>Hello   &lt;team&gt;,</p><p>See you</p>");
+
+        let markdown = RichContent::markdown(
+            "# Release\n\n- **fixed** the [parser](https://x.dev)\n- `lexer`",
+        );
+        assert!(markdown.html.contains("<strong>fixed</strong>"));
+        assert_eq!(markdown.text, "Release\n\n- fixed the parser\n- lexer");
+
+        let html = RichContent::html(
+            "<div onclick=\"steal()\">Hi&nbsp;Bob &amp; co<script>alert(1)</script></div>\
+             <a href=\"javascript:alert(1)\">link</a><style>p{}</style><p>Caf&#233; at 5&#x2F;6</p>",
+        );
+        assert!(!html.html.contains("script"));
+        assert!(!html.html.contains("onclick"));
+        assert!(!html.html.contains("javascript"));
+        assert_eq!(html.text, "Hi Bob & co\n\nlink\n\nCafé at 5/6");
+
+        let adf = RichContent::adf(&json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                { "type": "heading", "attrs": { "level": 2 }, "content": [
+                    { "type": "text", "text": "Steps" }
+                ]},
+                { "type": "paragraph", "content": [
+                    { "type": "mention", "attrs": { "text": "@Alice" } },
+                    { "type": "text", "text": " please check " },
+                    { "type": "text", "text": "<b>", "marks": [{ "type": "code" }] },
+                    { "type": "hardBreak" },
+                    { "type": "text", "text": "docs", "marks": [
+                        { "type": "link", "attrs": { "href": "https://example.com" } }
+                    ]}
+                ]}
+            ]
+        }));
+        assert!(adf.html.contains("<h2>Steps</h2>"));
+        assert!(adf.html.contains("<code>&lt;b&gt;</code>"));
+        assert_eq!(adf.text, "Steps\n\n@Alice please check <b>\ndocs");
+    }
+}
@@ -0,0 +1,375 @@
+use super::notification::{
+    Notification, NotificationPriority, NotificationSource, NotificationStatus,
+};
+use crate::domain::error::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::RwLock};
+use uuid::Uuid;
+
+lazy_static! {
+    /// Rule patterns compiled once, `None` for those that don't compile
+    static ref PATTERNS: RwLock<HashMap<String, Option<Regex>>> = RwLock::new(HashMap::new());
+}
+
+/// A user-defined automation applied to incoming notifications before the AI runs.
+///
+/// Rules run in ascending `position`. A rule matches when all its conditions
+/// match; a rule without conditions matches every notification.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Rule {
+    pub id: Uuid,
+    pub name: String,
+    pub enabled: bool,
+    pub position: i32,
+    /// Skip the remaining rules once this one matched
+    pub stop_processing: bool,
+    pub conditions: Vec<RuleCondition>,
+    pub actions: Vec<RuleAction>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCondition {
+    Source {
+        source: NotificationSource,
+    },
+    /// Regular expression searched in the title
+    Title {
+        pattern: String,
+    },
+    /// Regular expression searched in the content
+    Content {
+        pattern: String,
+    },
+    /// The notification carries this tag, ignoring case
+    Tag {
+        tag: String,
+    },
+    PriorityAtLeast {
+        priority: NotificationPriority,
+    },
+    /// Regular expression searched in the sender's address and contact name
+    Sender {
+        pattern: String,
+    },
+    /// The sender is a VIP contact
+    Vip,
+    /// The sender is this contact
+    Contact {
+        contact_id: Uuid,
+    },
+    /// A value in the custom data, addressed by a dotted path such as
+    /// `issue.fields.status.name`. Without `equals`, the value only has to exist.
+    CustomData {
+        path: String,
+        #[serde(default)]
+        equals: Option<serde_json::Value>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    SetPriority {
+        priority: NotificationPriority,
+    },
+    AddTags {
+        tags: Vec<String>,
+    },
+    SetStatus {
+        status: NotificationStatus,
+    },
+    /// Leave the notification out of AI analysis
+    SkipAi,
+    /// Reply with the template, where `{{title}}`, `{{content}}`, `{{sender}}`
+    /// and `{{source}}` are replaced with the notification's values
+    AutoRespond {
+        template: String,
+    },
+    Forward {
+        to: String,
+    },
+}
+
+impl Rule {
+    pub fn new(name: String, conditions: Vec<RuleCondition>, actions: Vec<RuleAction>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            enabled: true,
+            position: 0,
+            stop_processing: false,
+            conditions,
+            actions,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Checks the rule can run: it has a name and actions, and its patterns compile
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Rule name cannot be empty".to_string(),
+            ));
+        }
+        if self.actions.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Rule must have at least one action".to_string(),
+            ));
+        }
+
+        for condition in &self.conditions {
+            condition.validate()?;
+        }
+
+        for action in &self.actions {
+            if let RuleAction::SetStatus {
+                status: NotificationStatus::Snoozed,
+            } = action
+            {
+                return Err(DomainError::ValidationError(
+                    "Rules cannot snooze notifications".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, notification: &Notification) -> bool {
+        self.enabled
+            && self
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(notification))
+    }
+}
+
+impl RuleCondition {
+    /// Checks the regular expressions of the condition compile
+    pub fn validate(&self) -> DomainResult<()> {
+        if let RuleCondition::Title { pattern }
+        | RuleCondition::Content { pattern }
+        | RuleCondition::Sender { pattern } = self
+        {
+            Regex::new(pattern).map_err(|e| {
+                DomainError::ValidationError(format!("Invalid pattern {}: {}", pattern, e))
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, notification: &Notification) -> bool {
+        match self {
+            RuleCondition::Source { source } => &notification.metadata.source == source,
+            RuleCondition::Title { pattern } => regex_matches(pattern, &notification.title),
+            RuleCondition::Content { pattern } => regex_matches(pattern, notification.text()),
+            RuleCondition::Tag { tag } => notification
+                .metadata
+                .tags
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(tag)),
+            RuleCondition::PriorityAtLeast { priority } => {
+                notification.priority.rank() >= priority.rank()
+            }
+            RuleCondition::Sender { pattern } => {
+                notification
+                    .sender()
+                    .is_some_and(|sender| regex_matches(pattern, &sender))
+                    || notification
+                        .metadata
+                        .contact
+                        .as_ref()
+                        .is_some_and(|contact| regex_matches(pattern, &contact.name))
+            }
+            RuleCondition::Vip => notification.is_from_vip(),
+            RuleCondition::Contact { contact_id } => notification
+                .metadata
+                .contact
+                .as_ref()
+                .is_some_and(|contact| &contact.contact_id == contact_id),
+            RuleCondition::CustomData { path, equals } => custom_data_matches(
+                notification.metadata.custom_data.as_ref(),
+                path,
+                equals.as_ref(),
+            ),
+        }
+    }
+}
+
+/// Renders an auto-response template for the notification
+pub fn render_template(template: &str, notification: &Notification) -> String {
+    template
+        .replace("{{title}}", &notification.title)
+        .replace("{{content}}", &notification.content)
+        .replace("{{sender}}", &notification.sender().unwrap_or_default())
+        .replace("{{source}}", &notification.metadata.source.to_string())
+}
+
+/// Invalid patterns are rejected when saving, so they simply never match here
+pub fn regex_matches(pattern: &str, text: &str) -> bool {
+    if let Some(regex) = PATTERNS.read().unwrap().get(pattern) {
+        return regex.as_ref().is_some_and(|regex| regex.is_match(text));
+    }
+
+    let regex = Regex::new(pattern).ok();
+    let matches = regex.as_ref().is_some_and(|regex| regex.is_match(text));
+    PATTERNS.write().unwrap().insert(pattern.to_string(), regex);
+    matches
+}
+
+/// Whether the value at the dotted `path` exists and, when given, equals `equals`
+pub fn custom_data_matches(
+    data: Option<&serde_json::Value>,
+    path: &str,
+    equals: Option<&serde_json::Value>,
+) -> bool {
+    match (data.and_then(|data| lookup_path(data, path)), equals) {
+        (Some(value), Some(expected)) => value == expected,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn lookup_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| match current {
+            serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => current.get(segment),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationMetadata, SenderContact};
+    use serde_json::json;
+
+    fn dependabot() -> Notification {
+        Notification::new(
+            "Bump serde from 1.0.1 to 1.0.2".to_string(),
+            "Dependabot opened a pull request".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: None,
+                url: None,
+                tags: vec!["PullRequestEvent".to_string()],
+                custom_data: Some(json!({
+                    "actor": { "login": "dependabot[bot]" },
+                    "pull_request": { "labels": [{ "name": "dependencies" }] }
+                })),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_conditions() {
+        let notification = dependabot();
+        let matching = [
+            RuleCondition::Source {
+                source: NotificationSource::Github,
+            },
+            RuleCondition::Title {
+                pattern: "(?i)^bump ".to_string(),
+            },
+            RuleCondition::Content {
+                pattern: "pull request".to_string(),
+            },
+            RuleCondition::Tag {
+                tag: "pullrequestevent".to_string(),
+            },
+            RuleCondition::PriorityAtLeast {
+                priority: NotificationPriority::Low,
+            },
+            RuleCondition::Sender {
+                pattern: "^dependabot".to_string(),
+            },
+            RuleCondition::CustomData {
+                path: "pull_request.labels.0.name".to_string(),
+                equals: Some(json!("dependencies")),
+            },
+            RuleCondition::CustomData {
+                path: "actor.login".to_string(),
+                equals: None,
+            },
+        ];
+        for condition in &matching {
+            assert!(condition.matches(&notification), "{:?}", condition);
+        }
+
+        let failing = [
+            RuleCondition::Source {
+                source: NotificationSource::Jira,
+            },
+            RuleCondition::PriorityAtLeast {
+                priority: NotificationPriority::High,
+            },
+            RuleCondition::CustomData {
+                path: "pull_request.labels.1.name".to_string(),
+                equals: None,
+            },
+            RuleCondition::Title {
+                pattern: "(unclosed".to_string(),
+            },
+            RuleCondition::Vip,
+        ];
+        for condition in &failing {
+            assert!(!condition.matches(&notification), "{:?}", condition);
+        }
+
+        let mut from_vip = dependabot();
+        let contact_id = Uuid::new_v4();
+        from_vip.metadata.contact = Some(SenderContact {
+            contact_id,
+            name: "Dependency Bot".to_string(),
+            vip: true,
+            auto_reply: true,
+        });
+        for condition in [
+            RuleCondition::Vip,
+            RuleCondition::Contact { contact_id },
+            RuleCondition::Sender {
+                pattern: "^Dependency Bot$".to_string(),
+            },
+        ] {
+            assert!(condition.matches(&from_vip), "{:?}", condition);
+        }
+    }
+
+    #[test]
+    fn test_validation_and_templates() {
+        let mut rule = Rule::new(
+            "Invalid".to_string(),
+            vec![RuleCondition::Sender {
+                pattern: "[".to_string(),
+            }],
+            vec![RuleAction::SkipAi],
+        );
+        assert!(rule.validate().is_err());
+
+        rule.conditions.clear();
+        assert!(rule.validate().is_ok());
+        assert!(rule.matches(&dependabot()));
+        rule.enabled = false;
+        assert!(!rule.matches(&dependabot()));
+
+        rule.actions = vec![RuleAction::SetStatus {
+            status: NotificationStatus::Snoozed,
+        }];
+        assert!(rule.validate().is_err());
+
+        assert_eq!(
+            render_template("Thanks {{sender}}, re: {{title}}", &dependabot()),
+            "Thanks dependabot[bot], re: Bump serde from 1.0.1 to 1.0.2"
+        );
+    }
+}
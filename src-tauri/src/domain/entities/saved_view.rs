@@ -0,0 +1,216 @@
+use super::notification::{Notification, NotificationStatus};
+use super::rule::RuleCondition;
+use crate::domain::error::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named, persisted notification filter such as "unread GitHub with tag
+/// review from VIPs".
+///
+/// A notification belongs to the view when it has one of `statuses`, matches
+/// every condition and each word of `query` appears in the notification or in
+/// a note on it or its thread. Deleted notifications only show up in views
+/// asking for them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub name: String,
+    /// Icon name chosen by the frontend
+    pub icon: Option<String>,
+    /// Pinned views are listed first
+    pub pinned: bool,
+    pub position: i32,
+    /// Any status but `Deleted` when empty
+    pub statuses: Vec<NotificationStatus>,
+    /// Words searched, ignoring case, in the title, content, sender, tags,
+    /// attachments and notes of the notification
+    pub query: Option<String>,
+    pub conditions: Vec<RuleCondition>,
+    pub sort: NotificationSort,
+    /// Shipped with the application, can be edited but not deleted
+    pub built_in: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A view with the number of notifications it currently shows
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SavedViewSummary {
+    #[serde(flatten)]
+    pub view: SavedView,
+    pub total_count: usize,
+    pub unread_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSort {
+    /// Most recent first
+    #[default]
+    Newest,
+    /// Highest priority score first, most recent first among equal scores
+    Score,
+}
+
+impl NotificationSort {
+    pub fn sort(&self, notifications: &mut [Notification]) {
+        match self {
+            NotificationSort::Newest => {
+                notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at))
+            }
+            NotificationSort::Score => notifications.sort_by(|a, b| {
+                b.score()
+                    .total_cmp(&a.score())
+                    .then(b.created_at.cmp(&a.created_at))
+            }),
+        }
+    }
+}
+
+/// Ids of the built-in views, stable so they survive being edited
+pub const NEEDS_REPLY_VIEW_ID: Uuid = Uuid::from_u128(1);
+pub const WAITING_ON_OTHERS_VIEW_ID: Uuid = Uuid::from_u128(2);
+pub const SNOOZED_VIEW_ID: Uuid = Uuid::from_u128(3);
+
+/// Notifications tagged this way show up in "Waiting on others". Replying
+/// adds the tag, a new message in the thread removes it.
+pub const WAITING_TAG: &str = "waiting";
+
+impl SavedView {
+    pub fn new(name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            icon: None,
+            pinned: false,
+            position: 0,
+            statuses: Vec::new(),
+            query: None,
+            conditions: Vec::new(),
+            sort: NotificationSort::default(),
+            built_in: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// "Needs reply", "Waiting on others" and "Snoozed"
+    pub fn built_in_views() -> Vec<SavedView> {
+        let view = |id, name: &str, icon: &str, position, statuses, conditions| SavedView {
+            id,
+            icon: Some(icon.to_string()),
+            pinned: true,
+            position,
+            statuses,
+            conditions,
+            built_in: true,
+            ..SavedView::new(name.to_string())
+        };
+
+        vec![
+            SavedView {
+                sort: NotificationSort::Score,
+                ..view(
+                    NEEDS_REPLY_VIEW_ID,
+                    "Needs reply",
+                    "reply",
+                    0,
+                    vec![NotificationStatus::ActionRequired],
+                    vec![],
+                )
+            },
+            view(
+                WAITING_ON_OTHERS_VIEW_ID,
+                "Waiting on others",
+                "hourglass",
+                1,
+                vec![
+                    NotificationStatus::New,
+                    NotificationStatus::Read,
+                    NotificationStatus::ActionRequired,
+                    NotificationStatus::ActionTaken,
+                ],
+                vec![RuleCondition::Tag {
+                    tag: WAITING_TAG.to_string(),
+                }],
+            ),
+            view(
+                SNOOZED_VIEW_ID,
+                "Snoozed",
+                "clock",
+                2,
+                vec![NotificationStatus::Snoozed],
+                vec![],
+            ),
+        ]
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "View name cannot be empty".to_string(),
+            ));
+        }
+        for condition in &self.conditions {
+            condition.validate()?;
+        }
+        Ok(())
+    }
+
+    /// The words of the query, lowercased
+    pub fn query_terms(&self) -> Vec<String> {
+        self.query
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect()
+    }
+}
+
+impl Notification {
+    /// The title, content, sender, tags and attachments, lowercased. The
+    /// contact name is left out as it changes with the contact.
+    pub fn search_text(&self) -> String {
+        let mut text = format!("{}\n{}", self.title, self.text());
+        if let Some(sender) = self.sender() {
+            text.push('\n');
+            text.push_str(&sender);
+        }
+        for tag in &self.metadata.tags {
+            text.push('\n');
+            text.push_str(tag);
+        }
+        for attachment in &self.metadata.attachments {
+            text.push('\n');
+            text.push_str(&attachment.name);
+            if let Some(attachment_text) = &attachment.text {
+                text.push('\n');
+                text.push_str(attachment_text);
+            }
+        }
+        text.to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_validates_conditions() {
+        let mut view = SavedView::new("Parser".to_string());
+        view.conditions = vec![RuleCondition::Title {
+            pattern: "(".to_string(),
+        }];
+        assert!(view.validate().is_err());
+        view.name = " ".to_string();
+        view.conditions.clear();
+        assert!(view.validate().is_err());
+        assert!(SavedView::built_in_views()
+            .iter()
+            .all(|view| view.built_in && view.validate().is_ok()));
+    }
+}
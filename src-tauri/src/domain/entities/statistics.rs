@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Width of the time buckets notifications are counted in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeBucket {
+    Hour,
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+/// What notifications cost over a period of time
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationStatistics {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub bucket: TimeBucket,
+    /// Notifications received during the period
+    pub total: usize,
+    /// Buckets with at least one notification, oldest first
+    pub buckets: Vec<BucketStatistics>,
+    /// Median delay between receiving and reading a notification
+    pub median_seconds_to_read: Option<f64>,
+    /// Median delay between receiving a notification and acting on it
+    pub median_seconds_to_action: Option<f64>,
+    pub ai: AiStatistics,
+    /// Replies sent by rules
+    pub auto_responses: usize,
+    /// Replies drafted by the AI
+    pub responses_generated: usize,
+}
+
+/// Notifications received during one time bucket
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BucketStatistics {
+    pub start: DateTime<Utc>,
+    pub total: usize,
+    pub by_source: BTreeMap<String, usize>,
+    pub by_status: BTreeMap<String, usize>,
+    pub by_priority: BTreeMap<String, usize>,
+}
+
+/// How often the AI flags notifications, and how often the user disagrees
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AiStatistics {
+    /// Notifications analysed by the AI
+    pub processed: usize,
+    /// Analysed notifications the AI found to require action
+    pub action_required: usize,
+    /// Flagged by the AI, then read or archived without acting on it
+    pub dismissed: usize,
+    /// Not flagged by the AI, then marked as requiring action by the user
+    pub escalated: usize,
+}
+
+impl AiStatistics {
+    /// Share of the analysed notifications the AI flagged
+    pub fn action_required_rate(&self) -> f64 {
+        ratio(self.action_required, self.processed)
+    }
+
+    /// Share of the analysed notifications where the user overrode the AI
+    pub fn override_rate(&self) -> f64 {
+        ratio(self.dismissed + self.escalated, self.processed)
+    }
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ai_rates() {
+        assert_eq!(AiStatistics::default().override_rate(), 0.0);
+
+        let ai = AiStatistics {
+            processed: 8,
+            action_required: 2,
+            dismissed: 1,
+            escalated: 1,
+        };
+        assert_eq!(ai.action_required_rate(), 0.25);
+        assert_eq!(ai.override_rate(), 0.25);
+    }
+}
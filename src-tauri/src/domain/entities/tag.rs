@@ -0,0 +1,83 @@
+use crate::domain::error::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Display settings of a tag. Notifications refer to tags by name, names
+/// are compared ignoring case.
+///
+/// A tag found on notifications without a saved `Tag` is still listed,
+/// without colour or description.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    /// Hex colour, e.g. "#1f883d"
+    pub color: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A tag with the number of notifications carrying it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TagSummary {
+    #[serde(flatten)]
+    pub tag: Tag,
+    pub notification_count: usize,
+}
+
+impl Tag {
+    pub fn new(name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            name,
+            color: None,
+            description: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Tag name cannot be empty".to_string(),
+            ));
+        }
+        if let Some(color) = &self.color {
+            let valid = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                return Err(DomainError::ValidationError(format!(
+                    "Invalid tag colour {}, expected #rrggbb",
+                    color
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_need_a_name_and_a_hex_colour() {
+        let mut tag = Tag::new("Release".to_string());
+        assert!(tag.validate().is_ok());
+        assert!(tag.is_named(" release "));
+
+        tag.color = Some("#1F883D".to_string());
+        assert!(tag.validate().is_ok());
+        tag.color = Some("green".to_string());
+        assert!(tag.validate().is_err());
+
+        let unnamed = Tag::new("  ".to_string());
+        assert!(unnamed.validate().is_err());
+    }
+}
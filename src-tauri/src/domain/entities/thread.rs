@@ -0,0 +1,153 @@
+use super::notification::{
+    Notification, NotificationPriority, NotificationSource, NotificationStatus,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Key prefix of the single-message threads of notifications without a thread key
+pub const STANDALONE_THREAD_PREFIX: &str = "notification:";
+
+/// A conversation made of the notifications sharing a thread key.
+///
+/// The latest message wins: it gives the thread its subject and status, so
+/// a new message in an archived thread brings the whole thread back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Thread {
+    pub key: String,
+    pub source: NotificationSource,
+    pub subject: String,
+    pub status: NotificationStatus,
+    /// Highest priority of any message in the thread
+    pub priority: NotificationPriority,
+    pub message_count: usize,
+    pub unread_count: usize,
+    pub latest_notification_id: Uuid,
+    pub latest_at: DateTime<Utc>,
+    /// Latest message first
+    pub notification_ids: Vec<Uuid>,
+}
+
+impl Notification {
+    /// The thread this notification belongs to, its own one if it has no thread key
+    pub fn thread_key(&self) -> String {
+        self.metadata
+            .thread_key
+            .clone()
+            .unwrap_or_else(|| format!("{}{}", STANDALONE_THREAD_PREFIX, self.id))
+    }
+}
+
+impl Thread {
+    /// Builds a thread from its messages, ignoring deleted ones
+    pub fn from_notifications(key: String, notifications: Vec<Notification>) -> Option<Self> {
+        let mut notifications: Vec<Notification> = notifications
+            .into_iter()
+            .filter(|n| n.status != NotificationStatus::Deleted)
+            .collect();
+        notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+
+        let latest = notifications.first()?;
+        let priority = notifications
+            .iter()
+            .map(|n| &n.priority)
+            .max_by_key(|priority| priority.rank())
+            .cloned()
+            .unwrap_or(NotificationPriority::Low);
+
+        Some(Self {
+            key,
+            source: latest.metadata.source.clone(),
+            subject: latest.title.clone(),
+            status: latest.status.clone(),
+            priority,
+            message_count: notifications.len(),
+            unread_count: notifications
+                .iter()
+                .filter(|n| n.status == NotificationStatus::New)
+                .count(),
+            latest_notification_id: latest.id,
+            latest_at: latest.created_at,
+            notification_ids: notifications.iter().map(|n| n.id).collect(),
+        })
+    }
+
+    /// Groups notifications by thread, most recently active thread first
+    pub fn group(notifications: Vec<Notification>) -> Vec<Self> {
+        let mut by_key: HashMap<String, Vec<Notification>> = HashMap::new();
+        for notification in notifications {
+            by_key
+                .entry(notification.thread_key())
+                .or_default()
+                .push(notification);
+        }
+
+        let mut threads: Vec<Self> = by_key
+            .into_iter()
+            .filter_map(|(key, notifications)| Self::from_notifications(key, notifications))
+            .collect();
+        threads.sort_by_key(|t| std::cmp::Reverse(t.latest_at));
+        threads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::NotificationMetadata;
+    use chrono::Duration;
+
+    fn message(
+        thread_key: Option<&str>,
+        priority: NotificationPriority,
+        minutes_ago: i64,
+    ) -> Notification {
+        let mut notification = Notification::new(
+            format!("Message from {} minutes ago", minutes_ago),
+            "Content".to_string(),
+            priority,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                thread_key: thread_key.map(str::to_string),
+                ..Default::default()
+            },
+        );
+        notification.created_at = Utc::now() - Duration::minutes(minutes_ago);
+        notification
+    }
+
+    #[test]
+    fn test_latest_message_wins() {
+        let mut archived = message(Some("github:o/r#1"), NotificationPriority::High, 30);
+        archived.archive();
+        let mut deleted = message(Some("github:o/r#1"), NotificationPriority::Critical, 1);
+        deleted.delete();
+        let reply = message(Some("github:o/r#1"), NotificationPriority::Low, 5);
+        let standalone = message(None, NotificationPriority::Medium, 10);
+
+        let threads = Thread::group(vec![
+            archived.clone(),
+            standalone.clone(),
+            reply.clone(),
+            deleted,
+        ]);
+
+        assert_eq!(threads.len(), 2);
+        let thread = &threads[0];
+        assert_eq!(thread.key, "github:o/r#1");
+        assert_eq!(thread.subject, reply.title);
+        assert_eq!(thread.status, NotificationStatus::New);
+        assert_eq!(thread.priority, NotificationPriority::High);
+        assert_eq!(thread.message_count, 2);
+        assert_eq!(thread.unread_count, 1);
+        assert_eq!(thread.notification_ids, vec![reply.id, archived.id]);
+
+        assert_eq!(threads[1].key, standalone.thread_key());
+        assert_eq!(threads[1].message_count, 1);
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub url: String,
+    /// Event type names (e.g. `NotificationCreated`). Empty means every event.
+    pub event_types: Vec<String>,
+    /// Only returned once, when the subscription is created
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    pub fn new(url: String, event_types: Vec<String>, secret: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            url,
+            event_types,
+            secret,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_subscribed_to(&self, event_type: &str) -> bool {
+        self.enabled
+            && (self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type))
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.updated_at = Utc::now();
+    }
+}
+
+/// One delivery attempt of an event to a webhook subscription.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_type: String,
+    pub event_sequence: u64,
+    pub attempt: u32,
+    pub status_code: Option<u16>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub delivered_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn new(
+        subscription_id: Uuid,
+        event_type: String,
+        event_sequence: u64,
+        attempt: u32,
+        status_code: Option<u16>,
+        error: Option<String>,
+    ) -> Self {
+        let success = error.is_none() && status_code.is_some_and(|code| (200..300).contains(&code));
+        Self {
+            id: Uuid::new_v4(),
+            subscription_id,
+            event_type,
+            event_sequence,
+            attempt,
+            status_code,
+            success,
+            error,
+            delivered_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_matches_event_types() {
+        let mut all = WebhookSubscription::new(
+            "https://example.com/hook".to_string(),
+            vec![],
+            "secret".to_string(),
+        );
+        assert!(all.is_subscribed_to("NotificationCreated"));
+
+        let only_read = WebhookSubscription::new(
+            "https://example.com/hook".to_string(),
+            vec!["NotificationRead".to_string()],
+            "secret".to_string(),
+        );
+        assert!(only_read.is_subscribed_to("NotificationRead"));
+        assert!(!only_read.is_subscribed_to("NotificationCreated"));
+
+        all.set_enabled(false);
+        assert!(!all.is_subscribed_to("NotificationCreated"));
+    }
+
+    #[test]
+    fn test_delivery_success_requires_2xx() {
+        let id = Uuid::new_v4();
+        assert!(WebhookDelivery::new(id, "A".to_string(), 1, 1, Some(204), None).success);
+        assert!(!WebhookDelivery::new(id, "A".to_string(), 1, 1, Some(500), None).success);
+        assert!(
+            !WebhookDelivery::new(id, "A".to_string(), 1, 1, None, Some("timeout".to_string()))
+                .success
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::RwLock;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use super::{DomainEvent, DynEventSubscriber, EventEnvelope, EventPublisher, NotificationEvent};
+use crate::domain::{error::DomainResult, repositories::DynEventStore};
+
+const DEFAULT_HISTORY_CAPACITY: usize = 1000;
+
+/// In-process event bus.
+///
+/// Assigns a sequence number to every published event, keeps a bounded
+/// history for catch-up and fans the envelope out to all subscribers.
+/// A failing subscriber is logged and never fails the publisher.
+///
+/// When backed by an [`EventStore`](crate::domain::repositories::EventStore)
+/// every event is also appended to the persistent log, and sequence numbers
+/// continue from the last stored event across restarts. An event that can't
+/// be appended is not delivered and the error goes back to the publisher.
+pub struct EventBus {
+    subscribers: RwLock<Vec<DynEventSubscriber>>,
+    history: Mutex<EventHistory>,
+    store: Option<DynEventStore>,
+}
+
+struct EventHistory {
+    next_sequence: u64,
+    capacity: usize,
+    entries: VecDeque<EventEnvelope>,
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscribers", &"Vec<Arc<dyn EventSubscriber>>")
+            .finish()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::with_history_capacity(DEFAULT_HISTORY_CAPACITY)
+    }
+
+    pub fn with_history_capacity(capacity: usize) -> Self {
+        Self {
+            subscribers: RwLock::new(Vec::new()),
+            history: Mutex::new(EventHistory {
+                next_sequence: 1,
+                capacity,
+                entries: VecDeque::with_capacity(capacity),
+            }),
+            store: None,
+        }
+    }
+
+    /// Creates a bus that appends every event to `store`
+    pub async fn persistent(store: DynEventStore) -> DomainResult<Self> {
+        let latest_sequence = store.latest_sequence().await?;
+        let mut bus = Self::new();
+        bus.history.get_mut().next_sequence = latest_sequence + 1;
+        bus.store = Some(store);
+        Ok(bus)
+    }
+
+    pub fn subscribe(&self, subscriber: DynEventSubscriber) {
+        self.subscribers.write().unwrap().push(subscriber);
+    }
+
+    /// Returns the events with a sequence greater than `sequence`, oldest
+    /// first. Falls back to the event store when the in-memory history no
+    /// longer reaches back that far.
+    pub async fn events_since(&self, sequence: u64) -> Vec<EventEnvelope> {
+        let (covered, capacity, recent) = {
+            let history = self.history.lock().await;
+            let covered = match history.entries.front() {
+                Some(oldest) => oldest.sequence <= sequence + 1,
+                None => history.next_sequence <= sequence + 1,
+            };
+            let recent: Vec<_> = history
+                .entries
+                .iter()
+                .filter(|envelope| envelope.sequence > sequence)
+                .cloned()
+                .collect();
+            (covered, history.capacity, recent)
+        };
+
+        if !covered {
+            if let Some(store) = &self.store {
+                match store.find_after(sequence, capacity).await {
+                    Ok(envelopes) => return envelopes,
+                    Err(e) => warn!("Failed to read events from the event store: {}", e),
+                }
+            }
+        }
+
+        recent
+    }
+
+    /// Sequence number of the most recently published event, 0 if none.
+    pub async fn latest_sequence(&self) -> u64 {
+        self.history.lock().await.next_sequence - 1
+    }
+
+    async fn record(&self, event: DomainEvent) -> DomainResult<EventEnvelope> {
+        // The sequence is only taken once the event is in the store, so the
+        // persisted log has no gaps and catch-up never skips an event whose
+        // append is still in flight.
+        let mut history = self.history.lock().await;
+        let envelope = EventEnvelope::new(history.next_sequence, event);
+        if let Some(store) = &self.store {
+            store.append(&envelope).await?;
+        }
+
+        history.next_sequence += 1;
+        if history.capacity > 0 {
+            if history.entries.len() >= history.capacity {
+                history.entries.pop_front();
+            }
+            history.entries.push_back(envelope.clone());
+        }
+
+        Ok(envelope)
+    }
+}
+
+#[async_trait]
+impl EventPublisher for EventBus {
+    async fn publish_event(&self, event: NotificationEvent) -> DomainResult<()> {
+        self.publish(event.into()).await
+    }
+
+    async fn publish(&self, event: DomainEvent) -> DomainResult<()> {
+        let envelope = self.record(event).await?;
+
+        let subscribers = self.subscribers.read().unwrap().clone();
+        for subscriber in subscribers {
+            if let Err(e) = subscriber.handle_event(&envelope).await {
+                warn!(
+                    "Event subscriber {} failed to handle event {}: {}",
+                    subscriber.name(),
+                    envelope.sequence,
+                    e
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use crate::domain::events::{EventSubscriber, ServiceEvent};
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct RecordingSubscriber {
+        received: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for RecordingSubscriber {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn handle_event(&self, envelope: &EventEnvelope) -> DomainResult<()> {
+            self.received.lock().await.push(envelope.sequence);
+            Ok(())
+        }
+    }
+
+    struct FailingSubscriber;
+
+    #[async_trait]
+    impl EventSubscriber for FailingSubscriber {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn handle_event(&self, _envelope: &EventEnvelope) -> DomainResult<()> {
+            Err(DomainError::InternalError("webview closed".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_assigns_sequence_and_fans_out() {
+        let bus = EventBus::new();
+        let recorder = Arc::new(RecordingSubscriber::default());
+        bus.subscribe(Arc::new(FailingSubscriber));
+        bus.subscribe(recorder.clone());
+
+        bus.publish_event(NotificationEvent::notification_read(Uuid::new_v4()))
+            .await
+            .unwrap();
+        bus.publish(ServiceEvent::sync_started(None).into())
+            .await
+            .unwrap();
+
+        assert_eq!(*recorder.received.lock().await, vec![1, 2]);
+        assert_eq!(bus.latest_sequence().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_events_since_returns_newer_events() {
+        let bus = EventBus::new();
+        for _ in 0..5 {
+            bus.publish_event(NotificationEvent::notification_read(Uuid::new_v4()))
+                .await
+                .unwrap();
+        }
+
+        let events = bus.events_since(3).await;
+        let sequences: Vec<u64> = events.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![4, 5]);
+        assert!(bus.events_since(5).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_bus_appends_and_resumes_sequence() {
+        use crate::domain::repositories::EventStore;
+        use crate::infrastructure::repositories::SqliteEventStore;
+
+        let store = Arc::new(SqliteEventStore::new(":memory:").unwrap());
+
+        let bus = EventBus::persistent(store.clone()).await.unwrap();
+        for _ in 0..3 {
+            bus.publish(ServiceEvent::sync_started(None).into())
+                .await
+                .unwrap();
+        }
+        assert_eq!(store.latest_sequence().await.unwrap(), 3);
+
+        // A new bus over the same store continues the sequence and can
+        // serve catch-up requests from before it was created.
+        let restarted = EventBus::persistent(store.clone()).await.unwrap();
+        restarted
+            .publish(ServiceEvent::sync_started(None).into())
+            .await
+            .unwrap();
+        assert_eq!(restarted.latest_sequence().await, 4);
+
+        let sequences: Vec<u64> = restarted
+            .events_since(1)
+            .await
+            .iter()
+            .map(|e| e.sequence)
+            .collect();
+        assert_eq!(sequences, vec![2, 3, 4]);
+    }
+
+    /// Rejects every append
+    struct FailingStore;
+
+    #[async_trait]
+    impl crate::domain::repositories::EventStore for FailingStore {
+        async fn append(&self, _envelope: &EventEnvelope) -> DomainResult<()> {
+            Err(DomainError::InternalError("disk full".to_string()))
+        }
+
+        async fn latest_sequence(&self) -> DomainResult<u64> {
+            Ok(0)
+        }
+
+        async fn find_after(&self, _after: u64, _limit: usize) -> DomainResult<Vec<EventEnvelope>> {
+            Ok(Vec::new())
+        }
+
+        async fn find_from(
+            &self,
+            _from: chrono::DateTime<chrono::Utc>,
+            _after: u64,
+            _limit: usize,
+        ) -> DomainResult<Vec<EventEnvelope>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_append_is_returned_and_takes_no_sequence() {
+        let bus = EventBus::persistent(Arc::new(FailingStore)).await.unwrap();
+        let recorder = Arc::new(RecordingSubscriber::default());
+        bus.subscribe(recorder.clone());
+
+        assert!(bus
+            .publish(ServiceEvent::sync_started(None).into())
+            .await
+            .is_err());
+
+        assert_eq!(bus.latest_sequence().await, 0);
+        assert!(bus.events_since(0).await.is_empty());
+        assert!(recorder.received.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_history_is_bounded() {
+        let bus = EventBus::with_history_capacity(2);
+        for _ in 0..4 {
+            bus.publish(ServiceEvent::sync_started(None).into())
+                .await
+                .unwrap();
+        }
+
+        let sequences: Vec<u64> = bus
+            .events_since(0)
+            .await
+            .iter()
+            .map(|e| e.sequence)
+            .collect();
+        assert_eq!(sequences, vec![3, 4]);
+        assert_eq!(bus.latest_sequence().await, 4);
+    }
+}
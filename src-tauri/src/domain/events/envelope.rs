@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{JobEvent, NotificationEvent, ServiceEvent};
+
+/// Every event family that flows through the event bus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DomainEvent {
+    Notification(NotificationEvent),
+    Job(JobEvent),
+    Service(ServiceEvent),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventCategory {
+    Notification,
+    Job,
+    Service,
+}
+
+impl EventCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventCategory::Notification => "notification",
+            EventCategory::Job => "job",
+            EventCategory::Service => "service",
+        }
+    }
+}
+
+impl DomainEvent {
+    pub fn category(&self) -> EventCategory {
+        match self {
+            DomainEvent::Notification(_) => EventCategory::Notification,
+            DomainEvent::Job(_) => EventCategory::Job,
+            DomainEvent::Service(_) => EventCategory::Service,
+        }
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::Notification(event) => event.event_type(),
+            DomainEvent::Job(event) => event.event_type(),
+            DomainEvent::Service(event) => event.event_type(),
+        }
+    }
+}
+
+impl From<NotificationEvent> for DomainEvent {
+    fn from(event: NotificationEvent) -> Self {
+        DomainEvent::Notification(event)
+    }
+}
+
+impl From<JobEvent> for DomainEvent {
+    fn from(event: JobEvent) -> Self {
+        DomainEvent::Job(event)
+    }
+}
+
+impl From<ServiceEvent> for DomainEvent {
+    fn from(event: ServiceEvent) -> Self {
+        DomainEvent::Service(event)
+    }
+}
+
+/// Wire format shared by the webview forwarder and the catch-up command.
+///
+/// `sequence` is strictly increasing for the lifetime of the bus, so a client
+/// can ask for everything after the last sequence it has seen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    pub sequence: u64,
+    pub category: EventCategory,
+    pub occurred_at: DateTime<Utc>,
+    pub event: DomainEvent,
+}
+
+impl EventEnvelope {
+    pub fn new(sequence: u64, event: DomainEvent) -> Self {
+        Self {
+            sequence,
+            category: event.category(),
+            occurred_at: Utc::now(),
+            event,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_envelope_json_schema() {
+        let notification_id = Uuid::new_v4();
+        let envelope = EventEnvelope::new(
+            7,
+            NotificationEvent::notification_read(notification_id).into(),
+        );
+
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["sequence"], 7);
+        assert_eq!(json["category"], "notification");
+        assert_eq!(json["event"]["type"], "NotificationRead");
+        assert_eq!(envelope.event.event_type(), "NotificationRead");
+        assert_eq!(
+            json["event"]["notification_id"],
+            notification_id.to_string()
+        );
+
+        let decoded: EventEnvelope = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            decoded.event,
+            DomainEvent::Notification(NotificationEvent::NotificationRead { .. })
+        ));
+    }
+
+    #[test]
+    fn test_envelope_round_trips_every_family() {
+        let events: Vec<DomainEvent> = vec![
+            NotificationEvent::response_generated(Uuid::new_v4(), "ok".to_string()).into(),
+            JobEvent::job_completed(
+                Uuid::new_v4(),
+                crate::domain::services::background::JobType::NotificationProcessing,
+            )
+            .into(),
+            ServiceEvent::sync_completed(None, 3, 3, 0).into(),
+        ];
+
+        for (index, event) in events.into_iter().enumerate() {
+            let category = event.category();
+            let envelope = EventEnvelope::new(index as u64, event);
+            let json = serde_json::to_string(&envelope).unwrap();
+            let decoded: EventEnvelope = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.category, category);
+            assert_eq!(decoded.event.category(), category);
+        }
+    }
+}
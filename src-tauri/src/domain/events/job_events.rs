@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::services::background::types::JobType;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum JobEvent {
+    JobSubmitted {
+        job_id: Uuid,
+        job_type: JobType,
+        submitted_at: DateTime<Utc>,
+    },
+    JobStarted {
+        job_id: Uuid,
+        job_type: JobType,
+        started_at: DateTime<Utc>,
+    },
+    JobCompleted {
+        job_id: Uuid,
+        job_type: JobType,
+        completed_at: DateTime<Utc>,
+    },
+    JobFailed {
+        job_id: Uuid,
+        job_type: JobType,
+        error: String,
+        will_retry: bool,
+        failed_at: DateTime<Utc>,
+    },
+}
+
+impl JobEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::JobSubmitted { .. } => "JobSubmitted",
+            Self::JobStarted { .. } => "JobStarted",
+            Self::JobCompleted { .. } => "JobCompleted",
+            Self::JobFailed { .. } => "JobFailed",
+        }
+    }
+
+    pub fn job_submitted(job_id: Uuid, job_type: JobType) -> Self {
+        Self::JobSubmitted {
+            job_id,
+            job_type,
+            submitted_at: Utc::now(),
+        }
+    }
+
+    pub fn job_started(job_id: Uuid, job_type: JobType) -> Self {
+        Self::JobStarted {
+            job_id,
+            job_type,
+            started_at: Utc::now(),
+        }
+    }
+
+    pub fn job_completed(job_id: Uuid, job_type: JobType) -> Self {
+        Self::JobCompleted {
+            job_id,
+            job_type,
+            completed_at: Utc::now(),
+        }
+    }
+
+    pub fn job_failed(job_id: Uuid, job_type: JobType, error: String, will_retry: bool) -> Self {
+        Self::JobFailed {
+            job_id,
+            job_type,
+            error,
+            will_retry,
+            failed_at: Utc::now(),
+        }
+    }
+}
@@ -1,5 +1,17 @@
+pub mod bus;
+pub mod envelope;
+pub mod job_events;
 pub mod notification_events;
 pub mod publisher;
+pub mod replay;
+pub mod service_events;
+pub mod subscriber;
 
+pub use bus::EventBus;
+pub use envelope::{DomainEvent, EventCategory, EventEnvelope};
+pub use job_events::JobEvent;
 pub use notification_events::NotificationEvent;
 pub use publisher::{DynEventPublisher, EventPublisher, NoopEventPublisher};
+pub use replay::EventReplayer;
+pub use service_events::ServiceEvent;
+pub use subscriber::{DynEventSubscriber, EventSubscriber};
@@ -2,9 +2,18 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::entities::notification::{NotificationPriority, NotificationSource};
+use crate::domain::entities::escalation_policy::EscalationAction;
+use crate::domain::entities::notification::{
+    Notification, NotificationPriority, NotificationSource, NotificationStatus,
+};
+
+/// Responses logged before failures were recorded were all delivered
+fn delivered() -> bool {
+    true
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
 pub enum NotificationEvent {
     NotificationCreated {
         notification_id: Uuid,
@@ -13,6 +22,9 @@ pub enum NotificationEvent {
         priority: NotificationPriority,
         source: NotificationSource,
         created_at: DateTime<Utc>,
+        /// Held back by Do Not Disturb: recorded, but not to be alerted on
+        #[serde(default)]
+        held: bool,
     },
     NotificationProcessed {
         notification_id: Uuid,
@@ -39,11 +51,70 @@ pub enum NotificationEvent {
         notification_id: Uuid,
         deleted_at: DateTime<Utc>,
     },
+    NotificationSnoozed {
+        notification_id: Uuid,
+        snoozed_until: DateTime<Utc>,
+        snoozed_at: DateTime<Utc>,
+    },
+    NotificationUnsnoozed {
+        notification_id: Uuid,
+        restored_status: NotificationStatus,
+        unsnoozed_at: DateTime<Utc>,
+    },
+    /// Notifications held back by Do Not Disturb, released together once it ended
+    HeldNotificationsReleased {
+        notification_ids: Vec<Uuid>,
+        released_at: DateTime<Utc>,
+    },
+    /// Another item was merged into an aggregate notification
+    NotificationCoalesced {
+        notification_id: Uuid,
+        count: usize,
+        coalesced_at: DateTime<Utc>,
+        /// Held back by Do Not Disturb: recorded, but not to be alerted on
+        #[serde(default)]
+        held: bool,
+    },
+    /// A step of an escalation policy ran for a notification left `ActionRequired`
+    NotificationEscalated {
+        notification_id: Uuid,
+        policy_id: Uuid,
+        /// Index of the step in the policy
+        step: usize,
+        actions: Vec<EscalationAction>,
+        priority: NotificationPriority,
+        escalated_at: DateTime<Utc>,
+    },
+    /// Forwarded to `recipient`, for the webhooks relaying forwards to deliver
+    NotificationForwarded {
+        notification_id: Uuid,
+        recipient: String,
+        title: String,
+        content: String,
+        priority: NotificationPriority,
+        source: NotificationSource,
+        url: Option<String>,
+        forwarded_at: DateTime<Utc>,
+    },
+    RulesApplied {
+        notification_id: Uuid,
+        rule_ids: Vec<Uuid>,
+        applied_at: DateTime<Utc>,
+    },
     ResponseGenerated {
         notification_id: Uuid,
         response: String,
         generated_at: DateTime<Utc>,
     },
+    /// A reply was sent through the integration, `automatic` when a rule sent
+    /// it. `success` is false when the integration failed to deliver it.
+    ResponseSent {
+        notification_id: Uuid,
+        automatic: bool,
+        #[serde(default = "delivered")]
+        success: bool,
+        sent_at: DateTime<Utc>,
+    },
     ActionExecuted {
         notification_id: Uuid,
         executed_at: DateTime<Utc>,
@@ -53,6 +124,41 @@ pub enum NotificationEvent {
 }
 
 impl NotificationEvent {
+    /// Name of the variant, matching the `type` field of the serialized event
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::NotificationCreated { .. } => "NotificationCreated",
+            Self::NotificationProcessed { .. } => "NotificationProcessed",
+            Self::NotificationActionRequired { .. } => "NotificationActionRequired",
+            Self::NotificationActionTaken { .. } => "NotificationActionTaken",
+            Self::NotificationRead { .. } => "NotificationRead",
+            Self::NotificationArchived { .. } => "NotificationArchived",
+            Self::NotificationDeleted { .. } => "NotificationDeleted",
+            Self::NotificationSnoozed { .. } => "NotificationSnoozed",
+            Self::NotificationUnsnoozed { .. } => "NotificationUnsnoozed",
+            Self::HeldNotificationsReleased { .. } => "HeldNotificationsReleased",
+            Self::NotificationCoalesced { .. } => "NotificationCoalesced",
+            Self::NotificationEscalated { .. } => "NotificationEscalated",
+            Self::NotificationForwarded { .. } => "NotificationForwarded",
+            Self::RulesApplied { .. } => "RulesApplied",
+            Self::ResponseGenerated { .. } => "ResponseGenerated",
+            Self::ResponseSent { .. } => "ResponseSent",
+            Self::ActionExecuted { .. } => "ActionExecuted",
+        }
+    }
+
+    pub fn notification_created(notification: &Notification, held: bool) -> Self {
+        Self::NotificationCreated {
+            notification_id: notification.id,
+            title: notification.title.clone(),
+            content: notification.content.clone(),
+            priority: notification.priority.clone(),
+            source: notification.metadata.source.clone(),
+            created_at: notification.created_at,
+            held,
+        }
+    }
+
     pub fn notification_processed(notification_id: Uuid, requires_action: bool) -> Self {
         Self::NotificationProcessed {
             notification_id,
@@ -68,6 +174,13 @@ impl NotificationEvent {
         }
     }
 
+    pub fn notification_action_taken(notification_id: Uuid) -> Self {
+        Self::NotificationActionTaken {
+            notification_id,
+            marked_at: Utc::now(),
+        }
+    }
+
     pub fn notification_read(notification_id: Uuid) -> Self {
         Self::NotificationRead {
             notification_id,
@@ -75,6 +188,93 @@ impl NotificationEvent {
         }
     }
 
+    pub fn notification_archived(notification_id: Uuid) -> Self {
+        Self::NotificationArchived {
+            notification_id,
+            archived_at: Utc::now(),
+        }
+    }
+
+    pub fn notification_deleted(notification_id: Uuid) -> Self {
+        Self::NotificationDeleted {
+            notification_id,
+            deleted_at: Utc::now(),
+        }
+    }
+
+    pub fn notification_snoozed(notification_id: Uuid, snoozed_until: DateTime<Utc>) -> Self {
+        Self::NotificationSnoozed {
+            notification_id,
+            snoozed_until,
+            snoozed_at: Utc::now(),
+        }
+    }
+
+    pub fn notification_unsnoozed(
+        notification_id: Uuid,
+        restored_status: NotificationStatus,
+    ) -> Self {
+        Self::NotificationUnsnoozed {
+            notification_id,
+            restored_status,
+            unsnoozed_at: Utc::now(),
+        }
+    }
+
+    pub fn held_notifications_released(notification_ids: Vec<Uuid>) -> Self {
+        Self::HeldNotificationsReleased {
+            notification_ids,
+            released_at: Utc::now(),
+        }
+    }
+
+    pub fn notification_coalesced(notification_id: Uuid, count: usize, held: bool) -> Self {
+        Self::NotificationCoalesced {
+            notification_id,
+            count,
+            coalesced_at: Utc::now(),
+            held,
+        }
+    }
+
+    pub fn notification_escalated(
+        notification_id: Uuid,
+        policy_id: Uuid,
+        step: usize,
+        actions: Vec<EscalationAction>,
+        priority: NotificationPriority,
+    ) -> Self {
+        Self::NotificationEscalated {
+            notification_id,
+            policy_id,
+            step,
+            actions,
+            priority,
+            escalated_at: Utc::now(),
+        }
+    }
+
+    pub fn notification_forwarded(notification: &Notification, recipient: &str) -> Self {
+        Self::NotificationForwarded {
+            notification_id: notification.id,
+            recipient: recipient.to_string(),
+            title: notification.title.clone(),
+            content: notification.content.clone(),
+            priority: notification.priority.clone(),
+            source: notification.metadata.source.clone(),
+            url: notification.metadata.url.clone(),
+            forwarded_at: Utc::now(),
+        }
+    }
+
+    pub fn rules_applied(notification_id: Uuid, rule_ids: Vec<Uuid>) -> Self {
+        Self::RulesApplied {
+            notification_id,
+            rule_ids,
+            applied_at: Utc::now(),
+        }
+    }
+
     pub fn response_generated(notification_id: Uuid, response: String) -> Self {
         Self::ResponseGenerated {
             notification_id,
@@ -83,6 +283,15 @@ impl NotificationEvent {
         }
     }
 
+    pub fn response_sent(notification_id: Uuid, automatic: bool, success: bool) -> Self {
+        Self::ResponseSent {
+            notification_id,
+            automatic,
+            success,
+            sent_at: Utc::now(),
+        }
+    }
+
     pub fn action_executed(notification_id: Uuid, success: bool, error: Option<String>) -> Self {
         Self::ActionExecuted {
             notification_id,
@@ -1,12 +1,21 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 
-use super::NotificationEvent;
+use super::{DomainEvent, NotificationEvent};
 use crate::domain::error::DomainResult;
 
 #[async_trait]
 pub trait EventPublisher: Send + Sync {
     async fn publish_event(&self, event: NotificationEvent) -> DomainResult<()>;
+
+    /// Publishes an event of any family. Publishers that only understand
+    /// notification events drop the others.
+    async fn publish(&self, event: DomainEvent) -> DomainResult<()> {
+        match event {
+            DomainEvent::Notification(event) => self.publish_event(event).await,
+            _ => Ok(()),
+        }
+    }
 }
 
 pub type DynEventPublisher = Arc<dyn EventPublisher>;
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+use super::{EventEnvelope, EventSubscriber};
+use crate::domain::{
+    error::{DomainError, DomainResult},
+    repositories::DynEventStore,
+};
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// Reads the persisted event log back in sequence order, for rebuilding
+/// projections (statistics, search indexes, audit views) from scratch.
+pub struct EventReplayer {
+    store: DynEventStore,
+    batch_size: usize,
+}
+
+impl EventReplayer {
+    pub fn new(store: DynEventStore) -> Self {
+        Self {
+            store,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Streams every stored event that occurred at or after `from`, oldest
+    /// first. Events are fetched lazily in batches.
+    pub fn stream_from(
+        &self,
+        from: DateTime<Utc>,
+    ) -> BoxStream<'static, DomainResult<EventEnvelope>> {
+        let store = self.store.clone();
+        let batch_size = self.batch_size;
+
+        stream::try_unfold(0u64, move |after_sequence| {
+            let store = store.clone();
+            async move {
+                let batch = store.find_from(from, after_sequence, batch_size).await?;
+                let next = batch.last().map(|last| last.sequence);
+                Ok::<_, DomainError>(next.map(|next| {
+                    let events = batch.into_iter().map(Ok::<_, DomainError>);
+                    (stream::iter(events), next)
+                }))
+            }
+        })
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Feeds every event from `from` onwards to `handler`, stopping at the
+    /// first error. Returns the number of events replayed.
+    pub async fn replay_from(
+        &self,
+        from: DateTime<Utc>,
+        handler: &dyn EventSubscriber,
+    ) -> DomainResult<usize> {
+        let mut events = self.stream_from(from);
+        let mut replayed = 0;
+
+        while let Some(envelope) = events.next().await {
+            handler.handle_event(&envelope?).await?;
+            replayed += 1;
+        }
+
+        Ok(replayed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::{EventBus, EventPublisher, NotificationEvent, ServiceEvent};
+    use crate::domain::repositories::EventStore;
+    use crate::infrastructure::repositories::SqliteEventStore;
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct ReadCounter {
+        reads: Mutex<usize>,
+        sequences: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for ReadCounter {
+        fn name(&self) -> &str {
+            "read-counter"
+        }
+
+        async fn handle_event(&self, envelope: &EventEnvelope) -> DomainResult<()> {
+            if envelope.event.event_type() == "NotificationRead" {
+                *self.reads.lock().await += 1;
+            }
+            self.sequences.lock().await.push(envelope.sequence);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_rebuilds_projection_across_batches() {
+        let store = Arc::new(SqliteEventStore::new(":memory:").unwrap());
+        let bus = EventBus::persistent(store.clone()).await.unwrap();
+        for _ in 0..5 {
+            bus.publish_event(NotificationEvent::notification_read(Uuid::new_v4()))
+                .await
+                .unwrap();
+            bus.publish(ServiceEvent::sync_started(None).into())
+                .await
+                .unwrap();
+        }
+
+        let projection = ReadCounter::default();
+        let replayed = EventReplayer::new(store)
+            .with_batch_size(3)
+            .replay_from(Utc::now() - chrono::Duration::hours(1), &projection)
+            .await
+            .unwrap();
+
+        assert_eq!(replayed, 10);
+        assert_eq!(*projection.reads.lock().await, 5);
+        assert_eq!(
+            *projection.sequences.lock().await,
+            (1..=10).collect::<Vec<u64>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_from_skips_older_events() {
+        let store = Arc::new(SqliteEventStore::new(":memory:").unwrap());
+        let mut old = EventEnvelope::new(1, ServiceEvent::sync_started(None).into());
+        old.occurred_at = Utc::now() - chrono::Duration::days(3);
+        store.append(&old).await.unwrap();
+        store
+            .append(&EventEnvelope::new(
+                2,
+                ServiceEvent::sync_completed(None, 1, 1, 0).into(),
+            ))
+            .await
+            .unwrap();
+
+        let events: Vec<EventEnvelope> = EventReplayer::new(store)
+            .stream_from(Utc::now() - chrono::Duration::days(1))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].sequence, 2);
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::service_config::ServiceType;
+
+/// Integration lifecycle and sync events.
+///
+/// Sync events carry the `service_type` they concern, or `None` for the
+/// aggregate run across every connected service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ServiceEvent {
+    ServiceConnected {
+        service_type: ServiceType,
+        connected_at: DateTime<Utc>,
+    },
+    ServiceDisconnected {
+        service_type: ServiceType,
+        disconnected_at: DateTime<Utc>,
+    },
+    TokenExpired {
+        service_type: ServiceType,
+        detected_at: DateTime<Utc>,
+    },
+    SyncStarted {
+        #[serde(default)]
+        service_type: Option<ServiceType>,
+        started_at: DateTime<Utc>,
+    },
+    SyncCompleted {
+        #[serde(default)]
+        service_type: Option<ServiceType>,
+        fetched_count: usize,
+        created_count: usize,
+        /// Fetched items that could not be created, merged items count as created
+        failed_count: usize,
+        completed_at: DateTime<Utc>,
+    },
+    SyncFailed {
+        #[serde(default)]
+        service_type: Option<ServiceType>,
+        error: String,
+        failed_at: DateTime<Utc>,
+    },
+}
+
+impl ServiceEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::ServiceConnected { .. } => "ServiceConnected",
+            Self::ServiceDisconnected { .. } => "ServiceDisconnected",
+            Self::TokenExpired { .. } => "TokenExpired",
+            Self::SyncStarted { .. } => "SyncStarted",
+            Self::SyncCompleted { .. } => "SyncCompleted",
+            Self::SyncFailed { .. } => "SyncFailed",
+        }
+    }
+
+    pub fn service_connected(service_type: ServiceType) -> Self {
+        Self::ServiceConnected {
+            service_type,
+            connected_at: Utc::now(),
+        }
+    }
+
+    pub fn service_disconnected(service_type: ServiceType) -> Self {
+        Self::ServiceDisconnected {
+            service_type,
+            disconnected_at: Utc::now(),
+        }
+    }
+
+    pub fn token_expired(service_type: ServiceType) -> Self {
+        Self::TokenExpired {
+            service_type,
+            detected_at: Utc::now(),
+        }
+    }
+
+    pub fn sync_started(service_type: Option<ServiceType>) -> Self {
+        Self::SyncStarted {
+            service_type,
+            started_at: Utc::now(),
+        }
+    }
+
+    pub fn sync_completed(
+        service_type: Option<ServiceType>,
+        fetched_count: usize,
+        created_count: usize,
+        failed_count: usize,
+    ) -> Self {
+        Self::SyncCompleted {
+            service_type,
+            fetched_count,
+            created_count,
+            failed_count,
+            completed_at: Utc::now(),
+        }
+    }
+
+    pub fn sync_failed(service_type: Option<ServiceType>, error: String) -> Self {
+        Self::SyncFailed {
+            service_type,
+            error,
+            failed_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_completed_carries_counts() {
+        let event = ServiceEvent::sync_completed(Some(ServiceType::Github), 5, 3, 2);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "SyncCompleted");
+        assert_eq!(json["service_type"], "Github");
+        assert_eq!(json["fetched_count"], 5);
+        assert_eq!(json["created_count"], 3);
+        assert_eq!(json["failed_count"], 2);
+    }
+}
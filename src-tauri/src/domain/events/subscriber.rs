@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::EventEnvelope;
+use crate::domain::error::DomainResult;
+
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Short name used when logging delivery failures
+    fn name(&self) -> &str;
+
+    async fn handle_event(&self, envelope: &EventEnvelope) -> DomainResult<()>;
+}
+
+pub type DynEventSubscriber = Arc<dyn EventSubscriber>;
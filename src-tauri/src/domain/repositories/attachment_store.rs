@@ -0,0 +1,16 @@
+use crate::domain::error::DomainResult;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Local cache of attachment content, addressed by content hash
+#[async_trait]
+pub trait AttachmentStore: Send + Sync {
+    /// Stores the content, evicting the least recently stored content when
+    /// the cache is full. Returns false when the content is too large to be
+    /// cached.
+    async fn put(&self, hash: &str, data: &[u8]) -> DomainResult<bool>;
+    /// The content, unless it was never stored or has been evicted
+    async fn get(&self, hash: &str) -> DomainResult<Option<Vec<u8>>>;
+}
+
+pub type DynAttachmentStore = Arc<dyn AttachmentStore>;
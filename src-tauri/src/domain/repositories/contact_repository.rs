@@ -0,0 +1,34 @@
+use crate::domain::{
+    entities::{Contact, NotificationSource},
+    error::DomainResult,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Notifications keep a snapshot of their sender's contact. Saving, deleting
+/// and merging contacts refresh those snapshots in the same transaction.
+#[async_trait]
+pub trait ContactRepository: Send + Sync {
+    async fn save(&self, contact: &mut Contact) -> DomainResult<()>;
+    async fn find_by_id(&self, id: uuid::Uuid) -> DomainResult<Option<Contact>>;
+    /// All contacts by name
+    async fn find_all(&self) -> DomainResult<Vec<Contact>>;
+    /// The contact owning this identity, handles are compared ignoring case
+    async fn find_by_identity(
+        &self,
+        source: &NotificationSource,
+        handle: &str,
+    ) -> DomainResult<Option<Contact>> {
+        Ok(self
+            .find_all()
+            .await?
+            .into_iter()
+            .find(|contact| contact.has_identity(source, handle)))
+    }
+    /// Unlinks the notifications of the deleted contact
+    async fn delete(&self, id: uuid::Uuid) -> DomainResult<()>;
+    /// Saves `target` and deletes `other`, linking the notifications of both to `target`
+    async fn merge(&self, target: &mut Contact, other: uuid::Uuid) -> DomainResult<()>;
+}
+
+pub type DynContactRepository = Arc<dyn ContactRepository>;
@@ -0,0 +1,18 @@
+use crate::domain::{entities::ConversationEntry, error::DomainResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// What the AI remembers of each conversation, by conversation key
+#[async_trait]
+pub trait ConversationMemoryRepository: Send + Sync {
+    /// The remembered turns, oldest first, none for an unknown conversation
+    async fn find_entries(&self, conversation: &str) -> DomainResult<Vec<ConversationEntry>>;
+    async fn save_entries(
+        &self,
+        conversation: &str,
+        entries: &[ConversationEntry],
+    ) -> DomainResult<()>;
+    async fn delete(&self, conversation: &str) -> DomainResult<()>;
+}
+
+pub type DynConversationMemoryRepository = Arc<dyn ConversationMemoryRepository>;
@@ -0,0 +1,20 @@
+use crate::domain::{
+    entities::{Digest, DigestSettings},
+    error::DomainResult,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait DigestRepository: Send + Sync {
+    /// The saved settings, the defaults when nothing was saved yet
+    async fn get_settings(&self) -> DomainResult<DigestSettings>;
+    async fn save_settings(&self, settings: &mut DigestSettings) -> DomainResult<()>;
+    async fn save(&self, digest: &Digest) -> DomainResult<()>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Digest>>;
+    /// Most recent digests first
+    async fn find_recent(&self, limit: usize) -> DomainResult<Vec<Digest>>;
+}
+
+pub type DynDigestRepository = Arc<dyn DigestRepository>;
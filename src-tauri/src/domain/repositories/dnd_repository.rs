@@ -0,0 +1,25 @@
+use crate::domain::{entities::DndSettings, error::DomainResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait DndRepository: Send + Sync {
+    /// The saved settings, the defaults when nothing was saved yet
+    async fn get_settings(&self) -> DomainResult<DndSettings>;
+    async fn save_settings(&self, settings: &mut DndSettings) -> DomainResult<()>;
+    /// Records a notification held back while DND is active
+    async fn hold(&self, notification_id: Uuid) -> DomainResult<()>;
+    async fn is_held(&self, notification_id: Uuid) -> DomainResult<bool>;
+    /// Held notifications in the order they arrived
+    async fn find_held(&self) -> DomainResult<Vec<Uuid>>;
+    async fn release(&self, notification_ids: &[Uuid]) -> DomainResult<()>;
+    /// Queues an auto-response to send once DND ends
+    async fn defer_response(&self, notification_id: Uuid, response: &str) -> DomainResult<()>;
+    /// The queued auto-responses in the order they were deferred, each with
+    /// the id to remove it by once sent
+    async fn find_deferred_responses(&self) -> DomainResult<Vec<(i64, Uuid, String)>>;
+    async fn remove_deferred_response(&self, id: i64) -> DomainResult<()>;
+}
+
+pub type DynDndRepository = Arc<dyn DndRepository>;
@@ -0,0 +1,14 @@
+use crate::domain::{entities::EscalationPolicy, error::DomainResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait EscalationPolicyRepository: Send + Sync {
+    async fn save(&self, policy: &mut EscalationPolicy) -> DomainResult<()>;
+    async fn find_by_id(&self, id: uuid::Uuid) -> DomainResult<Option<EscalationPolicy>>;
+    /// All policies in evaluation order
+    async fn find_all(&self) -> DomainResult<Vec<EscalationPolicy>>;
+    async fn delete(&self, id: uuid::Uuid) -> DomainResult<()>;
+}
+
+pub type DynEscalationPolicyRepository = Arc<dyn EscalationPolicyRepository>;
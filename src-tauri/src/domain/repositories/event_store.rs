@@ -0,0 +1,27 @@
+use crate::domain::{error::DomainResult, events::EventEnvelope};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// Append-only log of every published event, keyed by sequence number.
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    async fn append(&self, envelope: &EventEnvelope) -> DomainResult<()>;
+    async fn latest_sequence(&self) -> DomainResult<u64>;
+    /// Events with a sequence greater than `after_sequence`, oldest first.
+    async fn find_after(
+        &self,
+        after_sequence: u64,
+        limit: usize,
+    ) -> DomainResult<Vec<EventEnvelope>>;
+    /// Events that occurred at or after `from` with a sequence greater than
+    /// `after_sequence`, oldest first.
+    async fn find_from(
+        &self,
+        from: DateTime<Utc>,
+        after_sequence: u64,
+        limit: usize,
+    ) -> DomainResult<Vec<EventEnvelope>>;
+}
+
+pub type DynEventStore = Arc<dyn EventStore>;
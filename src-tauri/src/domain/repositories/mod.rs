@@ -1,5 +1,39 @@
+pub mod attachment_store;
+pub mod contact_repository;
+pub mod conversation_memory_repository;
+pub mod digest_repository;
+pub mod dnd_repository;
+pub mod escalation_policy_repository;
+pub mod event_store;
+pub mod note_repository;
 pub mod notification_repository;
+pub mod rule_repository;
+pub mod saved_view_repository;
 pub mod service_config_repository;
+pub mod settings_repository;
+pub mod statistics_repository;
+pub mod tag_repository;
+pub mod webhook_repository;
 
-pub use notification_repository::{DynNotificationRepository, NotificationRepository};
+pub use attachment_store::{AttachmentStore, DynAttachmentStore};
+pub use contact_repository::{ContactRepository, DynContactRepository};
+pub use conversation_memory_repository::{
+    ConversationMemoryRepository, DynConversationMemoryRepository,
+};
+pub use digest_repository::{DigestRepository, DynDigestRepository};
+pub use dnd_repository::{DndRepository, DynDndRepository};
+pub use escalation_policy_repository::{DynEscalationPolicyRepository, EscalationPolicyRepository};
+pub use event_store::{DynEventStore, EventStore};
+pub use note_repository::{DynNoteRepository, NoteRepository};
+pub use notification_repository::{
+    CoalescingMerge, DynNotificationRepository, NotificationRepository,
+};
+pub use rule_repository::{DynRuleRepository, RuleRepository};
+pub use saved_view_repository::{DynSavedViewRepository, SavedViewRepository};
 pub use service_config_repository::{DynServiceConfigRepository, ServiceConfigRepository};
+pub use settings_repository::{
+    load_settings, store_settings, DynSettingsRepository, SettingsRepository,
+};
+pub use statistics_repository::{DynStatisticsRepository, StatisticsRepository};
+pub use tag_repository::{DynTagRepository, TagRepository};
+pub use webhook_repository::{DynWebhookRepository, WebhookRepository};
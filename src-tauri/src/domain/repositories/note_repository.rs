@@ -0,0 +1,22 @@
+use crate::domain::{
+    entities::{Note, NoteTarget, Notification},
+    error::DomainResult,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait NoteRepository: Send + Sync {
+    async fn save(&self, note: &mut Note) -> DomainResult<()>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Note>>;
+    /// Notes on exactly this notification or thread, oldest first
+    async fn find_by_target(&self, target: &NoteTarget) -> DomainResult<Vec<Note>>;
+    /// Notes on the notification and on its thread, oldest first
+    async fn find_for_notification(&self, notification: &Notification) -> DomainResult<Vec<Note>>;
+    /// Notes containing every word of the query, ignoring case, newest first
+    async fn search(&self, query: &str) -> DomainResult<Vec<Note>>;
+    async fn delete(&self, id: Uuid) -> DomainResult<()>;
+}
+
+pub type DynNoteRepository = Arc<dyn NoteRepository>;
@@ -1,4 +1,7 @@
-use crate::domain::{entities::Notification, error::DomainResult};
+use crate::domain::{
+    entities::{Notification, SavedView},
+    error::DomainResult,
+};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -15,6 +18,23 @@ pub trait NotificationRepository: Send + Sync + std::fmt::Debug {
         &self,
         source: crate::domain::entities::NotificationSource,
     ) -> DomainResult<Vec<Notification>>;
+    async fn find_by_thread_key(&self, thread_key: &str) -> DomainResult<Vec<Notification>>;
+    /// Aggregates of coalesced notifications sharing this grouping key
+    async fn find_by_coalescing_key(&self, key: &str) -> DomainResult<Vec<Notification>>;
+    /// Saves the notification `merge` builds from the aggregates sharing
+    /// `key`. Implementations look the aggregates up and save in one
+    /// transaction, so concurrent notifications cannot both start an aggregate.
+    async fn save_coalesced(&self, key: &str, merge: CoalescingMerge)
+        -> DomainResult<Notification>;
+    /// Notifications carrying this tag, compared ignoring case
+    async fn find_by_tag(&self, tag: &str) -> DomainResult<Vec<Notification>>;
+    /// Every tag in use with the number of notifications carrying it
+    async fn count_by_tag(&self) -> DomainResult<Vec<(String, usize)>>;
+    /// Notifications shown in the view, unsorted. The query is also matched
+    /// against the notes when they share the database.
+    async fn find_in_view(&self, view: &SavedView) -> DomainResult<Vec<Notification>>;
+    /// How many notifications the view shows and how many of them are `New`
+    async fn count_in_view(&self, view: &SavedView) -> DomainResult<(usize, usize)>;
     async fn delete(&self, id: uuid::Uuid) -> DomainResult<()>;
     async fn update_status(
         &self,
@@ -24,3 +44,6 @@ pub trait NotificationRepository: Send + Sync + std::fmt::Debug {
 }
 
 pub type DynNotificationRepository = Arc<dyn NotificationRepository>;
+
+/// Builds the notification to save from the aggregates sharing its coalescing key
+pub type CoalescingMerge = Box<dyn FnOnce(Vec<Notification>) -> Notification + Send>;
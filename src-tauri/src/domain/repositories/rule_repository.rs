@@ -0,0 +1,14 @@
+use crate::domain::{entities::Rule, error::DomainResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait RuleRepository: Send + Sync {
+    async fn save(&self, rule: &mut Rule) -> DomainResult<()>;
+    async fn find_by_id(&self, id: uuid::Uuid) -> DomainResult<Option<Rule>>;
+    /// All rules in evaluation order
+    async fn find_all(&self) -> DomainResult<Vec<Rule>>;
+    async fn delete(&self, id: uuid::Uuid) -> DomainResult<()>;
+}
+
+pub type DynRuleRepository = Arc<dyn RuleRepository>;
@@ -0,0 +1,15 @@
+use crate::domain::{entities::SavedView, error::DomainResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait SavedViewRepository: Send + Sync {
+    async fn save(&self, view: &mut SavedView) -> DomainResult<()>;
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<SavedView>>;
+    /// Pinned views first, then by position and name
+    async fn find_all(&self) -> DomainResult<Vec<SavedView>>;
+    async fn delete(&self, id: Uuid) -> DomainResult<()>;
+}
+
+pub type DynSavedViewRepository = Arc<dyn SavedViewRepository>;
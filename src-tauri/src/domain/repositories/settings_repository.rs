@@ -0,0 +1,40 @@
+use crate::domain::error::{DomainError, DomainResult};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Settings of the features without a table of their own, stored as JSON
+/// under a key per feature
+#[async_trait]
+pub trait SettingsRepository: Send + Sync {
+    /// The value saved under `key`, `None` when nothing was saved yet
+    async fn get(&self, key: &str) -> DomainResult<Option<Value>>;
+    async fn save(&self, key: &str, value: &Value) -> DomainResult<()>;
+}
+
+pub type DynSettingsRepository = Arc<dyn SettingsRepository>;
+
+/// The settings saved under `key`, the defaults when nothing was saved yet
+pub async fn load_settings<T: DeserializeOwned + Default>(
+    repository: &dyn SettingsRepository,
+    key: &str,
+) -> DomainResult<T> {
+    match repository.get(key).await? {
+        Some(value) => serde_json::from_value(value).map_err(|e| {
+            DomainError::InternalError(format!("Failed to read {} settings: {}", key, e))
+        }),
+        None => Ok(T::default()),
+    }
+}
+
+pub async fn store_settings<T: Serialize>(
+    repository: &dyn SettingsRepository,
+    key: &str,
+    settings: &T,
+) -> DomainResult<()> {
+    let value = serde_json::to_value(settings).map_err(|e| {
+        DomainError::InternalError(format!("Failed to write {} settings: {}", key, e))
+    })?;
+    repository.save(key, &value).await
+}
@@ -0,0 +1,21 @@
+use crate::domain::{
+    entities::{NotificationStatistics, TimeBucket},
+    error::DomainResult,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+#[async_trait]
+pub trait StatisticsRepository: Send + Sync {
+    /// Aggregates the notifications received in `[from, to)` and the events
+    /// recorded in the same period
+    async fn get_statistics(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: TimeBucket,
+    ) -> DomainResult<NotificationStatistics>;
+}
+
+pub type DynStatisticsRepository = Arc<dyn StatisticsRepository>;
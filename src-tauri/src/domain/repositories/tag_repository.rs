@@ -0,0 +1,16 @@
+use crate::domain::{entities::Tag, error::DomainResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    /// Creates or replaces the tag with the same name
+    async fn save(&self, tag: &mut Tag) -> DomainResult<()>;
+    /// Names are compared ignoring case
+    async fn find_by_name(&self, name: &str) -> DomainResult<Option<Tag>>;
+    /// All saved tags by name
+    async fn find_all(&self) -> DomainResult<Vec<Tag>>;
+    async fn delete(&self, name: &str) -> DomainResult<()>;
+}
+
+pub type DynTagRepository = Arc<dyn TagRepository>;
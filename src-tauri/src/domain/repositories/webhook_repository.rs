@@ -0,0 +1,23 @@
+use crate::domain::{
+    entities::{WebhookDelivery, WebhookSubscription},
+    error::DomainResult,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn save(&self, subscription: &mut WebhookSubscription) -> DomainResult<()>;
+    async fn find_by_id(&self, id: uuid::Uuid) -> DomainResult<Option<WebhookSubscription>>;
+    async fn find_all(&self) -> DomainResult<Vec<WebhookSubscription>>;
+    async fn find_enabled(&self) -> DomainResult<Vec<WebhookSubscription>>;
+    async fn delete(&self, id: uuid::Uuid) -> DomainResult<()>;
+    async fn record_delivery(&self, delivery: &WebhookDelivery) -> DomainResult<()>;
+    async fn find_deliveries(
+        &self,
+        subscription_id: uuid::Uuid,
+        limit: usize,
+    ) -> DomainResult<Vec<WebhookDelivery>>;
+}
+
+pub type DynWebhookRepository = Arc<dyn WebhookRepository>;
@@ -171,6 +171,7 @@ mod tests {
                 url: Some("http://test.com".to_string()),
                 tags: vec!["test".to_string()],
                 custom_data,
+                ..Default::default()
             },
         )
     }
@@ -0,0 +1,284 @@
+use crate::domain::error::{DomainError, DomainResult};
+use serde_json::{Map, Value};
+use std::future::Future;
+use tracing::debug;
+
+use super::{AIAnalysis, AnalysisRepair, ChatMessage};
+
+const ANALYSIS_INSTRUCTIONS: &str = "You analyze the notification the user sends and answer in \
+    JSON only, with the fields requires_action (true when the user has to do something), \
+    priority_level (Low, Medium, High or Critical), summary (one sentence) and \
+    suggested_actions (a list of short actions).";
+
+/// Asks the model, through `chat`, to analyse `content`. An answer that
+/// cannot be read even once repaired is fed back with the reason, once.
+pub async fn request_analysis<F, Fut>(content: &str, chat: F) -> DomainResult<AIAnalysis>
+where
+    F: Fn(Vec<ChatMessage>) -> Fut,
+    Fut: Future<Output = DomainResult<String>>,
+{
+    let mut messages = vec![
+        ChatMessage::system(ANALYSIS_INSTRUCTIONS),
+        ChatMessage::user(content),
+    ];
+    let response = chat(messages.clone()).await?;
+    let error = match parse_analysis(&response) {
+        Ok(analysis) => {
+            if analysis.repair != AnalysisRepair::NotNeeded {
+                debug!("Repaired AI analysis: {:?}", analysis.repair);
+            }
+            return Ok(analysis);
+        }
+        Err(error) => error,
+    };
+
+    debug!("Retrying unreadable AI analysis: {}", error);
+    messages.push(ChatMessage::assistant(response));
+    messages.push(ChatMessage::user(format!(
+        "Your answer could not be read: {}. Answer again with the JSON object only.",
+        error
+    )));
+    let response = chat(messages).await?;
+    parse_analysis(&response)
+        .map(|analysis| AIAnalysis {
+            retried: true,
+            ..analysis
+        })
+        .map_err(|e| DomainError::ValidationError(format!("Failed to parse AI analysis: {}", e)))
+}
+
+/// Reads the analysis out of the model's output, repairing what it can.
+///
+/// The output is read as is first, then with the JSON cut out of a code
+/// fence or surrounding prose, then with field names and values mapped to
+/// the expected ones. The error of the last attempt is returned when none
+/// worked, to be fed back to the model.
+pub fn parse_analysis(output: &str) -> Result<AIAnalysis, String> {
+    let output = output.trim();
+    if let Ok(analysis) = serde_json::from_str::<AIAnalysis>(output) {
+        return Ok(analysis.repaired(AnalysisRepair::NotNeeded));
+    }
+
+    let json = extract_json(output).ok_or_else(|| "the answer has no JSON object".to_string())?;
+    if json != output {
+        if let Ok(analysis) = serde_json::from_str::<AIAnalysis>(json) {
+            return Ok(analysis.repaired(AnalysisRepair::Extracted));
+        }
+    }
+
+    let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let Value::Object(fields) = value else {
+        return Err("the answer is not a JSON object".to_string());
+    };
+    serde_json::from_value::<AIAnalysis>(Value::Object(normalize(fields)))
+        .map(|analysis| analysis.repaired(AnalysisRepair::Normalized))
+        .map_err(|e| e.to_string())
+}
+
+impl AIAnalysis {
+    fn repaired(mut self, repair: AnalysisRepair) -> Self {
+        self.repair = repair;
+        self
+    }
+}
+
+/// The first JSON object of the output, looked for inside a code fence
+/// when there is one
+fn extract_json(output: &str) -> Option<&str> {
+    let mut text = output;
+    if let Some(start) = text.find("```") {
+        // Skips the language tag of the fence
+        let fenced = &text[start + 3..];
+        let fenced = &fenced[fenced.find('\n').map_or(0, |i| i + 1)..];
+        text = &fenced[..fenced.find("```").unwrap_or(fenced.len())];
+    }
+
+    let start = text.find('{')?;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[start..start + i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Maps field names and values to the expected ones. A field the output
+/// already has under its expected name wins over its aliases.
+fn normalize(fields: Map<String, Value>) -> Map<String, Value> {
+    let mut normalized = Map::new();
+    let mut canonical = Vec::new();
+    for (name, value) in fields {
+        let key = match snake_case(&name).as_str() {
+            "priority" | "priority_level" | "urgency" => "priority_level",
+            "requires_action" | "action_required" | "needs_action" => "requires_action",
+            "suggested_actions" | "actions" | "next_steps" => "suggested_actions",
+            _ => {
+                normalized.insert(name, value);
+                continue;
+            }
+        };
+        if canonical.contains(&key) {
+            continue;
+        }
+        if name == key {
+            canonical.push(key);
+        }
+        let value = match key {
+            "priority_level" => normalize_priority(value),
+            "requires_action" => normalize_bool(value),
+            _ => normalize_actions(value),
+        };
+        normalized.insert(key.to_string(), value);
+    }
+    normalized
+}
+
+/// "requiresAction", "Requires Action" and "PRIORITY" to "requires_action",
+/// "requires_action" and "priority"
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut after_lowercase = false;
+    for c in name.trim().chars() {
+        if c.is_uppercase() && after_lowercase {
+            snake.push('_');
+        }
+        after_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        match c {
+            ' ' | '-' => snake.push('_'),
+            _ => snake.extend(c.to_lowercase()),
+        }
+    }
+    snake
+}
+
+fn normalize_priority(value: Value) -> Value {
+    let Value::String(priority) = &value else {
+        return value;
+    };
+    let level = match priority.trim().to_lowercase().as_str() {
+        "low" | "minor" | "trivial" | "none" => "Low",
+        "medium" | "normal" | "moderate" => "Medium",
+        "high" | "important" | "major" => "High",
+        "critical" | "urgent" | "blocker" | "emergency" | "highest" => "Critical",
+        _ => return value,
+    };
+    Value::String(level.to_string())
+}
+
+fn normalize_bool(value: Value) -> Value {
+    match &value {
+        Value::String(text) => match text.trim().to_lowercase().as_str() {
+            "true" | "yes" | "y" => Value::Bool(true),
+            "false" | "no" | "n" => Value::Bool(false),
+            _ => value,
+        },
+        Value::Number(number) if number.as_u64() == Some(1) => Value::Bool(true),
+        Value::Number(number) if number.as_u64() == Some(0) => Value::Bool(false),
+        _ => value,
+    }
+}
+
+fn normalize_actions(value: Value) -> Value {
+    match value {
+        Value::Null => Value::Array(Vec::new()),
+        Value::String(action) if action.trim().is_empty() => Value::Array(Vec::new()),
+        Value::String(action) => Value::Array(vec![Value::String(action)]),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::ai::PriorityLevel;
+
+    const ANALYSIS: &str = r#"{"requires_action": true, "priority_level": "High", "summary": "Review the PR", "suggested_actions": ["Review"]}"#;
+
+    #[test]
+    fn test_clean_and_extracted_output() {
+        let analysis = parse_analysis(ANALYSIS).unwrap();
+        assert_eq!(analysis.repair, AnalysisRepair::NotNeeded);
+        assert_eq!(analysis.priority_level, PriorityLevel::High);
+
+        let fenced = format!(
+            "Here is the analysis:\n```json\n{}\n```\nHope it helps!",
+            ANALYSIS
+        );
+        let analysis = parse_analysis(&fenced).unwrap();
+        assert_eq!(analysis.repair, AnalysisRepair::Extracted);
+        assert_eq!(analysis.summary, "Review the PR");
+
+        let wrapped = format!("Sure! {} Let me know {{if}} you need more.", ANALYSIS);
+        assert_eq!(
+            parse_analysis(&wrapped).unwrap().repair,
+            AnalysisRepair::Extracted
+        );
+    }
+
+    #[test]
+    fn test_names_and_values_are_normalized() {
+        let analysis = parse_analysis(
+            r#"```
+{"requiresAction": "yes", "Priority": "URGENT", "summary": "Prod is down {again}", "actions": "Page the on-call"}
+```"#,
+        )
+        .unwrap();
+        assert_eq!(analysis.repair, AnalysisRepair::Normalized);
+        assert!(analysis.requires_action);
+        assert_eq!(analysis.priority_level, PriorityLevel::Critical);
+        assert_eq!(analysis.summary, "Prod is down {again}");
+        assert_eq!(analysis.suggested_actions, vec!["Page the on-call"]);
+
+        let analysis = parse_analysis(
+            r#"{"requires_action": 0, "priority_level": "normal", "summary": "FYI", "suggested_actions": null}"#,
+        )
+        .unwrap();
+        assert!(!analysis.requires_action);
+        assert_eq!(analysis.priority_level, PriorityLevel::Medium);
+        assert!(analysis.suggested_actions.is_empty());
+
+        // Upper case names, and the expected name wins over an alias
+        let analysis = parse_analysis(
+            r#"{"REQUIRES_ACTION": true, "PRIORITY": "low", "priority_level": "High", "urgency": "critical", "summary": "Deploy", "NextSteps": "Ship"}"#,
+        )
+        .unwrap();
+        assert!(analysis.requires_action);
+        assert_eq!(analysis.priority_level, PriorityLevel::High);
+        assert_eq!(analysis.summary, "Deploy");
+        assert_eq!(analysis.suggested_actions, vec!["Ship"]);
+    }
+
+    #[test]
+    fn test_snake_case_splits_words_only() {
+        assert_eq!(snake_case("requiresAction"), "requires_action");
+        assert_eq!(snake_case("Requires Action"), "requires_action");
+        assert_eq!(snake_case("PRIORITY"), "priority");
+        assert_eq!(snake_case("PriorityLevel"), "priority_level");
+        assert_eq!(snake_case("next-steps"), "next_steps");
+    }
+
+    #[test]
+    fn test_unreadable_output_reports_why() {
+        assert!(parse_analysis("I cannot help with that.")
+            .unwrap_err()
+            .contains("no JSON object"));
+        assert!(parse_analysis(
+            r#"{"requires_action": true, "priority_level": "someday", "summary": "x", "suggested_actions": []}"#
+        )
+        .unwrap_err()
+        .contains("someday"));
+    }
+}
@@ -0,0 +1,198 @@
+use super::ChatMessage;
+use crate::domain::{
+    entities::{ConversationEntry, ConversationMemory},
+    error::DomainResult,
+    repositories::DynConversationMemoryRepository,
+};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+/// Conversation of the prompts that do not belong to any notification
+pub const DEFAULT_CONVERSATION: &str = "default";
+
+/// Turns remembered of each conversation by default
+pub const MEMORY_MAX_ENTRIES: usize = 10;
+
+/// Tokens remembered of each conversation by default
+pub const MEMORY_MAX_TOKENS: usize = 2000;
+
+/// What the AI remembers, one memory per conversation key so the turns of a
+/// conversation never end up in the prompts of another.
+///
+/// The memories are kept in the repository when there is one, so they
+/// survive restarts, and only in this process otherwise.
+pub struct ConversationMemories {
+    /// Held while a memory is read and written back, so turns added at the
+    /// same time are not lost
+    local: Mutex<HashMap<String, Vec<ConversationEntry>>>,
+    repository: Option<DynConversationMemoryRepository>,
+    max_entries: usize,
+    max_tokens: usize,
+}
+
+impl std::fmt::Debug for ConversationMemories {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConversationMemories")
+            .field("persistent", &self.repository.is_some())
+            .field("max_entries", &self.max_entries)
+            .field("max_tokens", &self.max_tokens)
+            .finish()
+    }
+}
+
+impl Default for ConversationMemories {
+    fn default() -> Self {
+        Self::new(MEMORY_MAX_ENTRIES, MEMORY_MAX_TOKENS)
+    }
+}
+
+impl ConversationMemories {
+    pub fn new(max_entries: usize, max_tokens: usize) -> Self {
+        Self {
+            local: Mutex::new(HashMap::new()),
+            repository: None,
+            max_entries,
+            max_tokens,
+        }
+    }
+
+    pub fn with_repository(mut self, repository: DynConversationMemoryRepository) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// What is remembered of the conversation, empty for an unknown one
+    pub async fn get(&self, conversation: &str) -> DomainResult<ConversationMemory> {
+        let local = self.local.lock().await;
+        self.load(&local, conversation).await
+    }
+
+    pub async fn entries(&self, conversation: &str) -> DomainResult<Vec<ConversationEntry>> {
+        Ok(self.get(conversation).await?.entries.into_iter().collect())
+    }
+
+    /// The remembered turns as chat messages, "Assistant" entries being the
+    /// model's and any other the user's
+    pub async fn messages(&self, conversation: &str) -> DomainResult<Vec<ChatMessage>> {
+        Ok(self
+            .get(conversation)
+            .await?
+            .entries
+            .into_iter()
+            .map(|entry| {
+                if entry.role.eq_ignore_ascii_case("assistant") {
+                    ChatMessage::assistant(entry.content)
+                } else {
+                    ChatMessage::user(entry.content)
+                }
+            })
+            .collect())
+    }
+
+    pub async fn add_entry(
+        &self,
+        conversation: &str,
+        role: &str,
+        content: &str,
+    ) -> DomainResult<()> {
+        self.add_entries(conversation, &[(role, content)]).await
+    }
+
+    /// Adds a prompt and its reply in one write, so neither is kept
+    /// without the other
+    pub async fn add_exchange(
+        &self,
+        conversation: &str,
+        prompt: &str,
+        reply: &str,
+    ) -> DomainResult<()> {
+        self.add_entries(conversation, &[("User", prompt), ("Assistant", reply)])
+            .await
+    }
+
+    async fn add_entries(&self, conversation: &str, added: &[(&str, &str)]) -> DomainResult<()> {
+        let mut local = self.local.lock().await;
+        let mut memory = self.load(&local, conversation).await?;
+        for (role, content) in added {
+            memory.add_entry(role.to_string(), content.to_string());
+        }
+
+        let entries: Vec<_> = memory.entries.into_iter().collect();
+        match &self.repository {
+            Some(repository) => repository.save_entries(conversation, &entries).await?,
+            None => {
+                local.insert(conversation.to_string(), entries);
+            }
+        }
+        Ok(())
+    }
+
+    /// Forgets the conversation
+    pub async fn clear(&self, conversation: &str) -> DomainResult<()> {
+        let mut local = self.local.lock().await;
+        local.remove(conversation);
+        if let Some(repository) = &self.repository {
+            repository.delete(conversation).await?;
+        }
+        Ok(())
+    }
+
+    async fn load(
+        &self,
+        local: &HashMap<String, Vec<ConversationEntry>>,
+        conversation: &str,
+    ) -> DomainResult<ConversationMemory> {
+        let entries = match &self.repository {
+            Some(repository) => repository.find_entries(conversation).await?,
+            None => local.get(conversation).cloned().unwrap_or_default(),
+        };
+        Ok(ConversationMemory::new(self.max_entries)
+            .with_token_budget(self.max_tokens)
+            .with_entries(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::SqliteConversationMemoryRepository;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_conversations_are_remembered_apart_and_persisted() {
+        let repository = Arc::new(SqliteConversationMemoryRepository::new(":memory:").unwrap());
+        let memories =
+            ConversationMemories::new(2, MEMORY_MAX_TOKENS).with_repository(repository.clone());
+
+        memories
+            .add_entry("jira:OPS-1", "User", "Is OPS-1 fixed?")
+            .await
+            .unwrap();
+        memories
+            .add_entry("gmail:thread-7", "User", "Lunch on Friday?")
+            .await
+            .unwrap();
+        memories
+            .add_entry("jira:OPS-1", "Assistant", "Not yet.")
+            .await
+            .unwrap();
+        memories
+            .add_entry("jira:OPS-1", "User", "When then?")
+            .await
+            .unwrap();
+
+        let jira = memories.entries("jira:OPS-1").await.unwrap();
+        assert_eq!(
+            jira.iter().map(|e| e.content.as_str()).collect::<Vec<_>>(),
+            vec!["Not yet.", "When then?"]
+        );
+
+        // Another process reads the same memories
+        let restarted = ConversationMemories::default().with_repository(repository);
+        assert_eq!(restarted.entries("jira:OPS-1").await.unwrap(), jira);
+
+        restarted.clear("jira:OPS-1").await.unwrap();
+        assert!(memories.entries("jira:OPS-1").await.unwrap().is_empty());
+        assert_eq!(memories.entries("gmail:thread-7").await.unwrap().len(), 1);
+    }
+}
@@ -1,9 +1,18 @@
+pub mod analysis_parser;
 pub mod mcp_connector;
+pub mod memory;
+pub mod router;
 pub mod services;
 pub mod types;
 
 pub use mcp_connector::{DynMCPConnector, MCPConfig, MCPConnector};
+pub use memory::{
+    ConversationMemories, DEFAULT_CONVERSATION, MEMORY_MAX_ENTRIES, MEMORY_MAX_TOKENS,
+};
+pub use router::{AIProviderConfig, AIRouter, AIRouterConfig, AITask, ProviderCall};
+pub use services::create_ai_service;
 pub use services::ollama_service::OllamaService;
+pub use services::openai_compatible_service::OpenAICompatibleService;
 pub use types::*;
 
 #[cfg(test)]
@@ -13,7 +22,7 @@ pub use types::MockAIService;
 mod tests {
     use std::collections::HashMap;
 
-    use crate::domain::services::ai::services::ollama_service::ConversationMemory;
+    use crate::domain::entities::ConversationMemory;
     use crate::domain::DomainError;
 
     use super::*;
@@ -72,12 +81,12 @@ mod tests {
         let mock_server = MockServer::start().await;
 
         let sample_response = r#"{
-            "response": "{\"requires_action\": true, \"priority_level\": \"High\", \"summary\": \"Urgent review needed for project deployment\", \"suggested_actions\": [\"Review deployment plan\", \"Schedule team meeting\", \"Update documentation\"]}",
+            "message": { "role": "assistant", "content": "{\"requires_action\": true, \"priority_level\": \"High\", \"summary\": \"Urgent review needed for project deployment\", \"suggested_actions\": [\"Review deployment plan\", \"Schedule team meeting\", \"Update documentation\"]}" },
             "done": true
         }"#;
 
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(
                 ResponseTemplate::new(200).set_body_raw(sample_response, "application/json"),
             )
@@ -89,6 +98,7 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 5,
             user_preferences: UserPreferences::default(),
+            ..Default::default()
         };
 
         let service = OllamaService::new(config);
@@ -107,12 +117,12 @@ mod tests {
         let mock_server = MockServer::start().await;
 
         let sample_response = r#"{
-            "response": "I understand the urgency. Let's proceed with the deployment review.",
+            "message": { "role": "assistant", "content": "I understand the urgency. Let's proceed with the deployment review." },
             "done": true
         }"#;
 
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(
                 ResponseTemplate::new(200).set_body_raw(sample_response, "application/json"),
             )
@@ -130,6 +140,7 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 5,
             user_preferences: user_prefs,
+            ..Default::default()
         };
 
         let service = OllamaService::new(config);
@@ -146,13 +157,18 @@ mod tests {
             assert!(!response.is_empty());
 
             // Verify memory after each interaction
-            let memory_context = service.memory.lock().unwrap().get_context();
+            let memory_context = service
+                .memory
+                .get(DEFAULT_CONVERSATION)
+                .await
+                .unwrap()
+                .get_context();
             assert!(memory_context.contains(&response));
             assert!(memory_context.contains("Assistant"));
         }
 
         // Verify memory size constraint
-        let memory = service.memory.lock().unwrap();
+        let memory = service.memory.get(DEFAULT_CONVERSATION).await.unwrap();
         assert!(memory.entries.len() <= 10); // Max entries check
     }
 
@@ -166,6 +182,7 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 1,
             user_preferences: UserPreferences::default(),
+            ..Default::default()
         };
 
         let service = OllamaService::new(config);
@@ -177,9 +194,9 @@ mod tests {
 
         // Test invalid response format
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(
-                r#"{ "response": "Invalid JSON", "done": true }"#,
+                r#"{ "message": { "role": "assistant", "content": "Invalid JSON" }, "done": true }"#,
                 "application/json",
             ))
             .mount(&mock_server)
@@ -197,9 +214,9 @@ mod tests {
         let mock_server = MockServer::start().await;
 
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(
-                r#"{ "response": "Context-aware response", "done": true }"#,
+                r#"{ "message": { "role": "assistant", "content": "Context-aware response" }, "done": true }"#,
                 "application/json",
             ))
             .mount(&mock_server)
@@ -210,14 +227,16 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 5,
             user_preferences: UserPreferences::default(),
+            ..Default::default()
         });
 
         // Simulate a conversation flow
         // Add initial context
-        service.memory.lock().unwrap().add_entry(
-            "User".to_string(),
-            "Initial context information".to_string(),
-        );
+        service
+            .memory
+            .add_entry(DEFAULT_CONVERSATION, "User", "Initial context information")
+            .await
+            .unwrap();
 
         // Generate response to follow-up question
         let response = service
@@ -227,7 +246,7 @@ mod tests {
         assert!(!response.is_empty());
 
         // Verify that the memory maintains conversation order
-        let memory = service.memory.lock().unwrap();
+        let memory = service.memory.get(DEFAULT_CONVERSATION).await.unwrap();
         let context = memory.get_context();
 
         // Check all entries are present in the context string
@@ -0,0 +1,806 @@
+use crate::domain::{
+    entities::ConversationEntry,
+    error::{DomainError, DomainResult},
+    repositories::{load_settings, store_settings, DynSettingsRepository},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashSet, VecDeque},
+    future::Future,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+use tokio::time::{timeout, Instant};
+use tracing::{info, warn};
+
+use super::{
+    create_ai_service, AIAnalysis, AIConfig, AIService, ConversationMemories, DynAIService,
+    ResponseStream,
+};
+
+/// Calls remembered by `AIRouter::recent_calls`
+const RECENT_CALLS: usize = 100;
+
+const SETTINGS_KEY: &str = "ai_router";
+
+/// What a provider is asked to do
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AITask {
+    /// `analyze_content`
+    Analysis,
+    /// `generate_response` and the other replies
+    Response,
+}
+
+/// A provider of the router
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AIProviderConfig {
+    /// Names the provider in logs and in the calls it served
+    pub name: String,
+    pub config: AIConfig,
+    /// Longest a call may take before the next provider is tried, where
+    /// `config.timeout_seconds` only bounds each wait for the server
+    #[serde(default = "default_provider_timeout")]
+    pub timeout_seconds: u64,
+    /// Tasks the provider is used for, all of them when empty
+    #[serde(default)]
+    pub tasks: Vec<AITask>,
+}
+
+/// Providers tried in order, e.g. a small fast model for analyses followed
+/// by a larger one for replies and a remote server as a fallback
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AIRouterConfig {
+    pub providers: Vec<AIProviderConfig>,
+    /// How long a failed provider is skipped
+    #[serde(default = "default_cooldown")]
+    pub cooldown_seconds: u64,
+}
+
+fn default_cooldown() -> u64 {
+    60
+}
+
+fn default_provider_timeout() -> u64 {
+    120
+}
+
+impl Default for AIRouterConfig {
+    fn default() -> Self {
+        Self {
+            providers: vec![AIProviderConfig {
+                name: "ollama".to_string(),
+                config: AIConfig::default(),
+                timeout_seconds: default_provider_timeout(),
+                tasks: Vec::new(),
+            }],
+            cooldown_seconds: default_cooldown(),
+        }
+    }
+}
+
+impl AIRouterConfig {
+    pub fn validate(&self) -> DomainResult<()> {
+        let mut names = HashSet::new();
+        for provider in &self.providers {
+            if provider.name.trim().is_empty() {
+                return Err(DomainError::ValidationError(
+                    "AI provider name cannot be empty".to_string(),
+                ));
+            }
+            if !names.insert(provider.name.as_str()) {
+                return Err(DomainError::ValidationError(format!(
+                    "AI provider {} is listed twice",
+                    provider.name
+                )));
+            }
+            if provider.config.model.trim().is_empty() || provider.config.base_url.trim().is_empty()
+            {
+                return Err(DomainError::ValidationError(format!(
+                    "AI provider {} needs a model and a base URL",
+                    provider.name
+                )));
+            }
+            if provider.timeout_seconds == 0 || provider.config.timeout_seconds == 0 {
+                return Err(DomainError::ValidationError(format!(
+                    "AI provider {} needs a timeout",
+                    provider.name
+                )));
+            }
+        }
+        for task in [AITask::Analysis, AITask::Response] {
+            if !self
+                .providers
+                .iter()
+                .any(|provider| provider.tasks.is_empty() || provider.tasks.contains(&task))
+            {
+                return Err(DomainError::ValidationError(format!(
+                    "No AI provider handles {:?}",
+                    task
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which provider served a call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderCall {
+    pub task: AITask,
+    pub provider: String,
+    /// Providers that failed before, in the order they were tried
+    pub failed: Vec<String>,
+    pub served_at: DateTime<Utc>,
+}
+
+struct RoutedProvider {
+    name: String,
+    service: DynAIService,
+    timeout: Duration,
+    tasks: Vec<AITask>,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl RoutedProvider {
+    fn handles(&self, task: AITask) -> bool {
+        self.tasks.is_empty() || self.tasks.contains(&task)
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until
+            .lock()
+            .unwrap()
+            .is_none_or(|until| now >= until)
+    }
+
+    /// Skips the provider for the cooldown
+    fn fail(&self, task: AITask, cooldown: Duration, error: &str) {
+        warn!(
+            "AI provider {} failed for {:?}, skipping it for {:?}: {}",
+            self.name, task, cooldown, error
+        );
+        *self.unhealthy_until.lock().unwrap() = Some(Instant::now() + cooldown);
+    }
+}
+
+/// The providers calls are spread over, replaced as a whole when the
+/// config is saved
+#[derive(Clone)]
+struct Routes {
+    providers: Vec<Arc<RoutedProvider>>,
+    cooldown: Duration,
+}
+
+/// An `AIService` spreading calls over several providers.
+///
+/// Each call goes to the first healthy provider handling its task. A
+/// provider that is unreachable, answers with an error or takes longer than
+/// its timeout is skipped for the cooldown, and the call moves on to the
+/// next one. Providers cooling down are still tried last rather than
+/// failing the call. Streams fail over until they start: once chunks flow,
+/// an error ends the stream and the next calls go to the other providers.
+pub struct AIRouter {
+    routes: RwLock<Arc<Routes>>,
+    config: RwLock<AIRouterConfig>,
+    settings: Option<DynSettingsRepository>,
+    /// Shared by the providers of every config
+    memory: Arc<ConversationMemories>,
+    calls: Mutex<VecDeque<ProviderCall>>,
+}
+
+impl std::fmt::Debug for AIRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let routes = self.routes();
+        f.debug_struct("AIRouter")
+            .field(
+                "providers",
+                &routes.providers.iter().map(|p| &p.name).collect::<Vec<_>>(),
+            )
+            .field("cooldown", &routes.cooldown)
+            .field("settings", &self.settings.is_some())
+            .finish()
+    }
+}
+
+impl AIRouter {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            routes: RwLock::new(Arc::new(Routes {
+                providers: Vec::new(),
+                cooldown,
+            })),
+            config: RwLock::new(AIRouterConfig {
+                providers: Vec::new(),
+                cooldown_seconds: cooldown.as_secs(),
+            }),
+            settings: None,
+            memory: Arc::new(ConversationMemories::default()),
+            calls: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// The providers of the config, sharing one conversation memory so a
+    /// conversation carries on whichever provider replies
+    pub fn from_config(config: AIRouterConfig, memory: Arc<ConversationMemories>) -> Self {
+        Self {
+            routes: RwLock::new(Arc::new(Self::build_routes(&config, &memory))),
+            config: RwLock::new(config),
+            settings: None,
+            memory,
+            calls: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Uses the config saved in the settings, the defaults when nothing was
+    /// saved yet
+    pub async fn from_settings(
+        settings: DynSettingsRepository,
+        memory: Arc<ConversationMemories>,
+    ) -> DomainResult<Self> {
+        let config = load_settings(settings.as_ref(), SETTINGS_KEY).await?;
+        Ok(Self {
+            settings: Some(settings),
+            ..Self::from_config(config, memory)
+        })
+    }
+
+    /// Adds a provider after the current ones, for `tasks` or all of them
+    /// when empty. A call taking longer than `timeout` fails over.
+    pub fn with_provider(
+        mut self,
+        name: impl Into<String>,
+        service: DynAIService,
+        timeout: Duration,
+        tasks: Vec<AITask>,
+    ) -> Self {
+        Arc::make_mut(self.routes.get_mut().unwrap())
+            .providers
+            .push(Arc::new(RoutedProvider {
+                name: name.into(),
+                service,
+                timeout,
+                tasks,
+                unhealthy_until: Mutex::new(None),
+            }));
+        self
+    }
+
+    pub fn config(&self) -> AIRouterConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Validates and saves the config. The next calls go to its providers,
+    /// the calls under way finish on the ones they started with.
+    pub async fn save_config(&self, config: AIRouterConfig) -> DomainResult<AIRouterConfig> {
+        config.validate()?;
+        if let Some(settings) = &self.settings {
+            store_settings(settings.as_ref(), SETTINGS_KEY, &config).await?;
+        }
+        *self.routes.write().unwrap() = Arc::new(Self::build_routes(&config, &self.memory));
+        *self.config.write().unwrap() = config.clone();
+        Ok(config)
+    }
+
+    /// The latest calls, most recent last
+    pub fn recent_calls(&self) -> Vec<ProviderCall> {
+        self.calls.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn build_routes(config: &AIRouterConfig, memory: &Arc<ConversationMemories>) -> Routes {
+        Routes {
+            providers: config
+                .providers
+                .iter()
+                .map(|provider| {
+                    Arc::new(RoutedProvider {
+                        name: provider.name.clone(),
+                        service: create_ai_service(provider.config.clone(), memory.clone()),
+                        timeout: Duration::from_secs(provider.timeout_seconds),
+                        tasks: provider.tasks.clone(),
+                        unhealthy_until: Mutex::new(None),
+                    })
+                })
+                .collect(),
+            cooldown: Duration::from_secs(config.cooldown_seconds),
+        }
+    }
+
+    fn routes(&self) -> Arc<Routes> {
+        self.routes.read().unwrap().clone()
+    }
+
+    async fn route<T, F, Fut>(&self, task: AITask, call: F) -> DomainResult<T>
+    where
+        F: Fn(DynAIService) -> Fut,
+        Fut: Future<Output = DomainResult<T>>,
+    {
+        let (value, _) = self.route_to(task, call).await?;
+        Ok(value)
+    }
+
+    /// The result of the first provider that served the call, and that
+    /// provider
+    async fn route_to<T, F, Fut>(
+        &self,
+        task: AITask,
+        call: F,
+    ) -> DomainResult<(T, Arc<RoutedProvider>)>
+    where
+        F: Fn(DynAIService) -> Fut,
+        Fut: Future<Output = DomainResult<T>>,
+    {
+        let routes = self.routes();
+        let now = Instant::now();
+        let (healthy, cooling): (Vec<_>, Vec<_>) = routes
+            .providers
+            .iter()
+            .filter(|provider| provider.handles(task))
+            .partition(|provider| provider.is_healthy(now));
+        if healthy.is_empty() && cooling.is_empty() {
+            return Err(DomainError::ExternalServiceError(format!(
+                "No AI provider handles {:?}",
+                task
+            )));
+        }
+
+        let mut failed = Vec::new();
+        let mut errors = Vec::new();
+        for provider in healthy.into_iter().chain(cooling) {
+            let error = match timeout(provider.timeout, call(provider.service.clone())).await {
+                Ok(Ok(value)) => {
+                    *provider.unhealthy_until.lock().unwrap() = None;
+                    self.record(task, &provider.name, failed);
+                    return Ok((value, provider.clone()));
+                }
+                Ok(Err(DomainError::ExternalServiceError(error))) => error,
+                // Not an outage, another provider would not do better
+                Ok(Err(error)) => return Err(error),
+                Err(_) => format!("timed out after {:?}", provider.timeout),
+            };
+
+            provider.fail(task, routes.cooldown, &error);
+            errors.push(format!("{}: {}", provider.name, error));
+            failed.push(provider.name.clone());
+        }
+        Err(DomainError::ExternalServiceError(format!(
+            "Every AI provider failed: {}",
+            errors.join("; ")
+        )))
+    }
+
+    /// Routes a reply stream. The provider of a stream failing once started
+    /// is skipped by the next calls.
+    async fn route_stream<F, Fut>(&self, call: F) -> DomainResult<ResponseStream>
+    where
+        F: Fn(DynAIService) -> Fut,
+        Fut: Future<Output = DomainResult<ResponseStream>>,
+    {
+        let (stream, provider) = self.route_to(AITask::Response, call).await?;
+        let cooldown = self.routes().cooldown;
+        Ok(Box::pin(stream.inspect_err(move |error| {
+            if let DomainError::ExternalServiceError(error) = error {
+                provider.fail(AITask::Response, cooldown, error);
+            }
+        })))
+    }
+
+    fn record(&self, task: AITask, provider: &str, failed: Vec<String>) {
+        if failed.is_empty() {
+            info!("AI provider {} served {:?}", provider, task);
+        } else {
+            info!(
+                "AI provider {} served {:?} after {} failed",
+                provider,
+                task,
+                failed.join(", ")
+            );
+        }
+
+        let mut calls = self.calls.lock().unwrap();
+        if calls.len() >= RECENT_CALLS {
+            calls.pop_front();
+        }
+        calls.push_back(ProviderCall {
+            task,
+            provider: provider.to_string(),
+            failed,
+            served_at: Utc::now(),
+        });
+    }
+
+    /// The first provider replying, whose memory all of them share
+    fn memory_provider(&self) -> DomainResult<Arc<RoutedProvider>> {
+        self.routes()
+            .providers
+            .iter()
+            .find(|provider| provider.handles(AITask::Response))
+            .cloned()
+            .ok_or_else(|| {
+                DomainError::ExternalServiceError("No AI provider handles Response".to_string())
+            })
+    }
+}
+
+#[async_trait]
+impl AIService for AIRouter {
+    async fn analyze_content(&self, content: &str) -> DomainResult<AIAnalysis> {
+        self.route(AITask::Analysis, |service| async move {
+            service.analyze_content(content).await
+        })
+        .await
+    }
+
+    async fn generate_response(&self, context: &str) -> DomainResult<String> {
+        self.route(AITask::Response, |service| async move {
+            service.generate_response(context).await
+        })
+        .await
+    }
+
+    async fn generate_response_stream(&self, context: &str) -> DomainResult<ResponseStream> {
+        self.route_stream(|service| async move { service.generate_response_stream(context).await })
+            .await
+    }
+
+    async fn generate_conversation_response(
+        &self,
+        conversation: &str,
+        context: &str,
+    ) -> DomainResult<String> {
+        self.route(AITask::Response, |service| async move {
+            service
+                .generate_conversation_response(conversation, context)
+                .await
+        })
+        .await
+    }
+
+    async fn generate_conversation_response_stream(
+        &self,
+        conversation: &str,
+        context: &str,
+    ) -> DomainResult<ResponseStream> {
+        self.route_stream(|service| async move {
+            service
+                .generate_conversation_response_stream(conversation, context)
+                .await
+        })
+        .await
+    }
+
+    async fn conversation_memory(
+        &self,
+        conversation: &str,
+    ) -> DomainResult<Vec<ConversationEntry>> {
+        self.memory_provider()?
+            .service
+            .conversation_memory(conversation)
+            .await
+    }
+
+    async fn clear_conversation_memory(&self, conversation: &str) -> DomainResult<()> {
+        for provider in &self.routes().providers {
+            provider
+                .service
+                .clear_conversation_memory(conversation)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::ai::{MockAIService, PriorityLevel};
+    use crate::infrastructure::repositories::SqliteSettingsRepository;
+    use futures::StreamExt;
+
+    fn analysis(summary: &str) -> AIAnalysis {
+        AIAnalysis {
+            requires_action: true,
+            priority_level: PriorityLevel::High,
+            summary: summary.to_string(),
+            suggested_actions: vec![],
+            ..Default::default()
+        }
+    }
+
+    fn down() -> MockAIService {
+        let mut service = MockAIService::new();
+        service.expect_analyze_content().times(1).returning(|_| {
+            Err(DomainError::ExternalServiceError(
+                "AI service unreachable".to_string(),
+            ))
+        });
+        service
+    }
+
+    /// Never answers
+    #[derive(Debug)]
+    struct Hanging;
+
+    #[async_trait]
+    impl AIService for Hanging {
+        async fn analyze_content(&self, _content: &str) -> DomainResult<AIAnalysis> {
+            futures::future::pending().await
+        }
+
+        async fn generate_response(&self, _context: &str) -> DomainResult<String> {
+            futures::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_and_skips_unhealthy_providers() {
+        let mut backup = MockAIService::new();
+        backup
+            .expect_analyze_content()
+            .times(2)
+            .returning(|_| Ok(analysis("From the backup")));
+
+        let router = AIRouter::new(Duration::from_secs(60))
+            .with_provider(
+                "local",
+                Arc::new(down()),
+                Duration::from_secs(5),
+                Vec::new(),
+            )
+            .with_provider(
+                "backup",
+                Arc::new(backup),
+                Duration::from_secs(5),
+                Vec::new(),
+            );
+
+        let first = router.analyze_content("Prod is down").await.unwrap();
+        assert_eq!(first.summary, "From the backup");
+        // The local provider is cooling down, it is not asked again
+        router.analyze_content("Prod is down").await.unwrap();
+
+        let calls = router.recent_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].provider, "backup");
+        assert_eq!(calls[0].failed, vec!["local"]);
+        assert!(calls[1].failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_fail_over_and_errors_are_reported() {
+        let router = AIRouter::new(Duration::from_secs(60))
+            .with_provider(
+                "hanging",
+                Arc::new(Hanging),
+                Duration::from_millis(50),
+                Vec::new(),
+            )
+            .with_provider("down", Arc::new(down()), Duration::from_secs(5), Vec::new());
+
+        match router.analyze_content("Prod is down").await {
+            Err(DomainError::ExternalServiceError(message)) => {
+                assert!(message.contains("hanging: timed out"));
+                assert!(message.contains("down: AI service unreachable"));
+            }
+            other => panic!("Expected every provider to fail, got {:?}", other),
+        }
+        assert!(router.recent_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_routes_by_task() {
+        let mut small = MockAIService::new();
+        small
+            .expect_analyze_content()
+            .returning(|_| Ok(analysis("Quick look")));
+        let mut large = MockAIService::new();
+        large
+            .expect_generate_conversation_response()
+            .returning(|_, _| Ok("A careful reply".to_string()));
+
+        let router = AIRouter::new(Duration::from_secs(60))
+            .with_provider(
+                "small",
+                Arc::new(small),
+                Duration::from_secs(5),
+                vec![AITask::Analysis],
+            )
+            .with_provider(
+                "large",
+                Arc::new(large),
+                Duration::from_secs(5),
+                vec![AITask::Response],
+            );
+
+        router.analyze_content("New PR").await.unwrap();
+        assert_eq!(
+            router
+                .generate_conversation_response("github:o/r#1", "New PR")
+                .await
+                .unwrap(),
+            "A careful reply"
+        );
+        let served: Vec<_> = router
+            .recent_calls()
+            .into_iter()
+            .map(|call| (call.task, call.provider))
+            .collect();
+        assert_eq!(
+            served,
+            vec![
+                (AITask::Analysis, "small".to_string()),
+                (AITask::Response, "large".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_streams_failing_after_the_first_chunk_fail_over_next_time() {
+        let mut local = MockAIService::new();
+        local
+            .expect_generate_conversation_response_stream()
+            .times(1)
+            .returning(|_, _| {
+                Ok(Box::pin(futures::stream::iter(vec![
+                    Ok("Deploy".to_string()),
+                    Err(DomainError::ExternalServiceError(
+                        "Failed to read AI response".to_string(),
+                    )),
+                ])))
+            });
+        let mut backup = MockAIService::new();
+        backup
+            .expect_generate_conversation_response_stream()
+            .times(1)
+            .returning(|_, _| {
+                Ok(Box::pin(futures::stream::iter(vec![Ok(
+                    "Deployment is approved.".to_string(),
+                )])))
+            });
+
+        let router = AIRouter::new(Duration::from_secs(60))
+            .with_provider("local", Arc::new(local), Duration::from_secs(5), Vec::new())
+            .with_provider(
+                "backup",
+                Arc::new(backup),
+                Duration::from_secs(5),
+                Vec::new(),
+            );
+
+        // The chunks already sent are not taken back, the stream ends in error
+        let chunks: Vec<_> = router
+            .generate_conversation_response_stream("jira:OPS-1", "Can we deploy?")
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_deref().unwrap(), "Deploy");
+        assert!(chunks[1].is_err());
+
+        let chunks: Vec<String> = router
+            .generate_conversation_response_stream("jira:OPS-1", "Can we deploy?")
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(chunks, vec!["Deployment is approved."]);
+        let calls = router.recent_calls();
+        assert_eq!(calls[0].provider, "local");
+        assert_eq!(calls[1].provider, "backup");
+    }
+
+    #[tokio::test]
+    async fn test_providers_of_the_config_time_out_and_fail_over() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let reply = r#"{ "message": { "role": "assistant", "content": "On it." }, "done": true }"#;
+        let slow = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(reply, "application/json")
+                    .set_delay(Duration::from_secs(30)),
+            )
+            .mount(&slow)
+            .await;
+        let backup = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(reply, "application/json"))
+            .mount(&backup)
+            .await;
+
+        let provider = |name: &str, base_url: String, timeout_seconds| AIProviderConfig {
+            name: name.to_string(),
+            config: AIConfig {
+                base_url,
+                timeout_seconds: 60,
+                ..AIConfig::default()
+            },
+            timeout_seconds,
+            tasks: Vec::new(),
+        };
+        let router = AIRouter::from_config(
+            AIRouterConfig {
+                providers: vec![
+                    provider("slow", slow.uri(), 1),
+                    provider("backup", backup.uri(), 5),
+                ],
+                cooldown_seconds: 60,
+            },
+            Arc::new(ConversationMemories::default()),
+        );
+
+        // The slow server is given up on well before its own timeout
+        let started = Instant::now();
+        assert_eq!(
+            router.generate_response("Prod is down").await.unwrap(),
+            "On it."
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(router.recent_calls()[0].failed, vec!["slow"]);
+
+        let mut config = router.config();
+        config.providers[0].timeout_seconds = 0;
+        assert!(router.save_config(config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_config_is_saved_in_the_settings() {
+        let settings: DynSettingsRepository =
+            Arc::new(SqliteSettingsRepository::new(":memory:").unwrap());
+        let memory = Arc::new(ConversationMemories::default());
+        let router = AIRouter::from_settings(settings.clone(), memory.clone())
+            .await
+            .unwrap();
+        assert_eq!(router.config(), AIRouterConfig::default());
+
+        let analyses_only = AIRouterConfig {
+            providers: vec![AIProviderConfig {
+                name: "small".to_string(),
+                config: AIConfig::default(),
+                timeout_seconds: 120,
+                tasks: vec![AITask::Analysis],
+            }],
+            ..AIRouterConfig::default()
+        };
+        assert!(router.save_config(analyses_only).await.is_err());
+
+        let config = AIRouterConfig {
+            providers: vec![
+                AIProviderConfig {
+                    name: "small".to_string(),
+                    config: AIConfig::default(),
+                    timeout_seconds: 20,
+                    tasks: vec![AITask::Analysis],
+                },
+                AIProviderConfig {
+                    name: "large".to_string(),
+                    config: AIConfig {
+                        model: "qwen2.5:32b".to_string(),
+                        ..AIConfig::default()
+                    },
+                    timeout_seconds: 120,
+                    tasks: vec![AITask::Response],
+                },
+            ],
+            cooldown_seconds: 30,
+        };
+        router.save_config(config.clone()).await.unwrap();
+        assert_eq!(
+            format!("{:?}", router),
+            "AIRouter { providers: [\"small\", \"large\"], cooldown: 30s, settings: true }"
+        );
+
+        let reloaded = AIRouter::from_settings(settings, memory).await.unwrap();
+        assert_eq!(reloaded.config(), config);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::domain::error::{DomainError, DomainResult};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use reqwest::{RequestBuilder, Response};
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use tokio::time::timeout;
+
+use super::super::{AIConfig, ChatMessage, ConversationMemories, ResponseStream};
+
+/// What a decoder found in a response body
+#[derive(Debug, PartialEq)]
+pub(super) enum StreamEvent {
+    Text(String),
+    /// The model is done, the rest of the body is not read
+    Done,
+}
+
+/// Splits a response body into events. Its parts may be cut anywhere
+/// between two network chunks.
+pub(super) trait StreamDecoder: Send + 'static {
+    fn push(&mut self, bytes: &[u8]) -> DomainResult<Vec<StreamEvent>>;
+
+    /// The events left once the body ended without `Done`, or why the
+    /// response is incomplete
+    fn finish(&mut self) -> DomainResult<Vec<StreamEvent>>;
+}
+
+/// Memory and conversation a prompt and its reply are added to once the
+/// reply is read
+pub(super) struct ReplyMemory {
+    pub memory: Arc<ConversationMemories>,
+    pub conversation: String,
+    pub prompt: String,
+}
+
+/// Progress of a streamed reply
+struct ChatStream<D> {
+    response: Option<Response>,
+    decoder: D,
+    pending: VecDeque<String>,
+    idle_timeout: Duration,
+    memory: Option<ReplyMemory>,
+    text: String,
+}
+
+impl<D: StreamDecoder> ChatStream<D> {
+    async fn next_chunk(&mut self) -> DomainResult<Option<String>> {
+        loop {
+            if let Some(chunk) = self.pending.pop_front() {
+                return Ok(Some(chunk));
+            }
+            let Some(response) = self.response.as_mut() else {
+                // Only a response read to the end joins the memory
+                if let Some(ReplyMemory {
+                    memory,
+                    conversation,
+                    prompt,
+                }) = self.memory.take()
+                {
+                    memory
+                        .add_exchange(&conversation, &prompt, &self.text)
+                        .await?;
+                }
+                return Ok(None);
+            };
+
+            let bytes = timeout(self.idle_timeout, response.chunk())
+                .await
+                .map_err(|e| {
+                    DomainError::ExternalServiceError(format!(
+                        "AI service request timed out: {}",
+                        e
+                    ))
+                })?
+                .map_err(|e| {
+                    DomainError::ExternalServiceError(format!("Failed to read AI response: {}", e))
+                })?;
+            let events = match bytes {
+                Some(bytes) => self.decoder.push(&bytes)?,
+                None => {
+                    self.response = None;
+                    self.decoder.finish()?
+                }
+            };
+            for event in events {
+                match event {
+                    StreamEvent::Text(text) => {
+                        self.text.push_str(&text);
+                        self.pending.push_back(text);
+                    }
+                    StreamEvent::Done => {
+                        // Closes the connection, the pending chunks are still yielded
+                        self.response = None;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sends a request, waiting at most `idle_timeout` for the response
+pub(super) async fn send(
+    request: RequestBuilder,
+    idle_timeout: Duration,
+) -> DomainResult<Response> {
+    timeout(idle_timeout, request.send())
+        .await
+        .map_err(|e| {
+            DomainError::ExternalServiceError(format!("AI service request timed out: {}", e))
+        })?
+        .map_err(|e| DomainError::ExternalServiceError(format!("AI service unreachable: {}", e)))
+}
+
+/// The chunks of a successful response, `idle_timeout` bounds the wait for
+/// each of them
+pub(super) fn response_stream<D: StreamDecoder>(
+    response: Response,
+    decoder: D,
+    idle_timeout: Duration,
+    memory: Option<ReplyMemory>,
+) -> ResponseStream {
+    let state = ChatStream {
+        response: Some(response),
+        decoder,
+        pending: VecDeque::new(),
+        idle_timeout,
+        memory,
+        text: String::new(),
+    };
+    Box::pin(futures::stream::try_unfold(state, |mut state| async move {
+        Ok(state.next_chunk().await?.map(|chunk| (chunk, state)))
+    }))
+}
+
+/// A server the model is chatted with. The services talking to one only
+/// say how a chat is sent, the memory of the conversations is kept here.
+#[async_trait]
+pub(super) trait ChatBackend: Send + Sync {
+    fn config(&self) -> &AIConfig;
+
+    fn memory(&self) -> &Arc<ConversationMemories>;
+
+    /// Streams the model's output. `timeout_seconds` bounds the wait for the
+    /// response and then for each chunk, not the whole generation.
+    /// `format` constrains the output, in the way the server expects it.
+    async fn stream_chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: Option<serde_json::Value>,
+        memory: Option<ReplyMemory>,
+    ) -> DomainResult<ResponseStream>;
+
+    /// The whole output of the model, for callers that do not stream
+    async fn chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: Option<serde_json::Value>,
+    ) -> DomainResult<String> {
+        self.stream_chat(messages, format, None)
+            .await?
+            .try_collect()
+            .await
+    }
+
+    /// Answers `context` with what is remembered of `conversation`
+    async fn conversation_stream(
+        &self,
+        conversation: &str,
+        context: &str,
+    ) -> DomainResult<ResponseStream> {
+        let memory = self.memory();
+        let mut messages = vec![ChatMessage::system(
+            self.config().user_preferences.response_instructions(),
+        )];
+        messages.extend(memory.messages(conversation).await?);
+        messages.push(ChatMessage::user(context));
+
+        // The prompt and the response join the memory once the response is
+        // read, so a cancelled or failed one leaves no trace another provider
+        // taking over would repeat
+        self.stream_chat(
+            messages,
+            None,
+            Some(ReplyMemory {
+                memory: memory.clone(),
+                conversation: conversation.to_string(),
+                prompt: context.to_string(),
+            }),
+        )
+        .await
+    }
+}
@@ -1 +1,18 @@
+mod chat;
 pub mod ollama_service;
+pub mod openai_compatible_service;
+
+use super::{AIConfig, AIProvider, ConversationMemories, DynAIService};
+use ollama_service::OllamaService;
+use openai_compatible_service::OpenAICompatibleService;
+use std::sync::Arc;
+
+/// The service of the provider selected in `config`
+pub fn create_ai_service(config: AIConfig, memory: Arc<ConversationMemories>) -> DynAIService {
+    match config.provider {
+        AIProvider::Ollama => Arc::new(OllamaService::new(config).with_memory(memory)),
+        AIProvider::OpenAiCompatible => {
+            Arc::new(OpenAICompatibleService::new(config).with_memory(memory))
+        }
+    }
+}
@@ -1,69 +1,94 @@
-use crate::domain::error::DomainResult;
+use crate::domain::error::{DomainError, DomainResult};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::{
-    collections::VecDeque,
-    sync::{Arc, Mutex},
-    time::Duration,
-};
-use tokio::time::timeout;
+use std::{sync::Arc, time::Duration};
 
-use super::super::{AIAnalysis, AIConfig, AIService};
+use super::super::{
+    analysis_parser::request_analysis, AIAnalysis, AIConfig, AIService, ChatMessage,
+    ConversationMemories, ResponseStream, DEFAULT_CONVERSATION,
+};
+use super::chat::{self, ChatBackend, ReplyMemory, StreamDecoder, StreamEvent};
+use crate::domain::entities::ConversationEntry;
 
 #[derive(Debug, Serialize)]
-struct OllamaRequest {
+struct OllamaChatRequest {
     model: String,
-    prompt: String,
+    messages: Vec<ChatMessage>,
     stream: bool,
+    /// JSON schema the output is constrained to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
+/// One line of Ollama's newline-delimited JSON output
 #[derive(Debug, Deserialize)]
 struct OllamaResponse {
-    response: String,
-    #[allow(dead_code)]
+    #[serde(default)]
+    message: Option<ChatMessage>,
+    #[serde(default)]
     done: bool,
+    #[serde(default)]
+    error: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConversationEntry {
-    pub timestamp: DateTime<Utc>,
-    pub role: String,
-    pub content: String,
-}
-
-#[derive(Debug)]
-pub struct ConversationMemory {
-    pub entries: VecDeque<ConversationEntry>,
-    pub max_entries: usize,
+/// Splits the response body into the JSON objects it is made of
+#[derive(Debug, Default)]
+struct OllamaDecoder {
+    buffer: Vec<u8>,
 }
 
-impl ConversationMemory {
-    pub fn new(max_entries: usize) -> Self {
-        Self {
-            entries: VecDeque::with_capacity(max_entries),
-            max_entries,
-        }
+impl OllamaDecoder {
+    fn is_empty(&self) -> bool {
+        self.buffer.iter().all(u8::is_ascii_whitespace)
     }
+}
 
-    pub fn add_entry(&mut self, role: String, content: String) {
-        if self.entries.len() >= self.max_entries {
-            self.entries.pop_front();
+impl StreamDecoder for OllamaDecoder {
+    fn push(&mut self, bytes: &[u8]) -> DomainResult<Vec<StreamEvent>> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut objects =
+            serde_json::Deserializer::from_slice(&self.buffer).into_iter::<OllamaResponse>();
+        let mut events = Vec::new();
+        loop {
+            match objects.next() {
+                Some(Ok(response)) => {
+                    if let Some(error) = response.error {
+                        return Err(DomainError::ExternalServiceError(format!(
+                            "AI service error: {}",
+                            error
+                        )));
+                    }
+                    if let Some(message) = response.message.filter(|m| !m.content.is_empty()) {
+                        events.push(StreamEvent::Text(message.content));
+                    }
+                    if response.done {
+                        events.push(StreamEvent::Done);
+                    }
+                }
+                // The rest of the object is in the next chunk
+                Some(Err(e)) if e.is_eof() => break,
+                Some(Err(e)) => {
+                    return Err(DomainError::ExternalServiceError(format!(
+                        "Failed to parse AI response: {}",
+                        e
+                    )))
+                }
+                None => break,
+            }
         }
-        self.entries.push_back(ConversationEntry {
-            timestamp: Utc::now(),
-            role,
-            content,
-        });
+        let consumed = objects.byte_offset();
+        self.buffer.drain(..consumed);
+        Ok(events)
     }
 
-    pub fn get_context(&self) -> String {
-        self.entries
-            .iter()
-            .map(|entry| format!("{}({}): {}", entry.role, entry.timestamp, entry.content))
-            .collect::<Vec<_>>()
-            .join("\n")
+    fn finish(&mut self) -> DomainResult<Vec<StreamEvent>> {
+        Err(DomainError::ExternalServiceError(if self.is_empty() {
+            "AI response ended before it was done".to_string()
+        } else {
+            "AI response ended in the middle of a message".to_string()
+        }))
     }
 }
 
@@ -71,7 +96,7 @@ impl ConversationMemory {
 pub struct OllamaService {
     pub config: AIConfig,
     pub client: Client,
-    pub memory: Arc<Mutex<ConversationMemory>>,
+    pub memory: Arc<ConversationMemories>,
 }
 
 impl OllamaService {
@@ -79,111 +104,116 @@ impl OllamaService {
         Self {
             config,
             client: Client::new(),
-            memory: Arc::new(Mutex::new(ConversationMemory::new(10))),
+            memory: Arc::new(ConversationMemories::default()),
         }
     }
 
-    async fn send_prompt(&self, prompt: &str) -> DomainResult<String> {
-        let request = OllamaRequest {
+    /// Replaces the memory kept only in this process, e.g. by one kept in
+    /// the database
+    pub fn with_memory(mut self, memory: impl Into<Arc<ConversationMemories>>) -> Self {
+        self.memory = memory.into();
+        self
+    }
+}
+
+#[async_trait]
+impl ChatBackend for OllamaService {
+    fn config(&self) -> &AIConfig {
+        &self.config
+    }
+
+    fn memory(&self) -> &Arc<ConversationMemories> {
+        &self.memory
+    }
+
+    async fn stream_chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: Option<serde_json::Value>,
+        memory: Option<ReplyMemory>,
+    ) -> DomainResult<ResponseStream> {
+        let request = OllamaChatRequest {
             model: self.config.model.clone(),
-            prompt: prompt.to_string(),
-            stream: false,
+            messages,
+            stream: true,
+            format,
         };
+        let idle_timeout = Duration::from_secs(self.config.timeout_seconds);
 
-        let response = timeout(
-            Duration::from_secs(self.config.timeout_seconds),
+        let response = chat::send(
             self.client
-                .post(format!("{}/api/generate", self.config.base_url))
-                .json(&request)
-                .send(),
+                .post(format!("{}/api/chat", self.config.base_url))
+                .json(&request),
+            idle_timeout,
         )
-        .await
-        .map_err(|e| {
-            DomainError::ExternalServiceError(format!("AI service request timed out: {}", e))
-        })??;
-
-        let ollama_response: OllamaResponse = response.json().await.map_err(|e| {
-            DomainError::ExternalServiceError(format!("Failed to parse AI response: {}", e))
-        })?;
+        .await?;
+        if !response.status().is_success() {
+            return Err(DomainError::ExternalServiceError(format!(
+                "AI service returned {}",
+                response.status()
+            )));
+        }
 
-        Ok(ollama_response.response)
+        Ok(chat::response_stream(
+            response,
+            OllamaDecoder::default(),
+            idle_timeout,
+            memory,
+        ))
     }
 }
 
 #[async_trait]
 impl AIService for OllamaService {
     async fn analyze_content(&self, content: &str) -> DomainResult<AIAnalysis> {
-        let prompt = format!(
-            "Analyze the following content and provide a structured response with the following information:\n\
-            1. Does this require action? (true/false)\n\
-            2. Priority level (Low, Medium, High, Critical)\n\
-            3. Brief summary\n\
-            4. List of suggested actions\n\n\
-            Content: {}\n\n\
-            Respond in JSON format.",
-            content
-        );
-
-        let response = self.send_prompt(&prompt).await?;
-
-        serde_json::from_str(&response).map_err(|e| {
-            DomainError::ValidationError(format!("Failed to parse AI analysis: {}", e))
+        request_analysis(content, |messages| {
+            self.chat(messages, Some(AIAnalysis::json_schema()))
         })
+        .await
     }
 
     async fn generate_response(&self, context: &str) -> DomainResult<String> {
-        let prefs = &self.config.user_preferences;
-        let tone = format!("{:?}", prefs.tone).to_lowercase();
-        let length = format!("{:?}", prefs.length).to_lowercase();
-        let formality = format!("{:?}", prefs.formality_level).to_lowercase();
+        self.generate_conversation_response(DEFAULT_CONVERSATION, context)
+            .await
+    }
 
-        let custom_instructions = prefs.custom_instructions.join("\n");
+    async fn generate_response_stream(&self, context: &str) -> DomainResult<ResponseStream> {
+        self.generate_conversation_response_stream(DEFAULT_CONVERSATION, context)
+            .await
+    }
 
-        let conversation_history = self
-            .memory
-            .lock()
-            .expect("Failed to acquire lock on memory for conversation history")
-            .get_context();
-        let prompt = format!(
-            "Generate a response for the following context, adhering to these specifications:\n\
-            Tone: {}\n\
-            Length: {}\n\
-            Language: {}\n\
-            Formality: {}\n\
-            Custom Instructions:\n{}\n\n\
-            Previous Conversation:\n{}\n\n\
-            Current Context:\n{}\n\n\
-            The response should be clear and actionable while maintaining consistency with previous interactions.",
-            tone, length, prefs.language, formality, custom_instructions, conversation_history, context
-        );
+    async fn generate_conversation_response_stream(
+        &self,
+        conversation: &str,
+        context: &str,
+    ) -> DomainResult<ResponseStream> {
+        self.conversation_stream(conversation, context).await
+    }
 
-        // Add user's message to memory first
-        self.memory
-            .lock()
-            .unwrap()
-            .add_entry("User".to_string(), context.to_string());
+    async fn conversation_memory(
+        &self,
+        conversation: &str,
+    ) -> DomainResult<Vec<ConversationEntry>> {
+        self.memory.entries(conversation).await
+    }
 
-        let response = self.send_prompt(&prompt).await?;
-        self.memory
-            .lock()
-            .unwrap()
-            .add_entry("Assistant".to_string(), response.clone());
-        Ok(response)
+    async fn clear_conversation_memory(&self, conversation: &str) -> DomainResult<()> {
+        self.memory.clear(conversation).await
     }
 }
 
-use crate::domain::error::DomainError;
-
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
+    use crate::domain::entities::ConversationMemory;
     use crate::domain::services::ai::{
-        FormalityLevel, ResponseLength, ResponseTone, UserPreferences,
+        AnalysisRepair, FormalityLevel, ResponseLength, ResponseTone, UserPreferences,
     };
     use crate::domain::services::PriorityLevel;
 
     use super::*;
+    use futures::{StreamExt, TryStreamExt};
     use tokio;
     use wiremock::matchers::{method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -239,12 +269,12 @@ mod tests {
         let mock_server = MockServer::start().await;
 
         let sample_response = r#"{
-            "response": "{\"requires_action\": true, \"priority_level\": \"High\", \"summary\": \"Urgent review needed for project deployment\", \"suggested_actions\": [\"Review deployment plan\", \"Schedule team meeting\", \"Update documentation\"]}",
+            "message": { "role": "assistant", "content": "{\"requires_action\": true, \"priority_level\": \"High\", \"summary\": \"Urgent review needed for project deployment\", \"suggested_actions\": [\"Review deployment plan\", \"Schedule team meeting\", \"Update documentation\"]}" },
             "done": true
         }"#;
 
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(
                 ResponseTemplate::new(200).set_body_raw(sample_response, "application/json"),
             )
@@ -256,6 +286,7 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 5,
             user_preferences: UserPreferences::default(),
+            ..Default::default()
         };
 
         let service = OllamaService::new(config);
@@ -274,12 +305,12 @@ mod tests {
         let mock_server = MockServer::start().await;
 
         let sample_response = r#"{
-            "response": "I understand the urgency. Let's proceed with the deployment review.",
+            "message": { "role": "assistant", "content": "I understand the urgency. Let's proceed with the deployment review." },
             "done": true
         }"#;
 
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(
                 ResponseTemplate::new(200).set_body_raw(sample_response, "application/json"),
             )
@@ -297,6 +328,7 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 5,
             user_preferences: user_prefs,
+            ..Default::default()
         };
 
         let service = OllamaService::new(config);
@@ -313,16 +345,262 @@ mod tests {
             assert!(!response.is_empty());
 
             // Verify memory after each interaction
-            let memory_context = service.memory.lock().unwrap().get_context();
+            let memory_context = service
+                .memory
+                .get(DEFAULT_CONVERSATION)
+                .await
+                .unwrap()
+                .get_context();
             assert!(memory_context.contains(&response));
             assert!(memory_context.contains("Assistant"));
         }
 
         // Verify memory size constraint
-        let memory = service.memory.lock().unwrap();
+        let memory = service.memory.get(DEFAULT_CONVERSATION).await.unwrap();
         assert!(memory.entries.len() <= 10); // Max entries check
     }
 
+    #[tokio::test]
+    async fn test_generate_response_stream() {
+        let mock_server = MockServer::start().await;
+
+        let body = concat!(
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"Deploy\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"ment is\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\" approved.\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true}\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/x-ndjson"))
+            .mount(&mock_server)
+            .await;
+
+        let service = OllamaService::new(AIConfig {
+            base_url: mock_server.uri(),
+            model: "test-model".to_string(),
+            timeout_seconds: 5,
+            user_preferences: UserPreferences::default(),
+            ..Default::default()
+        });
+
+        let chunks: Vec<String> = service
+            .generate_response_stream("Can we deploy?")
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(chunks, vec!["Deploy", "ment is", " approved."]);
+        assert_eq!(
+            service
+                .memory
+                .entries(DEFAULT_CONVERSATION)
+                .await
+                .unwrap()
+                .last()
+                .unwrap()
+                .content,
+            "Deployment is approved."
+        );
+
+        // A cancelled generation stays out of the memory, prompt included
+        let mut stream = service
+            .generate_response_stream("And tomorrow?")
+            .await
+            .unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "Deploy");
+        drop(stream);
+        let memory = service.memory.get(DEFAULT_CONVERSATION).await.unwrap();
+        assert_eq!(memory.entries.len(), 2);
+        assert_eq!(
+            memory.entries.back().unwrap().content,
+            "Deployment is approved."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chat_request_has_preferences_and_memory_turns() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{ "message": { "role": "assistant", "content": "On it." }, "done": true }"#,
+                "application/json",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let service = OllamaService::new(AIConfig {
+            base_url: mock_server.uri(),
+            model: "test-model".to_string(),
+            timeout_seconds: 5,
+            user_preferences: UserPreferences {
+                tone: ResponseTone::Friendly,
+                length: ResponseLength::Concise,
+                formality_level: FormalityLevel::Casual,
+                custom_instructions: vec!["Sign as Sam".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        service
+            .generate_response("Can you review PR #12?")
+            .await
+            .unwrap();
+        service.generate_response("Any update?").await.unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(body["model"], "test-model");
+        let messages = body["messages"].as_array().unwrap();
+        let roles: Vec<_> = messages
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+
+        // Preferences live in the system message, not in the user's turn
+        let system = messages[0]["content"].as_str().unwrap();
+        assert!(system.contains("friendly tone"));
+        assert!(system.contains("one or two sentences"));
+        assert!(system.contains("casual register"));
+        assert!(system.contains("- Sign as Sam"));
+        assert_eq!(messages[1]["content"], "Can you review PR #12?");
+        assert_eq!(messages[2]["content"], "On it.");
+        assert_eq!(messages[3]["content"], "Any update?");
+    }
+
+    #[tokio::test]
+    async fn test_analysis_is_constrained_and_retried_once() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{ "message": { "role": "assistant", "content": "This looks urgent to me." }, "done": true }"#,
+                "application/json",
+            ))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{ "message": { "role": "assistant", "content": "```json\n{\"requires_action\": true, \"priority_level\": \"Critical\", \"summary\": \"Prod is down\", \"suggested_actions\": []}\n```" }, "done": true }"#,
+                "application/json",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let service = OllamaService::new(AIConfig {
+            base_url: mock_server.uri(),
+            model: "test-model".to_string(),
+            timeout_seconds: 5,
+            user_preferences: UserPreferences::default(),
+            ..Default::default()
+        });
+        let analysis = service.analyze_content("Prod is down").await.unwrap();
+        assert_eq!(analysis.priority_level, PriorityLevel::Critical);
+        // The second answer still had to be cut out of its code fence
+        assert!(analysis.retried);
+        assert_eq!(analysis.repair, AnalysisRepair::Extracted);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let first: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(first["format"], AIAnalysis::json_schema());
+        let retry: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let messages = retry["messages"].as_array().unwrap();
+        assert_eq!(messages[2]["content"], "This looks urgent to me.");
+        assert!(messages[3]["content"]
+            .as_str()
+            .unwrap()
+            .contains("no JSON object"));
+    }
+
+    #[tokio::test]
+    async fn test_conversations_do_not_share_memory() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{ "message": { "role": "assistant", "content": "Noted." }, "done": true }"#,
+                "application/json",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let service = OllamaService::new(AIConfig {
+            base_url: mock_server.uri(),
+            model: "test-model".to_string(),
+            timeout_seconds: 5,
+            user_preferences: UserPreferences::default(),
+            ..Default::default()
+        });
+        service
+            .generate_conversation_response("jira:OPS-1", "Is OPS-1 fixed?")
+            .await
+            .unwrap();
+        service
+            .generate_conversation_response("gmail:thread-7", "Lunch on Friday?")
+            .await
+            .unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1]["content"], "Lunch on Friday?");
+
+        let jira = service.conversation_memory("jira:OPS-1").await.unwrap();
+        assert_eq!(
+            jira.iter().map(|e| e.content.as_str()).collect::<Vec<_>>(),
+            vec!["Is OPS-1 fixed?", "Noted."]
+        );
+        service
+            .clear_conversation_memory("jira:OPS-1")
+            .await
+            .unwrap();
+        assert!(service
+            .conversation_memory("jira:OPS-1")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            service
+                .conversation_memory("gmail:thread-7")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_decoder_handles_objects_split_across_chunks() {
+        let mut decoder = OllamaDecoder::default();
+        assert!(decoder
+            .push(b"{\"message\":{\"role\":\"assistant\",\"content\":\"Hel")
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            decoder
+                .push(b"lo\"},\"done\":false}\n{\"message\":{\"role\":\"assistant\",\"content\":\"!\"},\"done\":true}\n")
+                .unwrap(),
+            vec![
+                StreamEvent::Text("Hello".to_string()),
+                StreamEvent::Text("!".to_string()),
+                StreamEvent::Done
+            ]
+        );
+        assert!(decoder.is_empty());
+
+        assert!(matches!(
+            decoder.push(b"{\"error\":\"model not found\"}\n"),
+            Err(DomainError::ExternalServiceError(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_error_handling() {
         let mock_server = MockServer::start().await;
@@ -333,6 +611,7 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 1,
             user_preferences: UserPreferences::default(),
+            ..Default::default()
         };
 
         let service = OllamaService::new(config);
@@ -344,9 +623,9 @@ mod tests {
 
         // Test invalid response format
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(
-                r#"{ "response": "Invalid JSON", "done": true }"#,
+                r#"{ "message": { "role": "assistant", "content": "Invalid JSON" }, "done": true }"#,
                 "application/json",
             ))
             .mount(&mock_server)
@@ -364,9 +643,9 @@ mod tests {
         let mock_server = MockServer::start().await;
 
         Mock::given(method("POST"))
-            .and(path("/api/generate"))
+            .and(path("/api/chat"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(
-                r#"{ "response": "Context-aware response", "done": true }"#,
+                r#"{ "message": { "role": "assistant", "content": "Context-aware response" }, "done": true }"#,
                 "application/json",
             ))
             .mount(&mock_server)
@@ -377,14 +656,16 @@ mod tests {
             model: "test-model".to_string(),
             timeout_seconds: 5,
             user_preferences: UserPreferences::default(),
+            ..Default::default()
         });
 
         // Simulate a conversation flow
         // Add initial context
-        service.memory.lock().unwrap().add_entry(
-            "User".to_string(),
-            "Initial context information".to_string(),
-        );
+        service
+            .memory
+            .add_entry(DEFAULT_CONVERSATION, "User", "Initial context information")
+            .await
+            .unwrap();
 
         // Generate response to follow-up question
         let response = service
@@ -394,7 +675,7 @@ mod tests {
         assert!(!response.is_empty());
 
         // Verify that the memory maintains conversation order
-        let memory = service.memory.lock().unwrap();
+        let memory = service.memory.get(DEFAULT_CONVERSATION).await.unwrap();
         let context = memory.get_context();
 
         // Check all entries are present in the context string
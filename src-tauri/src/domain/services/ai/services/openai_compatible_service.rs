@@ -0,0 +1,448 @@
+use crate::domain::error::{DomainError, DomainResult};
+use async_trait::async_trait;
+use reqwest::{Client, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use super::super::{
+    analysis_parser::request_analysis, AIAnalysis, AIConfig, AIService, ChatMessage,
+    ConversationMemories, ResponseStream, DEFAULT_CONVERSATION,
+};
+use super::chat::{self, ChatBackend, ReplyMemory, StreamDecoder, StreamEvent};
+use crate::domain::entities::ConversationEntry;
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    /// JSON mode, with the schema the output is constrained to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+}
+
+impl ChatCompletionRequest {
+    fn uses_json_schema(&self) -> bool {
+        self.response_format
+            .as_ref()
+            .is_some_and(|format| format["type"] == "json_schema")
+    }
+}
+
+/// Payload of one `data:` line of the server-sent events
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    #[serde(default)]
+    delta: ChunkDelta,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChunkDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Splits the server-sent events of the response body into their payloads
+#[derive(Debug, Default)]
+struct SseDecoder {
+    buffer: Vec<u8>,
+    /// The model stopped writing, `[DONE]` may still follow
+    finished: bool,
+}
+
+impl StreamDecoder for SseDecoder {
+    fn push(&mut self, bytes: &[u8]) -> DomainResult<Vec<StreamEvent>> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            // Comments, event names and blank separators carry no text
+            let Some(data) = line.trim().strip_prefix("data:") else {
+                continue;
+            };
+            let data = data.trim();
+            if data == "[DONE]" {
+                events.push(StreamEvent::Done);
+                continue;
+            }
+
+            let chunk: ChatCompletionChunk = serde_json::from_str(data).map_err(|e| {
+                DomainError::ExternalServiceError(format!("Failed to parse AI response: {}", e))
+            })?;
+            if let Some(error) = chunk.error {
+                let message = error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .map_or_else(|| error.to_string(), str::to_string);
+                return Err(DomainError::ExternalServiceError(format!(
+                    "AI service error: {}",
+                    message
+                )));
+            }
+            for choice in chunk.choices {
+                if let Some(content) = choice.delta.content.filter(|c| !c.is_empty()) {
+                    events.push(StreamEvent::Text(content));
+                }
+                if choice.finish_reason.is_some() {
+                    self.finished = true;
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    fn finish(&mut self) -> DomainResult<Vec<StreamEvent>> {
+        // A last line may lack its newline
+        let mut events = self.push(b"\n")?;
+        if events.last() != Some(&StreamEvent::Done) {
+            // Some servers close the stream without `[DONE]`
+            if !self.finished {
+                return Err(DomainError::ExternalServiceError(
+                    "AI response ended before it was done".to_string(),
+                ));
+            }
+            events.push(StreamEvent::Done);
+        }
+        Ok(events)
+    }
+}
+
+/// Talks to servers exposing the OpenAI `/v1/chat/completions` API, such as
+/// llama.cpp server, vLLM or LM Studio
+#[derive(Debug)]
+pub struct OpenAICompatibleService {
+    pub config: AIConfig,
+    pub client: Client,
+    pub memory: Arc<ConversationMemories>,
+    /// The server turned down a `json_schema` response format and took
+    /// plain JSON mode instead
+    json_object_only: AtomicBool,
+}
+
+impl OpenAICompatibleService {
+    pub fn new(config: AIConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            memory: Arc::new(ConversationMemories::default()),
+            json_object_only: AtomicBool::new(false),
+        }
+    }
+
+    /// Replaces the memory kept only in this process, e.g. by one kept in
+    /// the database
+    pub fn with_memory(mut self, memory: impl Into<Arc<ConversationMemories>>) -> Self {
+        self.memory = memory.into();
+        self
+    }
+
+    /// The base URL may already end with the `/v1` of the API
+    fn completions_url(&self) -> String {
+        let base_url = self.config.base_url.trim_end_matches('/');
+        if base_url.ends_with("/v1") {
+            format!("{}/chat/completions", base_url)
+        } else {
+            format!("{}/v1/chat/completions", base_url)
+        }
+    }
+
+    async fn send(
+        &self,
+        request: &ChatCompletionRequest,
+        idle_timeout: Duration,
+    ) -> DomainResult<Response> {
+        let mut builder = self.client.post(self.completions_url()).json(request);
+        if let Some(api_key) = &self.config.api_key {
+            builder = builder.bearer_auth(api_key);
+        }
+        chat::send(builder, idle_timeout).await
+    }
+}
+
+#[async_trait]
+impl ChatBackend for OpenAICompatibleService {
+    fn config(&self) -> &AIConfig {
+        &self.config
+    }
+
+    fn memory(&self) -> &Arc<ConversationMemories> {
+        &self.memory
+    }
+
+    async fn stream_chat(
+        &self,
+        messages: Vec<ChatMessage>,
+        response_format: Option<serde_json::Value>,
+        memory: Option<ReplyMemory>,
+    ) -> DomainResult<ResponseStream> {
+        let mut request = ChatCompletionRequest {
+            model: self.config.model.clone(),
+            messages,
+            stream: true,
+            response_format,
+        };
+        if request.uses_json_schema() && self.json_object_only.load(Ordering::Relaxed) {
+            request.response_format = Some(serde_json::json!({ "type": "json_object" }));
+        }
+        let idle_timeout = Duration::from_secs(self.config.timeout_seconds);
+
+        let mut response = self.send(&request, idle_timeout).await?;
+        if response.status() == StatusCode::BAD_REQUEST && request.uses_json_schema() {
+            // Servers without structured outputs may still have JSON mode
+            request.response_format = Some(serde_json::json!({ "type": "json_object" }));
+            response = self.send(&request, idle_timeout).await?;
+            if response.status().is_success() {
+                self.json_object_only.store(true, Ordering::Relaxed);
+            }
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(DomainError::ExternalServiceError(format!(
+                "AI service returned {}: {}",
+                status,
+                body.trim()
+            )));
+        }
+
+        Ok(chat::response_stream(
+            response,
+            SseDecoder::default(),
+            idle_timeout,
+            memory,
+        ))
+    }
+}
+
+#[async_trait]
+impl AIService for OpenAICompatibleService {
+    async fn analyze_content(&self, content: &str) -> DomainResult<AIAnalysis> {
+        let response_format = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": { "name": "ai_analysis", "schema": AIAnalysis::json_schema() },
+        });
+        request_analysis(content, |messages| {
+            self.chat(messages, Some(response_format.clone()))
+        })
+        .await
+    }
+
+    async fn generate_response(&self, context: &str) -> DomainResult<String> {
+        self.generate_conversation_response(DEFAULT_CONVERSATION, context)
+            .await
+    }
+
+    async fn generate_response_stream(&self, context: &str) -> DomainResult<ResponseStream> {
+        self.generate_conversation_response_stream(DEFAULT_CONVERSATION, context)
+            .await
+    }
+
+    async fn generate_conversation_response_stream(
+        &self,
+        conversation: &str,
+        context: &str,
+    ) -> DomainResult<ResponseStream> {
+        self.conversation_stream(conversation, context).await
+    }
+
+    async fn conversation_memory(
+        &self,
+        conversation: &str,
+    ) -> DomainResult<Vec<ConversationEntry>> {
+        self.memory.entries(conversation).await
+    }
+
+    async fn clear_conversation_memory(&self, conversation: &str) -> DomainResult<()> {
+        self.memory.clear(conversation).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::ai::{AIProvider, AnalysisRepair, PriorityLevel};
+    use futures::{StreamExt, TryStreamExt};
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(base_url: String) -> AIConfig {
+        AIConfig {
+            provider: AIProvider::OpenAiCompatible,
+            model: "local-model".to_string(),
+            base_url,
+            api_key: Some("sk-local".to_string()),
+            timeout_seconds: 5,
+            ..Default::default()
+        }
+    }
+
+    fn sse(chunks: &[&str]) -> String {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!(
+                "data: {}\n\n",
+                serde_json::json!({ "choices": [{ "index": 0, "delta": { "content": chunk } }] })
+            ));
+        }
+        body.push_str(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n",
+        );
+        body.push_str("data: [DONE]\n\n");
+        body
+    }
+
+    #[tokio::test]
+    async fn test_generate_response_stream() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer sk-local"))
+            .and(body_partial_json(
+                serde_json::json!({ "model": "local-model", "stream": true }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                sse(&["Deploy", "ment is", " approved."]),
+                "text/event-stream",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let service = OpenAICompatibleService::new(config(mock_server.uri()));
+        let chunks: Vec<String> = service
+            .generate_conversation_response_stream("jira:OPS-1", "Can we deploy?")
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(chunks, vec!["Deploy", "ment is", " approved."]);
+
+        let memory = service.conversation_memory("jira:OPS-1").await.unwrap();
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory[1].content, "Deployment is approved.");
+
+        // A cancelled generation stays out of the memory, prompt included
+        let mut stream = service
+            .generate_conversation_response_stream("jira:OPS-1", "And tomorrow?")
+            .await
+            .unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "Deploy");
+        drop(stream);
+        let memory = service.conversation_memory("jira:OPS-1").await.unwrap();
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory[0].content, "Can we deploy?");
+    }
+
+    #[tokio::test]
+    async fn test_analyze_content_uses_json_mode() {
+        let mock_server = MockServer::start().await;
+        let analysis = r#"{"requires_action": true, "priority_level": "urgent", "summary": "Prod is down", "suggested_actions": ["Page the on-call"]}"#;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "response_format": { "type": "json_schema" }
+            })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(sse(&[analysis]), "text/event-stream"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        // LM Studio's base URL usually ends with the API version
+        let service = OpenAICompatibleService::new(config(format!("{}/v1/", mock_server.uri())));
+        let analysis = service.analyze_content("Prod is down").await.unwrap();
+        assert!(analysis.requires_action);
+        assert_eq!(analysis.priority_level, PriorityLevel::Critical);
+        assert_eq!(analysis.repair, AnalysisRepair::Normalized);
+    }
+
+    #[tokio::test]
+    async fn test_analysis_falls_back_to_json_mode() {
+        let mock_server = MockServer::start().await;
+        let analysis = r#"{"requires_action": false, "priority_level": "low", "summary": "Lunch menu", "suggested_actions": []}"#;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "response_format": { "type": "json_schema" }
+            })))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_string("response_format not supported"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "response_format": { "type": "json_object" }
+            })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(sse(&[analysis]), "text/event-stream"),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let service = OpenAICompatibleService::new(config(mock_server.uri()));
+        let analysis = service.analyze_content("Lunch menu").await.unwrap();
+        assert!(!analysis.requires_action);
+        assert_eq!(analysis.summary, "Lunch menu");
+
+        // The schema is not offered again to a server that turned it down
+        service.analyze_content("Lunch menu").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_error_handling() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("invalid api key"))
+            .mount(&mock_server)
+            .await;
+
+        let service = OpenAICompatibleService::new(config(mock_server.uri()));
+        match service.generate_response("Hello").await {
+            Err(DomainError::ExternalServiceError(message)) => {
+                assert!(message.contains("invalid api key"))
+            }
+            other => panic!("Expected an external service error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decoder_handles_lines_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder
+            .push(b": keep-alive\n\ndata: {\"choices\":[{\"delta\":{\"content\":\"Hel")
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            decoder.push(b"lo\"}}]}\r\n\r\ndata: [DONE]\n\n").unwrap(),
+            vec![StreamEvent::Text("Hello".to_string()), StreamEvent::Done]
+        );
+
+        assert!(matches!(
+            decoder.push(b"data: {\"error\":{\"message\":\"model not loaded\"}}\n"),
+            Err(DomainError::ExternalServiceError(message)) if message.contains("model not loaded")
+        ));
+    }
+}
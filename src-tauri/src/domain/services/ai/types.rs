@@ -1,7 +1,9 @@
+use crate::domain::entities::{ConversationEntry, NotificationPriority};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, pin::Pin, sync::Arc};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct UserPreferences {
     pub tone: ResponseTone,
     pub length: ResponseLength,
@@ -24,6 +26,43 @@ impl Default for UserPreferences {
     }
 }
 
+impl UserPreferences {
+    /// System prompt telling the model how replies should be written
+    pub fn response_instructions(&self) -> String {
+        let tone = format!("{:?}", self.tone).to_lowercase();
+        let length = match self.length {
+            ResponseLength::Concise => "Keep replies to one or two sentences.",
+            ResponseLength::Medium => "Keep replies to a short paragraph.",
+            ResponseLength::Detailed => "Write detailed replies that address every point.",
+        };
+        let formality = match self.formality_level {
+            FormalityLevel::Casual => {
+                "Use a casual register; first names and contractions are fine."
+            }
+            FormalityLevel::Standard => "Use a standard business register.",
+            FormalityLevel::Formal => "Use a formal register.",
+            FormalityLevel::VeryFormal => {
+                "Use a very formal register with full salutations and closings."
+            }
+        };
+
+        let mut instructions = format!(
+            "You write replies to the user's notifications on their behalf. \
+            Reply with the text of the reply only, clear and actionable, and \
+            consistent with the earlier turns of the conversation.\n\
+            Write in a {} tone. {}\n\
+            {}\n\
+            Write in the language with code \"{}\".",
+            tone, length, formality, self.language
+        );
+        for instruction in &self.custom_instructions {
+            instructions.push_str("\n- ");
+            instructions.push_str(instruction);
+        }
+        instructions
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ResponseTone {
     Professional,
@@ -48,10 +87,16 @@ pub enum FormalityLevel {
     VeryFormal,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AIConfig {
+    /// The API the server at `base_url` speaks
+    #[serde(default)]
+    pub provider: AIProvider,
     pub model: String,
     pub base_url: String,
+    /// Sent as a bearer token to OpenAI-compatible servers that require one
+    #[serde(default)]
+    pub api_key: Option<String>,
     pub timeout_seconds: u64,
     pub user_preferences: UserPreferences,
 }
@@ -59,30 +104,110 @@ pub struct AIConfig {
 impl Default for AIConfig {
     fn default() -> Self {
         Self {
+            provider: AIProvider::default(),
             model: "qwen2.5:latest".to_string(),
             base_url: "http://localhost:11434".to_string(),
+            api_key: None,
             timeout_seconds: 30,
             user_preferences: UserPreferences::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AIProvider {
+    /// Ollama's own `/api/chat` API
+    #[default]
+    Ollama,
+    /// The OpenAI `/v1/chat/completions` API of llama.cpp server, vLLM,
+    /// LM Studio and the like
+    OpenAiCompatible,
+}
+
+/// The default is an analysis finding nothing to act on
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AIAnalysis {
     pub requires_action: bool,
     pub priority_level: PriorityLevel,
     pub summary: String,
     pub suggested_actions: Vec<String>,
+    /// What had to be fixed in the model's output to read it
+    #[serde(default)]
+    pub repair: AnalysisRepair,
+    /// Whether the model was asked again, with the reason its first answer
+    /// could not be read. `repair` is then about the second answer.
+    #[serde(default)]
+    pub retried: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl AIAnalysis {
+    /// JSON schema of the fields the model fills in, for services that can
+    /// constrain their output to it
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "requires_action": { "type": "boolean" },
+                "priority_level": {
+                    "type": "string",
+                    "enum": PriorityLevel::ALL,
+                },
+                "summary": { "type": "string" },
+                "suggested_actions": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                },
+            },
+            "required": ["requires_action", "priority_level", "summary", "suggested_actions"],
+        })
+    }
+}
+
+/// How the model's output was turned into an analysis. Each repair is only
+/// tried when the previous ones were not enough.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisRepair {
+    /// The output was the JSON asked for
+    #[default]
+    NotNeeded,
+    /// The JSON was cut out of a code fence or surrounding prose
+    Extracted,
+    /// Field names or values were mapped to the expected ones, e.g.
+    /// "urgent" to `Critical`
+    Normalized,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum PriorityLevel {
+    #[default]
     Low,
     Medium,
     High,
     Critical,
 }
 
+impl PriorityLevel {
+    pub const ALL: [PriorityLevel; 4] = [
+        PriorityLevel::Low,
+        PriorityLevel::Medium,
+        PriorityLevel::High,
+        PriorityLevel::Critical,
+    ];
+}
+
+impl From<&PriorityLevel> for NotificationPriority {
+    fn from(level: &PriorityLevel) -> Self {
+        match level {
+            PriorityLevel::Low => NotificationPriority::Low,
+            PriorityLevel::Medium => NotificationPriority::Medium,
+            PriorityLevel::High => NotificationPriority::High,
+            PriorityLevel::Critical => NotificationPriority::Critical,
+        }
+    }
+}
+
 #[cfg_attr(test, mockall::automock)]
 #[async_trait::async_trait]
 pub trait AIService: Send + Sync + std::fmt::Debug {
@@ -91,6 +216,91 @@ pub trait AIService: Send + Sync + std::fmt::Debug {
         content: &str,
     ) -> crate::domain::error::DomainResult<AIAnalysis>;
     async fn generate_response(&self, context: &str) -> crate::domain::error::DomainResult<String>;
+    /// Generates the response chunk by chunk as the model writes it.
+    /// Dropping the stream cancels the generation. Services that cannot
+    /// stream yield the whole response as a single chunk.
+    async fn generate_response_stream(
+        &self,
+        context: &str,
+    ) -> crate::domain::error::DomainResult<ResponseStream> {
+        let response = self.generate_response(context).await?;
+        Ok(Box::pin(futures::stream::once(async move { Ok(response) })))
+    }
+    /// Like `generate_response`, remembering only the earlier turns of
+    /// `conversation`, a notification's thread key. Services without
+    /// memory ignore the conversation.
+    async fn generate_conversation_response(
+        &self,
+        conversation: &str,
+        context: &str,
+    ) -> crate::domain::error::DomainResult<String> {
+        let stream = self
+            .generate_conversation_response_stream(conversation, context)
+            .await?;
+        futures::TryStreamExt::try_collect(stream).await
+    }
+    async fn generate_conversation_response_stream(
+        &self,
+        _conversation: &str,
+        context: &str,
+    ) -> crate::domain::error::DomainResult<ResponseStream> {
+        self.generate_response_stream(context).await
+    }
+    /// The remembered turns of the conversation, oldest first
+    async fn conversation_memory(
+        &self,
+        _conversation: &str,
+    ) -> crate::domain::error::DomainResult<Vec<ConversationEntry>> {
+        Ok(Vec::new())
+    }
+    async fn clear_conversation_memory(
+        &self,
+        _conversation: &str,
+    ) -> crate::domain::error::DomainResult<()> {
+        Ok(())
+    }
 }
 
+/// A turn of a conversation with the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::System,
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// Chunks of a response, in the order the model wrote them
+pub type ResponseStream =
+    Pin<Box<dyn Stream<Item = crate::domain::error::DomainResult<String>> + Send>>;
+
 pub type DynAIService = Arc<dyn AIService>;
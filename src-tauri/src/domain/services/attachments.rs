@@ -0,0 +1,314 @@
+use crate::domain::{
+    entities::{Attachment, CachedBlob, Notification},
+    error::{DomainError, DomainResult},
+    repositories::{DynAttachmentStore, DynNotificationRepository},
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Fetches the content of attachments from the service they came from
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AttachmentDownloader: Send + Sync + std::fmt::Debug {
+    async fn download(
+        &self,
+        notification: &Notification,
+        attachment: &Attachment,
+    ) -> DomainResult<Vec<u8>>;
+}
+
+pub type DynAttachmentDownloader = Arc<dyn AttachmentDownloader>;
+
+/// An attachment with its content
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentContent {
+    pub attachment: Attachment,
+    pub data: Vec<u8>,
+    /// Whether the content came from the local cache
+    pub from_cache: bool,
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AttachmentService: Send + Sync + std::fmt::Debug {
+    async fn get_attachments(&self, notification_id: Uuid) -> DomainResult<Vec<Attachment>>;
+    /// The content from the cache, or downloaded from the service. A download
+    /// caches the content when it fits and extracts its text for search and
+    /// the AI.
+    async fn download_attachment(
+        &self,
+        notification_id: Uuid,
+        attachment_id: Uuid,
+    ) -> DomainResult<AttachmentContent>;
+}
+
+pub type DynAttachmentService = Arc<dyn AttachmentService>;
+
+pub struct DefaultAttachmentService {
+    notification_repository: DynNotificationRepository,
+    store: DynAttachmentStore,
+    downloader: Option<DynAttachmentDownloader>,
+}
+
+impl std::fmt::Debug for DefaultAttachmentService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultAttachmentService")
+            .field("notification_repository", &self.notification_repository)
+            .field("store", &"Arc<dyn AttachmentStore>")
+            .field("downloader", &self.downloader)
+            .finish()
+    }
+}
+
+impl DefaultAttachmentService {
+    pub fn new(
+        notification_repository: DynNotificationRepository,
+        store: DynAttachmentStore,
+    ) -> Self {
+        Self {
+            notification_repository,
+            store,
+            downloader: None,
+        }
+    }
+
+    pub fn with_downloader(mut self, downloader: DynAttachmentDownloader) -> Self {
+        self.downloader = Some(downloader);
+        self
+    }
+
+    async fn notification(&self, id: Uuid) -> DomainResult<Notification> {
+        self.notification_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFoundError(format!("Notification {} not found", id)))
+    }
+}
+
+#[async_trait]
+impl AttachmentService for DefaultAttachmentService {
+    async fn get_attachments(&self, notification_id: Uuid) -> DomainResult<Vec<Attachment>> {
+        Ok(self
+            .notification(notification_id)
+            .await?
+            .metadata
+            .attachments)
+    }
+
+    async fn download_attachment(
+        &self,
+        notification_id: Uuid,
+        attachment_id: Uuid,
+    ) -> DomainResult<AttachmentContent> {
+        let notification = self.notification(notification_id).await?;
+        let attachment = notification
+            .attachment(attachment_id)
+            .cloned()
+            .ok_or_else(|| {
+                DomainError::NotFoundError(format!("Attachment {} not found", attachment_id))
+            })?;
+
+        // Evicted content is downloaded again
+        if let Some(cached) = &attachment.cached {
+            if let Some(data) = self.store.get(&cached.hash).await? {
+                return Ok(AttachmentContent {
+                    attachment,
+                    data,
+                    from_cache: true,
+                });
+            }
+        }
+
+        let downloader = self.downloader.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError("No integration services are connected".to_string())
+        })?;
+        let data = downloader.download(&notification, &attachment).await?;
+
+        let hash = hex::encode(Sha256::digest(&data));
+        let cached = self.store.put(&hash, &data).await?.then(|| CachedBlob {
+            hash,
+            size: data.len() as u64,
+            cached_at: Utc::now(),
+        });
+        let attachment = Attachment {
+            cached,
+            text: Attachment::extract_text(&attachment.mime_type, &data),
+            ..attachment
+        };
+
+        // Read again, the notification may have changed during the download
+        let mut notification = self.notification(notification_id).await?;
+        if let Some(stored) = notification
+            .metadata
+            .attachments
+            .iter_mut()
+            .find(|stored| stored.id == attachment_id)
+        {
+            *stored = attachment.clone();
+            self.notification_repository.save(&mut notification).await?;
+        }
+
+        Ok(AttachmentContent {
+            attachment,
+            data,
+            from_cache: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        NotificationMetadata, NotificationPriority, NotificationSource, NotificationStatus,
+    };
+    use crate::infrastructure::repositories::{FileAttachmentStore, SqliteNotificationRepository};
+
+    #[tokio::test]
+    async fn test_download_caches_content_and_extracts_text() {
+        let directory = tempfile::tempdir().unwrap();
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+
+        let attachment = Attachment::new(
+            "minutes.md".to_string(),
+            "text/markdown".to_string(),
+            Some(24),
+            "messages/1/attachments/2".to_string(),
+        );
+        let mut notification = Notification::new(
+            "Minutes".to_string(),
+            "See attached".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Google,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                attachments: vec![attachment.clone()],
+                ..Default::default()
+            },
+        );
+        notifications.save(&mut notification).await.unwrap();
+
+        // Downloaded once, then served from the cache
+        let mut downloader = MockAttachmentDownloader::new();
+        downloader
+            .expect_download()
+            .times(1)
+            .returning(|_, _| Ok(b"# Budget\n\nApproved **Q3**".to_vec()));
+        let service = DefaultAttachmentService::new(
+            notifications.clone(),
+            Arc::new(FileAttachmentStore::new(directory.path()).unwrap()),
+        )
+        .with_downloader(Arc::new(downloader));
+
+        let downloaded = service
+            .download_attachment(notification.id, attachment.id)
+            .await
+            .unwrap();
+        assert!(!downloaded.from_cache);
+        assert_eq!(
+            downloaded.attachment.text.as_deref(),
+            Some("Budget\n\nApproved Q3")
+        );
+
+        let cached = service
+            .download_attachment(notification.id, attachment.id)
+            .await
+            .unwrap();
+        assert!(cached.from_cache);
+        assert_eq!(cached.data, downloaded.data);
+
+        let stored = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.metadata.attachments[0], downloaded.attachment);
+        let text = stored.search_text();
+        assert!(text.contains("q3") && text.contains("minutes.md"));
+
+        assert!(matches!(
+            service
+                .download_attachment(notification.id, Uuid::new_v4())
+                .await,
+            Err(DomainError::NotFoundError(_))
+        ));
+    }
+    /// Changes the notification while its attachment downloads
+    #[derive(Debug)]
+    struct SlowDownloader {
+        notifications: DynNotificationRepository,
+    }
+
+    #[async_trait]
+    impl AttachmentDownloader for SlowDownloader {
+        async fn download(
+            &self,
+            notification: &Notification,
+            _attachment: &Attachment,
+        ) -> DomainResult<Vec<u8>> {
+            self.notifications
+                .update_status(notification.id, NotificationStatus::Read)
+                .await?;
+            Ok(b"done".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_keeps_changes_made_meanwhile() {
+        let directory = tempfile::tempdir().unwrap();
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let attachment = Attachment::new(
+            "status.txt".to_string(),
+            "text/plain".to_string(),
+            Some(4),
+            "attachments/1".to_string(),
+        );
+        let mut notification = Notification::new(
+            "Status".to_string(),
+            "See attached".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Google,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                attachments: vec![attachment.clone()],
+                ..Default::default()
+            },
+        );
+        notifications.save(&mut notification).await.unwrap();
+
+        let service = DefaultAttachmentService::new(
+            notifications.clone(),
+            Arc::new(FileAttachmentStore::new(directory.path()).unwrap()),
+        )
+        .with_downloader(Arc::new(SlowDownloader {
+            notifications: notifications.clone(),
+        }));
+        service
+            .download_attachment(notification.id, attachment.id)
+            .await
+            .unwrap();
+
+        let stored = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, NotificationStatus::Read);
+        assert_eq!(stored.metadata.attachments[0].text.as_deref(), Some("done"));
+    }
+}
@@ -0,0 +1,59 @@
+use super::types::{Job, JobHandler, JobPriority, JobType};
+use crate::domain::services::digest_service::DynDigestService;
+use chrono::Utc;
+
+/// How often the digest schedule is checked
+pub const DIGEST_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Generates the scheduled digest once its time of day has come
+#[derive(Debug)]
+pub struct DigestJobHandler {
+    digest_service: DynDigestService,
+}
+
+impl DigestJobHandler {
+    pub fn new(digest_service: DynDigestService) -> Self {
+        Self { digest_service }
+    }
+
+    /// Builds the job the scheduler submits on every check
+    pub fn job() -> Job {
+        Job::new(
+            serde_json::json!({}),
+            JobPriority::Low,
+            JobType::DigestGeneration,
+            1,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl JobHandler for DigestJobHandler {
+    async fn handle(&self, _job: &mut Job) -> Result<(), String> {
+        self.digest_service
+            .run_if_due(Utc::now())
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to generate digest: {}", e))
+    }
+
+    fn job_type(&self) -> JobType {
+        JobType::DigestGeneration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::MockDigestService;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_checks_the_schedule() {
+        let mut service = MockDigestService::new();
+        service.expect_run_if_due().times(1).returning(|_| Ok(None));
+
+        let handler = DigestJobHandler::new(Arc::new(service));
+        handler.handle(&mut DigestJobHandler::job()).await.unwrap();
+    }
+}
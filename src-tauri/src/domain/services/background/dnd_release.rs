@@ -0,0 +1,82 @@
+use super::types::{Job, JobHandler, JobPriority, JobType};
+use crate::domain::services::do_not_disturb::DynDoNotDisturb;
+use chrono::Utc;
+
+/// How often the end of Do Not Disturb is checked
+pub const DND_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Releases the notifications held by Do Not Disturb once a quiet window
+/// or the manual switch has ended.
+pub struct DndReleaseHandler {
+    dnd: DynDoNotDisturb,
+}
+
+impl std::fmt::Debug for DndReleaseHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DndReleaseHandler").finish()
+    }
+}
+
+impl DndReleaseHandler {
+    pub fn new(dnd: DynDoNotDisturb) -> Self {
+        Self { dnd }
+    }
+
+    /// Builds the job the scheduler submits on every check
+    pub fn job() -> Job {
+        Job::new(
+            serde_json::json!({}),
+            JobPriority::Normal,
+            JobType::DndRelease,
+            1,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl JobHandler for DndReleaseHandler {
+    async fn handle(&self, _job: &mut Job) -> Result<(), String> {
+        self.dnd
+            .release_if_ended(Utc::now())
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to release held notifications: {}", e))
+    }
+
+    fn job_type(&self) -> JobType {
+        JobType::DndRelease
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::DndSettings, repositories::DndRepository, services::do_not_disturb::DoNotDisturb,
+    };
+    use crate::infrastructure::repositories::SqliteDndRepository;
+    use chrono::Duration;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_releases_once_the_manual_switch_expired() {
+        let repository = Arc::new(SqliteDndRepository::new(":memory:").unwrap());
+        let mut settings = DndSettings {
+            manual_enabled: true,
+            manual_until: Some(Utc::now() + Duration::milliseconds(20)),
+            ..DndSettings::default()
+        };
+        repository.save_settings(&mut settings).await.unwrap();
+        let held = Uuid::new_v4();
+        repository.hold(held).await.unwrap();
+
+        let handler = DndReleaseHandler::new(Arc::new(DoNotDisturb::new(repository.clone())));
+        handler.handle(&mut DndReleaseHandler::job()).await.unwrap();
+        assert_eq!(repository.find_held().await.unwrap(), vec![held]);
+
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        handler.handle(&mut DndReleaseHandler::job()).await.unwrap();
+        assert!(repository.find_held().await.unwrap().is_empty());
+    }
+}
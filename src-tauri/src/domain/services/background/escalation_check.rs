@@ -0,0 +1,46 @@
+use super::types::{Job, JobHandler, JobPriority, JobType};
+use crate::domain::services::escalation::DynEscalator;
+use chrono::Utc;
+use tracing::debug;
+
+/// How often notifications left `ActionRequired` are checked for escalation
+pub const ESCALATION_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// Runs the escalation steps that fell due since the last check
+#[derive(Debug)]
+pub struct EscalationCheckHandler {
+    escalator: DynEscalator,
+}
+
+impl EscalationCheckHandler {
+    pub fn new(escalator: DynEscalator) -> Self {
+        Self { escalator }
+    }
+
+    /// Builds the job the scheduler submits on every check
+    pub fn job() -> Job {
+        Job::new(
+            serde_json::json!({}),
+            JobPriority::Normal,
+            JobType::EscalationCheck,
+            1,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl JobHandler for EscalationCheckHandler {
+    async fn handle(&self, _job: &mut Job) -> Result<(), String> {
+        let steps = self
+            .escalator
+            .run(Utc::now())
+            .await
+            .map_err(|e| format!("Failed to escalate notifications: {}", e))?;
+        debug!("Ran {} escalation steps", steps);
+        Ok(())
+    }
+
+    fn job_type(&self) -> JobType {
+        JobType::EscalationCheck
+    }
+}
@@ -1,8 +1,10 @@
 use super::types::{Job, JobHandler, JobStatus, JobType};
 use crate::domain::error::DomainError;
+use crate::domain::events::{DynEventPublisher, JobEvent};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
@@ -17,10 +19,22 @@ pub trait BackgroundJobManagerTrait: Send + Sync + std::fmt::Debug {
     async fn cancel_job(&self, job_id: uuid::Uuid) -> Result<(), DomainError>;
 }
 
-#[derive(Debug)]
 pub struct BackgroundJobManager {
     handlers: Arc<RwLock<HashMap<JobType, Arc<dyn JobHandler>>>>,
     active_jobs: Arc<RwLock<HashMap<uuid::Uuid, Arc<RwLock<Job>>>>>,
+    event_publisher: Option<DynEventPublisher>,
+    retry_backoff: Duration,
+}
+
+impl std::fmt::Debug for BackgroundJobManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackgroundJobManager")
+            .field("handlers", &self.handlers)
+            .field("active_jobs", &self.active_jobs)
+            .field("event_publisher", &self.event_publisher.is_some())
+            .field("retry_backoff", &self.retry_backoff)
+            .finish()
+    }
 }
 
 impl Default for BackgroundJobManager {
@@ -58,15 +72,31 @@ impl BackgroundJobManagerTrait for BackgroundJobManager {
         let job = Arc::new(RwLock::new(job));
         let job_id = { job.read().await.id };
 
+        let (job_type, routine) = {
+            let job = job.read().await;
+            (job.metadata.job_type.clone(), job.metadata.routine)
+        };
+
         let mut active_jobs = self.active_jobs.write().await;
         active_jobs.insert(job_id, job.clone());
+        drop(active_jobs);
+
+        if !routine {
+            Self::publish(
+                &self.event_publisher,
+                JobEvent::job_submitted(job_id, job_type.clone()),
+            )
+            .await;
+        }
 
         // Spawn task to process the job
-        let handler = handlers[&job.read().await.metadata.job_type].clone();
+        let handler = handlers[&job_type].clone();
         let active_jobs = self.active_jobs.clone();
+        let event_publisher = self.event_publisher.clone();
+        let retry_backoff = self.retry_backoff;
 
         tokio::spawn(async move {
-            Self::process_job(job, handler, active_jobs).await;
+            Self::process_job(job, handler, active_jobs, event_publisher, retry_backoff).await;
         });
 
         Ok(job_id)
@@ -110,6 +140,30 @@ impl BackgroundJobManager {
         Self {
             handlers: Arc::new(RwLock::new(HashMap::new())),
             active_jobs: Arc::new(RwLock::new(HashMap::new())),
+            event_publisher: None,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// Publishes job lifecycle events (submitted, started, completed, failed).
+    /// Routine jobs only publish their failures.
+    pub fn with_event_publisher(mut self, event_publisher: DynEventPublisher) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    /// Base delay before a failed job is retried; doubled on every attempt.
+    /// Only handlers that opt in through `JobHandler::retries_on_failure` are retried.
+    pub fn with_retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    async fn publish(event_publisher: &Option<DynEventPublisher>, event: JobEvent) {
+        if let Some(publisher) = event_publisher {
+            if let Err(e) = publisher.publish(event.into()).await {
+                warn!("Failed to publish job event: {}", e);
+            }
         }
     }
 
@@ -117,69 +171,117 @@ impl BackgroundJobManager {
         job: Arc<RwLock<Job>>,
         handler: Arc<dyn JobHandler>,
         active_jobs: Arc<RwLock<HashMap<uuid::Uuid, Arc<RwLock<Job>>>>>,
+        event_publisher: Option<DynEventPublisher>,
+        retry_backoff: Duration,
     ) {
-        let (job_id, job_type) = {
+        let (job_id, job_type, routine) = {
             let job_read = job.read().await;
-            (job_read.id, job_read.metadata.job_type.clone())
+            (
+                job_read.id,
+                job_read.metadata.job_type.clone(),
+                job_read.metadata.routine,
+            )
         };
 
-        let start_time = std::time::Instant::now();
+        loop {
+            let start_time = std::time::Instant::now();
 
-        info!(
-            "Starting job processing. ID: {}, Type: {:?}",
-            job_id, job_type
-        );
+            info!(
+                "Starting job processing. ID: {}, Type: {:?}",
+                job_id, job_type
+            );
 
-        // Mark job as started
-        {
-            let mut job_write = job.write().await;
-            job_write.start();
-        }
-        info!("Job state changed to Running. ID: {}", job_id);
-
-        // Process the job with minimal lock time
-        let mut job_inner = job.write().await;
-        let result = handler.handle(&mut job_inner).await;
-        let status = match &result {
-            Ok(()) => {
-                let elapsed = start_time.elapsed();
-                job_inner.complete();
-                info!(
-                    "Job completed successfully. ID: {}, Type: {:?}, Duration: {:?}",
-                    job_id, job_type, elapsed
-                );
-                JobStatus::Completed
+            // Mark job as started
+            {
+                let mut job_write = job.write().await;
+                job_write.start();
+            }
+            info!("Job state changed to Running. ID: {}", job_id);
+            if !routine {
+                Self::publish(
+                    &event_publisher,
+                    JobEvent::job_started(job_id, job_type.clone()),
+                )
+                .await;
             }
-            Err(error) => {
-                let elapsed = start_time.elapsed();
-                job_inner.fail(error.clone());
-                warn!(
-                    "Job failed. ID: {}, Type: {:?}, Duration: {:?}, Error: {}",
-                    job_id, job_type, elapsed, error
-                );
-
-                if !job_inner.can_retry() {
-                    error!(
-                        "Job exceeded maximum retries. ID: {}, Type: {:?}",
-                        job_id, job_type
+
+            // Process the job with minimal lock time
+            let mut job_inner = job.write().await;
+            let result = handler.handle(&mut job_inner).await;
+            let (retry_in, event) = match &result {
+                Ok(()) => {
+                    let elapsed = start_time.elapsed();
+                    job_inner.complete();
+                    info!(
+                        "Job completed successfully. ID: {}, Type: {:?}, Duration: {:?}",
+                        job_id, job_type, elapsed
+                    );
+                    (None, JobEvent::job_completed(job_id, job_type.clone()))
+                }
+                Err(error) => {
+                    let elapsed = start_time.elapsed();
+                    job_inner.fail(error.clone());
+                    warn!(
+                        "Job failed. ID: {}, Type: {:?}, Duration: {:?}, Error: {}",
+                        job_id, job_type, elapsed, error
                     );
+
+                    let will_retry = handler.retries_on_failure() && job_inner.can_retry();
+                    if !will_retry {
+                        // Not run again, whatever retries remain on the job
+                        job_inner.status = JobStatus::Failed;
+                    }
+                    if handler.retries_on_failure() && !will_retry {
+                        error!(
+                            "Job exceeded maximum retries. ID: {}, Type: {:?}",
+                            job_id, job_type
+                        );
+                    }
+                    let retry_in = will_retry
+                        .then(|| Self::backoff_delay(retry_backoff, job_inner.metadata.retry_count));
+                    (
+                        retry_in,
+                        JobEvent::job_failed(job_id, job_type.clone(), error.clone(), will_retry),
+                    )
                 }
-                JobStatus::Failed
+            };
+            drop(job_inner); // Explicitly release the lock
+
+            // Routine runs only surface when they fail
+            if !routine || matches!(event, JobEvent::JobFailed { .. }) {
+                Self::publish(&event_publisher, event).await;
             }
-        };
-        drop(job_inner); // Explicitly release the lock
-
-        // Remove from active jobs if complete/failed
-        if matches!(status, JobStatus::Completed | JobStatus::Failed) {
-            let mut active_jobs = active_jobs.write().await;
-            match active_jobs.remove(&job_id) {
-                Some(_) => info!("Job removed from active jobs. ID: {}", job_id),
-                None => warn!(
-                    "Job not found in active jobs during cleanup. ID: {}",
-                    job_id
-                ),
+
+            let Some(delay) = retry_in else {
+                break;
+            };
+
+            info!(
+                "Retrying job in {:?}. ID: {}, Type: {:?}",
+                delay, job_id, job_type
+            );
+            tokio::time::sleep(delay).await;
+
+            if job.read().await.status == JobStatus::Cancelled {
+                info!("Job cancelled while waiting to retry. ID: {}", job_id);
+                break;
             }
         }
+
+        // Remove from active jobs once it will not run again
+        let mut active_jobs = active_jobs.write().await;
+        match active_jobs.remove(&job_id) {
+            Some(_) => info!("Job removed from active jobs. ID: {}", job_id),
+            None => warn!(
+                "Job not found in active jobs during cleanup. ID: {}",
+                job_id
+            ),
+        }
+    }
+
+    /// Exponential backoff: `base`, `2 * base`, `4 * base`, ... capped at 2^10.
+    fn backoff_delay(base: Duration, retry_count: u32) -> Duration {
+        base * 2u32.pow(retry_count.saturating_sub(1).min(10))
     }
 }
 
@@ -276,4 +378,136 @@ mod tests {
             timeout, last_status, attempts
         );
     }
+
+    #[tokio::test]
+    async fn test_job_lifecycle_events_are_published() {
+        use crate::domain::events::{DomainEvent, EventBus};
+
+        let bus = Arc::new(EventBus::new());
+        let manager = BackgroundJobManager::new().with_event_publisher(bus.clone());
+        manager.register_handler(Arc::new(TestHandler)).await.unwrap();
+
+        let job = Job::new(
+            serde_json::Value::Null,
+            JobPriority::Normal,
+            JobType::Custom("test".to_string()),
+            3,
+        );
+        manager.submit_job(job).await.unwrap();
+
+        let start_time = std::time::Instant::now();
+        while bus.latest_sequence().await < 3 {
+            assert!(
+                start_time.elapsed() < std::time::Duration::from_secs(1),
+                "job events were not published"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+
+        let events: Vec<DomainEvent> = bus
+            .events_since(0)
+            .await
+            .into_iter()
+            .map(|envelope| envelope.event)
+            .collect();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                DomainEvent::Job(JobEvent::JobSubmitted { .. }),
+                DomainEvent::Job(JobEvent::JobStarted { .. }),
+                DomainEvent::Job(JobEvent::JobCompleted { .. }),
+            ]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_routine_job_success_is_not_published() {
+        use crate::domain::events::EventBus;
+
+        let bus = Arc::new(EventBus::new());
+        let manager = BackgroundJobManager::new().with_event_publisher(bus.clone());
+        manager
+            .register_handler(Arc::new(TestHandler))
+            .await
+            .unwrap();
+
+        let job = Job::new(
+            serde_json::Value::Null,
+            JobPriority::Normal,
+            JobType::Custom("test".to_string()),
+            3,
+        )
+        .routine();
+        let job_id = manager.submit_job(job).await.unwrap();
+
+        let start_time = std::time::Instant::now();
+        while manager.get_job_status(job_id).await.is_some() {
+            assert!(
+                start_time.elapsed() < Duration::from_secs(1),
+                "job did not finish"
+            );
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(bus.latest_sequence().await, 0);
+    }
+
+    #[derive(Debug, Default)]
+    struct FailingHandler {
+        runs: std::sync::atomic::AtomicUsize,
+        retries: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl JobHandler for FailingHandler {
+        async fn handle(&self, _job: &mut Job) -> Result<(), String> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err("boom".to_string())
+        }
+
+        fn job_type(&self) -> JobType {
+            JobType::Custom("failing".to_string())
+        }
+
+        fn retries_on_failure(&self) -> bool {
+            self.retries
+        }
+    }
+
+    async fn run_failing_job(handler: Arc<FailingHandler>) -> usize {
+        let manager = BackgroundJobManager::new().with_retry_backoff(Duration::from_millis(1));
+        manager.register_handler(handler.clone()).await.unwrap();
+
+        let job = Job::new(
+            serde_json::Value::Null,
+            JobPriority::Normal,
+            JobType::Custom("failing".to_string()),
+            3,
+        );
+        let job_id = manager.submit_job(job).await.unwrap();
+
+        let start_time = std::time::Instant::now();
+        while manager.get_job_status(job_id).await.is_some() {
+            assert!(
+                start_time.elapsed() < Duration::from_secs(1),
+                "job did not finish"
+            );
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        handler.runs.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_is_not_retried_by_default() {
+        let handler = Arc::new(FailingHandler::default());
+        assert_eq!(run_failing_job(handler).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_is_retried_when_handler_opts_in() {
+        let handler = Arc::new(FailingHandler {
+            retries: true,
+            ..Default::default()
+        });
+        assert_eq!(run_failing_job(handler).await, 3);
+    }
 }
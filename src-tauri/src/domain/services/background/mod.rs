@@ -1,10 +1,22 @@
+pub mod digest_job;
+pub mod dnd_release;
+pub mod escalation_check;
 pub mod manager;
 pub mod mcp_server_job;
 pub mod notification_processor;
+pub mod scheduler;
+pub mod score_refresh;
+pub mod snooze_wakeup;
 pub mod types;
 
+pub use digest_job::DigestJobHandler;
+pub use dnd_release::DndReleaseHandler;
+pub use escalation_check::EscalationCheckHandler;
 pub use manager::BackgroundJobManager;
 pub use notification_processor::{NotificationActionType, NotificationProcessor};
+pub use scheduler::JobScheduler;
+pub use score_refresh::ScoreRefreshHandler;
+pub use snooze_wakeup::SnoozeWakeupHandler;
 pub use types::{Job, JobHandler, JobPriority, JobStatus, JobType};
 
 #[cfg(test)]
@@ -1,9 +1,14 @@
 use super::types::{Job, JobHandler, JobType};
 use crate::domain::{
-    entities::notification::{Notification, NotificationStatus},
+    entities::notification::{Notification, NotificationPriority, NotificationStatus},
     events::{EventPublisher, NotificationEvent},
-    repositories::notification_repository::NotificationRepository,
-    services::notification_service::NotificationService,
+    repositories::{notification_repository::NotificationRepository, RuleRepository},
+    services::{
+        do_not_disturb::DynDoNotDisturb,
+        notification_service::NotificationService,
+        rules::{evaluate_rules, RuleEvaluation},
+        scoring::DynPriorityScorer,
+    },
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -55,6 +60,9 @@ pub struct NotificationProcessor {
     notification_service: Arc<dyn NotificationService + Send + Sync>,
     notification_repository: Arc<dyn NotificationRepository + Send + Sync>,
     event_publisher: Arc<dyn EventPublisher>,
+    rule_repository: Option<Arc<dyn RuleRepository>>,
+    dnd: Option<DynDoNotDisturb>,
+    scorer: Option<DynPriorityScorer>,
 }
 
 impl std::fmt::Debug for NotificationProcessor {
@@ -65,6 +73,9 @@ impl std::fmt::Debug for NotificationProcessor {
                 "notification_repository",
                 &"Arc<dyn NotificationRepository>",
             )
+            .field("rule_repository", &self.rule_repository.is_some())
+            .field("dnd", &self.dnd.is_some())
+            .field("scorer", &self.scorer.is_some())
             .finish()
     }
 }
@@ -79,20 +90,191 @@ impl NotificationProcessor {
             notification_service,
             notification_repository,
             event_publisher,
+            rule_repository: None,
+            dnd: None,
+            scorer: None,
         }
     }
 
+    /// Runs the user's rules on new notifications before the AI analysis
+    pub fn with_rule_repository(mut self, rule_repository: Arc<dyn RuleRepository>) -> Self {
+        self.rule_repository = Some(rule_repository);
+        self
+    }
+
+    /// Defers auto-responses to the notifications Do Not Disturb holds until it ends
+    pub fn with_do_not_disturb(mut self, dnd: DynDoNotDisturb) -> Self {
+        self.dnd = Some(dnd);
+        self
+    }
+
+    /// Scores the analysed notifications with the priority suggested by the AI
+    pub fn with_scorer(mut self, scorer: DynPriorityScorer) -> Self {
+        self.scorer = Some(scorer);
+        self
+    }
+
+    /// The Do Not Disturb holding the notification, if any
+    async fn held_by_dnd(&self, notification: &Notification) -> Option<&DynDoNotDisturb> {
+        let dnd = self.dnd.as_ref()?;
+        let held = dnd.should_hold(notification).await.unwrap_or_else(|e| {
+            warn!("Failed to check Do Not Disturb: {}", e);
+            false
+        });
+        held.then_some(dnd)
+    }
+
+    async fn publish(&self, event: NotificationEvent) -> Result<(), ProcessorError> {
+        self.event_publisher
+            .publish_event(event)
+            .await
+            .map_err(|e| ProcessorError::Event(e.to_string()))
+    }
+
+    async fn evaluate_rules(
+        &self,
+        notification: &Notification,
+    ) -> Result<Option<RuleEvaluation>, ProcessorError> {
+        let Some(rule_repository) = &self.rule_repository else {
+            return Ok(None);
+        };
+        let rules = rule_repository
+            .find_all()
+            .await
+            .map_err(|e| ProcessorError::Repository(e.to_string()))?;
+        let evaluation = evaluate_rules(&rules, notification);
+        Ok(evaluation.matched().then_some(evaluation))
+    }
+
+    /// Saves the rule changes and sends the auto-responses and forwards.
+    /// Returns true when the rules settled the notification so the AI is skipped.
+    async fn apply_rules(&self, evaluation: RuleEvaluation) -> Result<bool, ProcessorError> {
+        let mut notification = evaluation.notification;
+        let notification_id = notification.id;
+
+        self.notification_repository
+            .save(&mut notification)
+            .await
+            .map_err(|e| ProcessorError::Repository(e.to_string()))?;
+        self.publish(NotificationEvent::rules_applied(
+            notification_id,
+            evaluation
+                .matched_rules
+                .iter()
+                .map(|rule| rule.rule_id)
+                .collect(),
+        ))
+        .await?;
+
+        let responses = if evaluation.responses.is_empty() {
+            &[][..]
+        } else if !notification.allows_auto_reply() {
+            info!(
+                "The sender of notification {} does not accept auto-responses",
+                notification_id
+            );
+            &[][..]
+        } else if let Some(dnd) = self.held_by_dnd(&notification).await {
+            info!(
+                "Do Not Disturb is active, deferring the auto-responses to notification {}",
+                notification_id
+            );
+            for response in &evaluation.responses {
+                if let Err(e) = dnd.defer_response(notification_id, response).await {
+                    warn!(
+                        "Failed to defer auto-response for notification {}: {}",
+                        notification_id, e
+                    );
+                }
+            }
+            &[][..]
+        } else {
+            &evaluation.responses[..]
+        };
+
+        // Delivery failures must not undo the rule changes already saved
+        for response in responses {
+            let sent = self
+                .notification_service
+                .send_response(&notification, response)
+                .await;
+            if let Err(e) = &sent {
+                warn!(
+                    "Failed to send auto-response for notification {}: {}",
+                    notification_id, e
+                );
+            }
+            if let Err(e) = self
+                .publish(NotificationEvent::response_sent(
+                    notification_id,
+                    true,
+                    sent.is_ok(),
+                ))
+                .await
+            {
+                warn!("Failed to publish response sent event: {}", e);
+            }
+        }
+        for recipient in &evaluation.forward_to {
+            if let Err(e) = self
+                .notification_service
+                .forward_notification(&notification, recipient)
+                .await
+            {
+                warn!(
+                    "Failed to forward notification {} to {}: {}",
+                    notification_id, recipient, e
+                );
+            }
+        }
+
+        let status_event = match notification.status {
+            NotificationStatus::Read => Some(NotificationEvent::notification_read(notification_id)),
+            NotificationStatus::Archived => {
+                Some(NotificationEvent::notification_archived(notification_id))
+            }
+            NotificationStatus::ActionRequired => Some(
+                NotificationEvent::notification_action_required(notification_id),
+            ),
+            NotificationStatus::ActionTaken => Some(NotificationEvent::notification_action_taken(
+                notification_id,
+            )),
+            NotificationStatus::Deleted => {
+                Some(NotificationEvent::notification_deleted(notification_id))
+            }
+            NotificationStatus::New | NotificationStatus::Snoozed => None,
+        };
+        if let Some(event) = status_event {
+            self.publish(event).await?;
+            return Ok(true);
+        }
+
+        Ok(evaluation.skip_ai)
+    }
+
     async fn process_notification(&self, notification_id: Uuid) -> Result<(), ProcessorError> {
-        let notification = self.get_notification(notification_id).await?;
+        let mut notification = self.get_notification(notification_id).await?;
 
         match notification.status {
             NotificationStatus::New => {
+                if let Some(evaluation) = self.evaluate_rules(&notification).await? {
+                    notification = evaluation.notification.clone();
+                    if self.apply_rules(evaluation).await? {
+                        info!(
+                            "Rules handled notification {}, skipping AI analysis",
+                            notification_id
+                        );
+                        return Ok(());
+                    }
+                }
+
                 // Analyze notification content and determine if action is required
-                let requires_action = self
+                let analysis = self
                     .notification_service
                     .analyze_notification_content(&notification)
                     .await
                     .map_err(|e| ProcessorError::Service(e.to_string()))?;
+                let requires_action = analysis.requires_action;
 
                 let mut updated_notification = notification.clone();
                 if requires_action {
@@ -100,22 +282,33 @@ impl NotificationProcessor {
                 } else {
                     updated_notification.mark_as_read();
                 }
+                if let Some(scorer) = &self.scorer {
+                    scorer.apply(
+                        &mut updated_notification,
+                        Some(NotificationPriority::from(&analysis.priority_level)),
+                    );
+                }
 
                 self.notification_repository
                     .save(&mut updated_notification)
                     .await
                     .map_err(|e| ProcessorError::Repository(e.to_string()))?;
 
-                // Publish event
-                let event = if requires_action {
+                // Publish events
+                let status_event = if requires_action {
                     NotificationEvent::notification_action_required(notification_id)
                 } else {
                     NotificationEvent::notification_read(notification_id)
                 };
-                self.event_publisher
-                    .publish_event(event)
-                    .await
-                    .map_err(|e| ProcessorError::Event(e.to_string()))?;
+                for event in [
+                    NotificationEvent::notification_processed(notification_id, requires_action),
+                    status_event,
+                ] {
+                    self.event_publisher
+                        .publish_event(event)
+                        .await
+                        .map_err(|e| ProcessorError::Event(e.to_string()))?;
+                }
             }
             _ => {
                 warn!("Notification {} is not in New status", notification_id);
@@ -205,12 +398,16 @@ impl NotificationProcessor {
             .await
             .map_err(|e| ProcessorError::Repository(e.to_string()))?;
 
-        // Publish action executed event
-        let event = NotificationEvent::action_executed(notification_id, true, None);
-        self.event_publisher
-            .publish_event(event)
-            .await
-            .map_err(|e| format!("Failed to publish event: {}", e))?;
+        // Publish action executed and action taken events
+        for event in [
+            NotificationEvent::action_executed(notification_id, true, None),
+            NotificationEvent::notification_action_taken(notification_id),
+        ] {
+            self.event_publisher
+                .publish_event(event)
+                .await
+                .map_err(|e| format!("Failed to publish event: {}", e))?;
+        }
 
         Ok(())
     }
@@ -247,11 +444,19 @@ mod tests {
         entities::notification::{
             Notification, NotificationMetadata, NotificationPriority, NotificationSource,
         },
-        events::NoopEventPublisher,
-        repositories::NotificationRepository,
-        services::{background::JobPriority, NotificationService},
+        entities::{DndSettings, Rule, RuleAction, RuleCondition, SavedView, SenderContact},
+        events::{DomainEvent, EventBus, NoopEventPublisher},
+        repositories::{CoalescingMerge, DndRepository, NotificationRepository, RuleRepository},
+        services::{
+            ai::{AIAnalysis, PriorityLevel},
+            background::JobPriority,
+            do_not_disturb::DoNotDisturb,
+            scoring::{PriorityScorer, ScoringConfig},
+            NotificationService,
+        },
         DomainError, DomainResult,
     };
+    use crate::infrastructure::repositories::{SqliteDndRepository, SqliteRuleRepository};
     use async_trait::async_trait;
     use std::{
         collections::HashMap,
@@ -309,6 +514,38 @@ mod tests {
                 .collect())
         }
 
+        async fn find_by_thread_key(&self, _thread_key: &str) -> DomainResult<Vec<Notification>> {
+            unimplemented!("Not needed for these tests")
+        }
+
+        async fn find_by_coalescing_key(&self, _key: &str) -> DomainResult<Vec<Notification>> {
+            unimplemented!("Not needed for these tests")
+        }
+
+        async fn save_coalesced(
+            &self,
+            _key: &str,
+            _merge: CoalescingMerge,
+        ) -> DomainResult<Notification> {
+            unimplemented!("Not needed for these tests")
+        }
+
+        async fn find_by_tag(&self, _tag: &str) -> DomainResult<Vec<Notification>> {
+            unimplemented!("Not needed for these tests")
+        }
+
+        async fn count_by_tag(&self) -> DomainResult<Vec<(String, usize)>> {
+            unimplemented!("Not needed for these tests")
+        }
+
+        async fn find_in_view(&self, _view: &SavedView) -> DomainResult<Vec<Notification>> {
+            unimplemented!("Not needed for these tests")
+        }
+
+        async fn count_in_view(&self, _view: &SavedView) -> DomainResult<(usize, usize)> {
+            unimplemented!("Not needed for these tests")
+        }
+
         async fn delete(&self, id: Uuid) -> DomainResult<()> {
             let mut notifications = self.notifications.lock().unwrap();
             notifications.remove(&id);
@@ -331,7 +568,10 @@ mod tests {
     }
 
     #[derive(Default, Debug)]
-    struct TestNotificationService {}
+    struct TestNotificationService {
+        sent_responses: Mutex<Vec<String>>,
+        fail_responses: bool,
+    }
 
     #[async_trait]
     impl NotificationService for TestNotificationService {
@@ -346,7 +586,7 @@ mod tests {
         }
 
         async fn get_notification(&self, _id: Uuid) -> DomainResult<Notification> {
-            unimplemented!()
+            Ok(create_test_notification())
         }
 
         async fn get_all_notifications(&self) -> DomainResult<Vec<Notification>> {
@@ -367,6 +607,10 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_notifications_by_tag(&self, _tag: &str) -> DomainResult<Vec<Notification>> {
+            Ok(vec![])
+        }
+
         async fn mark_as_read(&self, _id: Uuid) -> DomainResult<()> {
             Ok(())
         }
@@ -387,11 +631,29 @@ mod tests {
             Ok(())
         }
 
+        async fn snooze_notification(
+            &self,
+            _id: Uuid,
+            _until: chrono::DateTime<chrono::Utc>,
+        ) -> DomainResult<()> {
+            Ok(())
+        }
+
+        async fn unsnooze_notification(&self, _id: Uuid) -> DomainResult<()> {
+            Ok(())
+        }
+
         async fn analyze_notification_content(
             &self,
             _notification: &Notification,
-        ) -> DomainResult<bool> {
-            Ok(true)
+        ) -> DomainResult<AIAnalysis> {
+            Ok(AIAnalysis {
+                requires_action: true,
+                priority_level: PriorityLevel::High,
+                summary: TEST_TITLE.to_string(),
+                suggested_actions: vec![],
+                ..Default::default()
+            })
         }
 
         async fn generate_response(&self, _notification: &Notification) -> DomainResult<String> {
@@ -401,6 +663,31 @@ mod tests {
         async fn execute_action(&self, _notification: &Notification) -> DomainResult<()> {
             Ok(())
         }
+
+        async fn send_response(
+            &self,
+            _notification: &Notification,
+            response: &str,
+        ) -> DomainResult<()> {
+            if self.fail_responses {
+                return Err(DomainError::ExternalServiceError(
+                    "Integration unavailable".to_string(),
+                ));
+            }
+            self.sent_responses
+                .lock()
+                .unwrap()
+                .push(response.to_string());
+            Ok(())
+        }
+
+        async fn forward_notification(
+            &self,
+            _notification: &Notification,
+            _recipient: &str,
+        ) -> DomainResult<()> {
+            Ok(())
+        }
     }
 
     fn create_test_notification() -> Notification {
@@ -414,6 +701,7 @@ mod tests {
                 url: None,
                 tags: vec![],
                 custom_data: None,
+                ..Default::default()
             },
         )
     }
@@ -464,6 +752,204 @@ mod tests {
         assert_eq!(processed.status, NotificationStatus::ActionRequired);
     }
 
+    #[tokio::test]
+    async fn test_ai_priority_is_part_of_the_score() {
+        let notification = create_test_notification();
+        let (repository, _service, processor) = setup_test_environment();
+        let processor = processor.with_scorer(Arc::new(PriorityScorer::new(
+            repository.clone(),
+            ScoringConfig::default(),
+        )));
+        repository.save(&mut notification.clone()).await.unwrap();
+
+        processor
+            .process_notification(notification.id)
+            .await
+            .unwrap();
+
+        let processed = repository
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let score = processed.metadata.score.unwrap();
+        assert_eq!(score.ai_priority, Some(NotificationPriority::High));
+        // Medium from the source and High from the AI
+        assert_eq!(score.score, 36.0);
+    }
+
+    #[tokio::test]
+    async fn test_rules_run_before_ai_analysis() {
+        let notification = create_test_notification();
+        let (repository, service, processor) = setup_test_environment();
+        repository.save(&mut notification.clone()).await.unwrap();
+
+        let rule_repository = Arc::new(SqliteRuleRepository::new(":memory:").unwrap());
+        let mut rule = Rule::new(
+            "Archive test mail".to_string(),
+            vec![RuleCondition::Title {
+                pattern: "^Test".to_string(),
+            }],
+            vec![
+                RuleAction::AddTags {
+                    tags: vec!["automated".to_string()],
+                },
+                RuleAction::SetStatus {
+                    status: NotificationStatus::Archived,
+                },
+                RuleAction::AutoRespond {
+                    template: "Received: {{title}}".to_string(),
+                },
+            ],
+        );
+        rule_repository.save(&mut rule).await.unwrap();
+        let processor = processor.with_rule_repository(rule_repository);
+
+        processor
+            .process_notification(notification.id)
+            .await
+            .unwrap();
+
+        // The AI would have marked it ActionRequired
+        let processed = repository
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(processed.status, NotificationStatus::Archived);
+        assert_eq!(processed.metadata.tags, vec!["automated"]);
+        assert_eq!(
+            *service.sent_responses.lock().unwrap(),
+            vec![format!("Received: {}", TEST_TITLE)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_auto_responses_are_recorded() {
+        let notification = create_test_notification();
+        let repository = Arc::new(TestNotificationRepository::default());
+        repository.save(&mut notification.clone()).await.unwrap();
+        let bus = Arc::new(EventBus::new());
+        let service = Arc::new(TestNotificationService {
+            fail_responses: true,
+            ..TestNotificationService::default()
+        });
+
+        let rule_repository = Arc::new(SqliteRuleRepository::new(":memory:").unwrap());
+        let mut rule = Rule::new(
+            "Acknowledge".to_string(),
+            vec![],
+            vec![RuleAction::AutoRespond {
+                template: "Received: {{title}}".to_string(),
+            }],
+        );
+        rule_repository.save(&mut rule).await.unwrap();
+        let processor = NotificationProcessor::new(service, repository.clone(), bus.clone())
+            .with_rule_repository(rule_repository);
+
+        processor
+            .process_notification(notification.id)
+            .await
+            .unwrap();
+
+        // The failure is recorded and the analysis still runs
+        assert!(bus.events_since(0).await.iter().any(|envelope| matches!(
+            &envelope.event,
+            DomainEvent::Notification(NotificationEvent::ResponseSent {
+                automatic: true,
+                success: false,
+                ..
+            })
+        )));
+        let processed = repository
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(processed.status, NotificationStatus::ActionRequired);
+    }
+
+    #[tokio::test]
+    async fn test_do_not_disturb_defers_auto_responses() {
+        let notification = create_test_notification();
+        let (repository, service, processor) = setup_test_environment();
+        repository.save(&mut notification.clone()).await.unwrap();
+
+        let rule_repository = Arc::new(SqliteRuleRepository::new(":memory:").unwrap());
+        let mut rule = Rule::new(
+            "Acknowledge".to_string(),
+            vec![],
+            vec![RuleAction::AutoRespond {
+                template: "Received: {{title}}".to_string(),
+            }],
+        );
+        rule_repository.save(&mut rule).await.unwrap();
+
+        let dnd_repository = Arc::new(SqliteDndRepository::new(":memory:").unwrap());
+        let mut settings = DndSettings {
+            manual_enabled: true,
+            ..DndSettings::default()
+        };
+        dnd_repository.save_settings(&mut settings).await.unwrap();
+
+        let dnd = Arc::new(
+            DoNotDisturb::new(dnd_repository).with_notification_service(Arc::downgrade(&service)),
+        );
+        let processor = processor
+            .with_rule_repository(rule_repository)
+            .with_do_not_disturb(dnd.clone());
+        processor
+            .process_notification(notification.id)
+            .await
+            .unwrap();
+
+        // The rules and the AI still run, only the reply waits
+        assert!(service.sent_responses.lock().unwrap().is_empty());
+        let processed = repository
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(processed.status, NotificationStatus::ActionRequired);
+
+        dnd.set_manual(false, None).await.unwrap();
+        assert_eq!(
+            *service.sent_responses.lock().unwrap(),
+            vec![format!("Received: {}", TEST_TITLE)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_contacts_can_opt_out_of_auto_responses() {
+        let mut notification = create_test_notification();
+        notification.metadata.contact = Some(SenderContact {
+            contact_id: Uuid::new_v4(),
+            name: "Grace".to_string(),
+            vip: true,
+            auto_reply: false,
+        });
+        let (repository, service, processor) = setup_test_environment();
+        repository.save(&mut notification.clone()).await.unwrap();
+
+        let rule_repository = Arc::new(SqliteRuleRepository::new(":memory:").unwrap());
+        let mut rule = Rule::new(
+            "Acknowledge VIPs".to_string(),
+            vec![RuleCondition::Vip],
+            vec![RuleAction::AutoRespond {
+                template: "Received: {{title}}".to_string(),
+            }],
+        );
+        rule_repository.save(&mut rule).await.unwrap();
+
+        let processor = processor.with_rule_repository(rule_repository);
+        processor
+            .process_notification(notification.id)
+            .await
+            .unwrap();
+
+        assert!(service.sent_responses.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_generate_response() {
         let mut notification = create_test_notification();
@@ -0,0 +1,109 @@
+use super::{manager::DynBackgroundJobManager, types::Job};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Submits recurring jobs to the background job manager.
+///
+/// Each schedule runs on its own task and submits a freshly built job on
+/// every tick, starting immediately so work that fell due while the app
+/// was closed is picked up at startup. Scheduled jobs are marked routine so
+/// their lifecycle events don't flood the event log.
+pub struct JobScheduler {
+    job_manager: DynBackgroundJobManager,
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl std::fmt::Debug for JobScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobScheduler")
+            .field("job_manager", &self.job_manager)
+            .finish()
+    }
+}
+
+impl JobScheduler {
+    pub fn new(job_manager: DynBackgroundJobManager) -> Self {
+        Self {
+            job_manager,
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Submits the job built by `make_job` every `interval`
+    pub async fn schedule_every<F>(&self, interval: Duration, make_job: F)
+    where
+        F: Fn() -> Job + Send + Sync + 'static,
+    {
+        let job_manager = self.job_manager.clone();
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let job = make_job().routine();
+                let job_type = job.metadata.job_type.clone();
+                if let Err(e) = job_manager.submit_job(job).await {
+                    warn!("Failed to submit scheduled {:?} job: {}", job_type, e);
+                }
+            }
+        });
+
+        self.tasks.lock().await.push(handle);
+    }
+
+    /// Stops every schedule. Jobs already submitted keep running.
+    pub async fn shutdown(&self) {
+        for handle in self.tasks.lock().await.drain(..) {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::background::{
+        manager::MockBackgroundJobManagerTrait, JobPriority, JobType,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_schedule_submits_a_new_job_on_every_tick() {
+        let submitted = Arc::new(AtomicUsize::new(0));
+        let counter = submitted.clone();
+
+        let mut job_manager = MockBackgroundJobManagerTrait::new();
+        job_manager.expect_submit_job().returning(move |job| {
+            assert!(job.metadata.routine);
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok(job.id) })
+        });
+
+        let scheduler = JobScheduler::new(Arc::new(job_manager));
+        scheduler
+            .schedule_every(Duration::from_millis(20), || {
+                Job::new(
+                    serde_json::json!({}),
+                    JobPriority::Low,
+                    JobType::SnoozeWakeup,
+                    1,
+                )
+            })
+            .await;
+
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        scheduler.shutdown().await;
+        let count = submitted.load(Ordering::SeqCst);
+        assert!(
+            count >= 2,
+            "expected at least two submissions, got {}",
+            count
+        );
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(submitted.load(Ordering::SeqCst), count);
+    }
+}
@@ -0,0 +1,94 @@
+use super::types::{Job, JobHandler, JobPriority, JobType};
+use crate::domain::services::scoring::DynPriorityScorer;
+use tracing::debug;
+
+/// How often the scores of open notifications are refreshed
+pub const SCORE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Rescores the notifications still waiting for the user, so that items
+/// getting older or closer to their deadline move up the list.
+#[derive(Debug)]
+pub struct ScoreRefreshHandler {
+    scorer: DynPriorityScorer,
+}
+
+impl ScoreRefreshHandler {
+    pub fn new(scorer: DynPriorityScorer) -> Self {
+        Self { scorer }
+    }
+
+    /// Builds the job the scheduler submits on every refresh
+    pub fn job() -> Job {
+        Job::new(
+            serde_json::json!({}),
+            JobPriority::Low,
+            JobType::ScoreRefresh,
+            1,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl JobHandler for ScoreRefreshHandler {
+    async fn handle(&self, _job: &mut Job) -> Result<(), String> {
+        let changed = self
+            .scorer
+            .refresh()
+            .await
+            .map_err(|e| format!("Failed to refresh priority scores: {}", e))?;
+        debug!("Refreshed {} priority scores", changed);
+        Ok(())
+    }
+
+    fn job_type(&self) -> JobType {
+        JobType::ScoreRefresh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{Notification, NotificationMetadata, NotificationPriority, NotificationSource},
+        repositories::DynNotificationRepository,
+        services::scoring::{PriorityScorer, ScoringConfig},
+    };
+    use crate::infrastructure::repositories::SqliteNotificationRepository;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_scores_unscored_open_notifications() {
+        let repository: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let mut notification = Notification::new(
+            "Outage in eu-west".to_string(),
+            "Checkout is failing".to_string(),
+            NotificationPriority::Critical,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        repository.save(&mut notification).await.unwrap();
+
+        let handler = ScoreRefreshHandler::new(Arc::new(PriorityScorer::new(
+            repository.clone(),
+            ScoringConfig::default(),
+        )));
+        handler
+            .handle(&mut ScoreRefreshHandler::job())
+            .await
+            .unwrap();
+
+        let scored = repository
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(scored.score(), 45.0);
+    }
+}
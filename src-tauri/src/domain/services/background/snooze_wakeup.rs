@@ -0,0 +1,122 @@
+use super::types::{Job, JobHandler, JobPriority, JobType};
+use crate::domain::{
+    entities::notification::NotificationStatus, services::notification_service::NotificationService,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// How often snoozed notifications are checked for their wake-up time
+pub const SNOOZE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Brings snoozed notifications back once their snooze time has passed.
+///
+/// Unsnoozing goes through the notification service, which restores the
+/// previous status and publishes `NotificationUnsnoozed`.
+#[derive(Debug)]
+pub struct SnoozeWakeupHandler {
+    notification_service: Arc<dyn NotificationService>,
+}
+
+impl SnoozeWakeupHandler {
+    pub fn new(notification_service: Arc<dyn NotificationService>) -> Self {
+        Self {
+            notification_service,
+        }
+    }
+
+    /// Builds the job the scheduler submits on every check
+    pub fn job() -> Job {
+        Job::new(
+            serde_json::json!({}),
+            JobPriority::Normal,
+            JobType::SnoozeWakeup,
+            1,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl JobHandler for SnoozeWakeupHandler {
+    async fn handle(&self, _job: &mut Job) -> Result<(), String> {
+        let now = Utc::now();
+        let snoozed = self
+            .notification_service
+            .get_notifications_by_status(NotificationStatus::Snoozed)
+            .await
+            .map_err(|e| format!("Failed to load snoozed notifications: {}", e))?;
+
+        for notification in snoozed.iter().filter(|n| n.is_snooze_due(now)) {
+            match self
+                .notification_service
+                .unsnooze_notification(notification.id)
+                .await
+            {
+                Ok(()) => info!("Notification {} woke up from snooze", notification.id),
+                Err(e) => warn!("Failed to unsnooze notification {}: {}", notification.id, e),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn job_type(&self) -> JobType {
+        JobType::SnoozeWakeup
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::notification::{
+            Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+        },
+        services::MockNotificationService,
+    };
+    use chrono::Duration;
+    use mockall::predicate;
+
+    fn snoozed(until: chrono::DateTime<Utc>) -> Notification {
+        let mut notification = Notification::new(
+            "Follow up".to_string(),
+            "Ping the team on Monday".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        notification.snooze(until);
+        notification
+    }
+
+    #[tokio::test]
+    async fn test_only_due_notifications_are_unsnoozed() {
+        let due = snoozed(Utc::now() + Duration::milliseconds(1));
+        let later = snoozed(Utc::now() + Duration::hours(4));
+        let due_id = due.id;
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        let mut service = MockNotificationService::new();
+        service
+            .expect_get_notifications_by_status()
+            .with(predicate::eq(NotificationStatus::Snoozed))
+            .returning(move |_| Ok(vec![due.clone(), later.clone()]));
+        service
+            .expect_unsnooze_notification()
+            .with(predicate::eq(due_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let handler = SnoozeWakeupHandler::new(Arc::new(service));
+        handler
+            .handle(&mut SnoozeWakeupHandler::job())
+            .await
+            .unwrap();
+    }
+}
@@ -26,6 +26,12 @@ pub enum JobType {
     ResponseGeneration,
     ActionExecution,
     ServiceSync,
+    WebhookDelivery,
+    SnoozeWakeup,
+    DndRelease,
+    DigestGeneration,
+    ScoreRefresh,
+    EscalationCheck,
     Custom(String),
 }
 
@@ -36,6 +42,9 @@ pub struct JobMetadata {
     pub max_retries: u32,
     pub last_error: Option<String>,
     pub custom_data: Option<serde_json::Value>,
+    /// Recurring housekeeping job: only its failures are published as events
+    #[serde(default)]
+    pub routine: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +79,7 @@ impl Job {
                 max_retries,
                 last_error: None,
                 custom_data: None,
+                routine: false,
             },
             created_at: now,
             updated_at: now,
@@ -78,6 +88,12 @@ impl Job {
         }
     }
 
+    /// Marks the job as a recurring housekeeping run
+    pub fn routine(mut self) -> Self {
+        self.metadata.routine = true;
+        self
+    }
+
     pub fn start(&mut self) {
         let now = Utc::now();
         self.status = JobStatus::Running;
@@ -121,4 +137,11 @@ impl Job {
 pub trait JobHandler: Send + Sync + Debug {
     async fn handle(&self, job: &mut Job) -> Result<(), String>;
     fn job_type(&self) -> JobType;
+
+    /// Whether a failed job is run again, up to its `max_retries`.
+    ///
+    /// Off by default: only handlers that are safe to run twice opt in.
+    fn retries_on_failure(&self) -> bool {
+        false
+    }
 }
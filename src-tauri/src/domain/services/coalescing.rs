@@ -0,0 +1,386 @@
+use crate::domain::{
+    entities::{Notification, NotificationStatus},
+    error::{DomainError, DomainResult},
+    repositories::{
+        load_settings, store_settings, DynNotificationRepository, DynSettingsRepository,
+    },
+};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// Which notifications are merged together and for how long.
+///
+/// Notifications are grouped by source, the first value found at one of
+/// `key_paths` in their custom data, and event type (their first tag).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoalescingConfig {
+    pub enabled: bool,
+    /// Items arriving this long after the first one of an aggregate start a new one
+    pub window_minutes: i64,
+    /// Only these event types are merged, every type when empty
+    pub event_types: Vec<String>,
+    /// Dotted paths into the custom data, e.g. "repository.full_name"
+    pub key_paths: Vec<String>,
+}
+
+impl Default for CoalescingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_minutes: 60,
+            event_types: Vec::new(),
+            key_paths: vec![
+                "repository.full_name".to_string(),
+                "project.path_with_namespace".to_string(),
+            ],
+        }
+    }
+}
+
+impl CoalescingConfig {
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.window_minutes <= 0 {
+            return Err(DomainError::ValidationError(
+                "The coalescing window must be at least one minute".to_string(),
+            ));
+        }
+        if self.key_paths.iter().any(|path| path.trim().is_empty()) {
+            return Err(DomainError::ValidationError(
+                "Key paths cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Result of passing an incoming notification through the coalescer
+#[derive(Debug, Clone)]
+pub enum Coalesced {
+    /// Never merged, the notification should be stored as is
+    Skipped(Notification),
+    /// Stored as the first item of a new aggregate
+    New(Notification),
+    /// Merged into this existing aggregate, stored
+    Merged(Notification),
+}
+
+/// Merges bursts of similar notifications, such as every push to a busy
+/// repository, into a single aggregate updated in place.
+pub struct NotificationCoalescer {
+    repository: DynNotificationRepository,
+    config: RwLock<CoalescingConfig>,
+    settings: Option<DynSettingsRepository>,
+}
+
+pub type DynNotificationCoalescer = Arc<NotificationCoalescer>;
+
+impl std::fmt::Debug for NotificationCoalescer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationCoalescer")
+            .field("config", &self.config())
+            .field("settings", &self.settings.is_some())
+            .finish()
+    }
+}
+
+impl NotificationCoalescer {
+    pub fn new(repository: DynNotificationRepository, config: CoalescingConfig) -> Self {
+        Self {
+            repository,
+            config: RwLock::new(config),
+            settings: None,
+        }
+    }
+
+    /// Uses the config saved in the settings, the defaults when nothing was
+    /// saved yet, and saves the changes there
+    pub async fn from_settings(
+        repository: DynNotificationRepository,
+        settings: DynSettingsRepository,
+    ) -> DomainResult<Self> {
+        let config = load_settings(settings.as_ref(), SETTINGS_KEY).await?;
+        Ok(Self {
+            settings: Some(settings),
+            ..Self::new(repository, config)
+        })
+    }
+
+    pub fn config(&self) -> CoalescingConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Validates and saves the config, which applies to the next notifications
+    pub async fn save_config(&self, config: CoalescingConfig) -> DomainResult<CoalescingConfig> {
+        config.validate()?;
+        if let Some(settings) = &self.settings {
+            store_settings(settings.as_ref(), SETTINGS_KEY, &config).await?;
+        }
+        *self.config.write().unwrap() = config.clone();
+        Ok(config)
+    }
+
+    /// Grouping key of the notification, `None` when it should never be merged.
+    /// Conversations have their own grouping through threads.
+    pub fn coalescing_key(&self, notification: &Notification) -> Option<String> {
+        Self::key(&self.config(), notification)
+    }
+
+    fn key(config: &CoalescingConfig, notification: &Notification) -> Option<String> {
+        if !config.enabled || notification.metadata.thread_key.is_some() {
+            return None;
+        }
+
+        let event_type = notification.metadata.tags.first()?;
+        if !config.event_types.is_empty()
+            && !config
+                .event_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(event_type))
+        {
+            return None;
+        }
+
+        let custom_data = notification.metadata.custom_data.as_ref()?;
+        let group = config.key_paths.iter().find_map(|path| {
+            path.split('.')
+                .try_fold(custom_data, |value, key| value.get(key))?
+                .as_str()
+                .map(str::to_string)
+        })?;
+
+        Some(format!(
+            "{}:{}:{}",
+            notification.metadata.source.to_string().to_lowercase(),
+            group,
+            event_type
+        ))
+    }
+
+    /// Merges the notification into a recent aggregate with the same key, or
+    /// makes it the first item of a new one. `prepare` runs on whichever is
+    /// stored, within the same transaction as the lookup.
+    pub async fn coalesce(
+        &self,
+        notification: Notification,
+        prepare: impl FnOnce(&mut Notification) + Send + 'static,
+    ) -> DomainResult<Coalesced> {
+        let config = self.config();
+        let Some(key) = Self::key(&config, &notification) else {
+            return Ok(Coalesced::Skipped(notification));
+        };
+
+        let id = notification.id;
+        let window = Duration::minutes(config.window_minutes);
+        let merge_key = key.clone();
+        let stored = self
+            .repository
+            .save_coalesced(
+                &key,
+                Box::new(move |aggregates| {
+                    let mut stored = merge(notification, merge_key, aggregates, window);
+                    prepare(&mut stored);
+                    stored
+                }),
+            )
+            .await?;
+
+        Ok(if stored.id == id {
+            Coalesced::New(stored)
+        } else {
+            Coalesced::Merged(stored)
+        })
+    }
+}
+
+const SETTINGS_KEY: &str = "coalescing";
+
+/// Absorbs the notification into the most recent aggregate still open, or
+/// starts a new aggregate with it
+fn merge(
+    mut notification: Notification,
+    key: String,
+    aggregates: Vec<Notification>,
+    window: Duration,
+) -> Notification {
+    let is_open = |candidate: &Notification| {
+        matches!(
+            candidate.status,
+            NotificationStatus::New | NotificationStatus::Read
+        ) && candidate
+            .metadata
+            .aggregate
+            .as_ref()
+            .is_some_and(|aggregate| notification.created_at - aggregate.first_at <= window)
+    };
+    let open = aggregates
+        .into_iter()
+        .filter(is_open)
+        .max_by_key(|n| n.created_at);
+
+    match open {
+        Some(mut aggregate) => {
+            aggregate.absorb(&notification);
+            // New items make the aggregate unread again
+            if aggregate.status == NotificationStatus::Read {
+                aggregate.status = NotificationStatus::New;
+                aggregate.read_at = None;
+            }
+            aggregate
+        }
+        None => {
+            notification.start_aggregate(key);
+            notification
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationMetadata, NotificationPriority, NotificationSource};
+    use crate::infrastructure::repositories::{
+        SqliteNotificationRepository, SqliteSettingsRepository,
+    };
+    use serde_json::json;
+
+    fn event(event_type: &str, repository: &str) -> Notification {
+        Notification::new(
+            format!("GitHub: {}", event_type),
+            "Payload".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: Some(uuid::Uuid::new_v4().to_string()),
+                url: None,
+                tags: vec![event_type.to_string()],
+                custom_data: Some(json!({ "repository": { "full_name": repository } })),
+                ..Default::default()
+            },
+        )
+    }
+
+    async fn coalesce(coalescer: &NotificationCoalescer, notification: Notification) -> Coalesced {
+        coalescer.coalesce(notification, |_| {}).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bursts_are_merged_within_the_window() {
+        let repository: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let coalescer = NotificationCoalescer::new(repository.clone(), CoalescingConfig::default());
+
+        let Coalesced::New(first) = coalesce(&coalescer, event("PushEvent", "octo/repo")).await
+        else {
+            panic!("expected a new aggregate");
+        };
+        assert_eq!(
+            coalescer.coalescing_key(&first).as_deref(),
+            Some("github:octo/repo:PushEvent")
+        );
+        assert!(repository.find_by_id(first.id).await.unwrap().is_some());
+
+        repository
+            .update_status(first.id, NotificationStatus::Read)
+            .await
+            .unwrap();
+        let Coalesced::Merged(merged) = coalesce(&coalescer, event("PushEvent", "octo/repo")).await
+        else {
+            panic!("expected the push to be merged");
+        };
+        assert_eq!(merged.id, first.id);
+        assert_eq!(merged.status, NotificationStatus::New);
+        assert_eq!(merged.metadata.aggregate.as_ref().unwrap().count, 2);
+        let stored = repository.find_by_id(first.id).await.unwrap().unwrap();
+        assert_eq!(stored.metadata.aggregate.unwrap().count, 2);
+
+        // Other repositories and event types get their own aggregate
+        assert!(matches!(
+            coalesce(&coalescer, event("PushEvent", "octo/other")).await,
+            Coalesced::New(_)
+        ));
+        assert!(matches!(
+            coalesce(&coalescer, event("IssuesEvent", "octo/repo")).await,
+            Coalesced::New(_)
+        ));
+
+        // Past the window a new aggregate is started
+        let mut late = event("PushEvent", "octo/repo");
+        late.created_at += Duration::minutes(61);
+        assert!(matches!(
+            coalesce(&coalescer, late).await,
+            Coalesced::New(_)
+        ));
+
+        // Threads are never merged
+        let mut threaded = event("PushEvent", "octo/repo");
+        threaded.metadata.thread_key = Some("github:octo/repo#1".to_string());
+        assert_eq!(coalescer.coalescing_key(&threaded), None);
+        assert!(matches!(
+            coalesce(&coalescer, threaded).await,
+            Coalesced::Skipped(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_notifications_share_one_aggregate() {
+        let repository: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let coalescer = Arc::new(NotificationCoalescer::new(
+            repository.clone(),
+            CoalescingConfig::default(),
+        ));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let coalescer = coalescer.clone();
+                tokio::spawn(async move {
+                    coalesce(&coalescer, event("PushEvent", "octo/repo")).await;
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let aggregates = repository
+            .find_by_coalescing_key("github:octo/repo:PushEvent")
+            .await
+            .unwrap();
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(aggregates[0].metadata.aggregate.as_ref().unwrap().count, 8);
+    }
+
+    #[tokio::test]
+    async fn test_config_is_saved_in_the_settings() {
+        let repository: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let settings: DynSettingsRepository =
+            Arc::new(SqliteSettingsRepository::new(":memory:").unwrap());
+        let coalescer = NotificationCoalescer::from_settings(repository.clone(), settings.clone())
+            .await
+            .unwrap();
+        assert_eq!(coalescer.config(), CoalescingConfig::default());
+
+        let invalid = CoalescingConfig {
+            window_minutes: 0,
+            ..CoalescingConfig::default()
+        };
+        assert!(coalescer.save_config(invalid).await.is_err());
+
+        let disabled = CoalescingConfig {
+            enabled: false,
+            ..CoalescingConfig::default()
+        };
+        coalescer.save_config(disabled.clone()).await.unwrap();
+        assert_eq!(
+            coalescer.coalescing_key(&event("PushEvent", "octo/repo")),
+            None
+        );
+
+        let reloaded = NotificationCoalescer::from_settings(repository, settings)
+            .await
+            .unwrap();
+        assert_eq!(reloaded.config(), disabled);
+    }
+}
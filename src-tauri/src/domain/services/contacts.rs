@@ -0,0 +1,193 @@
+use crate::domain::{
+    entities::{Contact, Notification},
+    error::{DomainError, DomainResult},
+    repositories::DynContactRepository,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ContactService: Send + Sync + std::fmt::Debug {
+    async fn get_contacts(&self) -> DomainResult<Vec<Contact>>;
+    async fn get_contact(&self, id: Uuid) -> DomainResult<Contact>;
+    /// Creates or updates a contact. An identity can only belong to one contact.
+    async fn save_contact(&self, contact: Contact) -> DomainResult<Contact>;
+    async fn delete_contact(&self, id: Uuid) -> DomainResult<()>;
+    /// Moves the identities of `other` into `target`, which keeps its name and
+    /// preferences, then deletes `other`
+    async fn merge_contacts(&self, target: Uuid, other: Uuid) -> DomainResult<Contact>;
+    /// The contact owning the notification's sender identity
+    async fn resolve_sender(&self, notification: &Notification) -> DomainResult<Option<Contact>>;
+}
+
+pub type DynContactService = Arc<dyn ContactService>;
+
+pub struct DefaultContactService {
+    repository: DynContactRepository,
+}
+
+impl std::fmt::Debug for DefaultContactService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultContactService")
+            .field("repository", &"Arc<dyn ContactRepository>")
+            .finish()
+    }
+}
+
+impl DefaultContactService {
+    pub fn new(repository: DynContactRepository) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl ContactService for DefaultContactService {
+    async fn get_contacts(&self) -> DomainResult<Vec<Contact>> {
+        self.repository.find_all().await
+    }
+
+    async fn get_contact(&self, id: Uuid) -> DomainResult<Contact> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFoundError(format!("Contact {} not found", id)))
+    }
+
+    async fn save_contact(&self, mut contact: Contact) -> DomainResult<Contact> {
+        contact.validate()?;
+
+        let others = self.repository.find_all().await?;
+        for identity in &contact.identities {
+            if let Some(owner) = others.iter().find(|other| {
+                other.id != contact.id && other.has_identity(&identity.source, &identity.handle)
+            }) {
+                return Err(DomainError::ValidationError(format!(
+                    "{} {} already belongs to {}",
+                    identity.source, identity.handle, owner.name
+                )));
+            }
+        }
+
+        self.repository.save(&mut contact).await?;
+        Ok(contact)
+    }
+
+    async fn delete_contact(&self, id: Uuid) -> DomainResult<()> {
+        self.get_contact(id).await?;
+        self.repository.delete(id).await
+    }
+
+    async fn merge_contacts(&self, target: Uuid, other: Uuid) -> DomainResult<Contact> {
+        if target == other {
+            return Err(DomainError::ValidationError(
+                "Cannot merge a contact with itself".to_string(),
+            ));
+        }
+        let mut contact = self.get_contact(target).await?;
+        let merged = self.get_contact(other).await?;
+
+        for identity in merged.identities {
+            if !contact.has_identity(&identity.source, &identity.handle) {
+                contact.identities.push(identity);
+            }
+        }
+        contact.vip |= merged.vip;
+        contact.notes = match (contact.notes.take(), merged.notes) {
+            (Some(notes), Some(more)) => Some(format!("{}\n\n{}", notes, more)),
+            (notes, more) => notes.or(more),
+        };
+
+        self.repository.merge(&mut contact, other).await?;
+        Ok(contact)
+    }
+
+    async fn resolve_sender(&self, notification: &Notification) -> DomainResult<Option<Contact>> {
+        let Some(sender) = notification.sender() else {
+            return Ok(None);
+        };
+        self.repository
+            .find_by_identity(&notification.metadata.source, &sender)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        ContactIdentity, NotificationMetadata, NotificationPriority, NotificationSource,
+    };
+    use crate::infrastructure::repositories::SqliteContactRepository;
+    use serde_json::json;
+
+    fn identity(source: NotificationSource, handle: &str) -> ContactIdentity {
+        ContactIdentity {
+            source,
+            handle: handle.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolves_senders_across_services() {
+        let service =
+            DefaultContactService::new(Arc::new(SqliteContactRepository::new(":memory:").unwrap()));
+
+        let mut work = Contact::new(
+            "Linus".to_string(),
+            vec![identity(
+                NotificationSource::Microsoft,
+                "linus@corp.example",
+            )],
+        );
+        work.notes = Some("Team lead".to_string());
+        let work = service.save_contact(work).await.unwrap();
+        let mut github = Contact::new(
+            "torvalds".to_string(),
+            vec![identity(NotificationSource::Github, "torvalds")],
+        );
+        github.vip = true;
+        let github = service.save_contact(github).await.unwrap();
+
+        // An identity cannot be claimed twice
+        let duplicate = Contact::new(
+            "Someone".to_string(),
+            vec![identity(NotificationSource::Github, "Torvalds")],
+        );
+        assert!(service.save_contact(duplicate).await.is_err());
+
+        let merged = service.merge_contacts(work.id, github.id).await.unwrap();
+        assert_eq!(merged.name, "Linus");
+        assert_eq!(merged.identities.len(), 2);
+        assert!(merged.vip);
+        assert_eq!(service.get_contacts().await.unwrap().len(), 1);
+
+        let push = Notification::new(
+            "GitHub: PushEvent".to_string(),
+            "Pushed".to_string(),
+            NotificationPriority::Low,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: Some(json!({ "sender": { "login": "torvalds" } })),
+                ..Default::default()
+            },
+        );
+        let resolved = service.resolve_sender(&push).await.unwrap().unwrap();
+        assert_eq!(resolved.id, work.id);
+
+        let mut email = push.clone();
+        email.metadata.source = NotificationSource::Microsoft;
+        email.metadata.custom_data = Some(json!({ "from_address": "Linus <LINUS@corp.example>" }));
+        assert!(service.resolve_sender(&email).await.unwrap().is_some());
+
+        email.metadata.custom_data = Some(json!({ "from_address": "someone@corp.example" }));
+        assert!(service.resolve_sender(&email).await.unwrap().is_none());
+    }
+}
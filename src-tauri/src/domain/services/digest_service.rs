@@ -0,0 +1,618 @@
+use crate::domain::{
+    entities::{
+        digest::DIGEST_SOURCE, Digest, DigestGroup, DigestGrouping, DigestSection, DigestSettings,
+        Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+        NotificationStatus,
+    },
+    error::{DomainError, DomainResult},
+    events::{DynEventPublisher, NoopEventPublisher, NotificationEvent},
+    repositories::{DynDigestRepository, DynNotificationRepository},
+    services::ai::DynAIService,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use std::{collections::HashMap, sync::Arc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Characters of each notification's content included in the prompt
+const PROMPT_CONTENT_CHARS: usize = 200;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DigestService: Send + Sync + std::fmt::Debug {
+    async fn get_settings(&self) -> DomainResult<DigestSettings>;
+    async fn save_settings(&self, settings: DigestSettings) -> DomainResult<DigestSettings>;
+    /// Summarizes the configured window ending at `period_end`, stores the
+    /// digest and surfaces it as a notification unless it is empty
+    async fn generate_digest(&self, period_end: DateTime<Utc>) -> DomainResult<Digest>;
+    /// Generates the scheduled digest if it is due
+    async fn run_if_due(&self, now: DateTime<Utc>) -> DomainResult<Option<Digest>>;
+    /// Most recent digests first
+    async fn get_digests(&self, limit: usize) -> DomainResult<Vec<Digest>>;
+    async fn get_digest(&self, id: Uuid) -> DomainResult<Digest>;
+}
+
+pub type DynDigestService = Arc<dyn DigestService>;
+
+/// Summarizes each source or thread through the AI and sorts the summaries
+/// by the urgency the AI gives them. When the AI is unavailable, the
+/// remaining groups get a plain list of titles ranked by their priority.
+pub struct DefaultDigestService {
+    notification_repository: DynNotificationRepository,
+    digest_repository: DynDigestRepository,
+    ai_service: DynAIService,
+    event_publisher: DynEventPublisher,
+}
+
+impl std::fmt::Debug for DefaultDigestService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultDigestService")
+            .field("notification_repository", &self.notification_repository)
+            .field("digest_repository", &"Arc<dyn DigestRepository>")
+            .field("ai_service", &self.ai_service)
+            .field("event_publisher", &"Arc<dyn EventPublisher>")
+            .finish()
+    }
+}
+
+impl DefaultDigestService {
+    pub fn new(
+        notification_repository: DynNotificationRepository,
+        digest_repository: DynDigestRepository,
+        ai_service: DynAIService,
+    ) -> Self {
+        Self {
+            notification_repository,
+            digest_repository,
+            ai_service,
+            event_publisher: Arc::new(NoopEventPublisher),
+        }
+    }
+
+    /// Publishes `NotificationCreated` for the notifications surfacing digests
+    pub fn with_event_publisher(mut self, event_publisher: DynEventPublisher) -> Self {
+        self.event_publisher = event_publisher;
+        self
+    }
+
+    async fn gather(
+        &self,
+        settings: &DigestSettings,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> DomainResult<Vec<Notification>> {
+        Ok(self
+            .notification_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .filter(|n| {
+                n.status != NotificationStatus::Deleted
+                    && n.created_at >= period_start
+                    && n.created_at < period_end
+                    && settings.includes(n)
+            })
+            .collect())
+    }
+
+    async fn surface(&self, digest: &Digest) -> DomainResult<Uuid> {
+        let mut notification = Notification::new(
+            format!("Digest: {} notifications", digest.notification_count),
+            digest.render(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Custom(DIGEST_SOURCE.to_string()),
+                external_id: Some(digest.id.to_string()),
+                url: None,
+                tags: vec!["digest".to_string()],
+                custom_data: Some(json!({ "digest_id": digest.id })),
+                ..Default::default()
+            },
+        );
+        self.notification_repository.save(&mut notification).await?;
+        if let Err(e) = self
+            .event_publisher
+            .publish_event(NotificationEvent::notification_created(
+                &notification,
+                false,
+            ))
+            .await
+        {
+            warn!("Failed to publish digest notification: {}", e);
+        }
+        Ok(notification.id)
+    }
+}
+
+/// Notifications of one source or thread, latest first
+struct PendingGroup {
+    key: String,
+    title: String,
+    notifications: Vec<Notification>,
+}
+
+impl PendingGroup {
+    fn max_priority(&self) -> NotificationPriority {
+        self.notifications
+            .iter()
+            .map(|n| n.priority.clone())
+            .max_by_key(|priority| priority.rank())
+            .unwrap_or(NotificationPriority::Low)
+    }
+
+    fn prompt(&self, max_items: usize) -> String {
+        let mut prompt = format!(
+            "Summarize these {} notifications from {} for a daily digest. \
+            Use the priority level for how urgent the group is as a whole, \
+            and suggest the actions the reader should take.\n",
+            self.notifications.len(),
+            self.title
+        );
+        for notification in self.notifications.iter().take(max_items) {
+            let content: String = notification
+                .text()
+                .chars()
+                .take(PROMPT_CONTENT_CHARS)
+                .collect();
+            prompt.push_str(&format!(
+                "\n- [{:?}] {}: {}",
+                notification.priority, notification.title, content
+            ));
+        }
+        prompt
+    }
+
+    /// Deterministic summary used when the AI is unavailable
+    fn fallback(&self, max_items: usize) -> DigestGroup {
+        let titles: Vec<&str> = self
+            .notifications
+            .iter()
+            .take(max_items)
+            .map(|n| n.title.as_str())
+            .collect();
+        let mut summary = format!(
+            "{} notifications: {}",
+            self.notifications.len(),
+            titles.join("; ")
+        );
+        if self.notifications.len() > titles.len() {
+            summary.push_str(&format!(
+                " (and {} more)",
+                self.notifications.len() - titles.len()
+            ));
+        }
+
+        self.summarized(
+            summary,
+            self.notifications
+                .iter()
+                .filter(|n| n.status == NotificationStatus::ActionRequired)
+                .take(max_items)
+                .map(|n| format!("Follow up on: {}", n.title))
+                .collect(),
+            false,
+        )
+    }
+
+    fn summarized(
+        &self,
+        summary: String,
+        suggested_actions: Vec<String>,
+        ai_generated: bool,
+    ) -> DigestGroup {
+        DigestGroup {
+            key: self.key.clone(),
+            title: self.title.clone(),
+            summary,
+            suggested_actions,
+            notification_ids: self.notifications.iter().map(|n| n.id).collect(),
+            ai_generated,
+        }
+    }
+}
+
+fn group_notifications(
+    notifications: Vec<Notification>,
+    group_by: DigestGrouping,
+) -> Vec<PendingGroup> {
+    let mut by_key: HashMap<String, Vec<Notification>> = HashMap::new();
+    for notification in notifications {
+        let key = match group_by {
+            DigestGrouping::Source => notification.metadata.source.to_string(),
+            DigestGrouping::Thread => notification.thread_key(),
+        };
+        by_key.entry(key).or_default().push(notification);
+    }
+
+    let mut groups: Vec<PendingGroup> = by_key
+        .into_iter()
+        .map(|(key, mut notifications)| {
+            notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+            let title = match group_by {
+                DigestGrouping::Source => key.clone(),
+                DigestGrouping::Thread => notifications[0].title.clone(),
+            };
+            PendingGroup {
+                key,
+                title,
+                notifications,
+            }
+        })
+        .collect();
+
+    // Most pressing first, so the groups over the limit are the least important
+    groups.sort_by(|a, b| {
+        b.max_priority()
+            .rank()
+            .cmp(&a.max_priority().rank())
+            .then(b.notifications.len().cmp(&a.notifications.len()))
+            .then(a.key.cmp(&b.key))
+    });
+    groups
+}
+
+#[async_trait]
+impl DigestService for DefaultDigestService {
+    async fn get_settings(&self) -> DomainResult<DigestSettings> {
+        self.digest_repository.get_settings().await
+    }
+
+    async fn save_settings(&self, mut settings: DigestSettings) -> DomainResult<DigestSettings> {
+        settings.validate()?;
+        // Only the scheduler records runs, a stale client copy must not move it
+        settings.last_run_at = self.digest_repository.get_settings().await?.last_run_at;
+        self.digest_repository.save_settings(&mut settings).await?;
+        Ok(settings)
+    }
+
+    async fn generate_digest(&self, period_end: DateTime<Utc>) -> DomainResult<Digest> {
+        let settings = self.digest_repository.get_settings().await?;
+        let period_start = period_end - Duration::hours(settings.window_hours as i64);
+        let notifications = self.gather(&settings, period_start, period_end).await?;
+        let notification_count = notifications.len();
+
+        let mut groups = group_notifications(notifications, settings.group_by);
+        let omitted_groups = groups.len().saturating_sub(settings.max_groups);
+        groups.truncate(settings.max_groups);
+
+        let mut ai_available = true;
+        let mut summarized: Vec<(NotificationPriority, DigestGroup)> = Vec::new();
+        for group in &groups {
+            if ai_available {
+                match self
+                    .ai_service
+                    .analyze_content(&group.prompt(settings.max_items_per_group))
+                    .await
+                {
+                    Ok(analysis) => {
+                        summarized.push((
+                            NotificationPriority::from(&analysis.priority_level),
+                            group.summarized(analysis.summary, analysis.suggested_actions, true),
+                        ));
+                        continue;
+                    }
+                    Err(e) => {
+                        // Do not wait on an unavailable model for every group
+                        warn!(
+                            "AI unavailable for the digest, using the plain format: {}",
+                            e
+                        );
+                        ai_available = false;
+                    }
+                }
+            }
+            summarized.push((
+                group.max_priority(),
+                group.fallback(settings.max_items_per_group),
+            ));
+        }
+
+        let mut sections: Vec<DigestSection> = Vec::new();
+        for urgency in [
+            NotificationPriority::Critical,
+            NotificationPriority::High,
+            NotificationPriority::Medium,
+            NotificationPriority::Low,
+        ] {
+            let groups: Vec<DigestGroup> = summarized
+                .iter()
+                .filter(|(group_urgency, _)| *group_urgency == urgency)
+                .map(|(_, group)| group.clone())
+                .collect();
+            if !groups.is_empty() {
+                sections.push(DigestSection { urgency, groups });
+            }
+        }
+
+        let mut digest = Digest {
+            id: Uuid::new_v4(),
+            period_start,
+            period_end,
+            group_by: settings.group_by,
+            sections,
+            notification_count,
+            omitted_groups,
+            used_fallback: summarized.iter().any(|(_, group)| !group.ai_generated),
+            notification_id: None,
+            created_at: Utc::now(),
+        };
+        if notification_count > 0 {
+            digest.notification_id = Some(self.surface(&digest).await?);
+        }
+        self.digest_repository.save(&digest).await?;
+
+        info!(
+            "Generated digest {} of {} notifications",
+            digest.id, notification_count
+        );
+        Ok(digest)
+    }
+
+    async fn run_if_due(&self, now: DateTime<Utc>) -> DomainResult<Option<Digest>> {
+        let mut settings = self.digest_repository.get_settings().await?;
+        if !settings.is_due(now) {
+            return Ok(None);
+        }
+
+        // Recorded first so a failing digest is not retried every check
+        settings.last_run_at = Some(now);
+        self.digest_repository.save_settings(&mut settings).await?;
+        self.generate_digest(now).await.map(Some)
+    }
+
+    async fn get_digests(&self, limit: usize) -> DomainResult<Vec<Digest>> {
+        self.digest_repository.find_recent(limit).await
+    }
+
+    async fn get_digest(&self, id: Uuid) -> DomainResult<Digest> {
+        self.digest_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFoundError(format!("Digest {} not found", id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        repositories::DigestRepository,
+        services::{
+            ai::{AIAnalysis, PriorityLevel},
+            MockAIService,
+        },
+    };
+    use crate::infrastructure::repositories::{
+        SqliteDigestRepository, SqliteNotificationRepository,
+    };
+
+    async fn setup(
+        ai_service: MockAIService,
+        settings: DigestSettings,
+    ) -> (DynNotificationRepository, DefaultDigestService) {
+        let notification_repository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap())
+                as DynNotificationRepository;
+        let digest_repository = Arc::new(SqliteDigestRepository::new(":memory:").unwrap());
+        digest_repository
+            .save_settings(&mut settings.clone())
+            .await
+            .unwrap();
+        let service = DefaultDigestService::new(
+            notification_repository.clone(),
+            digest_repository,
+            Arc::new(ai_service),
+        );
+        (notification_repository, service)
+    }
+
+    async fn add(
+        repository: &DynNotificationRepository,
+        title: &str,
+        source: NotificationSource,
+        priority: NotificationPriority,
+        tags: Vec<&str>,
+    ) -> Notification {
+        let mut notification = Notification::new(
+            title.to_string(),
+            format!("{} details", title),
+            priority,
+            NotificationMetadata {
+                source,
+                external_id: None,
+                url: None,
+                tags: tags.into_iter().map(str::to_string).collect(),
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        repository.save(&mut notification).await.unwrap();
+        notification
+    }
+
+    #[tokio::test]
+    async fn test_ai_summaries_are_grouped_by_urgency() {
+        let mut ai_service = MockAIService::new();
+        ai_service.expect_analyze_content().returning(|prompt| {
+            let (priority_level, summary) = if prompt.contains("from Jira") {
+                (PriorityLevel::Critical, "Production incident")
+            } else {
+                (PriorityLevel::Low, "Routine pushes")
+            };
+            Ok(AIAnalysis {
+                requires_action: true,
+                priority_level,
+                summary: summary.to_string(),
+                suggested_actions: vec!["Review".to_string()],
+                ..Default::default()
+            })
+        });
+        let (repository, service) = setup(
+            ai_service,
+            DigestSettings {
+                tags: vec!["work".to_string()],
+                ..DigestSettings::default()
+            },
+        )
+        .await;
+
+        let push = add(
+            &repository,
+            "Push to main",
+            NotificationSource::Github,
+            NotificationPriority::High,
+            vec!["work"],
+        )
+        .await;
+        add(
+            &repository,
+            "Outage",
+            NotificationSource::Jira,
+            NotificationPriority::Low,
+            vec!["WORK"],
+        )
+        .await;
+        add(
+            &repository,
+            "Newsletter",
+            NotificationSource::Email,
+            NotificationPriority::Low,
+            vec![],
+        )
+        .await;
+
+        let digest = service.generate_digest(Utc::now()).await.unwrap();
+
+        assert_eq!(digest.notification_count, 2);
+        assert!(!digest.used_fallback);
+        assert_eq!(
+            digest
+                .sections
+                .iter()
+                .map(|s| s.urgency.clone())
+                .collect::<Vec<_>>(),
+            vec![NotificationPriority::Critical, NotificationPriority::Low]
+        );
+        assert_eq!(digest.sections[0].groups[0].summary, "Production incident");
+        assert_eq!(digest.sections[1].groups[0].notification_ids, vec![push.id]);
+
+        let surfaced = repository
+            .find_by_id(digest.notification_id.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(surfaced.is_digest());
+        assert!(surfaced.content.contains("Production incident"));
+        assert_eq!(service.get_digest(digest.id).await.unwrap(), digest);
+
+        // The digest notification is not part of the next digest
+        let next = service.generate_digest(Utc::now()).await.unwrap();
+        assert_eq!(next.notification_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_when_ai_is_unavailable() {
+        let mut ai_service = MockAIService::new();
+        ai_service
+            .expect_analyze_content()
+            .times(1)
+            .returning(|_| Err(DomainError::ExternalServiceError("Ollama is down".into())));
+        let (repository, service) = setup(
+            ai_service,
+            DigestSettings {
+                group_by: DigestGrouping::Source,
+                max_groups: 1,
+                max_items_per_group: 1,
+                ..DigestSettings::default()
+            },
+        )
+        .await;
+
+        let mut failing = add(
+            &repository,
+            "Build failed",
+            NotificationSource::Github,
+            NotificationPriority::High,
+            vec![],
+        )
+        .await;
+        failing.mark_action_required();
+        repository.save(&mut failing).await.unwrap();
+        add(
+            &repository,
+            "Build passed",
+            NotificationSource::Github,
+            NotificationPriority::Low,
+            vec![],
+        )
+        .await;
+        add(
+            &repository,
+            "Hello",
+            NotificationSource::Email,
+            NotificationPriority::Low,
+            vec![],
+        )
+        .await;
+
+        let digest = service.generate_digest(Utc::now()).await.unwrap();
+
+        assert!(digest.used_fallback);
+        assert_eq!(digest.omitted_groups, 1);
+        assert_eq!(digest.sections.len(), 1);
+        assert_eq!(digest.sections[0].urgency, NotificationPriority::High);
+        let group = &digest.sections[0].groups[0];
+        assert_eq!(group.key, "Github");
+        assert!(group.summary.starts_with("2 notifications: "));
+        assert!(group.summary.ends_with("(and 1 more)"));
+        assert_eq!(group.suggested_actions, vec!["Follow up on: Build failed"]);
+        assert!(digest.render().contains("and 1 more groups"));
+    }
+
+    #[tokio::test]
+    async fn test_run_if_due_records_the_run() {
+        let (_repository, service) = setup(
+            MockAIService::new(),
+            DigestSettings {
+                enabled: true,
+                days: vec![
+                    chrono::Weekday::Mon,
+                    chrono::Weekday::Tue,
+                    chrono::Weekday::Wed,
+                    chrono::Weekday::Thu,
+                    chrono::Weekday::Fri,
+                    chrono::Weekday::Sat,
+                    chrono::Weekday::Sun,
+                ],
+                time: chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+                ..DigestSettings::default()
+            },
+        )
+        .await;
+
+        let now = Utc::now();
+        let digest = service.run_if_due(now).await.unwrap().unwrap();
+        assert_eq!(digest.notification_count, 0);
+        assert!(digest.notification_id.is_none());
+        assert!(service.run_if_due(now).await.unwrap().is_none());
+        assert_eq!(service.get_digests(10).await.unwrap().len(), 1);
+
+        let mut settings = service.get_settings().await.unwrap();
+        let last_run_at = settings.last_run_at;
+        assert!(last_run_at.is_some());
+        settings.last_run_at = None;
+        settings.max_groups = 3;
+        let saved = service.save_settings(settings).await.unwrap();
+        assert_eq!(saved.last_run_at, last_run_at);
+        assert_eq!(
+            service.get_settings().await.unwrap().last_run_at,
+            last_run_at
+        );
+        assert!(service.run_if_due(now).await.unwrap().is_none());
+    }
+}
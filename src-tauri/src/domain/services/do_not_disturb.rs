@@ -0,0 +1,396 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Weak};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{DndSettings, Notification},
+    error::DomainResult,
+    events::{
+        DomainEvent, DynEventPublisher, DynEventSubscriber, EventEnvelope, EventSubscriber,
+        NoopEventPublisher, NotificationEvent,
+    },
+    repositories::DynDndRepository,
+    services::notification_service::NotificationService,
+};
+
+/// Current Do Not Disturb state as shown to the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DndStatus {
+    pub active: bool,
+    pub manual: bool,
+    pub held_count: usize,
+    pub settings: DndSettings,
+}
+
+/// Decides which notifications Do Not Disturb holds back and releases them
+/// as a batch once it ends, together with the auto-responses it deferred.
+pub struct DoNotDisturb {
+    repository: DynDndRepository,
+    event_publisher: DynEventPublisher,
+    notification_service: Option<Weak<dyn NotificationService>>,
+}
+
+pub type DynDoNotDisturb = Arc<DoNotDisturb>;
+
+impl DoNotDisturb {
+    pub fn new(repository: DynDndRepository) -> Self {
+        Self {
+            repository,
+            event_publisher: Arc::new(NoopEventPublisher),
+            notification_service: None,
+        }
+    }
+
+    /// Publishes `HeldNotificationsReleased` when held notifications are released
+    pub fn with_event_publisher(mut self, event_publisher: DynEventPublisher) -> Self {
+        self.event_publisher = event_publisher;
+        self
+    }
+
+    /// Sends the deferred auto-responses once DND ends. They stay queued
+    /// until a notification service is set and they are sent. The reference is weak as the
+    /// notification service asks DND whether to hold what it creates.
+    pub fn with_notification_service(
+        mut self,
+        notification_service: Weak<dyn NotificationService>,
+    ) -> Self {
+        self.notification_service = Some(notification_service);
+        self
+    }
+
+    pub async fn settings(&self) -> DomainResult<DndSettings> {
+        self.repository.get_settings().await
+    }
+
+    /// Validates and saves the settings, releasing held notifications if
+    /// DND is no longer active
+    pub async fn save_settings(&self, mut settings: DndSettings) -> DomainResult<DndSettings> {
+        settings.validate()?;
+        self.repository.save_settings(&mut settings).await?;
+        self.release_if_ended(Utc::now()).await?;
+        Ok(settings)
+    }
+
+    /// Turns the manual switch on until `until`, or indefinitely, or off
+    pub async fn set_manual(
+        &self,
+        enabled: bool,
+        until: Option<DateTime<Utc>>,
+    ) -> DomainResult<DndSettings> {
+        let mut settings = self.repository.get_settings().await?;
+        settings.manual_enabled = enabled;
+        settings.manual_until = if enabled { until } else { None };
+        self.save_settings(settings).await
+    }
+
+    pub async fn status(&self, now: DateTime<Utc>) -> DomainResult<DndStatus> {
+        let settings = self.repository.get_settings().await?;
+        Ok(DndStatus {
+            active: settings.is_active(now),
+            manual: settings.is_manual_active(now),
+            held_count: self.repository.find_held().await?.len(),
+            settings,
+        })
+    }
+
+    /// Whether the notification should be held if it arrived now
+    pub async fn should_hold(&self, notification: &Notification) -> DomainResult<bool> {
+        Ok(self
+            .repository
+            .get_settings()
+            .await?
+            .holds(notification, Utc::now()))
+    }
+
+    pub async fn hold(&self, notification_id: Uuid) -> DomainResult<()> {
+        self.repository.hold(notification_id).await
+    }
+
+    /// Holds the notification if it arrived now and returns whether it was held
+    pub async fn hold_if_active(&self, notification: &Notification) -> DomainResult<bool> {
+        if !self.should_hold(notification).await? {
+            return Ok(false);
+        }
+        self.hold(notification.id).await?;
+        Ok(true)
+    }
+
+    pub async fn is_held(&self, notification_id: Uuid) -> DomainResult<bool> {
+        self.repository.is_held(notification_id).await
+    }
+
+    /// Queues an auto-response chosen by the rules while DND holds its notification
+    pub async fn defer_response(&self, notification_id: Uuid, response: &str) -> DomainResult<()> {
+        self.repository
+            .defer_response(notification_id, response)
+            .await
+    }
+
+    /// Releases every held notification once DND is over and returns their
+    /// ids, then sends the deferred auto-responses
+    pub async fn release_if_ended(&self, now: DateTime<Utc>) -> DomainResult<Vec<Uuid>> {
+        if self.repository.get_settings().await?.is_active(now) {
+            return Ok(Vec::new());
+        }
+        let held = self.repository.find_held().await?;
+        if !held.is_empty() {
+            self.release(&held).await?;
+            self.send_deferred_responses().await?;
+        }
+        Ok(held)
+    }
+
+    async fn release(&self, held: &[Uuid]) -> DomainResult<()> {
+        self.repository.release(held).await?;
+        info!(
+            "Do Not Disturb ended, releasing {} notifications",
+            held.len()
+        );
+        self.event_publisher
+            .publish_event(NotificationEvent::held_notifications_released(
+                held.to_vec(),
+            ))
+            .await
+    }
+
+    async fn send_deferred_responses(&self) -> DomainResult<()> {
+        let Some(notification_service) = self.notification_service.as_ref().and_then(Weak::upgrade)
+        else {
+            return Ok(());
+        };
+        // A response that fails stays queued and is tried again at the next release
+        for (id, notification_id, response) in self.repository.find_deferred_responses().await? {
+            let sent = match notification_service.get_notification(notification_id).await {
+                Ok(notification) => {
+                    notification_service
+                        .send_response(&notification, &response)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match &sent {
+                Ok(()) => self.repository.remove_deferred_response(id).await?,
+                Err(e) => warn!(
+                    "Failed to send deferred auto-response for notification {}: {}",
+                    notification_id, e
+                ),
+            }
+            if let Err(e) = self
+                .event_publisher
+                .publish_event(NotificationEvent::response_sent(
+                    notification_id,
+                    true,
+                    sent.is_ok(),
+                ))
+                .await
+            {
+                warn!("Failed to publish response sent event: {}", e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sits in front of the webview forwarder and drops the alerts of the
+/// notifications Do Not Disturb holds back. Whether a notification is held
+/// is decided when it is created and carried by its events, which still
+/// reach the log and every other subscriber.
+pub struct DndAlertFilter {
+    inner: DynEventSubscriber,
+    dnd: DynDoNotDisturb,
+}
+
+impl DndAlertFilter {
+    pub fn new(inner: DynEventSubscriber, dnd: DynDoNotDisturb) -> Self {
+        Self { inner, dnd }
+    }
+
+    async fn suppresses(&self, event: &NotificationEvent) -> DomainResult<bool> {
+        match event {
+            NotificationEvent::NotificationCreated { held, .. }
+            | NotificationEvent::NotificationCoalesced { held, .. } => Ok(*held),
+            NotificationEvent::NotificationActionRequired {
+                notification_id, ..
+            }
+            | NotificationEvent::NotificationEscalated {
+                notification_id, ..
+            } => self.dnd.is_held(*notification_id).await,
+            _ => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for DndAlertFilter {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn handle_event(&self, envelope: &EventEnvelope) -> DomainResult<()> {
+        if let DomainEvent::Notification(event) = &envelope.event {
+            match self.suppresses(event).await {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                // Better an alert too many than a lost one
+                Err(e) => warn!("Failed to check Do Not Disturb: {}", e),
+            }
+        }
+        self.inner.handle_event(envelope).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{NotificationMetadata, NotificationPriority, NotificationSource},
+        error::DomainError,
+        events::{EventBus, EventPublisher},
+        repositories::DndRepository,
+        services::MockNotificationService,
+    };
+    use crate::infrastructure::repositories::SqliteDndRepository;
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSubscriber {
+        received: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for RecordingSubscriber {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn handle_event(&self, envelope: &EventEnvelope) -> DomainResult<()> {
+            self.received.lock().await.push(envelope.event.event_type());
+            Ok(())
+        }
+    }
+
+    fn notification(priority: NotificationPriority) -> Notification {
+        Notification::new(
+            "Weekly report".to_string(),
+            "Numbers are in".to_string(),
+            priority,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn filtered_bus() -> (Arc<EventBus>, DynDoNotDisturb, Arc<RecordingSubscriber>) {
+        let bus = Arc::new(EventBus::new());
+        let dnd = Arc::new(
+            DoNotDisturb::new(Arc::new(SqliteDndRepository::new(":memory:").unwrap()))
+                .with_event_publisher(bus.clone()),
+        );
+        let recorder = Arc::new(RecordingSubscriber::default());
+        bus.subscribe(Arc::new(DndAlertFilter::new(recorder.clone(), dnd.clone())));
+        (bus, dnd, recorder)
+    }
+
+    #[tokio::test]
+    async fn test_held_alerts_are_released_as_a_batch() {
+        let (bus, dnd, recorder) = filtered_bus();
+
+        dnd.set_manual(true, None).await.unwrap();
+        let quiet = notification(NotificationPriority::Low);
+        let urgent = notification(NotificationPriority::Critical);
+        let quiet_held = dnd.hold_if_active(&quiet).await.unwrap();
+        let urgent_held = dnd.hold_if_active(&urgent).await.unwrap();
+        assert!(quiet_held && !urgent_held);
+        for event in [
+            NotificationEvent::notification_created(&quiet, quiet_held),
+            NotificationEvent::notification_action_required(quiet.id),
+            NotificationEvent::notification_created(&urgent, urgent_held),
+        ] {
+            bus.publish_event(event).await.unwrap();
+        }
+
+        assert_eq!(*recorder.received.lock().await, vec!["NotificationCreated"]);
+        let status = dnd.status(Utc::now()).await.unwrap();
+        assert!(status.active && status.manual);
+        assert_eq!(status.held_count, 1);
+        assert!(dnd.release_if_ended(Utc::now()).await.unwrap().is_empty());
+
+        dnd.set_manual(false, None).await.unwrap();
+
+        assert_eq!(
+            *recorder.received.lock().await,
+            vec!["NotificationCreated", "HeldNotificationsReleased"]
+        );
+        let released = bus.events_since(0).await;
+        assert!(matches!(
+            &released.last().unwrap().event,
+            DomainEvent::Notification(NotificationEvent::HeldNotificationsReleased {
+                notification_ids, ..
+            }) if notification_ids == &vec![quiet.id]
+        ));
+        assert_eq!(dnd.status(Utc::now()).await.unwrap().held_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_held_coalesced_alerts_are_dropped() {
+        let (bus, _dnd, recorder) = filtered_bus();
+        let aggregate = Uuid::new_v4();
+
+        for held in [true, false] {
+            bus.publish_event(NotificationEvent::notification_coalesced(
+                aggregate, 2, held,
+            ))
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(
+            *recorder.received.lock().await,
+            vec!["NotificationCoalesced"]
+        );
+        // Held events are still logged for every other subscriber
+        assert_eq!(bus.events_since(0).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deferred_responses_stay_queued_until_sent() {
+        let repository = Arc::new(SqliteDndRepository::new(":memory:").unwrap());
+        let mut service = MockNotificationService::new();
+        service
+            .expect_get_notification()
+            .returning(|_| Ok(notification(NotificationPriority::Low)));
+        // Once per response at the first release, never without one
+        service
+            .expect_send_response()
+            .times(2)
+            .returning(|_, response| match response {
+                "On it" => Err(DomainError::ExternalServiceError("offline".to_string())),
+                _ => Ok(()),
+            });
+        let service: Arc<dyn NotificationService> = Arc::new(service);
+        let dnd = DoNotDisturb::new(repository.clone())
+            .with_notification_service(Arc::downgrade(&service));
+
+        dnd.set_manual(true, None).await.unwrap();
+        let quiet = notification(NotificationPriority::Low);
+        assert!(dnd.hold_if_active(&quiet).await.unwrap());
+        dnd.defer_response(quiet.id, "Thanks").await.unwrap();
+        dnd.defer_response(quiet.id, "On it").await.unwrap();
+        dnd.set_manual(false, None).await.unwrap();
+
+        let queued = repository.find_deferred_responses().await.unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].2, "On it");
+
+        assert!(dnd.release_if_ended(Utc::now()).await.unwrap().is_empty());
+        assert_eq!(repository.find_deferred_responses().await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,663 @@
+use crate::domain::{
+    entities::{
+        EscalationAction, EscalationPolicy, EscalationState, Notification, NotificationStatus,
+    },
+    error::DomainResult,
+    events::{DynEventPublisher, NoopEventPublisher, NotificationEvent},
+    repositories::{DynEscalationPolicyRepository, DynNotificationRepository},
+    services::notification_service::DynNotificationService,
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Runs of a step with failing forwards before it is taken without them
+const MAX_FORWARD_ATTEMPTS: u32 = 3;
+
+/// Runs the steps of escalation policies on notifications left
+/// `ActionRequired` for too long.
+///
+/// Only `ActionRequired` notifications are looked at, so escalation stops
+/// as soon as the user handles, reads, snoozes or archives the notification.
+/// Every step taken is published as `NotificationEscalated`, which keeps it
+/// in the event history.
+pub struct Escalator {
+    notification_repository: DynNotificationRepository,
+    policy_repository: DynEscalationPolicyRepository,
+    notification_service: DynNotificationService,
+    event_publisher: DynEventPublisher,
+}
+
+pub type DynEscalator = Arc<Escalator>;
+
+impl std::fmt::Debug for Escalator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Escalator")
+            .field("notification_repository", &self.notification_repository)
+            .field("policy_repository", &"Arc<dyn EscalationPolicyRepository>")
+            .field("notification_service", &self.notification_service)
+            .finish()
+    }
+}
+
+impl Escalator {
+    pub fn new(
+        notification_repository: DynNotificationRepository,
+        policy_repository: DynEscalationPolicyRepository,
+        notification_service: DynNotificationService,
+    ) -> Self {
+        Self {
+            notification_repository,
+            policy_repository,
+            notification_service,
+            event_publisher: Arc::new(NoopEventPublisher),
+        }
+    }
+
+    pub fn with_event_publisher(mut self, event_publisher: DynEventPublisher) -> Self {
+        self.event_publisher = event_publisher;
+        self
+    }
+
+    /// Runs the steps due at `now`, returning how many ran. A notification
+    /// that fails to escalate is logged and retried on the next run.
+    pub async fn run(&self, now: DateTime<Utc>) -> DomainResult<usize> {
+        let policies = self.policy_repository.find_all().await?;
+        let waiting = self
+            .notification_repository
+            .find_by_status(NotificationStatus::ActionRequired)
+            .await?;
+
+        let mut steps_run = 0;
+        for notification in waiting {
+            let id = notification.id;
+            match self.escalate(notification, &policies, now).await {
+                Ok(steps) => steps_run += steps,
+                Err(e) => warn!("Failed to escalate notification {}: {}", id, e),
+            }
+        }
+        Ok(steps_run)
+    }
+
+    async fn escalate(
+        &self,
+        notification: Notification,
+        policies: &[EscalationPolicy],
+        now: DateTime<Utc>,
+    ) -> DomainResult<usize> {
+        // Notifications waiting since before escalation existed start now
+        let mut state = notification
+            .escalation
+            .clone()
+            .unwrap_or_else(|| EscalationState::started(now));
+
+        // A notification keeps the policy it started with, so raising its
+        // priority does not move it to another one
+        let policy = state
+            .policy_id
+            .and_then(|id| policies.iter().find(|p| p.id == id && p.enabled))
+            .or_else(|| policies.iter().find(|p| p.matches(&notification)));
+        let mut events = Vec::new();
+        let mut raises = 0;
+        if let Some(policy) = policy {
+            if state.policy_id != Some(policy.id) {
+                state.policy_id = Some(policy.id);
+                state.steps_taken = 0;
+                state.forwarded.clear();
+                state.forward_failures = 0;
+            }
+
+            let mut priority = notification.priority.clone();
+            'steps: for index in policy.due_steps(&state, now) {
+                let step = &policy.steps[index];
+                // A step counts as taken once its forwards went out, so a
+                // failed one is tried again on the next run, up to
+                // MAX_FORWARD_ATTEMPTS runs. Recipients that already got it
+                // are remembered and skipped.
+                let pending: Vec<&String> = step
+                    .actions
+                    .iter()
+                    .filter_map(|action| match action {
+                        EscalationAction::Forward { to } if !state.forwarded.contains(to) => {
+                            Some(to)
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                // The user may have handled it since the run started
+                if !pending.is_empty() && !self.is_action_required(notification.id).await? {
+                    return Ok(0);
+                }
+                let mut failed = false;
+                for to in pending {
+                    match self
+                        .notification_service
+                        .forward_notification(&notification, to)
+                        .await
+                    {
+                        Ok(()) => state.forwarded.push(to.clone()),
+                        Err(e) => {
+                            warn!(
+                                "Failed to forward escalated notification {} to {}: {}",
+                                notification.id, to, e
+                            );
+                            failed = true;
+                        }
+                    }
+                }
+                if failed {
+                    state.forward_failures += 1;
+                    if state.forward_failures < MAX_FORWARD_ATTEMPTS {
+                        break 'steps;
+                    }
+                    warn!(
+                        "Giving up forwarding escalated notification {} after {} attempts",
+                        notification.id, state.forward_failures
+                    );
+                }
+                for action in &step.actions {
+                    match action {
+                        EscalationAction::RaisePriority => {
+                            priority = priority.raised();
+                            raises += 1;
+                        }
+                        // The escalation event is the alert
+                        EscalationAction::Realert | EscalationAction::Forward { .. } => {}
+                    }
+                }
+                state.steps_taken = index + 1;
+                state.forwarded.clear();
+                state.forward_failures = 0;
+                events.push(NotificationEvent::notification_escalated(
+                    notification.id,
+                    policy.id,
+                    index,
+                    step.actions.clone(),
+                    priority.clone(),
+                ));
+            }
+        }
+
+        if notification.escalation.as_ref() == Some(&state) {
+            return Ok(0);
+        }
+
+        // Read again so changes made while forwarding are kept, and leave
+        // notifications the user handled in the meantime alone
+        let Some(mut stored) = self
+            .notification_repository
+            .find_by_id(notification.id)
+            .await?
+        else {
+            return Ok(0);
+        };
+        if stored.status != NotificationStatus::ActionRequired {
+            return Ok(0);
+        }
+        for _ in 0..raises {
+            stored.priority = stored.priority.raised();
+        }
+        stored.escalation = Some(state);
+        self.notification_repository.save(&mut stored).await?;
+
+        let steps_run = events.len();
+        for event in events {
+            if let Err(e) = self.event_publisher.publish_event(event).await {
+                warn!("Failed to publish escalation event: {}", e);
+            }
+        }
+        Ok(steps_run)
+    }
+
+    async fn is_action_required(&self, id: Uuid) -> DomainResult<bool> {
+        Ok(self
+            .notification_repository
+            .find_by_id(id)
+            .await?
+            .is_some_and(|n| n.status == NotificationStatus::ActionRequired))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{
+            EscalationStep, NotificationMetadata, NotificationPriority, NotificationSource,
+            RuleCondition, SavedView,
+        },
+        error::DomainError,
+        events::EventBus,
+        repositories::{CoalescingMerge, NotificationRepository},
+        services::MockNotificationService,
+    };
+    use crate::infrastructure::repositories::{
+        SqliteEscalationPolicyRepository, SqliteNotificationRepository,
+    };
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use mockall::predicate;
+
+    #[tokio::test]
+    async fn test_steps_run_once_and_stop_when_handled() {
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let policies: DynEscalationPolicyRepository =
+            Arc::new(SqliteEscalationPolicyRepository::new(":memory:").unwrap());
+
+        let mut policy = EscalationPolicy::new(
+            "Unanswered email".to_string(),
+            vec![RuleCondition::Source {
+                source: NotificationSource::Email,
+            }],
+            vec![
+                EscalationStep {
+                    after_minutes: 4 * 60,
+                    actions: vec![EscalationAction::RaisePriority, EscalationAction::Realert],
+                },
+                EscalationStep {
+                    after_minutes: 24 * 60,
+                    actions: vec![EscalationAction::Forward {
+                        to: "backup@example.com".to_string(),
+                    }],
+                },
+            ],
+        );
+        policies.save(&mut policy).await.unwrap();
+
+        let mut notification = Notification::new(
+            "Contract renewal".to_string(),
+            "Please sign before Friday".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        notification.mark_action_required();
+        let since = notification.escalation.as_ref().unwrap().since;
+        notifications.save(&mut notification).await.unwrap();
+
+        let mut service = MockNotificationService::new();
+        service
+            .expect_forward_notification()
+            .with(predicate::always(), predicate::eq("backup@example.com"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let bus = Arc::new(EventBus::new());
+        let escalator = Escalator::new(notifications.clone(), policies, Arc::new(service))
+            .with_event_publisher(bus.clone());
+
+        assert_eq!(escalator.run(since + Duration::hours(1)).await.unwrap(), 0);
+        assert_eq!(escalator.run(since + Duration::hours(5)).await.unwrap(), 1);
+        assert_eq!(escalator.run(since + Duration::hours(6)).await.unwrap(), 0);
+
+        let stored = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.priority, NotificationPriority::High);
+        assert_eq!(stored.escalation.as_ref().unwrap().steps_taken, 1);
+
+        assert_eq!(escalator.run(since + Duration::hours(25)).await.unwrap(), 1);
+
+        let escalations = bus
+            .events_since(0)
+            .await
+            .into_iter()
+            .filter(|envelope| envelope.event.event_type() == "NotificationEscalated")
+            .count();
+        assert_eq!(escalations, 2);
+
+        // Nothing more happens once the notification is handled
+        let mut handled = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        handled.mark_action_taken();
+        notifications.save(&mut handled).await.unwrap();
+        assert_eq!(escalator.run(since + Duration::days(3)).await.unwrap(), 0);
+    }
+
+    /// Fails to save one notification
+    #[derive(Debug)]
+    struct BrokenRepository {
+        inner: DynNotificationRepository,
+        broken: Uuid,
+    }
+
+    #[async_trait]
+    impl NotificationRepository for BrokenRepository {
+        async fn save(&self, notification: &mut Notification) -> DomainResult<()> {
+            if notification.id == self.broken {
+                return Err(DomainError::InternalError("Disk full".to_string()));
+            }
+            self.inner.save(notification).await
+        }
+        async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Notification>> {
+            self.inner.find_by_id(id).await
+        }
+        async fn find_all(&self) -> DomainResult<Vec<Notification>> {
+            self.inner.find_all().await
+        }
+        async fn find_by_status(
+            &self,
+            status: NotificationStatus,
+        ) -> DomainResult<Vec<Notification>> {
+            self.inner.find_by_status(status).await
+        }
+        async fn find_by_source(
+            &self,
+            source: NotificationSource,
+        ) -> DomainResult<Vec<Notification>> {
+            self.inner.find_by_source(source).await
+        }
+        async fn find_by_thread_key(&self, thread_key: &str) -> DomainResult<Vec<Notification>> {
+            self.inner.find_by_thread_key(thread_key).await
+        }
+        async fn find_by_coalescing_key(&self, key: &str) -> DomainResult<Vec<Notification>> {
+            self.inner.find_by_coalescing_key(key).await
+        }
+        async fn save_coalesced(
+            &self,
+            key: &str,
+            merge: CoalescingMerge,
+        ) -> DomainResult<Notification> {
+            self.inner.save_coalesced(key, merge).await
+        }
+        async fn find_by_tag(&self, tag: &str) -> DomainResult<Vec<Notification>> {
+            self.inner.find_by_tag(tag).await
+        }
+        async fn count_by_tag(&self) -> DomainResult<Vec<(String, usize)>> {
+            self.inner.count_by_tag().await
+        }
+        async fn find_in_view(&self, view: &SavedView) -> DomainResult<Vec<Notification>> {
+            self.inner.find_in_view(view).await
+        }
+        async fn count_in_view(&self, view: &SavedView) -> DomainResult<(usize, usize)> {
+            self.inner.count_in_view(view).await
+        }
+        async fn delete(&self, id: Uuid) -> DomainResult<()> {
+            self.inner.delete(id).await
+        }
+        async fn update_status(&self, id: Uuid, status: NotificationStatus) -> DomainResult<()> {
+            self.inner.update_status(id, status).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_steps_are_retried_without_stopping_the_run() {
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let policies: DynEscalationPolicyRepository =
+            Arc::new(SqliteEscalationPolicyRepository::new(":memory:").unwrap());
+        let mut policy = EscalationPolicy::new(
+            "Unanswered email".to_string(),
+            vec![],
+            vec![EscalationStep {
+                after_minutes: 60,
+                actions: vec![
+                    EscalationAction::RaisePriority,
+                    EscalationAction::Forward {
+                        to: "backup@example.com".to_string(),
+                    },
+                ],
+            }],
+        );
+        policies.save(&mut policy).await.unwrap();
+
+        let mut waiting = Vec::new();
+        for title in ["Broken", "Contract renewal"] {
+            let mut notification = Notification::new(
+                title.to_string(),
+                "Please sign before Friday".to_string(),
+                NotificationPriority::Medium,
+                NotificationMetadata {
+                    source: NotificationSource::Email,
+                    external_id: None,
+                    url: None,
+                    tags: vec![],
+                    custom_data: None,
+                    ..Default::default()
+                },
+            );
+            notification.mark_action_required();
+            notifications.save(&mut notification).await.unwrap();
+            waiting.push(notification);
+        }
+        let since = waiting[1].escalation.as_ref().unwrap().since;
+
+        // The first forward of the renewal fails, the second goes out
+        let mut service = MockNotificationService::new();
+        let mut sequence = mockall::Sequence::new();
+        service
+            .expect_forward_notification()
+            .withf(|notification, _| notification.title == "Broken")
+            .returning(|_, _| Ok(()));
+        service
+            .expect_forward_notification()
+            .withf(|notification, _| notification.title == "Contract renewal")
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_, _| Err(DomainError::ExternalServiceError("SMTP down".to_string())));
+        service
+            .expect_forward_notification()
+            .withf(|notification, _| notification.title == "Contract renewal")
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_, _| Ok(()));
+        let escalator = Escalator::new(
+            Arc::new(BrokenRepository {
+                inner: notifications.clone(),
+                broken: waiting[0].id,
+            }),
+            policies,
+            Arc::new(service),
+        );
+
+        let renewal = waiting[1].id;
+        let later = since + Duration::hours(2);
+        assert_eq!(escalator.run(later).await.unwrap(), 0);
+        let stored = notifications.find_by_id(renewal).await.unwrap().unwrap();
+        assert_eq!(stored.priority, NotificationPriority::Medium);
+        assert_eq!(stored.escalation.as_ref().unwrap().steps_taken, 0);
+
+        assert_eq!(escalator.run(later).await.unwrap(), 1);
+        let stored = notifications.find_by_id(renewal).await.unwrap().unwrap();
+        assert_eq!(stored.priority, NotificationPriority::High);
+        assert_eq!(stored.escalation.as_ref().unwrap().steps_taken, 1);
+    }
+
+    #[tokio::test]
+    async fn test_recipients_already_forwarded_to_are_skipped_on_retry() {
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let policies: DynEscalationPolicyRepository =
+            Arc::new(SqliteEscalationPolicyRepository::new(":memory:").unwrap());
+        let mut policy = EscalationPolicy::new(
+            "Unanswered email".to_string(),
+            vec![],
+            vec![EscalationStep {
+                after_minutes: 60,
+                actions: ["lead@example.com", "backup@example.com"]
+                    .into_iter()
+                    .map(|to| EscalationAction::Forward { to: to.to_string() })
+                    .collect(),
+            }],
+        );
+        policies.save(&mut policy).await.unwrap();
+
+        let mut notification = Notification::new(
+            "Contract renewal".to_string(),
+            "Please sign before Friday".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        notification.mark_action_required();
+        notifications.save(&mut notification).await.unwrap();
+        let since = notification.escalation.as_ref().unwrap().since;
+
+        // The lead gets it once, the backup only on the second run
+        let mut service = MockNotificationService::new();
+        let mut sequence = mockall::Sequence::new();
+        service
+            .expect_forward_notification()
+            .with(predicate::always(), predicate::eq("lead@example.com"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        service
+            .expect_forward_notification()
+            .with(predicate::always(), predicate::eq("backup@example.com"))
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_, _| Err(DomainError::ExternalServiceError("SMTP down".to_string())));
+        service
+            .expect_forward_notification()
+            .with(predicate::always(), predicate::eq("backup@example.com"))
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_, _| Ok(()));
+        let escalator = Escalator::new(notifications.clone(), policies, Arc::new(service));
+
+        let later = since + Duration::hours(2);
+        assert_eq!(escalator.run(later).await.unwrap(), 0);
+        let stored = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored.escalation.as_ref().unwrap().forwarded,
+            vec!["lead@example.com".to_string()]
+        );
+
+        assert_eq!(escalator.run(later).await.unwrap(), 1);
+        let stored = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let state = stored.escalation.unwrap();
+        assert_eq!(state.steps_taken, 1);
+        assert!(state.forwarded.is_empty());
+    }
+
+    fn forwarding_policy() -> EscalationPolicy {
+        EscalationPolicy::new(
+            "Unanswered email".to_string(),
+            vec![],
+            vec![
+                EscalationStep {
+                    after_minutes: 60,
+                    actions: vec![EscalationAction::Forward {
+                        to: "backup@example.com".to_string(),
+                    }],
+                },
+                EscalationStep {
+                    after_minutes: 120,
+                    actions: vec![EscalationAction::RaisePriority],
+                },
+            ],
+        )
+    }
+
+    async fn waiting_notification(notifications: &DynNotificationRepository) -> Notification {
+        let mut notification = Notification::new(
+            "Contract renewal".to_string(),
+            "Please sign before Friday".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                ..Default::default()
+            },
+        );
+        notification.mark_action_required();
+        notifications.save(&mut notification).await.unwrap();
+        notification
+    }
+
+    #[tokio::test]
+    async fn test_steps_whose_forwards_keep_failing_are_given_up() {
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let policies: DynEscalationPolicyRepository =
+            Arc::new(SqliteEscalationPolicyRepository::new(":memory:").unwrap());
+        policies.save(&mut forwarding_policy()).await.unwrap();
+        let notification = waiting_notification(&notifications).await;
+        let since = notification.escalation.as_ref().unwrap().since;
+
+        let mut service = MockNotificationService::new();
+        service
+            .expect_forward_notification()
+            .times(MAX_FORWARD_ATTEMPTS as usize)
+            .returning(|_, _| Err(DomainError::ConfigurationError("No relay".to_string())));
+        let escalator = Escalator::new(notifications.clone(), policies, Arc::new(service));
+
+        // The later step waits for the forward until it is given up
+        let later = since + Duration::hours(3);
+        for _ in 1..MAX_FORWARD_ATTEMPTS {
+            assert_eq!(escalator.run(later).await.unwrap(), 0);
+        }
+        let stored = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored.escalation.as_ref().unwrap().forward_failures,
+            MAX_FORWARD_ATTEMPTS - 1
+        );
+
+        assert_eq!(escalator.run(later).await.unwrap(), 2);
+        let stored = notifications
+            .find_by_id(notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.priority, NotificationPriority::High);
+        let state = stored.escalation.unwrap();
+        assert_eq!((state.steps_taken, state.forward_failures), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_notifications_handled_during_the_run_are_not_forwarded() {
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let policies: DynEscalationPolicyRepository =
+            Arc::new(SqliteEscalationPolicyRepository::new(":memory:").unwrap());
+        let policy = forwarding_policy();
+        let notification = waiting_notification(&notifications).await;
+        let since = notification.escalation.as_ref().unwrap().since;
+
+        let mut handled = notification.clone();
+        handled.mark_action_taken();
+        notifications.save(&mut handled).await.unwrap();
+
+        let mut service = MockNotificationService::new();
+        service.expect_forward_notification().never();
+        let escalator = Escalator::new(notifications.clone(), policies, Arc::new(service));
+
+        let steps = escalator
+            .escalate(notification, &[policy], since + Duration::hours(3))
+            .await
+            .unwrap();
+        assert_eq!(steps, 0);
+        let stored = notifications.find_by_id(handled.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, NotificationStatus::ActionTaken);
+        assert_eq!(stored.priority, NotificationPriority::Medium);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::domain::{
     entities::{
         notification::{Notification, NotificationPriority, NotificationSource},
+        rich_content::RichContent,
         service_config::{ServiceConfig, ServiceType},
     },
     error::{DomainError, DomainResult},
@@ -186,37 +187,74 @@ impl super::IntegrationService for GithubService {
     ) -> DomainResult<Notification> {
         let title = format!("GitHub: {}", event.event_type);
 
-        // Extract repository information if available
-        let repository_info = event
+        let repository = event.payload.get("repository").and_then(|r| r.as_object());
+
+        // Comments, reviews, issues and pull requests carry a Markdown body
+        let markdown = ["comment", "review", "issue", "pull_request"]
+            .iter()
+            .find_map(|key| event.payload.get(*key)?.get("body")?.as_str())
+            .filter(|body| !body.trim().is_empty());
+
+        let body = match markdown {
+            Some(markdown) => {
+                let repository_link = repository
+                    .map(|r| {
+                        format!(
+                            "Repository: [{}]({})\n\n",
+                            r.get("full_name")
+                                .and_then(|n| n.as_str())
+                                .unwrap_or("unknown"),
+                            r.get("html_url").and_then(|u| u.as_str()).unwrap_or("")
+                        )
+                    })
+                    .unwrap_or_default();
+                RichContent::markdown(format!("{}{}", repository_link, markdown))
+            }
+            None => {
+                // Extract repository information if available
+                let repository_info = repository
+                    .map(|r| {
+                        format!(
+                            "Repository: {}\nURL: {}\n\n",
+                            r.get("full_name")
+                                .and_then(|n| n.as_str())
+                                .unwrap_or("unknown"),
+                            r.get("html_url")
+                                .and_then(|u| u.as_str())
+                                .unwrap_or("unknown")
+                        )
+                    })
+                    .unwrap_or_default();
+
+                RichContent::plain(format!(
+                    "{}\n{}",
+                    repository_info,
+                    serde_json::to_string_pretty(&event.payload)
+                        .map_err(|e| DomainError::InternalError(e.to_string()))?
+                ))
+            }
+        };
+
+        // Issue and pull request events share the conversation of their number
+        let thread_key = event
             .payload
             .get("repository")
-            .and_then(|r| r.as_object())
-            .map(|r| {
-                format!(
-                    "Repository: {}\nURL: {}\n\n",
-                    r.get("full_name")
-                        .and_then(|n| n.as_str())
-                        .unwrap_or("unknown"),
-                    r.get("html_url")
-                        .and_then(|u| u.as_str())
-                        .unwrap_or("unknown")
-                )
-            })
-            .unwrap_or_default();
-
-        let content = format!(
-            "{}\n{}",
-            repository_info,
-            serde_json::to_string_pretty(&event.payload)
-                .map_err(|e| DomainError::InternalError(e.to_string()))?
-        );
+            .and_then(|r| r.get("full_name"))
+            .and_then(|n| n.as_str())
+            .zip(
+                ["issue", "pull_request"]
+                    .iter()
+                    .find_map(|key| event.payload.get(*key)?.get("number")?.as_i64()),
+            )
+            .map(|(repository, number)| format!("github:{}#{}", repository, number));
 
         Ok(<dyn IntegrationService>::event_to_notification(
             self,
             &event,
             title,
-            content,
+            body,
             NotificationPriority::Medium,
+            thread_key,
         ))
     }
 
@@ -287,12 +325,16 @@ impl From<GithubEvent> for super::IntegrationEvent {
     fn from(event: GithubEvent) -> Self {
         let mut payload = event.payload;
 
-        // Include repository information in the payload
+        // Include repository and sender information in the payload
         if let serde_json::Value::Object(ref mut map) = payload {
             map.insert(
                 "repository".to_string(),
                 serde_json::to_value(&event.repository).unwrap_or_default(),
             );
+            map.insert(
+                "sender".to_string(),
+                serde_json::to_value(&event.sender).unwrap_or_default(),
+            );
         }
 
         Self {
@@ -308,8 +350,9 @@ impl From<GithubEvent> for super::IntegrationEvent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::rich_content::BodyFormat;
     use crate::domain::entities::service_config::{AuthConfig, AuthType, OAuth2Config};
-    use crate::domain::services::integrations::IntegrationService;
+    use crate::domain::services::integrations::{IntegrationEvent, IntegrationService};
     use crate::domain::ServiceEndpoints;
     use serde_json::json;
     use wiremock::matchers::{method, path};
@@ -423,5 +466,36 @@ mod tests {
         assert!(notification.title.contains("PushEvent"));
         assert!(notification.content.contains("test/test"));
         assert_eq!(notification.priority, NotificationPriority::Medium);
+        assert_eq!(notification.sender().as_deref(), Some("test"));
+    }
+
+    #[tokio::test]
+    async fn test_github_comment_body_is_markdown() {
+        let service = GithubService::new();
+        let event = IntegrationEvent {
+            id: "comment-1".to_string(),
+            event_type: "issue_comment".to_string(),
+            source: NotificationSource::Github,
+            created_at: chrono::Utc::now(),
+            payload: json!({
+                "repository": {
+                    "full_name": "test/test",
+                    "html_url": "https://github.com/test/test"
+                },
+                "issue": { "number": 7, "body": "Original issue" },
+                "comment": { "body": "**LGTM**, see <script>x()</script>`fix.rs`" }
+            }),
+        };
+
+        let notification = service.create_notification_from_event(event).await.unwrap();
+        let body = notification.metadata.body.as_ref().unwrap();
+        assert_eq!(body.format, BodyFormat::Markdown);
+        assert!(body.html.contains("<strong>LGTM</strong>"));
+        assert!(!body.html.contains("script"));
+        assert_eq!(
+            notification.content,
+            "Repository: test/test\n\nLGTM, see fix.rs"
+        );
+        assert_eq!(notification.text(), notification.content);
     }
 }
@@ -1,6 +1,7 @@
 use crate::domain::{
     entities::{
         notification::{Notification, NotificationPriority, NotificationSource},
+        rich_content::RichContent,
         service_config::{ServiceConfig, ServiceType},
     },
     error::{DomainError, DomainResult},
@@ -25,6 +26,8 @@ pub struct GitlabEvent {
     pub project_id: i64,
     pub event_type: String,
     pub target_type: Option<String>,
+    #[serde(default)]
+    pub target_iid: Option<i64>,
     pub action_name: Option<String>,
     pub target_title: Option<String>,
     pub created_at: String,
@@ -197,15 +200,45 @@ impl super::IntegrationService for GitlabService {
                 .unwrap_or(""),
         );
 
-        let content = serde_json::to_string_pretty(&event.payload)
-            .map_err(|e| DomainError::InternalError(e.to_string()))?;
+        // Notes and the description of issues and merge requests are Markdown
+        let markdown = payload
+            .get("object_attributes")
+            .and_then(|attributes| {
+                ["note", "description"]
+                    .iter()
+                    .find_map(|key| attributes.get(*key)?.as_str())
+            })
+            .filter(|body| !body.trim().is_empty());
+
+        let body = match markdown {
+            Some(markdown) => RichContent::markdown(markdown),
+            None => RichContent::plain(
+                serde_json::to_string_pretty(&event.payload)
+                    .map_err(|e| DomainError::InternalError(e.to_string()))?,
+            ),
+        };
+
+        let thread_key = match (
+            payload.get("project_id").and_then(|v| v.as_i64()),
+            payload.get("target_type").and_then(|v| v.as_str()),
+            payload.get("target_iid").and_then(|v| v.as_i64()),
+        ) {
+            (Some(project_id), Some(target_type), Some(iid)) => Some(format!(
+                "gitlab:{}/{}/{}",
+                project_id,
+                target_type.to_lowercase(),
+                iid
+            )),
+            _ => None,
+        };
 
         Ok(<dyn IntegrationService>::event_to_notification(
             self,
             &event,
             title,
-            content,
+            body,
             NotificationPriority::Medium,
+            thread_key,
         ))
     }
 
@@ -299,6 +332,7 @@ impl From<GitlabEvent> for super::IntegrationEvent {
             payload: serde_json::json!({
                 "event_type": event.event_type,
                 "target_type": event.target_type,
+                "target_iid": event.target_iid,
                 "action_name": event.action_name,
                 "target_title": event.target_title,
                 "author": event.author,
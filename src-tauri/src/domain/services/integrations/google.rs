@@ -1,6 +1,8 @@
 use crate::domain::{
     entities::{
+        attachment::Attachment,
         notification::{Notification, NotificationPriority, NotificationSource},
+        rich_content::RichContent,
         service_config::{ServiceConfig, ServiceType},
     },
     error::{DomainError, DomainResult},
@@ -63,6 +65,9 @@ pub struct GmailHeader {
 pub struct GmailBody {
     pub size: i32,
     pub data: Option<String>,
+    /// Set on the parts of attachments, whose data is fetched separately
+    #[serde(default)]
+    pub attachment_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -216,6 +221,26 @@ impl GoogleService {
             .map_err(|e| DomainError::ExternalServiceError(e.to_string()))
     }
 
+    /// Parts with a file name, downloaded later by their attachment id
+    fn gmail_attachments(message: &GmailMessage) -> Vec<Attachment> {
+        message
+            .payload
+            .parts
+            .iter()
+            .flatten()
+            .filter(|part| !part.filename.is_empty())
+            .filter_map(|part| {
+                let attachment_id = part.body.attachment_id.as_ref()?;
+                Some(Attachment::new(
+                    part.filename.clone(),
+                    part.mime_type.clone(),
+                    u64::try_from(part.body.size).ok(),
+                    format!("messages/{}/attachments/{}", message.id, attachment_id),
+                ))
+            })
+            .collect()
+    }
+
     fn get_email_header(headers: &[GmailHeader], name: &str) -> Option<String> {
         headers
             .iter()
@@ -326,54 +351,79 @@ impl super::IntegrationService for GoogleService {
             .as_object()
             .ok_or_else(|| DomainError::InvalidInput("Invalid event payload".to_string()))?;
 
-        let (title, content, priority) = if let Some(message) = payload.get("message_details") {
-            let message_id = message
-                .get("id")
-                .and_then(|id| id.as_str())
-                .ok_or_else(|| DomainError::InvalidInput("Missing message ID".to_string()))?;
-
-            let gmail_message = self.fetch_gmail_message(message_id).await?;
-            let headers = gmail_message.payload.headers;
-
-            let subject = Self::get_email_header(&headers, "Subject")
-                .unwrap_or_else(|| "No Subject".to_string());
-            let from = Self::get_email_header(&headers, "From")
-                .unwrap_or_else(|| "Unknown Sender".to_string());
-
-            let title = format!("Gmail: {} (from: {})", subject, from);
-            let content = gmail_message.snippet;
-            let priority = Self::determine_priority(&headers);
-
-            (title, content, priority)
-        } else if let Some(calendar) = payload.get("calendar_details") {
-            let event_id = calendar
-                .get("id")
-                .and_then(|id| id.as_str())
-                .ok_or_else(|| {
-                    DomainError::InvalidInput("Missing calendar event ID".to_string())
-                })?;
-
-            let calendar_id = calendar
-                .get("calendar_id")
-                .and_then(|id| id.as_str())
-                .unwrap_or("primary");
-            let calendar_event = self.fetch_calendar_event(event_id, calendar_id).await?;
-
-            let title = format!("Calendar: {}", calendar_event.summary);
-            let content = calendar_event
-                .description
-                .unwrap_or_else(|| "No description".to_string());
-
-            (title, content, NotificationPriority::Medium)
-        } else {
-            return Err(DomainError::InvalidInput(
-                "Unsupported event type".to_string(),
-            ));
-        };
+        let (title, body, priority, thread_key, from, attachments) =
+            if let Some(message) = payload.get("message_details") {
+                let message_id = message
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .ok_or_else(|| DomainError::InvalidInput("Missing message ID".to_string()))?;
+
+                let gmail_message = self.fetch_gmail_message(message_id).await?;
+                let attachments = Self::gmail_attachments(&gmail_message);
+                let headers = gmail_message.payload.headers;
+
+                let subject = Self::get_email_header(&headers, "Subject")
+                    .unwrap_or_else(|| "No Subject".to_string());
+                let from = Self::get_email_header(&headers, "From");
+
+                let title = format!(
+                    "Gmail: {} (from: {})",
+                    subject,
+                    from.as_deref().unwrap_or("Unknown Sender")
+                );
+                // Gmail escapes the snippet as HTML
+                let body = RichContent::html(gmail_message.snippet);
+                let priority = Self::determine_priority(&headers);
+                let thread_key = format!("google:gmail:{}", gmail_message.thread_id);
+
+                (title, body, priority, Some(thread_key), from, attachments)
+            } else if let Some(calendar) = payload.get("calendar_details") {
+                let event_id = calendar
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .ok_or_else(|| {
+                        DomainError::InvalidInput("Missing calendar event ID".to_string())
+                    })?;
+
+                let calendar_id = calendar
+                    .get("calendar_id")
+                    .and_then(|id| id.as_str())
+                    .unwrap_or("primary");
+                let calendar_event = self.fetch_calendar_event(event_id, calendar_id).await?;
+
+                let title = format!("Calendar: {}", calendar_event.summary);
+                let body = calendar_event
+                    .description
+                    .map(RichContent::html)
+                    .unwrap_or_else(|| RichContent::plain("No description"));
+
+                let thread_key = format!("google:calendar:{}", calendar_event.id);
+
+                (
+                    title,
+                    body,
+                    NotificationPriority::Medium,
+                    Some(thread_key),
+                    None,
+                    Vec::new(),
+                )
+            } else {
+                return Err(DomainError::InvalidInput(
+                    "Unsupported event type".to_string(),
+                ));
+            };
 
-        Ok(<dyn IntegrationService>::event_to_notification(
-            self, &event, title, content, priority,
-        ))
+        let mut notification = <dyn IntegrationService>::event_to_notification(
+            self, &event, title, body, priority, thread_key,
+        );
+        // Keep the sender, only the message id is in the event payload
+        if let (Some(from), Some(serde_json::Value::Object(data))) =
+            (from, notification.metadata.custom_data.as_mut())
+        {
+            data.insert("from".to_string(), serde_json::Value::String(from));
+        }
+        notification.metadata.attachments = attachments;
+        Ok(notification)
     }
 
     async fn send_response(&self, notification: &Notification, response: &str) -> DomainResult<()> {
@@ -457,6 +507,37 @@ impl super::IntegrationService for GoogleService {
 
         Ok(())
     }
+
+    async fn download_attachment(
+        &self,
+        _notification: &Notification,
+        attachment: &Attachment,
+    ) -> DomainResult<Vec<u8>> {
+        let headers = self.get_headers().await?;
+        let base_url = self.get_base_url().await?;
+        let response: serde_json::Value = self
+            .client
+            .get(format!(
+                "{}/gmail/v1/users/me/{}",
+                base_url, attachment.remote_ref
+            ))
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+
+        let data = response["data"].as_str().ok_or_else(|| {
+            DomainError::ExternalServiceError("Attachment has no data".to_string())
+        })?;
+        BASE64_URL_SAFE_NO_PAD
+            .decode(data.trim_end_matches('='))
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))
+    }
 }
 
 impl From<GoogleEvent> for super::IntegrationEvent {
@@ -572,12 +653,29 @@ mod tests {
                                 "size": 100,
                                 "data": "VGVzdCBlbWFpbCBjb250ZW50"
                             },
-                            "parts": null
+                            "parts": [{
+                                "part_id": "1",
+                                "mime_type": "text/plain",
+                                "filename": "notes.txt",
+                                "headers": [],
+                                "body": { "size": 11, "attachment_id": "att_1" }
+                            }]
                         }
                     })),
             );
         details_mock.mount(&mock_server).await;
 
+        Mock::given(method("GET"))
+            .and(path(
+                "/gmail/v1/users/me/messages/test_id/attachments/att_1",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "size": 11,
+                "data": "TWVldGluZyBhdCA1"
+            })))
+            .mount(&mock_server)
+            .await;
+
         // Create service instance with mocked base URL
         let service = GoogleService::new();
         let config = ServiceConfig::new(
@@ -628,5 +726,19 @@ mod tests {
         assert!(notification.title.contains("Test Subject"));
         assert!(notification.content.contains("Test email content"));
         assert_eq!(notification.priority, NotificationPriority::High);
+        assert_eq!(
+            notification.metadata.thread_key.as_deref(),
+            Some("google:gmail:thread_id")
+        );
+        assert_eq!(notification.sender().as_deref(), Some("test@example.com"));
+
+        let attachment = &notification.metadata.attachments[0];
+        assert_eq!(attachment.name, "notes.txt");
+        assert_eq!(attachment.size, Some(11));
+        let data = service
+            .download_attachment(notification, attachment)
+            .await
+            .unwrap();
+        assert_eq!(data, b"Meeting at 5");
     }
 }
@@ -1,6 +1,8 @@
 use crate::domain::{
     entities::{
+        attachment::Attachment,
         notification::{Notification, NotificationPriority, NotificationSource},
+        rich_content::RichContent,
         service_config::{ServiceConfig, ServiceType},
     },
     error::{DomainError, DomainResult},
@@ -65,6 +67,19 @@ pub struct JiraIssueFields {
     pub status: JiraStatus,
     pub priority: Option<JiraPriority>,
     pub project: JiraProject,
+    #[serde(default)]
+    pub attachment: Vec<JiraAttachment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraAttachment {
+    pub id: String,
+    pub filename: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub size: u64,
+    /// Download url of the content
+    pub content: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -235,7 +250,10 @@ impl super::IntegrationService for JiraService {
             .headers(headers)
             .query(&[
                 ("jql", "assignee = currentUser() OR updated >= -24h"),
-                ("fields", "summary,description,status,priority,project"),
+                (
+                    "fields",
+                    "summary,description,status,priority,project,attachment",
+                ),
             ])
             .send()
             .await
@@ -299,20 +317,45 @@ impl super::IntegrationService for JiraService {
                 name: name.to_string(),
             });
 
-        let content = if let Some(description) = issue["fields"]["description"].as_str() {
-            description.to_string()
-        } else {
-            serde_json::to_string_pretty(&event.payload)
-                .map_err(|e| DomainError::InternalError(e.to_string()))?
+        // API v3 sends the description as an ADF document, v2 as text
+        let body = match &issue["fields"]["description"] {
+            serde_json::Value::String(description) => RichContent::plain(description.as_str()),
+            description @ serde_json::Value::Object(_) => RichContent::adf(description),
+            _ => RichContent::plain(
+                serde_json::to_string_pretty(&event.payload)
+                    .map_err(|e| DomainError::InternalError(e.to_string()))?,
+            ),
         };
 
-        Ok(<dyn IntegrationService>::event_to_notification(
+        let thread_key = issue["key"]
+            .as_str()
+            .filter(|key| !key.is_empty())
+            .map(|key| format!("jira:{}", key));
+
+        let attachments =
+            serde_json::from_value::<Vec<JiraAttachment>>(issue["fields"]["attachment"].clone())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|attachment| {
+                    Attachment::new(
+                        attachment.filename,
+                        attachment.mime_type,
+                        Some(attachment.size),
+                        attachment.content,
+                    )
+                })
+                .collect();
+
+        let mut notification = <dyn IntegrationService>::event_to_notification(
             self,
             &event,
             title,
-            content,
+            body,
             Self::map_jira_priority_to_notification_priority(priority.as_ref()),
-        ))
+            thread_key,
+        );
+        notification.metadata.attachments = attachments;
+        Ok(notification)
     }
 
     async fn send_response(&self, notification: &Notification, response: &str) -> DomainResult<()> {
@@ -394,6 +437,41 @@ impl super::IntegrationService for JiraService {
 
         Ok(())
     }
+
+    async fn download_attachment(
+        &self,
+        _notification: &Notification,
+        attachment: &Attachment,
+    ) -> DomainResult<Vec<u8>> {
+        // The credentials only ever go to the configured site
+        let url = reqwest::Url::parse(&attachment.remote_ref)
+            .map_err(|e| DomainError::InvalidInput(format!("Invalid attachment URL: {}", e)))?;
+        let base_url = reqwest::Url::parse(&self.get_base_url().await?)
+            .map_err(|e| DomainError::ConfigurationError(format!("Invalid Jira URL: {}", e)))?;
+        if url.origin() != base_url.origin() {
+            return Err(DomainError::InvalidInput(format!(
+                "Attachment {} is not on the Jira site",
+                attachment.remote_ref
+            )));
+        }
+
+        let headers = self.get_headers().await?;
+        let response = self
+            .client
+            .get(url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+
+        Ok(response
+            .bytes()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?
+            .to_vec())
+    }
 }
 
 impl From<JiraEvent> for super::IntegrationEvent {
@@ -455,6 +533,7 @@ mod tests {
                         key: "".to_string(),
                         name: "".to_string(),
                     },
+                    attachment: Vec::new(),
                 },
             }
         }
@@ -489,7 +568,10 @@ mod tests {
         Mock::given(method("GET"))
             .and(path("/rest/api/3/search"))
             .and(query_param("jql", "assignee = currentUser() OR updated >= -24h"))
-            .and(query_param("fields", "summary,description,status,priority,project"))
+            .and(query_param(
+                "fields",
+                "summary,description,status,priority,project,attachment",
+            ))
             .respond_with(ResponseTemplate::new(200)
                 .insert_header("content-type", "application/json")
                 .set_body_json(serde_json::json!({
@@ -509,6 +591,13 @@ mod tests {
                                 "key": "PROJ",
                                 "name": "Test Project"
                             },
+                            "attachment": [{
+                                "id": "10001",
+                                "filename": "trace.log",
+                                "mimeType": "text/plain",
+                                "size": 5,
+                                "content": format!("{}/rest/api/3/attachment/content/10001", mock_server.uri())
+                            }],
                             "assignee": {
                                 "self": "https://your-domain.atlassian.net/rest/api/3/user?accountId=123",
                                 "name": "test",
@@ -555,5 +644,31 @@ mod tests {
         assert!(notification.title.contains("PROJ-123"));
         assert!(notification.content.contains("Test Description"));
         assert_eq!(notification.priority, NotificationPriority::High);
+        assert_eq!(
+            notification.metadata.thread_key.as_deref(),
+            Some("jira:PROJ-123")
+        );
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/attachment/content/10001"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"panic".to_vec()))
+            .mount(&mock_server)
+            .await;
+        let attachment = &notification.metadata.attachments[0];
+        assert_eq!(attachment.name, "trace.log");
+        let data = service
+            .download_attachment(notification, attachment)
+            .await
+            .unwrap();
+        assert_eq!(data, b"panic");
+
+        let elsewhere = Attachment {
+            remote_ref: "https://attacker.example/rest/api/3/attachment/content/10001".to_string(),
+            ..attachment.clone()
+        };
+        assert!(matches!(
+            service.download_attachment(notification, &elsewhere).await,
+            Err(DomainError::InvalidInput(_))
+        ));
     }
 }
@@ -1,6 +1,7 @@
 use crate::domain::{
     entities::{
         notification::{Notification, NotificationPriority, NotificationSource},
+        rich_content::RichContent,
         service_config::{ServiceConfig, ServiceType},
     },
     error::{DomainError, DomainResult},
@@ -514,12 +515,24 @@ impl super::IntegrationService for LinkedInService {
             ),
         };
 
+        // Messages from the same person form one conversation
+        let thread_key = match event.event_type.as_str() {
+            "MESSAGE_RECEIVED" => payload
+                .get("messageDetails")
+                .and_then(|m| m.get("sender"))
+                .and_then(|s| s.get("id"))
+                .and_then(|id| id.as_str())
+                .map(|id| format!("linkedin:messages:{}", id)),
+            _ => None,
+        };
+
         Ok(<dyn IntegrationService>::event_to_notification(
             self,
             &event,
             title,
-            content,
+            RichContent::plain(content),
             self.determine_priority(&serde_json::from_value(event.payload.clone())?),
+            thread_key,
         ))
     }
 
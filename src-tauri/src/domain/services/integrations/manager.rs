@@ -4,6 +4,7 @@ use crate::domain::{
         service_config::{ServiceConfig, ServiceType},
     },
     error::{DomainError, DomainResult},
+    events::{DynEventPublisher, ServiceEvent},
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -15,9 +16,18 @@ use super::{
 };
 
 /// Manages the lifecycle and coordination of integration services
-#[derive(Debug)]
 pub struct IntegrationManager {
     services: Arc<RwLock<HashMap<ServiceType, DynIntegrationService>>>,
+    event_publisher: Option<DynEventPublisher>,
+}
+
+impl std::fmt::Debug for IntegrationManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntegrationManager")
+            .field("services", &self.services)
+            .field("event_publisher", &self.event_publisher.is_some())
+            .finish()
+    }
 }
 
 impl Default for IntegrationManager {
@@ -30,6 +40,21 @@ impl IntegrationManager {
     pub fn new() -> Self {
         Self {
             services: Arc::new(RwLock::new(HashMap::new())),
+            event_publisher: None,
+        }
+    }
+
+    /// Publishes connect and disconnect events for managed services
+    pub fn with_event_publisher(mut self, event_publisher: DynEventPublisher) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    async fn publish(&self, event: ServiceEvent) {
+        if let Some(publisher) = &self.event_publisher {
+            if let Err(e) = publisher.publish(event.into()).await {
+                log::warn!("Failed to publish service event: {}", e);
+            }
         }
     }
 
@@ -49,12 +74,21 @@ impl IntegrationManager {
             }
         };
 
-        service.initialize(config.clone()).await?;
+        service.initialize(config).await?;
+        self.add_service(service).await;
+        Ok(())
+    }
 
-        let mut services = self.services.write().await;
-        services.insert(config.service_type, service);
+    /// Adds an initialized service, replacing the one of the same type
+    pub async fn add_service(&self, service: DynIntegrationService) {
+        let service_type = service.service_type();
+        self.services
+            .write()
+            .await
+            .insert(service_type.clone(), service);
 
-        Ok(())
+        self.publish(ServiceEvent::service_connected(service_type))
+            .await;
     }
 
     /// Get a service by its type
@@ -101,6 +135,20 @@ impl IntegrationManager {
         results
     }
 
+    /// Types of all initialized services
+    pub async fn service_types(&self) -> Vec<ServiceType> {
+        self.services.read().await.keys().cloned().collect()
+    }
+
+    /// Sync notifications from a single initialized service
+    pub async fn sync_service(
+        &self,
+        service_type: &ServiceType,
+    ) -> DomainResult<Vec<crate::domain::entities::notification::Notification>> {
+        let service = self.get_service(service_type).await?;
+        service.sync_notifications().await
+    }
+
     /// Sync notifications from all initialized services
     pub async fn sync_all_notifications(
         &self,
@@ -126,10 +174,14 @@ impl IntegrationManager {
 
     /// Remove a service
     pub async fn remove_service(&self, service_type: &ServiceType) -> DomainResult<()> {
-        let mut services = self.services.write().await;
-        services
+        self.services
+            .write()
+            .await
             .remove(service_type)
             .ok_or_else(|| DomainError::NotFound("Service not found".to_string()))?;
+
+        self.publish(ServiceEvent::service_disconnected(service_type.clone()))
+            .await;
         Ok(())
     }
 }
@@ -140,6 +192,7 @@ mod tests {
     use crate::domain::entities::service_config::{
         AuthConfig, AuthType, OAuth2Config, ServiceEndpoints,
     };
+    use crate::domain::events::EventBus;
 
     #[tokio::test]
     async fn test_service_initialization() {
@@ -173,7 +226,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_service_removal() {
-        let manager = IntegrationManager::new();
+        let bus = Arc::new(EventBus::new());
+        let manager = IntegrationManager::new().with_event_publisher(bus.clone());
 
         let config = ServiceConfig::new(
             "GitHub".to_string(),
@@ -199,6 +253,14 @@ mod tests {
         assert!(manager.initialize_service(config).await.is_ok());
         assert!(manager.remove_service(&ServiceType::Github).await.is_ok());
         assert!(!manager.is_service_initialized(&ServiceType::Github).await);
+
+        let event_types: Vec<&str> = bus
+            .events_since(0)
+            .await
+            .iter()
+            .map(|envelope| envelope.event.event_type())
+            .collect();
+        assert_eq!(event_types, vec!["ServiceConnected", "ServiceDisconnected"]);
     }
 
     #[tokio::test]
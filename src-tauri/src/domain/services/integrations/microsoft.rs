@@ -1,15 +1,18 @@
 use crate::domain::{
     entities::{
+        attachment::Attachment,
         notification::{
             Notification, NotificationMetadata, NotificationPriority, NotificationSource,
             NotificationStatus,
         },
+        rich_content::RichContent,
         service_config::{ServiceConfig, ServiceType},
     },
     error::{DomainError, DomainResult},
     AuthConfig,
 };
 use async_trait::async_trait;
+use base64::prelude::*;
 use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -51,6 +54,64 @@ pub struct MicrosoftMessage {
     pub from: Option<MicrosoftEmailAddress>,
     #[serde(rename = "receivedDateTime")]
     pub received_date_time: String,
+    #[serde(rename = "conversationId", default)]
+    pub conversation_id: Option<String>,
+    #[serde(default)]
+    pub body: Option<MicrosoftItemBody>,
+    /// Present when the message is fetched with `$expand=attachments`
+    #[serde(default)]
+    pub attachments: Vec<MicrosoftAttachment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MicrosoftAttachment {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "contentType", default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MicrosoftItemBody {
+    /// "html" or "text"
+    #[serde(rename = "contentType")]
+    pub content_type: String,
+    pub content: String,
+}
+
+impl MicrosoftMessage {
+    fn attachments(&self) -> Vec<Attachment> {
+        self.attachments
+            .iter()
+            .map(|attachment| {
+                Attachment::new(
+                    attachment.name.clone(),
+                    attachment
+                        .content_type
+                        .clone()
+                        .unwrap_or_else(|| "application/octet-stream".to_string()),
+                    attachment.size,
+                    format!(
+                        "me/messages/{}/attachments/{}/$value",
+                        self.id, attachment.id
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// The full body when Graph returned it, the preview otherwise
+    pub fn rich_body(&self) -> Option<RichContent> {
+        match &self.body {
+            Some(body) if body.content_type.eq_ignore_ascii_case("html") => {
+                Some(RichContent::html(body.content.as_str()))
+            }
+            Some(body) => Some(RichContent::plain(body.content.as_str())),
+            None => self.body_preview.as_deref().map(RichContent::plain),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,11 +132,52 @@ pub struct MicrosoftTeamsMessage {
     #[serde(rename = "messageType")]
     pub message_type: String,
     pub content: Option<String>,
+    #[serde(rename = "replyToId", default)]
+    pub reply_to_id: Option<String>,
     #[serde(rename = "channelIdentity")]
     pub channel: Option<MicrosoftTeamsChannel>,
     pub from: MicrosoftTeamsFrom,
     #[serde(rename = "createdDateTime")]
     pub created_date_time: String,
+    #[serde(default)]
+    pub attachments: Vec<MicrosoftTeamsAttachment>,
+}
+
+/// Files shared in a Teams message, stored in SharePoint or OneDrive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MicrosoftTeamsAttachment {
+    pub id: String,
+    #[serde(rename = "contentType")]
+    pub content_type: String,
+    #[serde(rename = "contentUrl", default)]
+    pub content_url: Option<String>,
+    pub name: Option<String>,
+}
+
+impl MicrosoftTeamsMessage {
+    /// Shared files, downloaded through the shares API of their url. Cards
+    /// and other inline attachments are skipped.
+    fn attachments(&self) -> Vec<Attachment> {
+        self.attachments
+            .iter()
+            .filter(|attachment| attachment.content_type == "reference")
+            .filter_map(|attachment| {
+                let url = attachment.content_url.as_ref()?;
+                Some(Attachment::new(
+                    attachment
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| attachment.id.clone()),
+                    "application/octet-stream".to_string(),
+                    None,
+                    format!(
+                        "shares/u!{}/driveItem/content",
+                        BASE64_URL_SAFE_NO_PAD.encode(url)
+                    ),
+                ))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -158,35 +260,39 @@ impl MicrosoftService {
         }
     }
 
+    /// The Graph API url of a resource path
+    fn graph_url(&self, resource_url: &str) -> DomainResult<String> {
+        let config = self
+            .config
+            .read()
+            .map_err(|_| DomainError::InternalError("Failed to read config".to_string()))?;
+        let config = config.as_ref().ok_or_else(|| {
+            DomainError::ConfigurationError("Microsoft service not configured".to_string())
+        })?;
+
+        let url = config
+            .endpoints
+            .endpoints
+            .get("graph")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&config.endpoints.base_url);
+        Ok(format!(
+            "{}/{}",
+            url.trim_end_matches('/'),
+            resource_url.trim_start_matches('/')
+        ))
+    }
+
     async fn fetch_message_details(&self, resource_url: &str) -> DomainResult<MicrosoftMessage> {
         let headers = self.get_headers().await?;
 
-        let base_url = {
-            let config = self
-                .config
-                .read()
-                .map_err(|_| DomainError::InternalError("Failed to read config".to_string()))?;
-            let config = config.as_ref().ok_or_else(|| {
-                DomainError::ConfigurationError("Microsoft service not configured".to_string())
-            })?;
-
-            let url = config
-                .endpoints
-                .endpoints
-                .get("graph")
-                .and_then(|v| v.as_str())
-                .unwrap_or(&config.endpoints.base_url);
-            format!(
-                "{}/{}",
-                url.trim_end_matches('/'),
-                resource_url.trim_start_matches('/')
-            )
-        };
+        let base_url = self.graph_url(resource_url)?;
 
         let response = self
             .client
             .get(&base_url)
             .headers(headers)
+            .query(&[("$expand", "attachments")])
             .send()
             .await
             .map_err(|e| {
@@ -215,27 +321,7 @@ impl MicrosoftService {
     ) -> DomainResult<MicrosoftTeamsMessage> {
         let headers = self.get_headers().await?;
 
-        let base_url = {
-            let config = self
-                .config
-                .read()
-                .map_err(|_| DomainError::InternalError("Failed to read config".to_string()))?;
-            let config = config.as_ref().ok_or_else(|| {
-                DomainError::ConfigurationError("Microsoft service not configured".to_string())
-            })?;
-
-            let url = config
-                .endpoints
-                .endpoints
-                .get("graph")
-                .and_then(|v| v.as_str())
-                .unwrap_or(&config.endpoints.base_url);
-            format!(
-                "{}/{}",
-                url.trim_end_matches('/'),
-                resource_url.trim_start_matches('/')
-            )
-        };
+        let base_url = self.graph_url(resource_url)?;
 
         self.client
             .get(base_url)
@@ -346,7 +432,7 @@ impl super::IntegrationService for MicrosoftService {
         let response = self
             .client
             .get(&messages_url)
-            .query(&[("$filter", "isRead eq false")])
+            .query(&[("$filter", "isRead eq false"), ("$expand", "attachments")])
             .headers(headers.clone())
             .send()
             .await
@@ -378,16 +464,23 @@ impl super::IntegrationService for MicrosoftService {
             for message in value {
                 if let Ok(msg) = serde_json::from_value::<MicrosoftMessage>(message.clone()) {
                     // Create event directly from the message data we already have
+                    let body = msg.rich_body();
                     let notification = Notification {
                         id: uuid::Uuid::new_v4(),
                         title: msg.subject.clone().unwrap_or_default(),
-                        content: msg.body_preview.clone().unwrap_or_default(),
+                        content: body
+                            .as_ref()
+                            .map(|body| body.text.clone())
+                            .unwrap_or_default(),
                         created_at: DateTime::parse_from_rfc3339(&msg.received_date_time)
                             .unwrap_or_else(|_| Utc::now().into())
                             .into(),
                         updated_at: Utc::now(),
                         read_at: None,
                         action_taken_at: None,
+                        snoozed_until: None,
+                        snoozed_from: None,
+                        escalation: None,
                         priority: self
                             .map_importance_to_priority(msg.importance.as_deref().unwrap_or("")),
                         metadata: NotificationMetadata {
@@ -399,6 +492,13 @@ impl super::IntegrationService for MicrosoftService {
                             })),
                             tags: Vec::new(),
                             url: None,
+                            thread_key: msg
+                                .conversation_id
+                                .as_ref()
+                                .map(|id| format!("microsoft:mail:{}", id)),
+                            attachments: msg.attachments(),
+                            body,
+                            ..Default::default()
                         },
                         status: NotificationStatus::New,
                     };
@@ -424,28 +524,45 @@ impl super::IntegrationService for MicrosoftService {
             .and_then(|r| r.as_str())
             .ok_or_else(|| DomainError::InvalidInput("No resource found".to_string()))?;
 
-        let (title, content, priority) = if resource.starts_with("messages/") {
+        let (title, body, priority, thread_key, attachments) = if resource.starts_with("messages/")
+        {
             let message = self.fetch_message_details(resource).await?;
+            let thread_key = message
+                .conversation_id
+                .as_ref()
+                .map(|id| format!("microsoft:mail:{}", id));
             (
                 format!(
                     "Microsoft: {}",
-                    message
-                        .subject
-                        .unwrap_or_else(|| String::from("No Subject"))
+                    message.subject.as_deref().unwrap_or("No Subject")
                 ),
                 message
-                    .body_preview
-                    .unwrap_or_else(|| String::from("No preview available")),
+                    .rich_body()
+                    .unwrap_or_else(|| RichContent::plain("No preview available")),
                 self.map_importance_to_priority(message.importance.as_deref().unwrap_or("")),
+                thread_key,
+                message.attachments(),
             )
         } else if resource.starts_with("teams/") {
             let teams_message = self.fetch_teams_message_details(resource).await?;
+            // Replies belong to the thread of the message they answer
+            let thread_key = format!(
+                "microsoft:teams:{}",
+                teams_message
+                    .reply_to_id
+                    .as_deref()
+                    .unwrap_or(&teams_message.id)
+            );
+            let attachments = teams_message.attachments();
             (
                 format!("Microsoft Teams: {}", teams_message.message_type),
                 teams_message
                     .content
-                    .unwrap_or_else(|| String::from("No content available")),
+                    .map(RichContent::html)
+                    .unwrap_or_else(|| RichContent::plain("No content available")),
                 NotificationPriority::Medium,
+                Some(thread_key),
+                attachments,
             )
         } else {
             return Err(DomainError::InvalidInput(
@@ -453,9 +570,11 @@ impl super::IntegrationService for MicrosoftService {
             ));
         };
 
-        Ok(<dyn IntegrationService>::event_to_notification(
-            self, &event, title, content, priority,
-        ))
+        let mut notification = <dyn IntegrationService>::event_to_notification(
+            self, &event, title, body, priority, thread_key,
+        );
+        notification.metadata.attachments = attachments;
+        Ok(notification)
     }
 
     async fn send_response(&self, notification: &Notification, response: &str) -> DomainResult<()> {
@@ -587,6 +706,29 @@ impl super::IntegrationService for MicrosoftService {
 
         Ok(())
     }
+
+    async fn download_attachment(
+        &self,
+        _notification: &Notification,
+        attachment: &Attachment,
+    ) -> DomainResult<Vec<u8>> {
+        let headers = self.get_headers().await?;
+        let response = self
+            .client
+            .get(self.graph_url(&attachment.remote_ref)?)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+
+        Ok(response
+            .bytes()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?
+            .to_vec())
+    }
 }
 
 impl From<MicrosoftEvent> for super::IntegrationEvent {
@@ -656,6 +798,7 @@ mod tests {
         Mock::given(method("GET"))
             .and(path("/v1.0/me/messages"))
             .and(query_param("$filter", "isRead eq false"))
+            .and(query_param("$expand", "attachments"))
             .respond_with(
                 ResponseTemplate::new(200)
                     .insert_header("content-type", "application/json")
@@ -672,13 +815,25 @@ mod tests {
                                     "address": "test@example.com"
                                 }
                             },
-                            "receivedDateTime": "2024-01-01T00:00:00Z"
+                            "receivedDateTime": "2024-01-01T00:00:00Z",
+                            "attachments": [{
+                                "id": "att_1",
+                                "name": "agenda.txt",
+                                "contentType": "text/plain",
+                                "size": 6
+                            }]
                         }]
                     })),
             )
             .mount(&mock_server)
             .await;
 
+        Mock::given(method("GET"))
+            .and(path("/v1.0/me/messages/test_id/attachments/att_1/$value"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"Agenda".to_vec()))
+            .mount(&mock_server)
+            .await;
+
         // Mock message details endpoint
         Mock::given(method("GET"))
             .and(path("/v1.0/me/messages/test_id"))
@@ -760,5 +915,14 @@ mod tests {
         );
         assert!(email_notification.content.contains("Test content"));
         assert_eq!(email_notification.priority, NotificationPriority::High);
+
+        let attachment = &email_notification.metadata.attachments[0];
+        assert_eq!(attachment.name, "agenda.txt");
+        assert_eq!(attachment.mime_type, "text/plain");
+        let data = service
+            .download_attachment(email_notification, attachment)
+            .await
+            .unwrap();
+        assert_eq!(data, b"Agenda");
     }
 }
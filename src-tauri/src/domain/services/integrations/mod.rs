@@ -1,11 +1,13 @@
 use crate::domain::{
     entities::{
+        attachment::Attachment,
         notification::{
             Notification, NotificationMetadata, NotificationPriority, NotificationSource,
         },
+        rich_content::RichContent,
         service_config::{AuthConfig, ServiceConfig, ServiceType},
     },
-    error::DomainResult,
+    error::{DomainError, DomainResult},
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -58,6 +60,19 @@ pub trait IntegrationService: Send + Sync + std::fmt::Debug {
         action_type: &str,
         payload: serde_json::Value,
     ) -> DomainResult<()>;
+
+    /// Download the content of an attachment of a notification
+    async fn download_attachment(
+        &self,
+        notification: &Notification,
+        attachment: &Attachment,
+    ) -> DomainResult<Vec<u8>> {
+        let _ = (notification, attachment);
+        Err(DomainError::ValidationError(format!(
+            "{:?} does not support downloading attachments",
+            self.service_type()
+        )))
+    }
 }
 
 pub type DynIntegrationService = Arc<dyn IntegrationService>;
@@ -68,15 +83,20 @@ impl dyn IntegrationService {
         &self,
         event: &IntegrationEvent,
         title: String,
-        content: String,
+        body: RichContent,
         priority: NotificationPriority,
+        thread_key: Option<String>,
     ) -> Notification {
+        let content = body.text.clone();
         let metadata = NotificationMetadata {
             source: event.source.clone(),
             external_id: Some(event.id.clone()),
             url: None,
             tags: vec![event.event_type.clone()],
             custom_data: Some(event.payload.clone()),
+            thread_key,
+            body: Some(body),
+            ..Default::default()
         };
 
         Notification::new(title, content, priority, metadata)
@@ -1,18 +1,31 @@
 use crate::domain::{
-    entities::{notification::Notification, service_config::ServiceConfig},
-    error::DomainResult,
-    services::DynNotificationService,
+    entities::{attachment::Attachment, notification::Notification, service_config::ServiceConfig},
+    error::{DomainError, DomainResult},
+    events::{DynEventPublisher, ServiceEvent},
+    services::{AttachmentDownloader, DynNotificationService},
 };
+use async_trait::async_trait;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use super::manager::IntegrationManager;
 
-#[derive(Debug)]
 pub struct ServiceBridge {
     integration_manager: Arc<IntegrationManager>,
     notification_service: DynNotificationService,
     sync_interval: Arc<RwLock<std::time::Duration>>,
+    event_publisher: Option<DynEventPublisher>,
+}
+
+impl std::fmt::Debug for ServiceBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceBridge")
+            .field("integration_manager", &self.integration_manager)
+            .field("notification_service", &self.notification_service)
+            .field("sync_interval", &self.sync_interval)
+            .field("event_publisher", &self.event_publisher.is_some())
+            .finish()
+    }
 }
 
 impl ServiceBridge {
@@ -24,6 +37,21 @@ impl ServiceBridge {
             integration_manager,
             notification_service,
             sync_interval: Arc::new(RwLock::new(std::time::Duration::from_secs(300))), // 5 minutes default
+            event_publisher: None,
+        }
+    }
+
+    /// Publishes sync lifecycle events from the sync loop
+    pub fn with_event_publisher(mut self, event_publisher: DynEventPublisher) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    async fn publish(event_publisher: &Option<DynEventPublisher>, event: ServiceEvent) {
+        if let Some(publisher) = event_publisher {
+            if let Err(e) = publisher.publish(event.into()).await {
+                log::warn!("Failed to publish service event: {}", e);
+            }
         }
     }
 
@@ -40,13 +68,29 @@ impl ServiceBridge {
         let integration_manager = Arc::clone(&self.integration_manager);
         let notification_service = Arc::clone(&self.notification_service);
         let sync_interval = Arc::clone(&self.sync_interval);
+        let event_publisher = self.event_publisher.clone();
 
         let handle = tokio::spawn(async move {
             loop {
-                match Self::sync_notifications(&integration_manager, &notification_service).await {
-                    Ok(_) => log::info!("Successfully synced notifications"),
-                    Err(e) => log::error!("Error syncing notifications: {}", e),
-                }
+                Self::publish(&event_publisher, ServiceEvent::sync_started(None)).await;
+
+                let event = match Self::sync_notifications(
+                    &integration_manager,
+                    &notification_service,
+                    &event_publisher,
+                )
+                .await
+                {
+                    Ok((fetched, created, failed)) => {
+                        log::info!("Successfully synced {} notifications", created);
+                        ServiceEvent::sync_completed(None, fetched, created, failed)
+                    }
+                    Err(e) => {
+                        log::error!("Error syncing notifications: {}", e);
+                        ServiceEvent::sync_failed(None, e.to_string())
+                    }
+                };
+                Self::publish(&event_publisher, event).await;
 
                 let interval = *sync_interval.read().await;
                 tokio::time::sleep(interval).await;
@@ -56,55 +100,94 @@ impl ServiceBridge {
         Ok(handle)
     }
 
+    /// Syncs every initialized service in turn, publishing per-service sync
+    /// events. Returns the total fetched, created and failed notification
+    /// counts, where a notification merged into an aggregate counts as created.
     async fn sync_notifications(
         integration_manager: &IntegrationManager,
         notification_service: &DynNotificationService,
-    ) -> DomainResult<()> {
-        let notifications = integration_manager.sync_all_notifications().await?;
-
-        for notification in notifications {
-            if let Err(e) = notification_service
-                .create_notification(
-                    notification.title.clone(),
-                    notification.content.clone(),
-                    notification.priority,
-                    notification.metadata.clone(),
-                )
-                .await
-            {
-                log::error!(
-                    "Failed to create notification from service {}: {}",
-                    notification.metadata.source,
-                    e
-                );
+        event_publisher: &Option<DynEventPublisher>,
+    ) -> DomainResult<(usize, usize, usize)> {
+        let mut total_fetched = 0;
+        let mut total_created = 0;
+        let mut total_failed = 0;
+
+        for service_type in integration_manager.service_types().await {
+            Self::publish(
+                event_publisher,
+                ServiceEvent::sync_started(Some(service_type.clone())),
+            )
+            .await;
+
+            let notifications = match integration_manager.sync_service(&service_type).await {
+                Ok(notifications) => notifications,
+                Err(e) => {
+                    log::error!("Error syncing notifications from {:?}: {}", service_type, e);
+                    if let DomainError::UnauthorizedError(_) = e {
+                        Self::publish(
+                            event_publisher,
+                            ServiceEvent::token_expired(service_type.clone()),
+                        )
+                        .await;
+                    }
+                    Self::publish(
+                        event_publisher,
+                        ServiceEvent::sync_failed(Some(service_type), e.to_string()),
+                    )
+                    .await;
+                    continue;
+                }
+            };
+
+            let fetched = notifications.len();
+            let mut created = 0;
+            let mut failed = 0;
+            for notification in notifications {
+                match notification_service
+                    .create_notification(
+                        notification.title.clone(),
+                        notification.content.clone(),
+                        notification.priority,
+                        notification.metadata.clone(),
+                    )
+                    .await
+                {
+                    Ok(_) => created += 1,
+                    Err(e) => {
+                        failed += 1;
+                        log::error!(
+                            "Failed to create notification from service {}: {}",
+                            notification.metadata.source,
+                            e
+                        );
+                    }
+                }
             }
+
+            Self::publish(
+                event_publisher,
+                ServiceEvent::sync_completed(Some(service_type), fetched, created, failed),
+            )
+            .await;
+            total_fetched += fetched;
+            total_created += created;
+            total_failed += failed;
         }
 
-        Ok(())
+        Ok((total_fetched, total_created, total_failed))
     }
 
-    pub async fn process_notification(&self, notification: &Notification) -> DomainResult<()> {
-        // Get the appropriate service for this notification
+    pub async fn send_response(
+        &self,
+        notification: &Notification,
+        response: &str,
+    ) -> DomainResult<()> {
         let service = self
             .integration_manager
             .get_service_for_source(&notification.metadata.source)
             .await?;
 
-        // Generate response using the notification service's AI capabilities
-        let response = self
-            .notification_service
-            .generate_response(notification)
-            .await?;
-
-        // Send the response through the integration service
-        service.send_response(notification, &response).await?;
-
-        // Mark the notification as handled
-        self.notification_service
-            .mark_action_taken(notification.id)
-            .await?;
-
-        Ok(())
+        service.send_response(notification, response).await
     }
 
     pub async fn test_connections(&self) -> DomainResult<bool> {
@@ -139,6 +222,22 @@ impl ServiceBridge {
     }
 }
 
+#[async_trait]
+impl AttachmentDownloader for ServiceBridge {
+    async fn download(
+        &self,
+        notification: &Notification,
+        attachment: &Attachment,
+    ) -> DomainResult<Vec<u8>> {
+        let service = self
+            .integration_manager
+            .get_service_for_source(&notification.metadata.source)
+            .await?;
+
+        service.download_attachment(notification, attachment).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +281,75 @@ mod tests {
         assert!(bridge.initialize_service(config).await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_sync_counts_the_notifications_that_failed_to_be_created() {
+        use crate::domain::entities::{
+            NotificationMetadata, NotificationPriority, NotificationSource,
+        };
+        use crate::domain::events::{DomainEvent, EventBus};
+        use crate::domain::services::MockIntegrationService;
+
+        let mut github = MockIntegrationService::new();
+        github
+            .expect_service_type()
+            .return_const(ServiceType::Github);
+        github.expect_sync_notifications().returning(|| {
+            Ok(["Review requested", "CI failed", "Unreadable"]
+                .iter()
+                .map(|title| {
+                    Notification::new(
+                        title.to_string(),
+                        String::new(),
+                        NotificationPriority::Low,
+                        NotificationMetadata {
+                            source: NotificationSource::Github,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect())
+        });
+        let integration_manager = Arc::new(IntegrationManager::new());
+        integration_manager.add_service(Arc::new(github)).await;
+
+        // Created or merged into an aggregate, both succeed
+        let mut notification_service = MockNotificationService::new();
+        notification_service.expect_create_notification().returning(
+            |title, content, priority, metadata| {
+                if title == "Unreadable" {
+                    Err(DomainError::InternalError("disk full".to_string()))
+                } else {
+                    Ok(Notification::new(title, content, priority, metadata))
+                }
+            },
+        );
+        let notification_service: DynNotificationService = Arc::new(notification_service);
+        let bus = Arc::new(EventBus::new());
+        let event_publisher: Option<DynEventPublisher> = Some(bus.clone());
+
+        let counts = ServiceBridge::sync_notifications(
+            &integration_manager,
+            &notification_service,
+            &event_publisher,
+        )
+        .await
+        .unwrap();
+        assert_eq!(counts, (3, 2, 1));
+
+        let failed_counts: Vec<_> = bus
+            .events_since(0)
+            .await
+            .into_iter()
+            .filter_map(|envelope| match envelope.event {
+                DomainEvent::Service(ServiceEvent::SyncCompleted { failed_count, .. }) => {
+                    Some(failed_count)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(failed_counts, vec![1]);
+    }
+
     #[tokio::test]
     async fn test_service_bridge_sync_interval() {
         let integration_manager = Arc::new(IntegrationManager::new());
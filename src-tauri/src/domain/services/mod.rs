@@ -1,38 +1,110 @@
 pub mod actions;
 pub mod ai;
+pub mod attachments;
 pub mod background;
+pub mod coalescing;
+pub mod contacts;
+pub mod digest_service;
+pub mod do_not_disturb;
+pub mod escalation;
 pub mod integrations;
+pub mod notes;
 pub mod notification_service;
+pub mod rules;
+pub mod saved_views;
+pub mod scoring;
 pub mod search;
 pub mod service_config_service;
+pub mod snooze;
+pub mod statistics_service;
+pub mod tags;
+pub mod thread_service;
+pub mod webhooks;
 
 pub use actions::executor::{ActionExecutor, ActionExecutorTrait, DynActionExecutor};
-pub use ai::{AIAnalysis, AIConfig, DynAIService, OllamaService, PriorityLevel};
+pub use ai::{AIAnalysis, AIConfig, DynAIService, OllamaService, PriorityLevel, ResponseStream};
+pub use attachments::{
+    AttachmentContent, AttachmentDownloader, AttachmentService, DefaultAttachmentService,
+    DynAttachmentDownloader, DynAttachmentService,
+};
 pub use background::{
     manager::{BackgroundJobManager, BackgroundJobManagerTrait, DynBackgroundJobManager},
     types::{Job, JobHandler, JobPriority, JobStatus, JobType},
     NotificationActionType, NotificationProcessor,
 };
 
+pub use coalescing::{
+    Coalesced, CoalescingConfig, DynNotificationCoalescer, NotificationCoalescer,
+};
+
+pub use contacts::{ContactService, DefaultContactService, DynContactService};
+
+pub use digest_service::{DefaultDigestService, DigestService, DynDigestService};
+
+pub use do_not_disturb::{DndAlertFilter, DndStatus, DoNotDisturb, DynDoNotDisturb};
+
+pub use escalation::{DynEscalator, Escalator};
+
 pub use integrations::{
     DynIntegrationService, GithubService, GitlabService, GoogleService, IntegrationService,
     JiraService, LinkedInService, MicrosoftService,
 };
 
+pub use notes::{DefaultNoteService, DynNoteService, NoteService};
+
 pub use notification_service::{
-    DefaultNotificationService, DynNotificationService, NotificationService,
+    DefaultNotificationService, DynNotificationForwarder, DynNotificationService,
+    NotificationForwarder, NotificationService,
 };
 
+pub use rules::{evaluate_rules, RuleEvaluation, RuleMatch};
+
+pub use saved_views::{DefaultSavedViewService, DynSavedViewService, SavedViewService};
+
+pub use scoring::{DynPriorityScorer, PriorityScorer, ScoringConfig};
+
 pub use service_config_service::{
     DefaultServiceConfigService, DynServiceConfigService, ServiceConfigService,
 };
 
+pub use snooze::{SnoozePreset, SnoozeUntil};
+
+pub use statistics_service::{DefaultStatisticsService, DynStatisticsService, StatisticsService};
+
+pub use tags::{DefaultTagService, DynTagService, TagService};
+
+pub use thread_service::{DefaultThreadService, DynThreadService, ThreadService};
+
+#[cfg(test)]
+pub use attachments::MockAttachmentService;
+
+#[cfg(test)]
+pub use contacts::MockContactService;
+
+#[cfg(test)]
+pub use digest_service::MockDigestService;
+
+#[cfg(test)]
+pub use notes::MockNoteService;
+
 #[cfg(test)]
 pub use notification_service::MockNotificationService;
 
+#[cfg(test)]
+pub use saved_views::MockSavedViewService;
+
 #[cfg(test)]
 pub use service_config_service::MockServiceConfigService;
 
+#[cfg(test)]
+pub use statistics_service::MockStatisticsService;
+
+#[cfg(test)]
+pub use tags::MockTagService;
+
+#[cfg(test)]
+pub use thread_service::MockThreadService;
+
 #[cfg(test)]
 pub use ai::MockAIService;
 
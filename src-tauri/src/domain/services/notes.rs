@@ -0,0 +1,192 @@
+use crate::domain::{
+    entities::{Note, NoteTarget},
+    error::{DomainError, DomainResult},
+    repositories::{DynNoteRepository, DynNotificationRepository},
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait NoteService: Send + Sync + std::fmt::Debug {
+    async fn get_note(&self, id: Uuid) -> DomainResult<Note>;
+    /// Notes on exactly this notification or thread, oldest first
+    async fn get_notes(&self, target: NoteTarget) -> DomainResult<Vec<Note>>;
+    /// Notes on the notification and on its thread, oldest first
+    async fn get_notification_notes(&self, notification_id: Uuid) -> DomainResult<Vec<Note>>;
+    /// Creates or updates a note. Notification notes need the notification to exist.
+    async fn save_note(&self, note: Note) -> DomainResult<Note>;
+    async fn delete_note(&self, id: Uuid) -> DomainResult<()>;
+    /// Notes containing every word of the query, newest first
+    async fn search_notes(&self, query: &str) -> DomainResult<Vec<Note>>;
+}
+
+pub type DynNoteService = Arc<dyn NoteService>;
+
+pub struct DefaultNoteService {
+    repository: DynNoteRepository,
+    notification_repository: DynNotificationRepository,
+}
+
+impl std::fmt::Debug for DefaultNoteService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultNoteService")
+            .field("repository", &"Arc<dyn NoteRepository>")
+            .field("notification_repository", &self.notification_repository)
+            .finish()
+    }
+}
+
+impl DefaultNoteService {
+    pub fn new(
+        repository: DynNoteRepository,
+        notification_repository: DynNotificationRepository,
+    ) -> Self {
+        Self {
+            repository,
+            notification_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl NoteService for DefaultNoteService {
+    async fn get_note(&self, id: Uuid) -> DomainResult<Note> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFoundError(format!("Note {} not found", id)))
+    }
+
+    async fn get_notes(&self, target: NoteTarget) -> DomainResult<Vec<Note>> {
+        self.repository.find_by_target(&target).await
+    }
+
+    async fn get_notification_notes(&self, notification_id: Uuid) -> DomainResult<Vec<Note>> {
+        let notification = self
+            .notification_repository
+            .find_by_id(notification_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFoundError(format!("Notification {} not found", notification_id))
+            })?;
+        self.repository.find_for_notification(&notification).await
+    }
+
+    async fn save_note(&self, mut note: Note) -> DomainResult<Note> {
+        note.body = note.body.trim().to_string();
+        note.validate()?;
+        if let NoteTarget::Notification { notification_id } = &note.target {
+            if self
+                .notification_repository
+                .find_by_id(*notification_id)
+                .await?
+                .is_none()
+            {
+                return Err(DomainError::NotFoundError(format!(
+                    "Notification {} not found",
+                    notification_id
+                )));
+            }
+        }
+        if let Some(existing) = self.repository.find_by_id(note.id).await? {
+            note.created_at = existing.created_at;
+        }
+
+        self.repository.save(&mut note).await?;
+        Ok(note)
+    }
+
+    async fn delete_note(&self, id: Uuid) -> DomainResult<()> {
+        self.get_note(id).await?;
+        self.repository.delete(id).await
+    }
+
+    async fn search_notes(&self, query: &str) -> DomainResult<Vec<Note>> {
+        self.repository.search(query).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+    };
+    use crate::infrastructure::repositories::{SqliteNoteRepository, SqliteNotificationRepository};
+
+    #[tokio::test]
+    async fn test_notes_on_a_notification_and_its_thread() {
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let service = DefaultNoteService::new(
+            Arc::new(SqliteNoteRepository::new(":memory:").unwrap()),
+            notifications.clone(),
+        );
+
+        let mut notification = Notification::new(
+            "Re: outage".to_string(),
+            "Still failing".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                thread_key: Some("outage".to_string()),
+                ..Default::default()
+            },
+        );
+        notifications.save(&mut notification).await.unwrap();
+
+        let on_thread = service
+            .save_note(Note::new(
+                NoteTarget::Thread {
+                    thread_key: "outage".to_string(),
+                },
+                "Relates to INC-42".to_string(),
+            ))
+            .await
+            .unwrap();
+        let mut waiting = service
+            .save_note(Note::new(
+                NoteTarget::Notification {
+                    notification_id: notification.id,
+                },
+                "  Waiting on Alice\n".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(waiting.body, "Waiting on Alice");
+
+        // Editing keeps the creation time
+        waiting.body = "Alice replied".to_string();
+        let edited = service.save_note(waiting.clone()).await.unwrap();
+        assert_eq!(edited.created_at, waiting.created_at);
+
+        let notes = service
+            .get_notification_notes(notification.id)
+            .await
+            .unwrap();
+        assert_eq!(notes, vec![on_thread.clone(), edited]);
+
+        let missing = Note::new(
+            NoteTarget::Notification {
+                notification_id: Uuid::new_v4(),
+            },
+            "Lost".to_string(),
+        );
+        assert!(matches!(
+            service.save_note(missing).await,
+            Err(DomainError::NotFoundError(_))
+        ));
+
+        service.delete_note(on_thread.id).await.unwrap();
+        assert!(service.search_notes("INC-42").await.unwrap().is_empty());
+    }
+}
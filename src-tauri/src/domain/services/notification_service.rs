@@ -1,20 +1,29 @@
 use crate::domain::{
-    entities::{Notification, NotificationMetadata, NotificationPriority, NotificationStatus},
+    entities::{
+        Notification, NotificationMetadata, NotificationPriority, NotificationStatus, WAITING_TAG,
+    },
     error::{DomainError, DomainResult},
-    repositories::DynNotificationRepository,
+    events::{DynEventPublisher, NoopEventPublisher, NotificationEvent},
+    repositories::{DynNoteRepository, DynNotificationRepository},
     services::{
         actions::executor::DynActionExecutor,
-        ai::DynAIService,
+        ai::{AIAnalysis, DynAIService, ResponseStream},
         background::{
             manager::DynBackgroundJobManager,
             types::{Job, JobPriority, JobType},
         },
+        coalescing::{Coalesced, DynNotificationCoalescer},
+        contacts::DynContactService,
+        do_not_disturb::DynDoNotDisturb,
+        scoring::DynPriorityScorer,
     },
     NotificationSource,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde_json::json;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use tracing::warn;
 use uuid::Uuid;
 
 #[cfg(test)]
@@ -22,6 +31,15 @@ use mockall::automock;
 
 use super::integrations::service_bridge::ServiceBridge;
 
+/// Sends forwarded notifications over a channel the user configured
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait NotificationForwarder: Send + Sync {
+    async fn forward(&self, notification: &Notification, recipient: &str) -> DomainResult<()>;
+}
+
+pub type DynNotificationForwarder = Arc<dyn NotificationForwarder>;
+
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait NotificationService: Send + Sync + std::fmt::Debug {
@@ -43,25 +61,79 @@ pub trait NotificationService: Send + Sync + std::fmt::Debug {
         &self,
         source: NotificationSource,
     ) -> DomainResult<Vec<Notification>>;
+    /// Notifications carrying the tag, compared ignoring case
+    async fn get_notifications_by_tag(&self, tag: &str) -> DomainResult<Vec<Notification>>;
     async fn mark_as_read(&self, id: Uuid) -> DomainResult<()>;
     async fn mark_action_required(&self, id: Uuid) -> DomainResult<()>;
     async fn mark_action_taken(&self, id: Uuid) -> DomainResult<()>;
     async fn archive_notification(&self, id: Uuid) -> DomainResult<()>;
     async fn delete_notification(&self, id: Uuid) -> DomainResult<()>;
+    async fn snooze_notification(&self, id: Uuid, until: DateTime<Utc>) -> DomainResult<()>;
+    async fn unsnooze_notification(&self, id: Uuid) -> DomainResult<()>;
 
-    async fn analyze_notification_content(&self, notification: &Notification)
-        -> DomainResult<bool>;
+    /// Whether the notification requires action and how urgent it is. Never
+    /// replies on its own: replies go out through rules or the user.
+    async fn analyze_notification_content(
+        &self,
+        notification: &Notification,
+    ) -> DomainResult<AIAnalysis>;
     async fn generate_response(&self, notification: &Notification) -> DomainResult<String>;
+    /// The response chunk by chunk as the AI writes it, dropping the stream
+    /// cancels the generation
+    async fn generate_response_stream(
+        &self,
+        notification: &Notification,
+    ) -> DomainResult<ResponseStream> {
+        let response = self.generate_response(notification).await?;
+        Ok(Box::pin(futures::stream::once(async move { Ok(response) })))
+    }
     async fn execute_action(&self, notification: &Notification) -> DomainResult<()>;
+    /// Sends a reply through the integration the notification came from
+    async fn send_response(&self, notification: &Notification, response: &str) -> DomainResult<()>;
+    /// Forwards through the configured forwarder, never through the
+    /// integration the notification came from
+    async fn forward_notification(
+        &self,
+        notification: &Notification,
+        recipient: &str,
+    ) -> DomainResult<()>;
 }
 
-#[derive(Debug)]
 pub struct DefaultNotificationService {
     repository: DynNotificationRepository,
     job_manager: DynBackgroundJobManager,
     action_executor: DynActionExecutor,
     ai_service: DynAIService,
-    service_bridge: Option<Arc<ServiceBridge>>,
+    service_bridge: Option<Weak<ServiceBridge>>,
+    event_publisher: DynEventPublisher,
+    coalescer: Option<DynNotificationCoalescer>,
+    contacts: Option<DynContactService>,
+    scorer: Option<DynPriorityScorer>,
+    notes: Option<DynNoteRepository>,
+    dnd: Option<DynDoNotDisturb>,
+    forwarder: Option<DynNotificationForwarder>,
+}
+
+impl std::fmt::Debug for DefaultNotificationService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultNotificationService")
+            .field("repository", &self.repository)
+            .field("job_manager", &self.job_manager)
+            .field("action_executor", &self.action_executor)
+            .field("ai_service", &self.ai_service)
+            .field("service_bridge", &self.service_bridge)
+            .field("event_publisher", &"Arc<dyn EventPublisher>")
+            .field("coalescer", &self.coalescer)
+            .field("contacts", &self.contacts)
+            .field("scorer", &self.scorer)
+            .field(
+                "notes",
+                &self.notes.as_ref().map(|_| "Arc<dyn NoteRepository>"),
+            )
+            .field("dnd", &self.dnd.is_some())
+            .field("forwarder", &self.forwarder.is_some())
+            .finish()
+    }
 }
 
 impl DefaultNotificationService {
@@ -77,13 +149,214 @@ impl DefaultNotificationService {
             action_executor,
             ai_service,
             service_bridge: None,
+            event_publisher: Arc::new(NoopEventPublisher),
+            coalescer: None,
+            contacts: None,
+            scorer: None,
+            notes: None,
+            dnd: None,
+            forwarder: None,
         }
     }
 
-    pub fn with_service_bridge(mut self, service_bridge: Arc<ServiceBridge>) -> Self {
+    /// Replies through the integrations of the bridge. The reference is weak
+    /// as the bridge holds the service to sync into it.
+    pub fn with_service_bridge(mut self, service_bridge: Weak<ServiceBridge>) -> Self {
         self.service_bridge = Some(service_bridge);
         self
     }
+
+    /// Publishes a lifecycle event for every state change made through the service
+    pub fn with_event_publisher(mut self, event_publisher: DynEventPublisher) -> Self {
+        self.event_publisher = event_publisher;
+        self
+    }
+
+    /// Merges bursts of similar notifications into aggregates as they are created
+    pub fn with_coalescer(mut self, coalescer: DynNotificationCoalescer) -> Self {
+        self.coalescer = Some(coalescer);
+        self
+    }
+
+    /// Links the sender of new notifications to their contact and adds what is
+    /// known about the contact to the response prompt
+    pub fn with_contacts(mut self, contacts: DynContactService) -> Self {
+        self.contacts = Some(contacts);
+        self
+    }
+
+    /// Gives new notifications a priority score before the AI has looked at them
+    pub fn with_scorer(mut self, scorer: DynPriorityScorer) -> Self {
+        self.scorer = Some(scorer);
+        self
+    }
+
+    /// Adds the user's notes on the notification and its thread to the
+    /// response prompt
+    pub fn with_notes(mut self, notes: DynNoteRepository) -> Self {
+        self.notes = Some(notes);
+        self
+    }
+
+    /// Holds back the alerts of what Do Not Disturb holds as it is created
+    pub fn with_do_not_disturb(mut self, dnd: DynDoNotDisturb) -> Self {
+        self.dnd = Some(dnd);
+        self
+    }
+
+    /// Sends the forwards of rules and escalations
+    pub fn with_forwarder(mut self, forwarder: DynNotificationForwarder) -> Self {
+        self.forwarder = Some(forwarder);
+        self
+    }
+
+    fn service_bridge(&self) -> Option<Arc<ServiceBridge>> {
+        self.service_bridge.as_ref().and_then(Weak::upgrade)
+    }
+
+    fn require_service_bridge(&self) -> DomainResult<Arc<ServiceBridge>> {
+        self.service_bridge().ok_or_else(|| {
+            DomainError::ConfigurationError("No integration services are connected".to_string())
+        })
+    }
+
+    /// Who the notification is from, with the contact's notes and preferred tone
+    async fn sender_context(&self, notification: &Notification, sender: &str) -> String {
+        let contact = match (&self.contacts, &notification.metadata.contact) {
+            (Some(contacts), Some(resolved)) => {
+                contacts.get_contact(resolved.contact_id).await.ok()
+            }
+            _ => None,
+        };
+        let Some(contact) = contact else {
+            return format!("From: {}\n", sender);
+        };
+
+        let mut context = format!(
+            "From: {} <{}>{}\n",
+            contact.name,
+            sender,
+            if contact.vip { " (VIP)" } else { "" }
+        );
+        if let Some(notes) = contact.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+            context.push_str(&format!("About the sender: {}\n", notes));
+        }
+        if let Some(tone) = &contact.preferences.tone {
+            context.push_str(&format!("Preferred tone: {}\n", tone));
+        }
+        context
+    }
+
+    /// The user's notes on the notification and its thread, as a list
+    async fn notes_context(&self, notification: &Notification) -> String {
+        let Some(notes) = &self.notes else {
+            return String::new();
+        };
+        match notes.find_for_notification(notification).await {
+            Ok(notes) if !notes.is_empty() => {
+                let mut context = "User notes:\n".to_string();
+                for note in notes {
+                    context.push_str(&format!("- {}\n", note.body.replace('\n', "\n  ")));
+                }
+                context
+            }
+            Ok(_) => String::new(),
+            Err(e) => {
+                warn!("Failed to load notes for {}: {}", notification.id, e);
+                String::new()
+            }
+        }
+    }
+
+    /// What the AI is given to write a reply to the notification
+    async fn response_context(&self, notification: &Notification) -> String {
+        let mut context = format!("Source: {}\n", notification.metadata.source);
+        if let Some(sender) = notification.sender() {
+            context.push_str(&self.sender_context(notification, &sender).await);
+        }
+        context.push_str(&format!(
+            "Title: {}\nContent: {}\n",
+            notification.title,
+            notification.text()
+        ));
+        context.push_str(&attachments_context(notification));
+        context.push_str(&self.notes_context(notification).await);
+        context
+    }
+
+    /// Whether Do Not Disturb holds the notification back, holding it if so
+    async fn hold_for_dnd(&self, notification: &Notification) -> bool {
+        let Some(dnd) = &self.dnd else {
+            return false;
+        };
+        // Better an alert too many than a lost one
+        dnd.hold_if_active(notification).await.unwrap_or_else(|e| {
+            warn!("Failed to check Do Not Disturb: {}", e);
+            false
+        })
+    }
+
+    async fn publish(&self, event: NotificationEvent) {
+        if let Err(e) = self.event_publisher.publish_event(event).await {
+            warn!("Failed to publish notification event: {}", e);
+        }
+    }
+
+    /// Tags the notification as waiting on the other side after a reply,
+    /// reading it again so concurrent changes are kept
+    async fn mark_waiting(&self, id: Uuid) -> DomainResult<()> {
+        let Some(mut notification) = self.repository.find_by_id(id).await? else {
+            return Ok(());
+        };
+        let tags = &notification.metadata.tags;
+        if !tags.iter().any(|tag| tag.eq_ignore_ascii_case(WAITING_TAG)) {
+            notification.metadata.tags.push(WAITING_TAG.to_string());
+            notification.updated_at = Utc::now();
+            self.repository.save(&mut notification).await?;
+        }
+        Ok(())
+    }
+
+    /// A new message in the thread means the other side answered
+    async fn stop_waiting(&self, notification: &Notification) -> DomainResult<()> {
+        let Some(thread_key) = &notification.metadata.thread_key else {
+            return Ok(());
+        };
+        for mut waiting in self.repository.find_by_thread_key(thread_key).await? {
+            let tags = &waiting.metadata.tags;
+            if waiting.id != notification.id
+                && tags.iter().any(|tag| tag.eq_ignore_ascii_case(WAITING_TAG))
+            {
+                waiting
+                    .metadata
+                    .tags
+                    .retain(|tag| !tag.eq_ignore_ascii_case(WAITING_TAG));
+                waiting.updated_at = Utc::now();
+                self.repository.save(&mut waiting).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extracted attachment text given to the AI, per attachment
+const PROMPT_ATTACHMENT_CHARS: usize = 2_000;
+
+/// The attachments with the start of their extracted text, as a list
+fn attachments_context(notification: &Notification) -> String {
+    if notification.metadata.attachments.is_empty() {
+        return String::new();
+    }
+    let mut context = "\nAttachments:\n".to_string();
+    for attachment in &notification.metadata.attachments {
+        context.push_str(&format!("- {} ({})", attachment.name, attachment.mime_type));
+        if let Some(text) = &attachment.text {
+            let excerpt: String = text.chars().take(PROMPT_ATTACHMENT_CHARS).collect();
+            context.push_str(&format!(": {}", excerpt.replace('\n', "\n  ")));
+        }
+        context.push('\n');
+    }
+    context
 }
 
 #[async_trait]
@@ -96,7 +369,57 @@ impl NotificationService for DefaultNotificationService {
         metadata: NotificationMetadata,
     ) -> DomainResult<Notification> {
         let mut notification = Notification::new(title, content, priority, metadata);
-        self.repository.save(&mut notification).await?;
+        if let Some(contacts) = &self.contacts {
+            match contacts.resolve_sender(&notification).await {
+                Ok(contact) => notification.metadata.contact = contact.map(|c| c.as_sender()),
+                Err(e) => warn!("Failed to resolve the sender of a notification: {}", e),
+            }
+        }
+        let coalesced = match &self.coalescer {
+            Some(coalescer) => {
+                let scorer = self.scorer.clone();
+                coalescer
+                    .coalesce(notification, move |stored| {
+                        if let Some(scorer) = &scorer {
+                            scorer.apply(stored, None);
+                        }
+                    })
+                    .await?
+            }
+            None => Coalesced::Skipped(notification),
+        };
+        let notification = match coalesced {
+            Coalesced::New(new) => new,
+            Coalesced::Skipped(mut notification) => {
+                if let Some(scorer) = &self.scorer {
+                    scorer.apply(&mut notification, None);
+                }
+                self.repository.save(&mut notification).await?;
+                notification
+            }
+            // The aggregate was already processed when it was created
+            Coalesced::Merged(aggregate) => {
+                let count = aggregate
+                    .metadata
+                    .aggregate
+                    .as_ref()
+                    .map_or(1, |aggregate| aggregate.count);
+                let held = self.hold_for_dnd(&aggregate).await;
+                self.publish(NotificationEvent::notification_coalesced(
+                    aggregate.id,
+                    count,
+                    held,
+                ))
+                .await;
+                return Ok(aggregate);
+            }
+        };
+        let held = self.hold_for_dnd(&notification).await;
+        self.publish(NotificationEvent::notification_created(&notification, held))
+            .await;
+        if let Err(e) = self.stop_waiting(&notification).await {
+            warn!("Failed to clear the waiting tag of the thread: {}", e);
+        }
 
         // Submit background job for processing
         let job = Job::new(
@@ -141,16 +464,24 @@ impl NotificationService for DefaultNotificationService {
         self.repository.find_by_source(source).await
     }
 
+    async fn get_notifications_by_tag(&self, tag: &str) -> DomainResult<Vec<Notification>> {
+        self.repository.find_by_tag(tag).await
+    }
+
     async fn mark_as_read(&self, id: Uuid) -> DomainResult<()> {
         let mut notification = self.get_notification(id).await?;
         notification.mark_as_read();
-        self.repository.save(&mut notification).await
+        self.repository.save(&mut notification).await?;
+        self.publish(NotificationEvent::notification_read(id)).await;
+        Ok(())
     }
 
     async fn mark_action_required(&self, id: Uuid) -> DomainResult<()> {
         let mut notification = self.get_notification(id).await?;
         notification.mark_action_required();
         self.repository.save(&mut notification).await?;
+        self.publish(NotificationEvent::notification_action_required(id))
+            .await;
 
         // Submit job for response generation
         let job = Job::new(
@@ -174,51 +505,103 @@ impl NotificationService for DefaultNotificationService {
     async fn mark_action_taken(&self, id: Uuid) -> DomainResult<()> {
         let mut notification = self.get_notification(id).await?;
         notification.mark_action_taken();
-        self.repository.save(&mut notification).await
+        self.repository.save(&mut notification).await?;
+        self.publish(NotificationEvent::notification_action_taken(id))
+            .await;
+        Ok(())
     }
 
     async fn archive_notification(&self, id: Uuid) -> DomainResult<()> {
         let mut notification = self.get_notification(id).await?;
         notification.archive();
-        self.repository.save(&mut notification).await
+        self.repository.save(&mut notification).await?;
+        self.publish(NotificationEvent::notification_archived(id))
+            .await;
+        Ok(())
     }
 
     async fn delete_notification(&self, id: Uuid) -> DomainResult<()> {
-        self.repository.delete(id).await
+        self.repository.delete(id).await?;
+        self.publish(NotificationEvent::notification_deleted(id))
+            .await;
+        Ok(())
+    }
+
+    async fn snooze_notification(&self, id: Uuid, until: DateTime<Utc>) -> DomainResult<()> {
+        if until <= Utc::now() {
+            return Err(DomainError::ValidationError(
+                "Snooze time must be in the future".to_string(),
+            ));
+        }
+
+        let mut notification = self.get_notification(id).await?;
+        if !matches!(
+            notification.status,
+            NotificationStatus::New | NotificationStatus::Read
+        ) {
+            return Err(DomainError::ValidationError(format!(
+                "Only new or read notifications can be snoozed, notification {} is {:?}",
+                id, notification.status
+            )));
+        }
+        notification.snooze(until);
+        self.repository.save(&mut notification).await?;
+        self.publish(NotificationEvent::notification_snoozed(id, until))
+            .await;
+        Ok(())
+    }
+
+    async fn unsnooze_notification(&self, id: Uuid) -> DomainResult<()> {
+        let mut notification = self.get_notification(id).await?;
+        if notification.status != NotificationStatus::Snoozed {
+            return Err(DomainError::InvalidOperation(format!(
+                "Notification {} is not snoozed",
+                id
+            )));
+        }
+
+        notification.unsnooze();
+        self.repository.save(&mut notification).await?;
+        self.publish(NotificationEvent::notification_unsnoozed(
+            id,
+            notification.status.clone(),
+        ))
+        .await;
+        Ok(())
     }
 
     async fn analyze_notification_content(
         &self,
         notification: &Notification,
-    ) -> DomainResult<bool> {
-        // Check if service bridge can handle this notification
-        if let Some(bridge) = &self.service_bridge {
-            if let Ok(()) = bridge.process_notification(notification).await {
-                return Ok(true);
-            }
-        }
-
-        // Fall back to AI analysis
-        let analysis = self
-            .ai_service
-            .analyze_content(&notification.content)
-            .await?;
-        Ok(analysis.requires_action)
+    ) -> DomainResult<AIAnalysis> {
+        let content = format!(
+            "{}{}",
+            notification.text(),
+            attachments_context(notification)
+        );
+        self.ai_service.analyze_content(&content).await
     }
 
     async fn generate_response(&self, notification: &Notification) -> DomainResult<String> {
-        // Generate response using AI service
-        let context = format!(
-            "Source: {}\nTitle: {}\nContent: {}\n",
-            notification.metadata.source, notification.title, notification.content
-        );
+        let context = self.response_context(notification).await;
+        self.ai_service
+            .generate_conversation_response(&notification.thread_key(), &context)
+            .await
+    }
 
-        self.ai_service.generate_response(&context).await
+    async fn generate_response_stream(
+        &self,
+        notification: &Notification,
+    ) -> DomainResult<ResponseStream> {
+        let context = self.response_context(notification).await;
+        self.ai_service
+            .generate_conversation_response_stream(&notification.thread_key(), &context)
+            .await
     }
 
     async fn execute_action(&self, notification: &Notification) -> DomainResult<()> {
         // Try service-specific action first if service bridge is available
-        if let Some(bridge) = &self.service_bridge {
+        if let Some(bridge) = self.service_bridge() {
             if let Ok(()) = bridge
                 .execute_action(notification, "default", serde_json::json!({}))
                 .await
@@ -230,6 +613,30 @@ impl NotificationService for DefaultNotificationService {
         // Fall back to default action executor
         self.action_executor.execute(notification).await
     }
+
+    async fn send_response(&self, notification: &Notification, response: &str) -> DomainResult<()> {
+        self.require_service_bridge()?
+            .send_response(notification, response)
+            .await?;
+        if let Err(e) = self.mark_waiting(notification.id).await {
+            warn!("Failed to tag {} as waiting: {}", notification.id, e);
+        }
+        Ok(())
+    }
+
+    async fn forward_notification(
+        &self,
+        notification: &Notification,
+        recipient: &str,
+    ) -> DomainResult<()> {
+        self.forwarder
+            .as_ref()
+            .ok_or_else(|| {
+                DomainError::ConfigurationError("No forward channel is configured".to_string())
+            })?
+            .forward(notification, recipient)
+            .await
+    }
 }
 
 pub type DynNotificationService = Arc<dyn NotificationService>;
@@ -237,20 +644,23 @@ pub type DynNotificationService = Arc<dyn NotificationService>;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::Contact;
     use crate::domain::entities::NotificationSource;
-    use crate::domain::events::NoopEventPublisher;
-    use crate::domain::repositories::NotificationRepository;
+    use crate::domain::entities::{Note, NoteTarget, SavedView};
+    use crate::domain::events::{DomainEvent, EventBus, NoopEventPublisher};
+    use crate::domain::repositories::{CoalescingMerge, NoteRepository, NotificationRepository};
     use crate::domain::services::actions::executor::MockActionExecutor;
     use crate::domain::services::actions::ActionExecutor;
-    use crate::domain::services::ai::{AIAnalysis, MockAIService, PriorityLevel};
+    use crate::domain::services::ai::{MockAIService, PriorityLevel};
     use crate::domain::services::background::{
         manager::BackgroundJobManagerTrait, NotificationProcessor,
     };
+    use crate::domain::services::coalescing::{CoalescingConfig, NotificationCoalescer};
+    use crate::domain::services::contacts::MockContactService;
     use crate::domain::services::BackgroundJobManager;
+    use crate::infrastructure::repositories::{SqliteNoteRepository, SqliteNotificationRepository};
     use async_trait::async_trait;
     use mockall::mock;
-    use std::collections::HashMap;
-    use std::sync::Mutex;
 
     mock! {
         #[derive(Debug)]
@@ -263,84 +673,21 @@ mod tests {
             async fn find_all(&self) -> DomainResult<Vec<Notification>>;
             async fn find_by_status(&self, status: NotificationStatus) -> DomainResult<Vec<Notification>>;
             async fn find_by_source(&self, source: NotificationSource) -> DomainResult<Vec<Notification>>;
+            async fn find_by_thread_key(&self, thread_key: &str) -> DomainResult<Vec<Notification>>;
+            async fn find_by_coalescing_key(&self, key: &str) -> DomainResult<Vec<Notification>>;
+            async fn save_coalesced(&self, key: &str, merge: CoalescingMerge) -> DomainResult<Notification>;
+            async fn find_by_tag(&self, tag: &str) -> DomainResult<Vec<Notification>>;
+            async fn count_by_tag(&self) -> DomainResult<Vec<(String, usize)>>;
+            async fn find_in_view(&self, view: &SavedView) -> DomainResult<Vec<Notification>>;
+            async fn count_in_view(&self, view: &SavedView) -> DomainResult<(usize, usize)>;
             async fn delete(&self, id: Uuid) -> DomainResult<()>;
             async fn update_status(&self, id: Uuid, status: NotificationStatus) -> DomainResult<()>;
         }
     }
 
-    #[derive(Debug)]
-    struct TestRepository {
-        notifications: Mutex<HashMap<Uuid, Notification>>,
-    }
-
-    #[async_trait]
-    impl NotificationRepository for TestRepository {
-        async fn save(&self, notification: &mut Notification) -> DomainResult<()> {
-            let mut notifications = self.notifications.lock().unwrap();
-            notifications.insert(notification.id, notification.clone());
-            Ok(())
-        }
-
-        async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Notification>> {
-            let notifications = self.notifications.lock().unwrap();
-            Ok(notifications.get(&id).cloned())
-        }
-
-        async fn find_all(&self) -> DomainResult<Vec<Notification>> {
-            let notifications = self.notifications.lock().unwrap();
-            Ok(notifications.values().cloned().collect())
-        }
-
-        async fn find_by_status(
-            &self,
-            status: NotificationStatus,
-        ) -> DomainResult<Vec<Notification>> {
-            let notifications = self.notifications.lock().unwrap();
-            Ok(notifications
-                .values()
-                .filter(|n| matches!(&n.status, s if *s == status))
-                .cloned()
-                .collect())
-        }
-
-        async fn find_by_source(
-            &self,
-            source: NotificationSource,
-        ) -> DomainResult<Vec<Notification>> {
-            let notifications = self.notifications.lock().unwrap();
-            Ok(notifications
-                .values()
-                .filter(|n| matches!(&n.metadata.source, s if *s == source))
-                .cloned()
-                .collect())
-        }
-
-        async fn delete(&self, id: Uuid) -> DomainResult<()> {
-            let mut notifications = self.notifications.lock().unwrap();
-            notifications.remove(&id);
-            Ok(())
-        }
-
-        async fn update_status(&self, id: Uuid, status: NotificationStatus) -> DomainResult<()> {
-            let mut notifications = self.notifications.lock().unwrap();
-            if let Some(notification) = notifications.get_mut(&id) {
-                notification.status = status;
-                notification.updated_at = chrono::Utc::now();
-                Ok(())
-            } else {
-                Err(DomainError::NotFoundError(format!(
-                    "Notification with id {} not found",
-                    id
-                )))
-            }
-        }
-    }
-
     #[tokio::test]
     async fn test_notification_lifecycle() {
-        let repository = Arc::new(TestRepository {
-            notifications: Mutex::new(HashMap::new()),
-        });
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
         let notification_service = Arc::new(MockNotificationService::new());
         let job_manager = Arc::new(BackgroundJobManager::new());
 
@@ -352,12 +699,14 @@ mod tests {
         ));
         job_manager.register_handler(processor).await.unwrap();
 
+        let bus = Arc::new(EventBus::new());
         let service = DefaultNotificationService::new(
             repository,
             job_manager,
             Arc::new(ActionExecutor::new()),
             Arc::new(MockAIService::new()),
-        );
+        )
+        .with_event_publisher(bus.clone());
 
         // Create a notification
         let metadata = NotificationMetadata {
@@ -366,6 +715,7 @@ mod tests {
             url: Some("https://example.com".to_string()),
             tags: vec!["test".to_string()],
             custom_data: None,
+            ..Default::default()
         };
 
         let notification = service
@@ -404,13 +754,244 @@ mod tests {
         service.delete_notification(notification.id).await.unwrap();
         let result = service.get_notification(notification.id).await;
         assert!(result.is_err());
+
+        let event_types: Vec<&str> = bus
+            .events_since(0)
+            .await
+            .iter()
+            .map(|envelope| envelope.event.event_type())
+            .collect();
+        assert_eq!(
+            event_types,
+            vec![
+                "NotificationCreated",
+                "NotificationRead",
+                "NotificationActionRequired",
+                "NotificationActionTaken",
+                "NotificationArchived",
+                "NotificationDeleted",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_notification_coalesces_bursts() {
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let job_manager = Arc::new(BackgroundJobManager::new());
+        let processor = Arc::new(NotificationProcessor::new(
+            Arc::new(MockNotificationService::new()),
+            repository.clone(),
+            Arc::new(NoopEventPublisher),
+        ));
+        job_manager.register_handler(processor).await.unwrap();
+
+        let bus = Arc::new(EventBus::new());
+        let service = DefaultNotificationService::new(
+            repository.clone(),
+            job_manager,
+            Arc::new(ActionExecutor::new()),
+            Arc::new(MockAIService::new()),
+        )
+        .with_event_publisher(bus.clone())
+        .with_coalescer(Arc::new(NotificationCoalescer::new(
+            repository.clone(),
+            CoalescingConfig::default(),
+        )));
+
+        let mut ids = Vec::new();
+        for commit in ["a1", "b2", "c3"] {
+            let notification = service
+                .create_notification(
+                    "GitHub: PushEvent".to_string(),
+                    format!("Pushed {}", commit),
+                    NotificationPriority::Medium,
+                    NotificationMetadata {
+                        source: NotificationSource::Github,
+                        external_id: Some(commit.to_string()),
+                        url: None,
+                        tags: vec!["PushEvent".to_string()],
+                        custom_data: Some(json!({ "repository": { "full_name": "octo/repo" } })),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            ids.push(notification.id);
+        }
+
+        // Every push lands on the same notification
+        assert!(ids.iter().all(|id| *id == ids[0]));
+        let all = service.get_all_notifications().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].title, "GitHub: PushEvent (3 events)");
+        assert_eq!(all[0].content, "Pushed c3");
+        assert_eq!(all[0].metadata.aggregate.as_ref().unwrap().count, 3);
+
+        let event_types: Vec<&str> = bus
+            .events_since(0)
+            .await
+            .iter()
+            .map(|envelope| envelope.event.event_type())
+            .collect();
+        assert_eq!(
+            event_types,
+            vec![
+                "NotificationCreated",
+                "NotificationCoalesced",
+                "NotificationCoalesced"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notifications_created_during_dnd_are_held() {
+        use crate::domain::services::do_not_disturb::DoNotDisturb;
+        use crate::infrastructure::repositories::SqliteDndRepository;
+
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let dnd = Arc::new(DoNotDisturb::new(Arc::new(
+            SqliteDndRepository::new(":memory:").unwrap(),
+        )));
+        dnd.set_manual(true, None).await.unwrap();
+        let job_manager = Arc::new(BackgroundJobManager::new());
+        let processor = Arc::new(NotificationProcessor::new(
+            Arc::new(MockNotificationService::new()),
+            repository.clone(),
+            Arc::new(NoopEventPublisher),
+        ));
+        job_manager.register_handler(processor).await.unwrap();
+
+        let bus = Arc::new(EventBus::new());
+        let service = DefaultNotificationService::new(
+            repository.clone(),
+            job_manager,
+            Arc::new(ActionExecutor::new()),
+            Arc::new(MockAIService::new()),
+        )
+        .with_event_publisher(bus.clone())
+        .with_do_not_disturb(dnd.clone())
+        .with_coalescer(Arc::new(NotificationCoalescer::new(
+            repository.clone(),
+            CoalescingConfig::default(),
+        )));
+
+        for commit in ["a1", "b2"] {
+            service
+                .create_notification(
+                    "GitHub: PushEvent".to_string(),
+                    format!("Pushed {}", commit),
+                    NotificationPriority::Medium,
+                    NotificationMetadata {
+                        source: NotificationSource::Github,
+                        external_id: Some(commit.to_string()),
+                        url: None,
+                        tags: vec!["PushEvent".to_string()],
+                        custom_data: Some(json!({ "repository": { "full_name": "octo/repo" } })),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let events = bus.events_since(0).await;
+        assert!(matches!(
+            events
+                .iter()
+                .map(|e| &e.event)
+                .collect::<Vec<_>>()
+                .as_slice(),
+            [
+                DomainEvent::Notification(NotificationEvent::NotificationCreated {
+                    held: true,
+                    ..
+                }),
+                DomainEvent::Notification(NotificationEvent::NotificationCoalesced {
+                    held: true,
+                    ..
+                }),
+            ]
+        ));
+        assert_eq!(dnd.status(chrono::Utc::now()).await.unwrap().held_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_snooze_restores_previous_status() {
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let bus = Arc::new(EventBus::new());
+        let service = DefaultNotificationService::new(
+            repository.clone(),
+            Arc::new(BackgroundJobManager::new()),
+            Arc::new(ActionExecutor::new()),
+            Arc::new(MockAIService::new()),
+        )
+        .with_event_publisher(bus.clone());
+
+        let mut notification = Notification::new(
+            "Review".to_string(),
+            "Please review the proposal".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        notification.archive();
+        repository.save(&mut notification).await.unwrap();
+        let until = chrono::Utc::now() + chrono::Duration::hours(1);
+        assert!(matches!(
+            service.snooze_notification(notification.id, until).await,
+            Err(DomainError::ValidationError(_))
+        ));
+
+        notification.mark_as_read();
+        repository.save(&mut notification).await.unwrap();
+
+        let past = chrono::Utc::now() - chrono::Duration::minutes(5);
+        assert!(service
+            .snooze_notification(notification.id, past)
+            .await
+            .is_err());
+
+        service
+            .snooze_notification(notification.id, until)
+            .await
+            .unwrap();
+        let snoozed = service.get_notification(notification.id).await.unwrap();
+        assert_eq!(snoozed.status, NotificationStatus::Snoozed);
+        assert_eq!(snoozed.snoozed_until, Some(until));
+
+        service
+            .unsnooze_notification(notification.id)
+            .await
+            .unwrap();
+        let restored = service.get_notification(notification.id).await.unwrap();
+        assert_eq!(restored.status, NotificationStatus::Read);
+        assert!(restored.snoozed_until.is_none());
+        assert!(service
+            .unsnooze_notification(notification.id)
+            .await
+            .is_err());
+
+        let event_types: Vec<&str> = bus
+            .events_since(0)
+            .await
+            .iter()
+            .map(|envelope| envelope.event.event_type())
+            .collect();
+        assert_eq!(
+            event_types,
+            vec!["NotificationSnoozed", "NotificationUnsnoozed"]
+        );
     }
 
     #[tokio::test]
     async fn test_analyze_notification_content() {
-        let repository = Arc::new(TestRepository {
-            notifications: Mutex::new(HashMap::new()),
-        });
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
         let job_manager = Arc::new(BackgroundJobManager::new());
 
         let mut mock_ai = MockAIService::new();
@@ -420,6 +1001,7 @@ mod tests {
                 priority_level: PriorityLevel::High,
                 summary: "Test summary".to_string(),
                 suggested_actions: vec!["Action 1".to_string()],
+                ..Default::default()
             })
         });
 
@@ -439,6 +1021,7 @@ mod tests {
             url: None,
             tags: vec![],
             custom_data: None,
+            ..Default::default()
         };
 
         // Test with action keywords
@@ -448,11 +1031,12 @@ mod tests {
             NotificationPriority::High,
             metadata.clone(),
         );
-        let requires_action = service
+        let analysis = service
             .analyze_notification_content(&notification)
             .await
             .unwrap();
-        assert!(requires_action);
+        assert!(analysis.requires_action);
+        assert_eq!(analysis.priority_level, PriorityLevel::High);
 
         // Test without action keywords
         let notification = Notification::new(
@@ -461,25 +1045,146 @@ mod tests {
             NotificationPriority::Low,
             metadata,
         );
-        let requires_action = service
+        let analysis = service
             .analyze_notification_content(&notification)
             .await
             .unwrap();
-        assert!(!requires_action);
+        assert!(!analysis.requires_action);
+    }
+
+    #[tokio::test]
+    async fn test_sender_contact_is_resolved_and_given_to_the_ai() {
+        let mut contact = Contact::new("Grace Hopper".to_string(), vec![]);
+        contact.vip = true;
+        contact.notes = Some("Admiral, prefers short answers".to_string());
+        contact.preferences.tone = Some("formal".to_string());
+
+        let mut contacts = MockContactService::new();
+        let resolved = contact.clone();
+        contacts
+            .expect_resolve_sender()
+            .returning(move |_| Ok(Some(resolved.clone())));
+        contacts
+            .expect_get_contact()
+            .returning(move |_| Ok(contact.clone()));
+
+        let mut mock_ai = MockAIService::new();
+        mock_ai
+            .expect_generate_conversation_response()
+            .withf(|_, context| {
+                context.contains("From: Grace Hopper <grace@navy.example> (VIP)")
+                    && context.contains("About the sender: Admiral, prefers short answers")
+                    && context.contains("Preferred tone: formal")
+            })
+            .returning(|_, _| Ok("Aye".to_string()));
+
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let job_manager = Arc::new(BackgroundJobManager::new());
+        job_manager
+            .register_handler(Arc::new(NotificationProcessor::new(
+                Arc::new(MockNotificationService::new()),
+                repository.clone(),
+                Arc::new(NoopEventPublisher),
+            )))
+            .await
+            .unwrap();
+        let service = DefaultNotificationService::new(
+            repository,
+            job_manager,
+            Arc::new(ActionExecutor::new()),
+            Arc::new(mock_ai),
+        )
+        .with_contacts(Arc::new(contacts));
+
+        let notification = service
+            .create_notification(
+                "COBOL".to_string(),
+                "Can we talk?".to_string(),
+                NotificationPriority::Medium,
+                NotificationMetadata {
+                    source: NotificationSource::Email,
+                    external_id: None,
+                    url: None,
+                    tags: vec![],
+                    custom_data: Some(json!({ "from": "Grace <grace@navy.example>" })),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(notification.is_from_vip());
+        assert_eq!(
+            service.generate_response(&notification).await.unwrap(),
+            "Aye"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_response_includes_notes() {
+        let notes = Arc::new(SqliteNoteRepository::new(":memory:").unwrap());
+        let mut mock_ai = MockAIService::new();
+        mock_ai
+            .expect_generate_conversation_response()
+            .withf(|conversation, context| {
+                conversation == "outage"
+                    && context
+                        .contains("User notes:\n- Waiting on Alice\n- Relates to INC-42\n  Sev 2")
+            })
+            .returning(|_, _| Ok("Noted".to_string()));
+
+        let service = DefaultNotificationService::new(
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap()),
+            Arc::new(BackgroundJobManager::new()),
+            Arc::new(ActionExecutor::new()),
+            Arc::new(mock_ai),
+        )
+        .with_notes(notes.clone());
+
+        let notification = Notification::new(
+            "Re: outage".to_string(),
+            "Any update?".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                thread_key: Some("outage".to_string()),
+                ..Default::default()
+            },
+        );
+        let mut waiting = Note::new(
+            NoteTarget::Notification {
+                notification_id: notification.id,
+            },
+            "Waiting on Alice".to_string(),
+        );
+        let mut incident = Note::new(
+            NoteTarget::Thread {
+                thread_key: "outage".to_string(),
+            },
+            "Relates to INC-42\nSev 2".to_string(),
+        );
+        notes.save(&mut waiting).await.unwrap();
+        notes.save(&mut incident).await.unwrap();
+
+        assert_eq!(
+            service.generate_response(&notification).await.unwrap(),
+            "Noted"
+        );
     }
 
     #[tokio::test]
     async fn test_generate_response() {
-        let repository = Arc::new(TestRepository {
-            notifications: Mutex::new(HashMap::new()),
-        });
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
         let job_manager = Arc::new(BackgroundJobManager::new());
         let mut mock_ai = MockAIService::new();
 
         // Set up mock expectations for each source
         mock_ai
-            .expect_generate_response()
-            .returning(|context| {
+            .expect_generate_conversation_response()
+            .returning(|_, context| {
                 let response = match context {
                     s if s.contains("Source: Email") => "This is an email response",
                     s if s.contains("Source: Github") => "This is a GitHub response",
@@ -520,6 +1225,7 @@ mod tests {
                 url: None,
                 tags: vec![],
                 custom_data: None,
+                ..Default::default()
             };
 
             let notification = Notification::new(
@@ -550,9 +1256,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_execute_action() {
-        let repository = Arc::new(TestRepository {
-            notifications: Mutex::new(HashMap::new()),
-        });
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
         let job_manager = Arc::new(BackgroundJobManager::new());
         let service = DefaultNotificationService::new(
             repository,
@@ -567,6 +1271,7 @@ mod tests {
             url: None,
             tags: vec![],
             custom_data: None,
+            ..Default::default()
         };
 
         let notification = Notification::new(
@@ -580,4 +1285,173 @@ mod tests {
         let result = service.execute_action(&notification).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_forward_goes_through_the_forwarder() {
+        let service = DefaultNotificationService::new(
+            Arc::new(MockRepository::new()),
+            Arc::new(BackgroundJobManager::new()),
+            Arc::new(ActionExecutor::new()),
+            Arc::new(MockAIService::new()),
+        );
+        let notification = Notification::new(
+            "OPS-1 is down".to_string(),
+            "Prod is down".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Jira,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            service
+                .forward_notification(&notification, "oncall@example.com")
+                .await,
+            Err(DomainError::ConfigurationError(_))
+        ));
+
+        let mut forwarder = MockNotificationForwarder::new();
+        forwarder
+            .expect_forward()
+            .withf(|notification, recipient| {
+                notification.title == "OPS-1 is down" && recipient == "oncall@example.com"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = service.with_forwarder(Arc::new(forwarder));
+
+        service
+            .forward_notification(&notification, "oncall@example.com")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replies_wait_until_the_thread_answers() {
+        use crate::domain::entities::ServiceType;
+        use crate::domain::services::integrations::manager::IntegrationManager;
+        use crate::domain::services::{MockIntegrationService, MockNotificationService};
+
+        let mut jira = MockIntegrationService::new();
+        jira.expect_service_type().return_const(ServiceType::Jira);
+        jira.expect_send_response().returning(|_, _| Ok(()));
+        let integration_manager = Arc::new(IntegrationManager::new());
+        integration_manager.add_service(Arc::new(jira)).await;
+        let bridge = Arc::new(ServiceBridge::new(
+            integration_manager,
+            Arc::new(MockNotificationService::new()),
+        ));
+
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let job_manager = Arc::new(BackgroundJobManager::new());
+        let processor = Arc::new(NotificationProcessor::new(
+            Arc::new(MockNotificationService::new()),
+            repository.clone(),
+            Arc::new(NoopEventPublisher),
+        ));
+        job_manager.register_handler(processor).await.unwrap();
+        let service = DefaultNotificationService::new(
+            repository.clone(),
+            job_manager,
+            Arc::new(ActionExecutor::new()),
+            Arc::new(MockAIService::new()),
+        )
+        .with_service_bridge(Arc::downgrade(&bridge));
+        let metadata = NotificationMetadata {
+            source: NotificationSource::Jira,
+            external_id: None,
+            url: None,
+            tags: vec![],
+            custom_data: None,
+            thread_key: Some("jira:OPS-1".to_string()),
+            ..Default::default()
+        };
+
+        let asked = service
+            .create_notification(
+                "OPS-1 is down".to_string(),
+                "Can you have a look?".to_string(),
+                NotificationPriority::High,
+                metadata.clone(),
+            )
+            .await
+            .unwrap();
+        service
+            .send_response(&asked, "Looking into it")
+            .await
+            .unwrap();
+        let waiting = service.get_notification(asked.id).await.unwrap();
+        assert_eq!(waiting.metadata.tags, vec![WAITING_TAG.to_string()]);
+
+        // The answer ends the wait
+        service
+            .create_notification(
+                "Re: OPS-1 is down".to_string(),
+                "Fixed by restarting".to_string(),
+                NotificationPriority::Medium,
+                metadata,
+            )
+            .await
+            .unwrap();
+        let answered = service.get_notification(asked.id).await.unwrap();
+        assert!(answered.metadata.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_connected_service_is_not_auto_answered() {
+        use crate::domain::entities::ServiceType;
+        use crate::domain::services::integrations::manager::IntegrationManager;
+        use crate::domain::services::{MockIntegrationService, MockNotificationService};
+
+        let mut jira = MockIntegrationService::new();
+        jira.expect_service_type().return_const(ServiceType::Jira);
+        jira.expect_send_response().never();
+        let integration_manager = Arc::new(IntegrationManager::new());
+        integration_manager.add_service(Arc::new(jira)).await;
+        let mut bridged = MockNotificationService::new();
+        bridged.expect_generate_response().never();
+        bridged.expect_mark_action_taken().never();
+        let bridge = Arc::new(ServiceBridge::new(integration_manager, Arc::new(bridged)));
+
+        let mut mock_ai = MockAIService::new();
+        mock_ai.expect_analyze_content().times(1).returning(|_| {
+            Ok(AIAnalysis {
+                requires_action: true,
+                priority_level: PriorityLevel::High,
+                summary: "Prod is down".to_string(),
+                suggested_actions: vec![],
+                ..Default::default()
+            })
+        });
+        let service = DefaultNotificationService::new(
+            Arc::new(MockRepository::new()),
+            Arc::new(BackgroundJobManager::new()),
+            Arc::new(ActionExecutor::new()),
+            Arc::new(mock_ai),
+        )
+        .with_service_bridge(Arc::downgrade(&bridge));
+        let notification = Notification::new(
+            "OPS-1 is down".to_string(),
+            "Prod is down".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Jira,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+
+        let analysis = service
+            .analyze_notification_content(&notification)
+            .await
+            .unwrap();
+        assert!(analysis.requires_action);
+    }
 }
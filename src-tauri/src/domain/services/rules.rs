@@ -0,0 +1,219 @@
+use crate::domain::entities::{
+    rule::render_template, Notification, NotificationStatus, Rule, RuleAction,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A rule that matched during evaluation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuleMatch {
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub actions: Vec<RuleAction>,
+    /// Evaluation stopped after this rule
+    pub stopped_processing: bool,
+}
+
+/// Outcome of running the rules against a notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleEvaluation {
+    pub matched_rules: Vec<RuleMatch>,
+    /// The notification with the priority, tag and status actions applied
+    pub notification: Notification,
+    pub skip_ai: bool,
+    /// Rendered auto-responses to send
+    pub responses: Vec<String>,
+    /// Recipients to forward the notification to
+    pub forward_to: Vec<String>,
+}
+
+impl RuleEvaluation {
+    pub fn matched(&self) -> bool {
+        !self.matched_rules.is_empty()
+    }
+}
+
+/// Runs the enabled rules in order. Each rule sees the changes made by the
+/// rules before it, and a matching rule with `stop_processing` ends the run.
+pub fn evaluate_rules(rules: &[Rule], notification: &Notification) -> RuleEvaluation {
+    let mut rules: Vec<&Rule> = rules.iter().filter(|rule| rule.enabled).collect();
+    rules.sort_by_key(|rule| rule.position);
+
+    let mut evaluation = RuleEvaluation {
+        matched_rules: Vec::new(),
+        notification: notification.clone(),
+        skip_ai: false,
+        responses: Vec::new(),
+        forward_to: Vec::new(),
+    };
+
+    for rule in rules {
+        if !rule.matches(&evaluation.notification) {
+            continue;
+        }
+
+        for action in &rule.actions {
+            apply_action(&mut evaluation, action);
+        }
+        evaluation.matched_rules.push(RuleMatch {
+            rule_id: rule.id,
+            rule_name: rule.name.clone(),
+            actions: rule.actions.clone(),
+            stopped_processing: rule.stop_processing,
+        });
+
+        if rule.stop_processing {
+            break;
+        }
+    }
+
+    evaluation
+}
+
+fn apply_action(evaluation: &mut RuleEvaluation, action: &RuleAction) {
+    let notification = &mut evaluation.notification;
+    match action {
+        RuleAction::SetPriority { priority } => notification.priority = priority.clone(),
+        RuleAction::AddTags { tags } => {
+            for tag in tags {
+                if !notification.metadata.tags.contains(tag) {
+                    notification.metadata.tags.push(tag.clone());
+                }
+            }
+        }
+        RuleAction::SetStatus { status } => match status {
+            NotificationStatus::Read => notification.mark_as_read(),
+            NotificationStatus::Archived => notification.archive(),
+            NotificationStatus::ActionRequired => notification.mark_action_required(),
+            NotificationStatus::ActionTaken => notification.mark_action_taken(),
+            NotificationStatus::Deleted => notification.delete(),
+            // Rules are validated against snoozing, which needs a wake-up time
+            NotificationStatus::New | NotificationStatus::Snoozed => {
+                notification.status = NotificationStatus::New
+            }
+        },
+        RuleAction::SkipAi => evaluation.skip_ai = true,
+        RuleAction::AutoRespond { template } => evaluation
+            .responses
+            .push(render_template(template, notification)),
+        RuleAction::Forward { to } => evaluation.forward_to.push(to.clone()),
+    }
+    notification.updated_at = chrono::Utc::now();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        NotificationMetadata, NotificationPriority, NotificationSource, RuleCondition,
+    };
+
+    fn notification(title: &str) -> Notification {
+        Notification::new(
+            title.to_string(),
+            "Content".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn rule(position: i32, conditions: Vec<RuleCondition>, actions: Vec<RuleAction>) -> Rule {
+        let mut rule = Rule::new(format!("Rule {}", position), conditions, actions);
+        rule.position = position;
+        rule
+    }
+
+    #[test]
+    fn test_rules_run_in_order_and_see_earlier_changes() {
+        let tag_github = rule(
+            1,
+            vec![RuleCondition::Source {
+                source: NotificationSource::Github,
+            }],
+            vec![RuleAction::AddTags {
+                tags: vec!["code".to_string()],
+            }],
+        );
+        let archive_tagged = rule(
+            2,
+            vec![
+                RuleCondition::Tag {
+                    tag: "code".to_string(),
+                },
+                RuleCondition::Title {
+                    pattern: "dependabot".to_string(),
+                },
+            ],
+            vec![
+                RuleAction::SetStatus {
+                    status: NotificationStatus::Archived,
+                },
+                RuleAction::SkipAi,
+                RuleAction::AutoRespond {
+                    template: "Got {{title}}".to_string(),
+                },
+                RuleAction::Forward {
+                    to: "team@example.com".to_string(),
+                },
+            ],
+        );
+        let mut disabled = rule(
+            0,
+            vec![],
+            vec![RuleAction::SetPriority {
+                priority: NotificationPriority::Critical,
+            }],
+        );
+        disabled.enabled = false;
+
+        let evaluation = evaluate_rules(
+            &[archive_tagged.clone(), disabled, tag_github.clone()],
+            &notification("dependabot bump"),
+        );
+
+        assert_eq!(
+            evaluation
+                .matched_rules
+                .iter()
+                .map(|m| m.rule_id)
+                .collect::<Vec<_>>(),
+            vec![tag_github.id, archive_tagged.id]
+        );
+        assert_eq!(evaluation.notification.status, NotificationStatus::Archived);
+        assert_eq!(evaluation.notification.metadata.tags, vec!["code"]);
+        assert_eq!(
+            evaluation.notification.priority,
+            NotificationPriority::Medium
+        );
+        assert!(evaluation.skip_ai);
+        assert_eq!(evaluation.responses, vec!["Got dependabot bump"]);
+        assert_eq!(evaluation.forward_to, vec!["team@example.com"]);
+    }
+
+    #[test]
+    fn test_stop_processing_skips_later_rules() {
+        let mut first = rule(
+            1,
+            vec![],
+            vec![RuleAction::SetPriority {
+                priority: NotificationPriority::High,
+            }],
+        );
+        first.stop_processing = true;
+        let second = rule(2, vec![], vec![RuleAction::SkipAi]);
+
+        let evaluation = evaluate_rules(&[first, second], &notification("Anything"));
+
+        assert_eq!(evaluation.matched_rules.len(), 1);
+        assert!(evaluation.matched_rules[0].stopped_processing);
+        assert_eq!(evaluation.notification.priority, NotificationPriority::High);
+        assert!(!evaluation.skip_ai);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::domain::{
+    entities::{Notification, SavedView, SavedViewSummary},
+    error::{DomainError, DomainResult},
+    repositories::{DynNotificationRepository, DynSavedViewRepository},
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SavedViewService: Send + Sync + std::fmt::Debug {
+    /// Every view with its current counts, pinned views first
+    async fn get_views(&self) -> DomainResult<Vec<SavedViewSummary>>;
+    async fn get_view(&self, id: Uuid) -> DomainResult<SavedView>;
+    async fn save_view(&self, view: SavedView) -> DomainResult<SavedView>;
+    /// Built-in views cannot be deleted
+    async fn delete_view(&self, id: Uuid) -> DomainResult<()>;
+    /// The notifications the view shows, in its sort order
+    async fn get_view_notifications(&self, id: Uuid) -> DomainResult<Vec<Notification>>;
+}
+
+pub type DynSavedViewService = Arc<dyn SavedViewService>;
+
+pub struct DefaultSavedViewService {
+    repository: DynSavedViewRepository,
+    notification_repository: DynNotificationRepository,
+}
+
+impl std::fmt::Debug for DefaultSavedViewService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultSavedViewService")
+            .field("repository", &"Arc<dyn SavedViewRepository>")
+            .field("notification_repository", &self.notification_repository)
+            .finish()
+    }
+}
+
+impl DefaultSavedViewService {
+    pub fn new(
+        repository: DynSavedViewRepository,
+        notification_repository: DynNotificationRepository,
+    ) -> Self {
+        Self {
+            repository,
+            notification_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl SavedViewService for DefaultSavedViewService {
+    async fn get_views(&self) -> DomainResult<Vec<SavedViewSummary>> {
+        let mut summaries = Vec::new();
+        for view in self.repository.find_all().await? {
+            let (total_count, unread_count) =
+                self.notification_repository.count_in_view(&view).await?;
+            summaries.push(SavedViewSummary {
+                view,
+                total_count,
+                unread_count,
+            });
+        }
+        Ok(summaries)
+    }
+
+    async fn get_view(&self, id: Uuid) -> DomainResult<SavedView> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFoundError(format!("View {} not found", id)))
+    }
+
+    async fn save_view(&self, mut view: SavedView) -> DomainResult<SavedView> {
+        view.name = view.name.trim().to_string();
+        view.query = view
+            .query
+            .map(|query| query.trim().to_string())
+            .filter(|query| !query.is_empty());
+        view.validate()?;
+
+        match self.repository.find_by_id(view.id).await? {
+            Some(existing) => {
+                view.created_at = existing.created_at;
+                view.built_in = existing.built_in;
+            }
+            None => view.built_in = false,
+        }
+
+        self.repository.save(&mut view).await?;
+        Ok(view)
+    }
+
+    async fn delete_view(&self, id: Uuid) -> DomainResult<()> {
+        if self.get_view(id).await?.built_in {
+            return Err(DomainError::ValidationError(
+                "Built-in views cannot be deleted".to_string(),
+            ));
+        }
+        self.repository.delete(id).await
+    }
+
+    async fn get_view_notifications(&self, id: Uuid) -> DomainResult<Vec<Notification>> {
+        let view = self.get_view(id).await?;
+        let mut notifications = self.notification_repository.find_in_view(&view).await?;
+        view.sort.sort(&mut notifications);
+        Ok(notifications)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Note, NoteTarget, NotificationMetadata, NotificationPriority, NotificationSource,
+        NotificationStatus, RuleCondition, NEEDS_REPLY_VIEW_ID, WAITING_ON_OTHERS_VIEW_ID,
+        WAITING_TAG,
+    };
+    use crate::domain::repositories::DynNoteRepository;
+    use crate::infrastructure::repositories::{
+        SqliteNoteRepository, SqliteNotificationRepository, SqliteSavedViewRepository,
+    };
+
+    async fn notification(
+        repository: &DynNotificationRepository,
+        title: &str,
+        source: NotificationSource,
+        tags: &[&str],
+        status: NotificationStatus,
+    ) -> Notification {
+        let mut notification = Notification::new(
+            title.to_string(),
+            "Details".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source,
+                external_id: None,
+                url: None,
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        notification.status = status;
+        repository.save(&mut notification).await.unwrap();
+        notification
+    }
+
+    #[tokio::test]
+    async fn test_views_count_and_list_matching_notifications() {
+        // Notes are searched where they share the database with the notifications
+        let db = tempfile::NamedTempFile::new().unwrap();
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(db.path()).unwrap());
+        let notes: DynNoteRepository = Arc::new(SqliteNoteRepository::new(db.path()).unwrap());
+        let service = DefaultSavedViewService::new(
+            Arc::new(SqliteSavedViewRepository::new(db.path()).unwrap()),
+            notifications.clone(),
+        );
+
+        let review = notification(
+            &notifications,
+            "Review the parser",
+            NotificationSource::Github,
+            &["review"],
+            NotificationStatus::New,
+        )
+        .await;
+        notification(
+            &notifications,
+            "Review the lexer",
+            NotificationSource::Github,
+            &["review"],
+            NotificationStatus::Read,
+        )
+        .await;
+        let asked = notification(
+            &notifications,
+            "Can you answer?",
+            NotificationSource::Email,
+            &[WAITING_TAG],
+            NotificationStatus::ActionRequired,
+        )
+        .await;
+
+        let mut github = SavedView::new(" GitHub reviews ".to_string());
+        github.conditions = vec![
+            RuleCondition::Source {
+                source: NotificationSource::Github,
+            },
+            RuleCondition::Tag {
+                tag: "review".to_string(),
+            },
+        ];
+        let github = service.save_view(github).await.unwrap();
+        assert_eq!(github.name, "GitHub reviews");
+
+        let counts: Vec<(String, usize, usize)> = service
+            .get_views()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.view.name, s.total_count, s.unread_count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("Needs reply".to_string(), 1, 0),
+                ("Waiting on others".to_string(), 1, 0),
+                ("Snoozed".to_string(), 0, 0),
+                ("GitHub reviews".to_string(), 2, 1),
+            ]
+        );
+
+        // Each word of the query matches the notification text or its notes
+        let mut searched = github.clone();
+        searched.query = Some("parser INC-42".to_string());
+        let mut searched = service.save_view(searched).await.unwrap();
+        assert!(service
+            .get_view_notifications(searched.id)
+            .await
+            .unwrap()
+            .is_empty());
+        let mut note = Note::new(
+            NoteTarget::Notification {
+                notification_id: review.id,
+            },
+            "Relates to INC-42".to_string(),
+        );
+        notes.save(&mut note).await.unwrap();
+        let ids: Vec<Uuid> = service
+            .get_view_notifications(searched.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(ids, vec![review.id]);
+
+        searched.query = None;
+        searched.conditions = vec![RuleCondition::Title {
+            pattern: "(?i)^review the (lexer|parser)$".to_string(),
+        }];
+        let searched = service.save_view(searched).await.unwrap();
+        let summary = service
+            .get_views()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|s| s.view.id == searched.id)
+            .unwrap();
+        assert_eq!((summary.total_count, summary.unread_count), (2, 1));
+
+        let waiting = service
+            .get_view_notifications(WAITING_ON_OTHERS_VIEW_ID)
+            .await
+            .unwrap();
+        assert_eq!(waiting[0].id, asked.id);
+
+        assert!(service.delete_view(NEEDS_REPLY_VIEW_ID).await.is_err());
+        service.delete_view(github.id).await.unwrap();
+        assert_eq!(service.get_views().await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,461 @@
+use crate::domain::{
+    entities::{
+        Notification, NotificationPriority, NotificationStatus, PriorityScore, ScoreFactor,
+        ScoreFactorKind,
+    },
+    error::{DomainError, DomainResult},
+    repositories::{
+        load_settings, store_settings, DynNotificationRepository, DynSettingsRepository,
+    },
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// What the priority score is made of and how much each part weighs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoringConfig {
+    /// Points per priority level given by the source, Low counting as one level
+    pub source_priority_points: f64,
+    /// Points per priority level above Low suggested by the AI
+    pub ai_priority_points: f64,
+    pub vip_points: f64,
+    pub mention_points: f64,
+    /// Points per keyword found, up to `max_keywords` of them
+    pub keyword_points: f64,
+    pub max_keywords: usize,
+    /// Points per hour an open notification has been waiting, up to `max_age_points`
+    pub age_points_per_hour: f64,
+    pub max_age_points: f64,
+    /// Points for a deadline that is due within a day or already passed.
+    /// Deadlines within three days get half of them, within a week a quarter.
+    pub deadline_points: f64,
+    /// How the user is mentioned, e.g. "@octocat". Matched as whole words
+    /// ignoring case.
+    pub mentions: Vec<String>,
+    /// Words raising the score, matched as whole words ignoring case
+    pub keywords: Vec<String>,
+    /// Dotted paths into the custom data holding a due date
+    pub deadline_paths: Vec<String>,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            source_priority_points: 10.0,
+            ai_priority_points: 8.0,
+            vip_points: 15.0,
+            mention_points: 15.0,
+            keyword_points: 5.0,
+            max_keywords: 3,
+            age_points_per_hour: 0.5,
+            max_age_points: 10.0,
+            deadline_points: 20.0,
+            mentions: Vec::new(),
+            keywords: ["urgent", "asap", "blocker", "outage", "deadline", "overdue"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            deadline_paths: vec![
+                "deadline".to_string(),
+                "due_date".to_string(),
+                "dueDate".to_string(),
+                "fields.duedate".to_string(),
+                "dueDateTime.dateTime".to_string(),
+            ],
+        }
+    }
+}
+
+impl ScoringConfig {
+    pub fn validate(&self) -> DomainResult<()> {
+        let points = [
+            self.source_priority_points,
+            self.ai_priority_points,
+            self.vip_points,
+            self.mention_points,
+            self.keyword_points,
+            self.age_points_per_hour,
+            self.max_age_points,
+            self.deadline_points,
+        ];
+        if points
+            .iter()
+            .any(|points| !points.is_finite() || *points < 0.0)
+        {
+            return Err(DomainError::ValidationError(
+                "Points cannot be negative".to_string(),
+            ));
+        }
+        if self
+            .mentions
+            .iter()
+            .chain(&self.keywords)
+            .chain(&self.deadline_paths)
+            .any(|word| word.trim().is_empty())
+        {
+            return Err(DomainError::ValidationError(
+                "Mentions, keywords and deadline paths cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `word` appears in `text` with no letter, digit or underscore
+/// right before or after it. `text` is expected in lowercase.
+fn contains_word(text: &str, word: &str) -> bool {
+    let word = word.to_lowercase();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(&word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+const SETTINGS_KEY: &str = "scoring";
+
+/// Ranks notifications by combining their priority, the AI analysis, the
+/// sender and their content into a single score.
+pub struct PriorityScorer {
+    repository: DynNotificationRepository,
+    config: RwLock<ScoringConfig>,
+    settings: Option<DynSettingsRepository>,
+}
+
+pub type DynPriorityScorer = Arc<PriorityScorer>;
+
+impl std::fmt::Debug for PriorityScorer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PriorityScorer")
+            .field("config", &self.config())
+            .field("settings", &self.settings.is_some())
+            .finish()
+    }
+}
+
+impl PriorityScorer {
+    pub fn new(repository: DynNotificationRepository, config: ScoringConfig) -> Self {
+        Self {
+            repository,
+            config: RwLock::new(config),
+            settings: None,
+        }
+    }
+
+    /// Uses the config saved in the settings, the defaults when nothing was
+    /// saved yet, and saves the changes there
+    pub async fn from_settings(
+        repository: DynNotificationRepository,
+        settings: DynSettingsRepository,
+    ) -> DomainResult<Self> {
+        let config = load_settings(settings.as_ref(), SETTINGS_KEY).await?;
+        Ok(Self {
+            settings: Some(settings),
+            ..Self::new(repository, config)
+        })
+    }
+
+    pub fn config(&self) -> ScoringConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Validates and saves the config, which applies to the next scores
+    pub async fn save_config(&self, config: ScoringConfig) -> DomainResult<ScoringConfig> {
+        config.validate()?;
+        if let Some(settings) = &self.settings {
+            store_settings(settings.as_ref(), SETTINGS_KEY, &config).await?;
+        }
+        *self.config.write().unwrap() = config.clone();
+        Ok(config)
+    }
+
+    /// Computes the score of the notification at `now`
+    pub fn score(
+        &self,
+        notification: &Notification,
+        ai_priority: Option<NotificationPriority>,
+        now: DateTime<Utc>,
+    ) -> PriorityScore {
+        let config = &self.config();
+        let mut factors = vec![ScoreFactor {
+            kind: ScoreFactorKind::SourcePriority,
+            points: (notification.priority.rank() + 1) as f64 * config.source_priority_points,
+            detail: format!("{:?} priority", notification.priority),
+        }];
+
+        if let Some(priority) = &ai_priority {
+            factors.push(ScoreFactor {
+                kind: ScoreFactorKind::AiPriority,
+                points: priority.rank() as f64 * config.ai_priority_points,
+                detail: format!("Rated {:?} by the AI", priority),
+            });
+        }
+
+        if let Some(contact) = notification.metadata.contact.as_ref().filter(|c| c.vip) {
+            factors.push(ScoreFactor {
+                kind: ScoreFactorKind::Vip,
+                points: config.vip_points,
+                detail: format!("From VIP {}", contact.name),
+            });
+        }
+
+        let text = format!("{}\n{}", notification.title, notification.text()).to_lowercase();
+        if let Some(mention) = config
+            .mentions
+            .iter()
+            .find(|mention| contains_word(&text, mention))
+        {
+            factors.push(ScoreFactor {
+                kind: ScoreFactorKind::Mention,
+                points: config.mention_points,
+                detail: format!("Mentions {}", mention),
+            });
+        }
+
+        let keywords: Vec<&str> = config
+            .keywords
+            .iter()
+            .filter(|keyword| contains_word(&text, keyword))
+            .take(config.max_keywords)
+            .map(String::as_str)
+            .collect();
+        if !keywords.is_empty() {
+            factors.push(ScoreFactor {
+                kind: ScoreFactorKind::Keyword,
+                points: keywords.len() as f64 * config.keyword_points,
+                detail: format!("Contains {}", keywords.join(", ")),
+            });
+        }
+
+        let waiting = now - notification.created_at;
+        if matches!(
+            notification.status,
+            NotificationStatus::New | NotificationStatus::ActionRequired
+        ) && waiting >= Duration::hours(1)
+        {
+            factors.push(ScoreFactor {
+                kind: ScoreFactorKind::Age,
+                points: (waiting.num_hours() as f64 * config.age_points_per_hour)
+                    .min(config.max_age_points),
+                detail: format!("Waiting for {}h", waiting.num_hours()),
+            });
+        }
+
+        if let Some(deadline) = Self::deadline(config, notification) {
+            let left = deadline - now;
+            let share = if left <= Duration::days(1) {
+                1.0
+            } else if left <= Duration::days(3) {
+                0.5
+            } else if left <= Duration::days(7) {
+                0.25
+            } else {
+                0.0
+            };
+            if share > 0.0 {
+                factors.push(ScoreFactor {
+                    kind: ScoreFactorKind::Deadline,
+                    points: share * config.deadline_points,
+                    detail: if left < Duration::zero() {
+                        format!("Overdue since {}", deadline.format("%Y-%m-%d %H:%M"))
+                    } else {
+                        format!("Due {}", deadline.format("%Y-%m-%d %H:%M"))
+                    },
+                });
+            }
+        }
+
+        PriorityScore::new(factors, ai_priority, now)
+    }
+
+    /// Scores the notification in place. Without a new AI priority the one
+    /// from the previous score is kept.
+    pub fn apply(
+        &self,
+        notification: &mut Notification,
+        ai_priority: Option<NotificationPriority>,
+    ) {
+        let ai_priority = ai_priority.or_else(|| {
+            notification
+                .metadata
+                .score
+                .as_ref()
+                .and_then(|score| score.ai_priority.clone())
+        });
+        notification.metadata.score = Some(self.score(notification, ai_priority, Utc::now()));
+    }
+
+    /// Rescores the open notifications, whose age and deadlines change over
+    /// time. Returns how many scores changed.
+    pub async fn refresh(&self) -> DomainResult<usize> {
+        let mut changed = 0;
+        for status in [NotificationStatus::New, NotificationStatus::ActionRequired] {
+            for mut notification in self.repository.find_by_status(status).await? {
+                let before = notification.metadata.score.as_ref().map(|s| s.score);
+                self.apply(&mut notification, None);
+                if before != notification.metadata.score.as_ref().map(|s| s.score) {
+                    self.repository.save(&mut notification).await?;
+                    changed += 1;
+                }
+            }
+        }
+        Ok(changed)
+    }
+
+    /// The first due date found in the custom data
+    fn deadline(config: &ScoringConfig, notification: &Notification) -> Option<DateTime<Utc>> {
+        let custom_data = notification.metadata.custom_data.as_ref()?;
+        config.deadline_paths.iter().find_map(|path| {
+            let value = path
+                .split('.')
+                .try_fold(custom_data, |value, key| value.get(key))?
+                .as_str()?;
+            DateTime::parse_from_rfc3339(value)
+                .map(|date| date.with_timezone(&Utc))
+                .ok()
+                .or_else(|| {
+                    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                        .ok()
+                        .map(|date| date.and_utc())
+                })
+                .or_else(|| {
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(23, 59, 59))
+                        .map(|date| date.and_utc())
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationMetadata, NotificationSource, SenderContact};
+    use crate::infrastructure::repositories::{
+        SqliteNotificationRepository, SqliteSettingsRepository,
+    };
+    use serde_json::json;
+
+    fn notification(title: &str, custom_data: serde_json::Value) -> Notification {
+        Notification::new(
+            title.to_string(),
+            "Could you take a look?".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Jira,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: Some(custom_data),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn points(score: &PriorityScore, kind: ScoreFactorKind) -> Option<f64> {
+        score
+            .factors
+            .iter()
+            .find(|factor| factor.kind == kind)
+            .map(|factor| factor.points)
+    }
+
+    #[tokio::test]
+    async fn test_score_combines_every_factor() {
+        let repository: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let scorer = PriorityScorer::new(
+            repository.clone(),
+            ScoringConfig {
+                mentions: vec!["@octocat".to_string()],
+                ..ScoringConfig::default()
+            },
+        );
+        let now = Utc::now();
+
+        let plain = notification("Weekly report", json!({}));
+        let score = scorer.score(&plain, None, now);
+        assert_eq!(score.score, 20.0);
+        assert_eq!(score.factors.len(), 1);
+
+        let mut urgent = notification(
+            "URGENT: @Octocat the release is blocked",
+            json!({ "fields": { "duedate": (now + Duration::hours(6)).to_rfc3339() } }),
+        );
+        urgent.metadata.contact = Some(SenderContact {
+            contact_id: uuid::Uuid::new_v4(),
+            name: "Mona".to_string(),
+            vip: true,
+            auto_reply: true,
+        });
+        urgent.created_at = now - Duration::hours(4);
+        let score = scorer.score(&urgent, Some(NotificationPriority::High), now);
+        assert_eq!(points(&score, ScoreFactorKind::AiPriority), Some(16.0));
+        assert_eq!(points(&score, ScoreFactorKind::Vip), Some(15.0));
+        assert_eq!(points(&score, ScoreFactorKind::Mention), Some(15.0));
+        assert_eq!(points(&score, ScoreFactorKind::Keyword), Some(5.0));
+        assert_eq!(points(&score, ScoreFactorKind::Age), Some(2.0));
+        assert_eq!(points(&score, ScoreFactorKind::Deadline), Some(20.0));
+        assert_eq!(score.score, 93.0);
+
+        // A date-only deadline a few days away counts for less
+        let later = notification(
+            "Renew the certificate",
+            json!({ "due_date": (now + Duration::days(2)).format("%Y-%m-%d").to_string() }),
+        );
+        let score = scorer.score(&later, None, now);
+        assert_eq!(points(&score, ScoreFactorKind::Deadline), Some(10.0));
+
+        // Refreshing keeps the AI priority and picks up the time spent waiting
+        let mut stored = notification("Deploy approval", json!({}));
+        scorer.apply(&mut stored, Some(NotificationPriority::Critical));
+        stored.created_at = now - Duration::hours(10);
+        repository.save(&mut stored).await.unwrap();
+        assert_eq!(scorer.refresh().await.unwrap(), 1);
+        let refreshed = repository.find_by_id(stored.id).await.unwrap().unwrap();
+        assert_eq!(refreshed.score(), 49.0);
+        let score = refreshed.metadata.score.unwrap();
+        assert_eq!(score.ai_priority, Some(NotificationPriority::Critical));
+        assert_eq!(points(&score, ScoreFactorKind::Age), Some(5.0));
+    }
+
+    #[tokio::test]
+    async fn test_mentions_and_keywords_match_whole_words() {
+        let repository: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let scorer = PriorityScorer::from_settings(
+            repository.clone(),
+            Arc::new(SqliteSettingsRepository::new(":memory:").unwrap()),
+        )
+        .await
+        .unwrap();
+        let now = Utc::now();
+
+        let partial = notification("Ping @octocats about the deadlines", json!({}));
+        assert!(scorer
+            .save_config(ScoringConfig {
+                keyword_points: -1.0,
+                ..ScoringConfig::default()
+            })
+            .await
+            .is_err());
+        scorer
+            .save_config(ScoringConfig {
+                mentions: vec!["@octocat".to_string()],
+                ..ScoringConfig::default()
+            })
+            .await
+            .unwrap();
+        let score = scorer.score(&partial, None, now);
+        assert_eq!(points(&score, ScoreFactorKind::Mention), None);
+        assert_eq!(points(&score, ScoreFactorKind::Keyword), None);
+
+        let whole = notification("Ping @octocat: deadline (urgent)", json!({}));
+        let score = scorer.score(&whole, None, now);
+        assert_eq!(points(&score, ScoreFactorKind::Mention), Some(15.0));
+        assert_eq!(points(&score, ScoreFactorKind::Keyword), Some(10.0));
+    }
+}
@@ -0,0 +1,176 @@
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{DomainError, DomainResult};
+
+/// Hour of the morning presets such as "tomorrow 9am"
+const MORNING_HOUR: u32 = 9;
+const EVENING_HOUR: u32 = 18;
+const LATER_TODAY_HOURS: i64 = 3;
+
+/// When a snoozed notification should come back
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SnoozeUntil {
+    At { time: DateTime<Utc> },
+    Preset { preset: SnoozePreset },
+}
+
+/// Relative snooze times, resolved in the user's timezone
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnoozePreset {
+    /// Three hours from now
+    LaterToday,
+    /// 6pm today, or tomorrow if that has already passed
+    ThisEvening,
+    /// 9am tomorrow
+    Tomorrow,
+    /// 9am on the coming Saturday
+    ThisWeekend,
+    /// 9am on the coming Monday
+    NextWeek,
+}
+
+impl SnoozeUntil {
+    /// Resolves to an absolute time, rejecting times that are not in the future
+    pub fn resolve(&self, now: DateTime<Utc>, timezone: Tz) -> DomainResult<DateTime<Utc>> {
+        let until = match self {
+            SnoozeUntil::At { time } => *time,
+            SnoozeUntil::Preset { preset } => preset.resolve(now, timezone),
+        };
+
+        if until <= now {
+            return Err(DomainError::ValidationError(
+                "Snooze time must be in the future".to_string(),
+            ));
+        }
+        Ok(until)
+    }
+}
+
+impl SnoozePreset {
+    pub fn resolve(&self, now: DateTime<Utc>, timezone: Tz) -> DateTime<Utc> {
+        let local_today = now.with_timezone(&timezone).date_naive();
+
+        let (days_ahead, hour) = match self {
+            SnoozePreset::LaterToday => return now + Duration::hours(LATER_TODAY_HOURS),
+            SnoozePreset::ThisEvening => {
+                let evening = local_time(timezone, local_today, EVENING_HOUR);
+                if evening > now {
+                    return evening;
+                }
+                (1, EVENING_HOUR)
+            }
+            SnoozePreset::Tomorrow => (1, MORNING_HOUR),
+            SnoozePreset::ThisWeekend => (
+                days_until(local_today.weekday(), Weekday::Sat),
+                MORNING_HOUR,
+            ),
+            SnoozePreset::NextWeek => (
+                days_until(local_today.weekday(), Weekday::Mon),
+                MORNING_HOUR,
+            ),
+        };
+
+        local_time(timezone, local_today + Duration::days(days_ahead), hour)
+    }
+}
+
+/// Parses an IANA timezone name such as "Europe/Paris"
+pub fn parse_timezone(name: &str) -> DomainResult<Tz> {
+    name.parse::<Tz>()
+        .map_err(|_| DomainError::ValidationError(format!("Unknown timezone: {}", name)))
+}
+
+/// Days until the next `target` weekday, always at least one
+fn days_until(from: Weekday, target: Weekday) -> i64 {
+    let days = (target.num_days_from_monday() + 7 - from.num_days_from_monday()) % 7;
+    if days == 0 {
+        7
+    } else {
+        days as i64
+    }
+}
+
+fn local_time(timezone: Tz, date: chrono::NaiveDate, hour: u32) -> DateTime<Utc> {
+    let naive = date.and_time(NaiveTime::from_hms_opt(hour, 0, 0).unwrap());
+    timezone
+        .from_local_datetime(&naive)
+        .earliest()
+        // The hour does not exist on a spring-forward day, use the next one
+        .or_else(|| {
+            timezone
+                .from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_presets_resolve_in_the_users_timezone() {
+        let paris = parse_timezone("Europe/Paris").unwrap();
+        // Friday 2025-01-10, 20:30 in Paris
+        let now = utc("2025-01-10T19:30:00Z");
+
+        assert_eq!(
+            SnoozePreset::Tomorrow.resolve(now, paris),
+            utc("2025-01-11T08:00:00Z")
+        );
+        assert_eq!(
+            SnoozePreset::ThisEvening.resolve(now, paris),
+            utc("2025-01-11T17:00:00Z")
+        );
+        assert_eq!(
+            SnoozePreset::ThisWeekend.resolve(now, paris),
+            utc("2025-01-11T08:00:00Z")
+        );
+        assert_eq!(
+            SnoozePreset::NextWeek.resolve(now, paris),
+            utc("2025-01-13T08:00:00Z")
+        );
+        assert_eq!(
+            SnoozePreset::LaterToday.resolve(now, paris),
+            utc("2025-01-10T22:30:00Z")
+        );
+    }
+
+    #[test]
+    fn test_next_week_from_monday_is_a_week_away() {
+        let tokyo = parse_timezone("Asia/Tokyo").unwrap();
+        // Monday 2025-01-13, 10:00 in Tokyo
+        let now = utc("2025-01-13T01:00:00Z");
+        assert_eq!(
+            SnoozePreset::NextWeek.resolve(now, tokyo),
+            utc("2025-01-20T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_absolute_time_must_be_in_the_future() {
+        let now = Utc::now();
+        let past = SnoozeUntil::At {
+            time: now - Duration::minutes(1),
+        };
+        assert!(past.resolve(now, Tz::UTC).is_err());
+
+        let future = SnoozeUntil::At {
+            time: now + Duration::hours(1),
+        };
+        assert_eq!(
+            future.resolve(now, Tz::UTC).unwrap(),
+            now + Duration::hours(1)
+        );
+        assert!(parse_timezone("Mars/Olympus").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::domain::{
+    entities::{NotificationStatistics, TimeBucket},
+    error::{DomainError, DomainResult},
+    repositories::DynStatisticsRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StatisticsService: Send + Sync + std::fmt::Debug {
+    /// Counts, response times and AI accuracy for the notifications received
+    /// in `[from, to)`
+    async fn get_statistics(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: TimeBucket,
+    ) -> DomainResult<NotificationStatistics>;
+}
+
+pub type DynStatisticsService = Arc<dyn StatisticsService>;
+
+pub struct DefaultStatisticsService {
+    repository: DynStatisticsRepository,
+}
+
+impl std::fmt::Debug for DefaultStatisticsService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultStatisticsService")
+            .field("repository", &"Arc<dyn StatisticsRepository>")
+            .finish()
+    }
+}
+
+impl DefaultStatisticsService {
+    pub fn new(repository: DynStatisticsRepository) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl StatisticsService for DefaultStatisticsService {
+    async fn get_statistics(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: TimeBucket,
+    ) -> DomainResult<NotificationStatistics> {
+        if from >= to {
+            return Err(DomainError::ValidationError(
+                "The start of the period must be before its end".to_string(),
+            ));
+        }
+        self.repository.get_statistics(from, to, bucket).await
+    }
+}
@@ -0,0 +1,293 @@
+use crate::domain::{
+    entities::{Tag, TagSummary},
+    error::{DomainError, DomainResult},
+    repositories::{DynNotificationRepository, DynTagRepository},
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TagService: Send + Sync + std::fmt::Debug {
+    /// Saved tags and tags in use, by name, with their notification count
+    async fn get_tags(&self) -> DomainResult<Vec<TagSummary>>;
+    /// Creates or updates the colour and description of a tag
+    async fn save_tag(&self, tag: Tag) -> DomainResult<Tag>;
+    /// Renames a tag on every notification. Fails when `new_name` is taken.
+    async fn rename_tag(&self, name: &str, new_name: &str) -> DomainResult<TagSummary>;
+    /// Replaces `source` by `target` on every notification, `target` keeps
+    /// its colour and description
+    async fn merge_tags(&self, source: &str, target: &str) -> DomainResult<TagSummary>;
+    /// Removes the tag from every notification, returns how many were changed
+    async fn delete_tag(&self, name: &str) -> DomainResult<usize>;
+}
+
+pub type DynTagService = Arc<dyn TagService>;
+
+pub struct DefaultTagService {
+    tag_repository: DynTagRepository,
+    notification_repository: DynNotificationRepository,
+}
+
+impl std::fmt::Debug for DefaultTagService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DefaultTagService")
+            .field("tag_repository", &"Arc<dyn TagRepository>")
+            .field("notification_repository", &self.notification_repository)
+            .finish()
+    }
+}
+
+impl DefaultTagService {
+    pub fn new(
+        tag_repository: DynTagRepository,
+        notification_repository: DynNotificationRepository,
+    ) -> Self {
+        Self {
+            tag_repository,
+            notification_repository,
+        }
+    }
+
+    async fn count(&self, name: &str) -> DomainResult<usize> {
+        Ok(self
+            .notification_repository
+            .count_by_tag()
+            .await?
+            .into_iter()
+            .find(|(tag, _)| tag.eq_ignore_ascii_case(name))
+            .map_or(0, |(_, count)| count))
+    }
+
+    async fn summary(&self, tag: Tag) -> DomainResult<TagSummary> {
+        let notification_count = self.count(&tag.name).await?;
+        Ok(TagSummary {
+            tag,
+            notification_count,
+        })
+    }
+
+    /// Replaces `from` by `to` on every notification, or removes it when `to`
+    /// is `None`. Returns how many notifications were changed.
+    async fn retag(&self, from: &str, to: Option<&str>) -> DomainResult<usize> {
+        let notifications = self.notification_repository.find_by_tag(from).await?;
+        let changed = notifications.len();
+        for mut notification in notifications {
+            let mut tags = Vec::with_capacity(notification.metadata.tags.len());
+            for tag in notification.metadata.tags.drain(..) {
+                let tag = match to {
+                    Some(to) if tag.eq_ignore_ascii_case(from) => to.to_string(),
+                    None if tag.eq_ignore_ascii_case(from) => continue,
+                    _ => tag,
+                };
+                if !tags.iter().any(|t: &String| t.eq_ignore_ascii_case(&tag)) {
+                    tags.push(tag);
+                }
+            }
+            notification.metadata.tags = tags;
+            self.notification_repository.save(&mut notification).await?;
+        }
+        Ok(changed)
+    }
+}
+
+#[async_trait]
+impl TagService for DefaultTagService {
+    async fn get_tags(&self) -> DomainResult<Vec<TagSummary>> {
+        let mut tags: Vec<TagSummary> = self
+            .tag_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|tag| TagSummary {
+                tag,
+                notification_count: 0,
+            })
+            .collect();
+
+        for (name, count) in self.notification_repository.count_by_tag().await? {
+            match tags.iter_mut().find(|summary| summary.tag.is_named(&name)) {
+                Some(summary) => summary.notification_count = count,
+                None => tags.push(TagSummary {
+                    tag: Tag::new(name),
+                    notification_count: count,
+                }),
+            }
+        }
+
+        tags.sort_by_key(|summary| summary.tag.name.to_lowercase());
+        Ok(tags)
+    }
+
+    async fn save_tag(&self, mut tag: Tag) -> DomainResult<Tag> {
+        tag.name = tag.name.trim().to_string();
+        tag.validate()?;
+        if let Some(existing) = self.tag_repository.find_by_name(&tag.name).await? {
+            tag.created_at = existing.created_at;
+        }
+        self.tag_repository.save(&mut tag).await?;
+        Ok(tag)
+    }
+
+    async fn rename_tag(&self, name: &str, new_name: &str) -> DomainResult<TagSummary> {
+        let new_name = new_name.trim();
+        // Changing the case of a name is fine, anything else must be free
+        if !name.trim().eq_ignore_ascii_case(new_name)
+            && (self.count(new_name).await? > 0
+                || self.tag_repository.find_by_name(new_name).await?.is_some())
+        {
+            return Err(DomainError::ValidationError(format!(
+                "Tag {} already exists, merge the tags instead",
+                new_name
+            )));
+        }
+
+        let mut tag = match self.tag_repository.find_by_name(name).await? {
+            Some(tag) => tag,
+            None if self.count(name).await? > 0 => Tag::new(name.trim().to_string()),
+            None => {
+                return Err(DomainError::NotFoundError(format!(
+                    "Tag {} not found",
+                    name
+                )))
+            }
+        };
+        tag.name = new_name.to_string();
+        tag.validate()?;
+
+        self.retag(name, Some(new_name)).await?;
+        self.tag_repository.delete(name).await?;
+        self.tag_repository.save(&mut tag).await?;
+        self.summary(tag).await
+    }
+
+    async fn merge_tags(&self, source: &str, target: &str) -> DomainResult<TagSummary> {
+        if source.trim().eq_ignore_ascii_case(target.trim()) {
+            return Err(DomainError::ValidationError(
+                "Cannot merge a tag with itself".to_string(),
+            ));
+        }
+        let source_tag = self.tag_repository.find_by_name(source).await?;
+        let mut tag = match self.tag_repository.find_by_name(target).await? {
+            Some(tag) => tag,
+            None => Tag::new(target.trim().to_string()),
+        };
+        tag.validate()?;
+        if let Some(source_tag) = source_tag {
+            tag.color = tag.color.or(source_tag.color);
+            tag.description = tag.description.or(source_tag.description);
+        }
+
+        self.retag(source, Some(&tag.name)).await?;
+        self.tag_repository.delete(source).await?;
+        self.tag_repository.save(&mut tag).await?;
+        self.summary(tag).await
+    }
+
+    async fn delete_tag(&self, name: &str) -> DomainResult<usize> {
+        let changed = self.retag(name, None).await?;
+        self.tag_repository.delete(name).await?;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+    };
+    use crate::infrastructure::repositories::{SqliteNotificationRepository, SqliteTagRepository};
+
+    async fn tagged(repository: &DynNotificationRepository, tags: &[&str]) -> Notification {
+        let mut notification = Notification::new(
+            "Build finished".to_string(),
+            "All green".to_string(),
+            NotificationPriority::Low,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                external_id: None,
+                url: None,
+                tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        repository.save(&mut notification).await.unwrap();
+        notification
+    }
+
+    #[tokio::test]
+    async fn test_rename_merge_and_delete_across_notifications() {
+        let notifications: DynNotificationRepository =
+            Arc::new(SqliteNotificationRepository::new(":memory:").unwrap());
+        let service = DefaultTagService::new(
+            Arc::new(SqliteTagRepository::new(":memory:").unwrap()),
+            notifications.clone(),
+        );
+
+        let both = tagged(&notifications, &["ci", "release"]).await;
+        tagged(&notifications, &["CI"]).await;
+        tagged(&notifications, &["deploy"]).await;
+
+        let mut ci = Tag::new("ci".to_string());
+        ci.color = Some("#1f883d".to_string());
+        service.save_tag(ci).await.unwrap();
+        service
+            .save_tag(Tag::new("unused".to_string()))
+            .await
+            .unwrap();
+
+        let counts: Vec<_> = service
+            .get_tags()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|summary| (summary.tag.name, summary.notification_count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("ci".to_string(), 2),
+                ("deploy".to_string(), 1),
+                ("release".to_string(), 1),
+                ("unused".to_string(), 0),
+            ]
+        );
+
+        // Renaming keeps the colour and cannot take an existing name
+        let renamed = service.rename_tag("ci", "build").await.unwrap();
+        assert_eq!(renamed.notification_count, 2);
+        assert_eq!(renamed.tag.color.as_deref(), Some("#1f883d"));
+        assert!(service.rename_tag("build", "deploy").await.is_err());
+        assert!(matches!(
+            service.rename_tag("missing", "found").await,
+            Err(DomainError::NotFoundError(_))
+        ));
+        assert!(service
+            .get_tags()
+            .await
+            .unwrap()
+            .iter()
+            .all(|s| s.tag.name != "found"));
+
+        // Merging a tag into one the notification already has leaves one copy
+        let merged = service.merge_tags("release", "build").await.unwrap();
+        assert_eq!(merged.notification_count, 2);
+        let stored = notifications.find_by_id(both.id).await.unwrap().unwrap();
+        assert_eq!(stored.metadata.tags, vec!["build"]);
+
+        assert_eq!(service.delete_tag("build").await.unwrap(), 2);
+        let names: Vec<_> = service
+            .get_tags()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.tag.name)
+            .collect();
+        assert_eq!(names, vec!["deploy", "unused"]);
+    }
+}
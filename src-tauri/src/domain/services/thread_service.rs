@@ -0,0 +1,202 @@
+use crate::domain::{
+    entities::{thread::STANDALONE_THREAD_PREFIX, Notification, NotificationStatus, Thread},
+    error::{DomainError, DomainResult},
+    repositories::DynNotificationRepository,
+    services::notification_service::DynNotificationService,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ThreadService: Send + Sync + std::fmt::Debug {
+    /// Lists threads, most recently active first
+    async fn get_threads(&self, include_archived: bool) -> DomainResult<Vec<Thread>>;
+    async fn get_thread(&self, key: &str) -> DomainResult<Thread>;
+    /// Messages of a thread, latest first
+    async fn get_thread_notifications(&self, key: &str) -> DomainResult<Vec<Notification>>;
+    /// Archives every message of the thread
+    async fn archive_thread(&self, key: &str) -> DomainResult<()>;
+    /// Marks every unread message of the thread as read
+    async fn mark_thread_read(&self, key: &str) -> DomainResult<()>;
+}
+
+pub type DynThreadService = Arc<dyn ThreadService>;
+
+/// Thread status changes go through the notification service so every
+/// message publishes its own lifecycle event.
+#[derive(Debug)]
+pub struct DefaultThreadService {
+    repository: DynNotificationRepository,
+    notification_service: DynNotificationService,
+}
+
+impl DefaultThreadService {
+    pub fn new(
+        repository: DynNotificationRepository,
+        notification_service: DynNotificationService,
+    ) -> Self {
+        Self {
+            repository,
+            notification_service,
+        }
+    }
+
+    async fn find_messages(&self, key: &str) -> DomainResult<Vec<Notification>> {
+        let notifications = match key.strip_prefix(STANDALONE_THREAD_PREFIX) {
+            Some(id) => {
+                let id = Uuid::parse_str(id).map_err(|_| {
+                    DomainError::InvalidInput(format!("Invalid thread key: {}", key))
+                })?;
+                self.repository.find_by_id(id).await?.into_iter().collect()
+            }
+            None => self.repository.find_by_thread_key(key).await?,
+        };
+
+        let notifications: Vec<Notification> = notifications
+            .into_iter()
+            .filter(|n| n.status != NotificationStatus::Deleted)
+            .collect();
+        if notifications.is_empty() {
+            return Err(DomainError::NotFoundError(format!(
+                "Thread {} not found",
+                key
+            )));
+        }
+        Ok(notifications)
+    }
+}
+
+#[async_trait]
+impl ThreadService for DefaultThreadService {
+    async fn get_threads(&self, include_archived: bool) -> DomainResult<Vec<Thread>> {
+        let threads = Thread::group(self.repository.find_all().await?);
+        Ok(threads
+            .into_iter()
+            .filter(|thread| include_archived || thread.status != NotificationStatus::Archived)
+            .collect())
+    }
+
+    async fn get_thread(&self, key: &str) -> DomainResult<Thread> {
+        let notifications = self.find_messages(key).await?;
+        Thread::from_notifications(key.to_string(), notifications)
+            .ok_or_else(|| DomainError::NotFoundError(format!("Thread {} not found", key)))
+    }
+
+    async fn get_thread_notifications(&self, key: &str) -> DomainResult<Vec<Notification>> {
+        let mut notifications = self.find_messages(key).await?;
+        notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+        Ok(notifications)
+    }
+
+    async fn archive_thread(&self, key: &str) -> DomainResult<()> {
+        for notification in self.find_messages(key).await? {
+            if notification.status != NotificationStatus::Archived {
+                self.notification_service
+                    .archive_notification(notification.id)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn mark_thread_read(&self, key: &str) -> DomainResult<()> {
+        for notification in self.find_messages(key).await? {
+            if notification.status == NotificationStatus::New {
+                self.notification_service
+                    .mark_as_read(notification.id)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{NotificationMetadata, NotificationPriority, NotificationSource},
+        services::MockNotificationService,
+    };
+    use crate::infrastructure::repositories::SqliteNotificationRepository;
+    use chrono::{Duration, Utc};
+    use mockall::predicate;
+
+    async fn save(
+        repository: &DynNotificationRepository,
+        thread_key: Option<&str>,
+        minutes_ago: i64,
+    ) -> Notification {
+        let mut notification = Notification::new(
+            "Re: Release plan".to_string(),
+            "Sounds good".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                thread_key: thread_key.map(str::to_string),
+                ..Default::default()
+            },
+        );
+        notification.created_at = Utc::now() - Duration::minutes(minutes_ago);
+        repository.save(&mut notification).await.unwrap();
+        notification
+    }
+
+    #[tokio::test]
+    async fn test_archiving_a_thread_archives_all_its_items() {
+        let repository = Arc::new(SqliteNotificationRepository::new(":memory:").unwrap())
+            as DynNotificationRepository;
+        let first = save(&repository, Some("google:gmail:t1"), 20).await;
+        let second = save(&repository, Some("google:gmail:t1"), 5).await;
+        let standalone = save(&repository, None, 10).await;
+
+        let mut notification_service = MockNotificationService::new();
+        for id in [first.id, second.id] {
+            notification_service
+                .expect_archive_notification()
+                .with(predicate::eq(id))
+                .times(1)
+                .returning(|_| Ok(()));
+        }
+
+        let service = DefaultThreadService::new(repository, Arc::new(notification_service));
+
+        let threads = service.get_threads(false).await.unwrap();
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].key, "google:gmail:t1");
+        assert_eq!(threads[0].latest_notification_id, second.id);
+        assert_eq!(threads[1].key, standalone.thread_key());
+
+        let messages = service
+            .get_thread_notifications("google:gmail:t1")
+            .await
+            .unwrap();
+        assert_eq!(
+            messages.iter().map(|n| n.id).collect::<Vec<_>>(),
+            vec![second.id, first.id]
+        );
+        assert_eq!(
+            service
+                .get_thread(&standalone.thread_key())
+                .await
+                .unwrap()
+                .message_count,
+            1
+        );
+
+        service.archive_thread("google:gmail:t1").await.unwrap();
+        assert!(matches!(
+            service.get_thread("google:gmail:missing").await,
+            Err(DomainError::NotFoundError(_))
+        ));
+    }
+}
@@ -0,0 +1,278 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::{sign_payload, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+use crate::domain::{
+    entities::WebhookDelivery,
+    events::EventEnvelope,
+    repositories::DynWebhookRepository,
+    services::background::types::{Job, JobHandler, JobType},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDeliveryPayload {
+    pub subscription_id: Uuid,
+    pub envelope: EventEnvelope,
+}
+
+/// Posts one event to one subscription and records the attempt.
+///
+/// Any non-2xx response or transport error fails the job so the job
+/// manager retries it with backoff.
+pub struct WebhookDeliveryHandler {
+    repository: DynWebhookRepository,
+    client: reqwest::Client,
+}
+
+impl std::fmt::Debug for WebhookDeliveryHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookDeliveryHandler").finish()
+    }
+}
+
+impl WebhookDeliveryHandler {
+    pub fn new(repository: DynWebhookRepository) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        Self { repository, client }
+    }
+
+    async fn record(&self, delivery: WebhookDelivery) {
+        if let Err(e) = self.repository.record_delivery(&delivery).await {
+            warn!(
+                "Failed to record webhook delivery for subscription {}: {}",
+                delivery.subscription_id, e
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl JobHandler for WebhookDeliveryHandler {
+    async fn handle(&self, job: &mut Job) -> Result<(), String> {
+        let payload: WebhookDeliveryPayload = serde_json::from_value(job.payload.clone())
+            .map_err(|e| format!("Invalid webhook delivery payload: {}", e))?;
+
+        let subscription = match self
+            .repository
+            .find_by_id(payload.subscription_id)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            Some(subscription) if subscription.enabled => subscription,
+            _ => {
+                info!(
+                    "Skipping delivery to removed or disabled webhook {}",
+                    payload.subscription_id
+                );
+                return Ok(());
+            }
+        };
+
+        let event_type = payload.envelope.event.event_type();
+        let body = serde_json::to_vec(&payload.envelope).map_err(|e| e.to_string())?;
+        let attempt = job.metadata.retry_count + 1;
+
+        let response = self
+            .client
+            .post(&subscription.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign_payload(&subscription.secret, &body))
+            .header(EVENT_HEADER, event_type)
+            .header(DELIVERY_HEADER, job.id.to_string())
+            .body(body)
+            .send()
+            .await;
+
+        let (status_code, error) = match response {
+            Ok(response) => {
+                let status = response.status();
+                let error = (!status.is_success())
+                    .then(|| format!("Webhook responded with status {}", status));
+                (Some(status.as_u16()), error)
+            }
+            Err(e) => (None, Some(format!("Webhook request failed: {}", e))),
+        };
+
+        self.record(WebhookDelivery::new(
+            subscription.id,
+            event_type.to_string(),
+            payload.envelope.sequence,
+            attempt,
+            status_code,
+            error.clone(),
+        ))
+        .await;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn job_type(&self) -> JobType {
+        JobType::WebhookDelivery
+    }
+
+    fn retries_on_failure(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::WebhookSubscription,
+        events::{EventBus, EventPublisher, NotificationEvent},
+        repositories::WebhookRepository,
+        services::{
+            background::{manager::BackgroundJobManagerTrait, types::JobPriority},
+            webhooks::WebhookDispatcher,
+            BackgroundJobManager,
+        },
+    };
+    use crate::infrastructure::repositories::SqliteWebhookRepository;
+    use std::sync::Arc;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn setup(server: &MockServer) -> (DynWebhookRepository, WebhookSubscription) {
+        let repository = Arc::new(SqliteWebhookRepository::new(":memory:").unwrap());
+        let mut subscription = WebhookSubscription::new(
+            format!("{}/hook", server.uri()),
+            vec![],
+            "shared-secret".to_string(),
+        );
+        repository.save(&mut subscription).await.unwrap();
+        (repository, subscription)
+    }
+
+    fn delivery_job(subscription_id: Uuid) -> Job {
+        let envelope = EventEnvelope::new(
+            42,
+            NotificationEvent::notification_read(Uuid::new_v4()).into(),
+        );
+        Job::new(
+            serde_json::to_value(WebhookDeliveryPayload {
+                subscription_id,
+                envelope,
+            })
+            .unwrap(),
+            JobPriority::Normal,
+            JobType::WebhookDelivery,
+            3,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_delivers_signed_payload() {
+        let server = MockServer::start().await;
+        let (repository, subscription) = setup(&server).await;
+        let mut job = delivery_job(subscription.id);
+
+        let payload: WebhookDeliveryPayload = serde_json::from_value(job.payload.clone()).unwrap();
+        let body = serde_json::to_vec(&payload.envelope).unwrap();
+        let signature = sign_payload("shared-secret", &body);
+
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header(EVENT_HEADER, "NotificationRead"))
+            .and(header(SIGNATURE_HEADER, signature.as_str()))
+            .and(header_exists(DELIVERY_HEADER))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let handler = WebhookDeliveryHandler::new(repository.clone());
+        handler.handle(&mut job).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let received: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(received["sequence"], 42);
+        assert_eq!(received["event"]["type"], "NotificationRead");
+
+        let deliveries = repository.find_deliveries(subscription.id, 10).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status_code, Some(204));
+        assert!(deliveries[0].success);
+    }
+
+    #[tokio::test]
+    async fn test_error_response_fails_job_and_is_logged() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let (repository, subscription) = setup(&server).await;
+        let handler = WebhookDeliveryHandler::new(repository.clone());
+        let mut job = delivery_job(subscription.id);
+
+        assert!(handler.handle(&mut job).await.is_err());
+
+        let deliveries = repository.find_deliveries(subscription.id, 10).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status_code, Some(500));
+        assert!(!deliveries[0].success);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried_through_job_manager() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let (repository, subscription) = setup(&server).await;
+        let bus = Arc::new(EventBus::new());
+        let job_manager = Arc::new(
+            BackgroundJobManager::new().with_retry_backoff(Duration::from_millis(10)),
+        );
+        job_manager
+            .register_handler(Arc::new(WebhookDeliveryHandler::new(repository.clone())))
+            .await
+            .unwrap();
+        bus.subscribe(Arc::new(WebhookDispatcher::new(
+            repository.clone(),
+            job_manager.clone(),
+        )));
+
+        bus.publish_event(NotificationEvent::notification_read(Uuid::new_v4()))
+            .await
+            .unwrap();
+
+        let start_time = std::time::Instant::now();
+        loop {
+            let deliveries = repository.find_deliveries(subscription.id, 10).await.unwrap();
+            if deliveries.iter().any(|d| d.success) {
+                assert_eq!(deliveries.len(), 2);
+                assert_eq!(deliveries[0].attempt, 2);
+                assert_eq!(deliveries[1].status_code, Some(503));
+                break;
+            }
+            assert!(
+                start_time.elapsed() < Duration::from_secs(5),
+                "webhook was not redelivered"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::WebhookDeliveryPayload;
+use crate::domain::{
+    error::DomainResult,
+    events::{EventCategory, EventEnvelope, EventSubscriber},
+    repositories::DynWebhookRepository,
+    services::background::{
+        manager::DynBackgroundJobManager,
+        types::{Job, JobPriority, JobType},
+    },
+};
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Turns notification events into webhook delivery jobs, one per matching
+/// subscription. The HTTP call itself happens in the job so failures are
+/// retried by the job manager.
+pub struct WebhookDispatcher {
+    repository: DynWebhookRepository,
+    job_manager: DynBackgroundJobManager,
+    max_attempts: u32,
+}
+
+impl WebhookDispatcher {
+    pub fn new(repository: DynWebhookRepository, job_manager: DynBackgroundJobManager) -> Self {
+        Self {
+            repository,
+            job_manager,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for WebhookDispatcher {
+    fn name(&self) -> &str {
+        "webhook-dispatcher"
+    }
+
+    async fn handle_event(&self, envelope: &EventEnvelope) -> DomainResult<()> {
+        if envelope.category != EventCategory::Notification {
+            return Ok(());
+        }
+
+        let event_type = envelope.event.event_type();
+        let subscriptions = self.repository.find_enabled().await?;
+
+        for subscription in subscriptions
+            .into_iter()
+            .filter(|s| s.is_subscribed_to(event_type))
+        {
+            let payload = WebhookDeliveryPayload {
+                subscription_id: subscription.id,
+                envelope: envelope.clone(),
+            };
+            let job = Job::new(
+                json!(payload),
+                JobPriority::Normal,
+                JobType::WebhookDelivery,
+                self.max_attempts,
+            );
+            self.job_manager.submit_job(job).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::WebhookSubscription,
+        events::{JobEvent, NotificationEvent},
+        repositories::WebhookRepository,
+        services::background::MockBackgroundJobManagerTrait,
+    };
+    use crate::infrastructure::repositories::SqliteWebhookRepository;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    async fn repository_with(subscriptions: Vec<WebhookSubscription>) -> DynWebhookRepository {
+        let repository = SqliteWebhookRepository::new(":memory:").unwrap();
+        for mut subscription in subscriptions {
+            repository.save(&mut subscription).await.unwrap();
+        }
+        Arc::new(repository)
+    }
+
+    #[tokio::test]
+    async fn test_submits_one_job_per_matching_subscription() {
+        let mut disabled = WebhookSubscription::new(
+            "http://localhost/disabled".to_string(),
+            vec![],
+            "secret".to_string(),
+        );
+        disabled.set_enabled(false);
+        let repository = repository_with(vec![
+            WebhookSubscription::new(
+                "http://localhost/all".to_string(),
+                vec![],
+                "secret".to_string(),
+            ),
+            WebhookSubscription::new(
+                "http://localhost/read".to_string(),
+                vec!["NotificationRead".to_string()],
+                "secret".to_string(),
+            ),
+            WebhookSubscription::new(
+                "http://localhost/archived".to_string(),
+                vec!["NotificationArchived".to_string()],
+                "secret".to_string(),
+            ),
+            disabled,
+        ])
+        .await;
+
+        let mut job_manager = MockBackgroundJobManagerTrait::new();
+        job_manager
+            .expect_submit_job()
+            .withf(|job| job.metadata.job_type == JobType::WebhookDelivery)
+            .times(2)
+            .returning(|job| Box::pin(async move { Ok(job.id) }));
+
+        let dispatcher = WebhookDispatcher::new(repository, Arc::new(job_manager));
+        let envelope =
+            EventEnvelope::new(1, NotificationEvent::notification_read(Uuid::new_v4()).into());
+
+        dispatcher.handle_event(&envelope).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ignores_non_notification_events() {
+        let repository = repository_with(vec![WebhookSubscription::new(
+            "http://localhost/all".to_string(),
+            vec![],
+            "secret".to_string(),
+        )])
+        .await;
+
+        let mut job_manager = MockBackgroundJobManagerTrait::new();
+        job_manager.expect_submit_job().never();
+
+        let dispatcher = WebhookDispatcher::new(repository, Arc::new(job_manager));
+        let envelope = EventEnvelope::new(
+            1,
+            JobEvent::job_started(Uuid::new_v4(), JobType::WebhookDelivery).into(),
+        );
+
+        dispatcher.handle_event(&envelope).await.unwrap();
+    }
+}
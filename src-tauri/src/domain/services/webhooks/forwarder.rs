@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::Notification,
+    error::{DomainError, DomainResult},
+    events::{DynEventPublisher, NotificationEvent},
+    repositories::DynWebhookRepository,
+    services::notification_service::NotificationForwarder,
+};
+
+const FORWARDED_EVENT: &str = "NotificationForwarded";
+
+/// Forwards notifications through the webhooks that list the
+/// `NotificationForwarded` event, e.g. a relay mailing the recipient.
+///
+/// Forwarding publishes the event with the notification and the recipient,
+/// and the webhook dispatcher delivers it like any other event. A webhook
+/// receiving every event does not count as a forward channel: without one
+/// listing the event explicitly, forwarding fails.
+pub struct WebhookForwarder {
+    repository: DynWebhookRepository,
+    event_publisher: DynEventPublisher,
+}
+
+impl WebhookForwarder {
+    pub fn new(repository: DynWebhookRepository, event_publisher: DynEventPublisher) -> Self {
+        Self {
+            repository,
+            event_publisher,
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationForwarder for WebhookForwarder {
+    async fn forward(&self, notification: &Notification, recipient: &str) -> DomainResult<()> {
+        let configured = self
+            .repository
+            .find_enabled()
+            .await?
+            .iter()
+            .any(|subscription| {
+                subscription
+                    .event_types
+                    .iter()
+                    .any(|t| t == FORWARDED_EVENT)
+            });
+        if !configured {
+            return Err(DomainError::ConfigurationError(format!(
+                "No webhook receives {} events",
+                FORWARDED_EVENT
+            )));
+        }
+
+        self.event_publisher
+            .publish_event(NotificationEvent::notification_forwarded(
+                notification,
+                recipient,
+            ))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{
+            NotificationMetadata, NotificationPriority, NotificationSource, WebhookSubscription,
+        },
+        events::{DomainEvent, EventBus},
+        repositories::WebhookRepository,
+    };
+    use crate::infrastructure::repositories::SqliteWebhookRepository;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_forwards_only_through_webhooks_listing_the_event() {
+        let repository = Arc::new(SqliteWebhookRepository::new(":memory:").unwrap());
+        let bus = Arc::new(EventBus::new());
+        let forwarder = WebhookForwarder::new(repository.clone(), bus.clone());
+        let notification = Notification::new(
+            "OPS-1 is down".to_string(),
+            "Prod is down".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Jira,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+
+        let mut every_event = WebhookSubscription::new(
+            "http://localhost/audit".to_string(),
+            vec![],
+            "secret".to_string(),
+        );
+        repository.save(&mut every_event).await.unwrap();
+        assert!(matches!(
+            forwarder.forward(&notification, "oncall@example.com").await,
+            Err(DomainError::ConfigurationError(_))
+        ));
+
+        let mut relay = WebhookSubscription::new(
+            "http://localhost/relay".to_string(),
+            vec![FORWARDED_EVENT.to_string()],
+            "secret".to_string(),
+        );
+        repository.save(&mut relay).await.unwrap();
+        forwarder
+            .forward(&notification, "oncall@example.com")
+            .await
+            .unwrap();
+
+        let events = bus.events_since(0).await;
+        assert_eq!(events.len(), 1);
+        match &events[0].event {
+            DomainEvent::Notification(NotificationEvent::NotificationForwarded {
+                notification_id,
+                recipient,
+                ..
+            }) => {
+                assert_eq!(*notification_id, notification.id);
+                assert_eq!(recipient, "oncall@example.com");
+            }
+            other => panic!("Expected a forwarded event, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+pub mod delivery;
+pub mod dispatcher;
+pub mod forwarder;
+
+pub use delivery::{WebhookDeliveryHandler, WebhookDeliveryPayload};
+pub use dispatcher::WebhookDispatcher;
+pub use forwarder::WebhookForwarder;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "X-Autoresponse-Signature";
+pub const EVENT_HEADER: &str = "X-Autoresponse-Event";
+pub const DELIVERY_HEADER: &str = "X-Autoresponse-Delivery";
+
+/// Signs a webhook body, returning the `sha256=<hex>` header value.
+///
+/// Receivers recompute the HMAC-SHA256 of the raw request body with the
+/// shared secret and compare it to the signature header.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload_matches_known_vector() {
+        // RFC 4231 test case 2
+        let signature = sign_payload("Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::domain::{
+    error::{DomainError, DomainResult},
+    repositories::AttachmentStore,
+};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tokio::sync::Mutex;
+
+/// Default size of the whole cache
+pub const DEFAULT_CACHE_BYTES: u64 = 500 * 1024 * 1024;
+/// Default size above which attachments are downloaded but not cached
+pub const DEFAULT_MAX_BLOB_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Keeps attachment content in a directory, one file per content hash
+pub struct FileAttachmentStore {
+    directory: PathBuf,
+    max_bytes: u64,
+    max_blob_bytes: u64,
+    /// Serializes writes so eviction sees a consistent directory
+    write_lock: Mutex<()>,
+}
+
+impl std::fmt::Debug for FileAttachmentStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileAttachmentStore")
+            .field("directory", &self.directory)
+            .field("max_bytes", &self.max_bytes)
+            .field("max_blob_bytes", &self.max_blob_bytes)
+            .finish()
+    }
+}
+
+impl FileAttachmentStore {
+    pub fn new<P: AsRef<Path>>(directory: P) -> Result<Self, DomainError> {
+        std::fs::create_dir_all(directory.as_ref()).map_err(|e| {
+            DomainError::InternalError(format!("Failed to create attachment cache: {}", e))
+        })?;
+        Ok(Self {
+            directory: directory.as_ref().to_path_buf(),
+            max_bytes: DEFAULT_CACHE_BYTES,
+            max_blob_bytes: DEFAULT_MAX_BLOB_BYTES,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// Caps the size of the whole cache and of a single attachment
+    pub fn with_limits(mut self, max_bytes: u64, max_blob_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self.max_blob_bytes = max_blob_bytes.min(max_bytes);
+        self
+    }
+
+    fn path(&self, hash: &str) -> DomainResult<PathBuf> {
+        // Hashes are hex, anything else could escape the directory
+        if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(DomainError::ValidationError(format!(
+                "Invalid content hash: {}",
+                hash
+            )));
+        }
+        Ok(self.directory.join(hash))
+    }
+
+    /// Removes the oldest files until `incoming` more bytes fit
+    async fn evict(&self, incoming: u64) -> std::io::Result<()> {
+        let mut files: Vec<(SystemTime, u64, PathBuf)> = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if metadata.is_file() {
+                files.push((
+                    metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    metadata.len(),
+                    entry.path(),
+                ));
+            }
+        }
+
+        let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+        files.sort_by_key(|(modified, _, _)| *modified);
+        for (_, size, path) in files {
+            if total + incoming <= self.max_bytes {
+                break;
+            }
+            tokio::fs::remove_file(path).await?;
+            total -= size;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AttachmentStore for FileAttachmentStore {
+    async fn put(&self, hash: &str, data: &[u8]) -> DomainResult<bool> {
+        let path = self.path(hash)?;
+        if data.len() as u64 > self.max_blob_bytes {
+            return Ok(false);
+        }
+
+        let _guard = self.write_lock.lock().await;
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(true);
+        }
+        self.evict(data.len() as u64).await?;
+
+        // Written under another name first so readers never see a partial file
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, data).await?;
+        tokio::fs::rename(&partial, &path).await?;
+        Ok(true)
+    }
+
+    async fn get(&self, hash: &str) -> DomainResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path(hash)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cache_is_size_limited() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = FileAttachmentStore::new(directory.path())
+            .unwrap()
+            .with_limits(10, 6);
+
+        assert!(store.put("aa", b"12345").await.unwrap());
+        assert!(!store.put("bb", b"1234567").await.unwrap());
+        assert_eq!(store.get("bb").await.unwrap(), None);
+
+        // The oldest content makes room for the new one
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(store.put("cc", b"123456").await.unwrap());
+        assert_eq!(store.get("aa").await.unwrap(), None);
+        assert_eq!(
+            store.get("cc").await.unwrap().as_deref(),
+            Some(&b"123456"[..])
+        );
+
+        assert!(store.get("../secret").await.is_err());
+    }
+}
@@ -1,10 +1,38 @@
 pub mod cached_repository;
+pub mod file_attachment_store;
 pub mod service_config_repository;
 pub mod sqlite_base;
+pub mod sqlite_contact_repository;
+pub mod sqlite_conversation_memory_repository;
+pub mod sqlite_digest_repository;
+pub mod sqlite_dnd_repository;
+pub mod sqlite_escalation_policy_repository;
+pub mod sqlite_event_store;
+pub mod sqlite_note_repository;
 pub mod sqlite_notification_repository;
+pub mod sqlite_rule_repository;
+pub mod sqlite_saved_view_repository;
 pub mod sqlite_service_config_repository;
+pub mod sqlite_settings_repository;
+pub mod sqlite_statistics_repository;
+pub mod sqlite_tag_repository;
+pub mod sqlite_webhook_repository;
 
+pub use file_attachment_store::FileAttachmentStore;
 pub use service_config_repository::ServiceConfigRepository;
 pub use sqlite_base::SqliteRepository;
+pub use sqlite_contact_repository::SqliteContactRepository;
+pub use sqlite_conversation_memory_repository::SqliteConversationMemoryRepository;
+pub use sqlite_digest_repository::SqliteDigestRepository;
+pub use sqlite_dnd_repository::SqliteDndRepository;
+pub use sqlite_escalation_policy_repository::SqliteEscalationPolicyRepository;
+pub use sqlite_event_store::SqliteEventStore;
+pub use sqlite_note_repository::SqliteNoteRepository;
 pub use sqlite_notification_repository::SqliteNotificationRepository;
+pub use sqlite_rule_repository::SqliteRuleRepository;
+pub use sqlite_saved_view_repository::SqliteSavedViewRepository;
 pub use sqlite_service_config_repository::SqliteServiceConfigRepository;
+pub use sqlite_settings_repository::SqliteSettingsRepository;
+pub use sqlite_statistics_repository::SqliteStatisticsRepository;
+pub use sqlite_tag_repository::SqliteTagRepository;
+pub use sqlite_webhook_repository::SqliteWebhookRepository;
@@ -0,0 +1,320 @@
+use crate::domain::{
+    entities::Contact,
+    error::{DomainError, DomainResult},
+    repositories::ContactRepository,
+};
+use crate::infrastructure::repositories::sqlite_base::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqliteContactRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteContactRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS contacts (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                identities TEXT NOT NULL,
+                vip BOOLEAN NOT NULL DEFAULT 0,
+                notes TEXT,
+                preferences TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+                [],
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn write(&self, conn: &Connection, contact: &Contact) -> DomainResult<()> {
+        let columns = self.column_names();
+        let query = format!(
+            "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+            self.table_name(),
+            columns.join(", "),
+            std::iter::repeat_n("?", columns.len())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        conn.execute(
+            &query,
+            rusqlite::params_from_iter(self.map_entity_to_params(contact)),
+        )?;
+        Ok(())
+    }
+
+    /// Points the notifications linked to any of `contact_ids` at `contact`,
+    /// or unlinks them. The notifications table lives in the same database
+    /// file once the notification repository has created it.
+    fn relink_notifications(
+        conn: &Connection,
+        contact_ids: &[Uuid],
+        contact: Option<&Contact>,
+    ) -> DomainResult<()> {
+        let exists = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'notifications'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+        if !exists {
+            return Ok(());
+        }
+
+        let snapshot = contact.map(|contact| serde_json::to_string(&contact.as_sender()).unwrap());
+        let contact_id = contact.map(|contact| contact.id.to_string());
+        for id in contact_ids {
+            conn.execute(
+                "UPDATE notifications SET contact = ?1, contact_id = ?2 WHERE contact_id = ?3",
+                params![snapshot, contact_id, id.to_string()],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl SqliteRepository<Contact> for SqliteContactRepository {
+    fn table_name(&self) -> &str {
+        "contacts"
+    }
+
+    fn column_names(&self) -> Vec<&str> {
+        vec![
+            "id",
+            "name",
+            "identities",
+            "vip",
+            "notes",
+            "preferences",
+            "created_at",
+            "updated_at",
+        ]
+    }
+
+    fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.connection
+    }
+
+    fn map_row(&self, row: &Row) -> rusqlite::Result<Contact> {
+        Ok(Contact {
+            id: Uuid::parse_str(&row.get::<_, String>("id")?).unwrap(),
+            name: row.get("name")?,
+            identities: serde_json::from_str(&row.get::<_, String>("identities")?).unwrap(),
+            vip: row.get("vip")?,
+            notes: row.get("notes")?,
+            preferences: serde_json::from_str(&row.get::<_, String>("preferences")?)
+                .unwrap_or_default(),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("updated_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    fn map_entity_to_params(&self, contact: &Contact) -> Vec<Box<dyn rusqlite::ToSql + Send>> {
+        vec![
+            Box::new(contact.id.to_string()),
+            Box::new(contact.name.clone()),
+            Box::new(serde_json::to_string(&contact.identities).unwrap()),
+            Box::new(contact.vip),
+            Box::new(contact.notes.clone()),
+            Box::new(serde_json::to_string(&contact.preferences).unwrap()),
+            Box::new(contact.created_at.to_rfc3339()),
+            Box::new(contact.updated_at.to_rfc3339()),
+        ]
+    }
+}
+
+#[async_trait]
+impl ContactRepository for SqliteContactRepository {
+    async fn save(&self, contact: &mut Contact) -> DomainResult<()> {
+        contact.updated_at = Utc::now();
+        let mut conn = self.connection().lock().await;
+        let tx = conn.transaction()?;
+        self.write(&tx, contact)?;
+        Self::relink_notifications(&tx, &[contact.id], Some(contact))?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Contact>> {
+        <Self as SqliteRepository<Contact>>::find_by_id(self, id).await
+    }
+
+    async fn find_all(&self) -> DomainResult<Vec<Contact>> {
+        let conn = self.connection().lock().await;
+        let query = format!(
+            "SELECT * FROM {} ORDER BY name COLLATE NOCASE ASC",
+            self.table_name()
+        );
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map([], |row| self.map_row(row))?;
+
+        let mut contacts = Vec::new();
+        for contact in rows {
+            contacts.push(contact?);
+        }
+        Ok(contacts)
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        let mut conn = self.connection().lock().await;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM contacts WHERE id = ?1",
+            params![id.to_string()],
+        )?;
+        Self::relink_notifications(&tx, &[id], None)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    async fn merge(&self, target: &mut Contact, other: Uuid) -> DomainResult<()> {
+        target.updated_at = Utc::now();
+        let mut conn = self.connection().lock().await;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM contacts WHERE id = ?1",
+            params![other.to_string()],
+        )?;
+        self.write(&tx, target)?;
+        Self::relink_notifications(&tx, &[target.id, other], Some(target))?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{
+            ContactIdentity, Notification, NotificationMetadata, NotificationPriority,
+            NotificationSource,
+        },
+        repositories::{DynContactRepository, NotificationRepository},
+    };
+    use crate::infrastructure::repositories::SqliteNotificationRepository;
+
+    #[tokio::test]
+    async fn test_sqlite_contact_repository() {
+        let repo: DynContactRepository =
+            Arc::new(SqliteContactRepository::new(":memory:").unwrap());
+
+        let mut grace = Contact::new(
+            "grace Hopper".to_string(),
+            vec![ContactIdentity {
+                source: NotificationSource::Email,
+                handle: "grace@example.com".to_string(),
+            }],
+        );
+        grace.vip = true;
+        grace.notes = Some("Prefers short answers".to_string());
+        grace.preferences.tone = Some("formal".to_string());
+        let mut ada = Contact::new("Ada Lovelace".to_string(), vec![]);
+        repo.save(&mut grace).await.unwrap();
+        repo.save(&mut ada).await.unwrap();
+
+        let found = repo.find_by_id(grace.id).await.unwrap().unwrap();
+        assert_eq!(found, grace);
+
+        let names: Vec<String> = repo
+            .find_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|contact| contact.name)
+            .collect();
+        assert_eq!(names, vec!["Ada Lovelace", "grace Hopper"]);
+
+        let by_identity = repo
+            .find_by_identity(&NotificationSource::Email, "GRACE@example.com")
+            .await
+            .unwrap();
+        assert_eq!(by_identity.map(|contact| contact.id), Some(grace.id));
+
+        repo.delete(grace.id).await.unwrap();
+        assert!(repo.find_by_id(grace.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_notification_snapshots_follow_the_contact() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let notifications = SqliteNotificationRepository::new(file.path()).unwrap();
+        let repo: DynContactRepository =
+            Arc::new(SqliteContactRepository::new(file.path()).unwrap());
+
+        let mut grace = Contact::new("Grace".to_string(), vec![]);
+        let mut ada = Contact::new("Ada".to_string(), vec![]);
+        repo.save(&mut grace).await.unwrap();
+        repo.save(&mut ada).await.unwrap();
+
+        let mut from_grace = notification(&grace);
+        let mut from_ada = notification(&ada);
+        for notification in [&mut from_grace, &mut from_ada] {
+            NotificationRepository::save(&notifications, notification)
+                .await
+                .unwrap();
+        }
+        let contact_of = |id| {
+            let notifications = &notifications;
+            async move {
+                NotificationRepository::find_by_id(notifications, id)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .metadata
+                    .contact
+            }
+        };
+
+        grace.vip = true;
+        grace.name = "Grace Hopper".to_string();
+        repo.save(&mut grace).await.unwrap();
+        let snapshot = contact_of(from_grace.id).await.unwrap();
+        assert!(snapshot.vip);
+        assert_eq!(snapshot.name, "Grace Hopper");
+
+        repo.merge(&mut grace, ada.id).await.unwrap();
+        assert!(repo.find_by_id(ada.id).await.unwrap().is_none());
+        assert_eq!(contact_of(from_ada.id).await, Some(grace.as_sender()));
+
+        repo.delete(grace.id).await.unwrap();
+        assert_eq!(contact_of(from_grace.id).await, None);
+        assert_eq!(contact_of(from_ada.id).await, None);
+    }
+
+    fn notification(contact: &Contact) -> Notification {
+        Notification::new(
+            "Hello".to_string(),
+            "Hi there".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                contact: Some(contact.as_sender()),
+                ..Default::default()
+            },
+        )
+    }
+}
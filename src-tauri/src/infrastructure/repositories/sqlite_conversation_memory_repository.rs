@@ -0,0 +1,135 @@
+use crate::domain::{
+    entities::ConversationEntry,
+    error::{DomainError, DomainResult},
+    repositories::ConversationMemoryRepository,
+    services::ai::MEMORY_MAX_ENTRIES,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Stores the remembered turns of each conversation as a JSON row, keeping
+/// only the latest `max_entries` of them
+pub struct SqliteConversationMemoryRepository {
+    connection: Arc<Mutex<Connection>>,
+    max_entries: usize,
+}
+
+impl SqliteConversationMemoryRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS conversation_memories (
+                conversation TEXT PRIMARY KEY,
+                entries TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+                [],
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            max_entries: MEMORY_MAX_ENTRIES,
+        })
+    }
+
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+}
+
+#[async_trait]
+impl ConversationMemoryRepository for SqliteConversationMemoryRepository {
+    async fn find_entries(&self, conversation: &str) -> DomainResult<Vec<ConversationEntry>> {
+        let conn = self.connection.lock().await;
+        let entries: Option<String> = conn
+            .query_row(
+                "SELECT entries FROM conversation_memories WHERE conversation = ?1",
+                params![conversation],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match entries {
+            Some(entries) => serde_json::from_str(&entries).map_err(|e| {
+                DomainError::InternalError(format!("Failed to read conversation memory: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn save_entries(
+        &self,
+        conversation: &str,
+        entries: &[ConversationEntry],
+    ) -> DomainResult<()> {
+        if entries.is_empty() {
+            return self.delete(conversation).await;
+        }
+        let entries = &entries[entries.len().saturating_sub(self.max_entries)..];
+        let json = serde_json::to_string(entries).map_err(|e| {
+            DomainError::InternalError(format!("Failed to write conversation memory: {}", e))
+        })?;
+
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO conversation_memories (conversation, entries, updated_at)
+            VALUES (?1, ?2, ?3)",
+            params![conversation, json, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    async fn delete(&self, conversation: &str) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "DELETE FROM conversation_memories WHERE conversation = ?1",
+            params![conversation],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::ConversationMemory;
+
+    #[tokio::test]
+    async fn test_sqlite_conversation_memory_repository() {
+        let repo = SqliteConversationMemoryRepository::new(":memory:").unwrap();
+        assert!(repo.find_entries("jira:OPS-1").await.unwrap().is_empty());
+
+        let mut memory = ConversationMemory::new(10);
+        memory.add_entry("User".to_string(), "Is OPS-1 fixed?".to_string());
+        memory.add_entry("Assistant".to_string(), "Not yet.".to_string());
+        let entries: Vec<_> = memory.entries.into_iter().collect();
+        repo.save_entries("jira:OPS-1", &entries).await.unwrap();
+        repo.save_entries("gmail:thread-7", &entries[..1])
+            .await
+            .unwrap();
+
+        assert_eq!(repo.find_entries("jira:OPS-1").await.unwrap(), entries);
+        assert_eq!(repo.find_entries("gmail:thread-7").await.unwrap().len(), 1);
+
+        repo.delete("jira:OPS-1").await.unwrap();
+        assert!(repo.find_entries("jira:OPS-1").await.unwrap().is_empty());
+        assert_eq!(repo.find_entries("gmail:thread-7").await.unwrap().len(), 1);
+
+        // Only the latest entries are written
+        let repo = repo.with_max_entries(1);
+        repo.save_entries("jira:OPS-1", &entries).await.unwrap();
+        assert_eq!(
+            repo.find_entries("jira:OPS-1").await.unwrap(),
+            entries[1..].to_vec()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use crate::domain::{
+    entities::{Digest, DigestSettings},
+    error::{DomainError, DomainResult},
+    repositories::DigestRepository,
+};
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Stores each digest as JSON, with the settings as a single row
+pub struct SqliteDigestRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDigestRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS digest_settings (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                settings TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS digests (
+                id TEXT PRIMARY KEY,
+                period_start TEXT NOT NULL,
+                period_end TEXT NOT NULL,
+                notification_id TEXT,
+                digest TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_digests_created_at ON digests (created_at);",
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn parse_digest(json: &str) -> DomainResult<Digest> {
+        serde_json::from_str(json)
+            .map_err(|e| DomainError::InternalError(format!("Failed to read digest: {}", e)))
+    }
+}
+
+#[async_trait]
+impl DigestRepository for SqliteDigestRepository {
+    async fn get_settings(&self) -> DomainResult<DigestSettings> {
+        let conn = self.connection.lock().await;
+        let settings: Option<String> = conn
+            .query_row(
+                "SELECT settings FROM digest_settings WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match settings {
+            Some(settings) => serde_json::from_str(&settings).map_err(|e| {
+                DomainError::InternalError(format!("Failed to read digest settings: {}", e))
+            }),
+            None => Ok(DigestSettings::default()),
+        }
+    }
+
+    async fn save_settings(&self, settings: &mut DigestSettings) -> DomainResult<()> {
+        settings.updated_at = Utc::now();
+        let json = serde_json::to_string(settings).map_err(|e| {
+            DomainError::InternalError(format!("Failed to write digest settings: {}", e))
+        })?;
+
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO digest_settings (id, settings, updated_at) VALUES (1, ?1, ?2)",
+            params![json, settings.updated_at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    async fn save(&self, digest: &Digest) -> DomainResult<()> {
+        let json = serde_json::to_string(digest)
+            .map_err(|e| DomainError::InternalError(format!("Failed to write digest: {}", e)))?;
+
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO digests
+                (id, period_start, period_end, notification_id, digest, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                digest.id.to_string(),
+                digest.period_start.to_rfc3339(),
+                digest.period_end.to_rfc3339(),
+                digest.notification_id.map(|id| id.to_string()),
+                json,
+                digest
+                    .created_at
+                    .to_rfc3339_opts(SecondsFormat::Micros, true),
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Digest>> {
+        let conn = self.connection.lock().await;
+        let digest: Option<String> = conn
+            .query_row(
+                "SELECT digest FROM digests WHERE id = ?1",
+                params![id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        digest.as_deref().map(Self::parse_digest).transpose()
+    }
+
+    async fn find_recent(&self, limit: usize) -> DomainResult<Vec<Digest>> {
+        let conn = self.connection.lock().await;
+        let mut stmt =
+            conn.prepare("SELECT digest FROM digests ORDER BY created_at DESC LIMIT ?1")?;
+        let rows = stmt.query_map(params![limit as i64], |row| row.get::<_, String>(0))?;
+
+        let mut digests = Vec::new();
+        for digest in rows {
+            digests.push(Self::parse_digest(&digest?)?);
+        }
+        Ok(digests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        DigestGroup, DigestGrouping, DigestSection, NotificationPriority,
+    };
+    use chrono::Duration;
+
+    fn digest(created_at: chrono::DateTime<Utc>) -> Digest {
+        Digest {
+            id: Uuid::new_v4(),
+            period_start: created_at - Duration::hours(24),
+            period_end: created_at,
+            group_by: DigestGrouping::Source,
+            sections: vec![DigestSection {
+                urgency: NotificationPriority::High,
+                groups: vec![DigestGroup {
+                    key: "Github".to_string(),
+                    title: "Github".to_string(),
+                    summary: "Two failing builds".to_string(),
+                    suggested_actions: vec!["Fix CI".to_string()],
+                    notification_ids: vec![Uuid::new_v4(), Uuid::new_v4()],
+                    ai_generated: true,
+                }],
+            }],
+            notification_count: 2,
+            omitted_groups: 0,
+            used_fallback: false,
+            notification_id: Some(Uuid::new_v4()),
+            created_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_digest_repository() {
+        let repo = SqliteDigestRepository::new(":memory:").unwrap();
+        assert!(!repo.get_settings().await.unwrap().enabled);
+
+        let mut settings = DigestSettings {
+            enabled: true,
+            tags: vec!["ci".to_string()],
+            ..DigestSettings::default()
+        };
+        repo.save_settings(&mut settings).await.unwrap();
+        assert_eq!(repo.get_settings().await.unwrap(), settings);
+
+        let older = digest(Utc::now() - Duration::days(1));
+        let newer = digest(Utc::now());
+        repo.save(&older).await.unwrap();
+        repo.save(&newer).await.unwrap();
+
+        assert_eq!(repo.find_by_id(older.id).await.unwrap(), Some(older));
+        assert_eq!(repo.find_recent(1).await.unwrap(), vec![newer]);
+        assert!(repo.find_by_id(Uuid::new_v4()).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::domain::{
+    entities::DndSettings,
+    error::{DomainError, DomainResult},
+    repositories::DndRepository,
+};
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Stores the DND settings as a single JSON row next to the held notifications
+pub struct SqliteDndRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDndRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS dnd_settings (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                settings TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS dnd_held_notifications (
+                notification_id TEXT PRIMARY KEY,
+                held_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS dnd_deferred_responses (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                notification_id TEXT NOT NULL,
+                response TEXT NOT NULL,
+                deferred_at TEXT NOT NULL
+            );",
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+}
+
+#[async_trait]
+impl DndRepository for SqliteDndRepository {
+    async fn get_settings(&self) -> DomainResult<DndSettings> {
+        let conn = self.connection.lock().await;
+        let settings: Option<String> = conn
+            .query_row(
+                "SELECT settings FROM dnd_settings WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match settings {
+            Some(settings) => serde_json::from_str(&settings).map_err(|e| {
+                DomainError::InternalError(format!("Failed to read DND settings: {}", e))
+            }),
+            None => Ok(DndSettings::default()),
+        }
+    }
+
+    async fn save_settings(&self, settings: &mut DndSettings) -> DomainResult<()> {
+        settings.updated_at = Utc::now();
+        let json = serde_json::to_string(settings).map_err(|e| {
+            DomainError::InternalError(format!("Failed to write DND settings: {}", e))
+        })?;
+
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO dnd_settings (id, settings, updated_at) VALUES (1, ?1, ?2)",
+            params![json, settings.updated_at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    async fn hold(&self, notification_id: Uuid) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT OR IGNORE INTO dnd_held_notifications (notification_id, held_at) VALUES (?1, ?2)",
+            params![
+                notification_id.to_string(),
+                Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true)
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn is_held(&self, notification_id: Uuid) -> DomainResult<bool> {
+        let conn = self.connection.lock().await;
+        let held = conn
+            .query_row(
+                "SELECT 1 FROM dnd_held_notifications WHERE notification_id = ?1",
+                params![notification_id.to_string()],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(held.is_some())
+    }
+
+    async fn find_held(&self) -> DomainResult<Vec<Uuid>> {
+        let conn = self.connection.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT notification_id FROM dnd_held_notifications ORDER BY held_at ASC, rowid ASC",
+        )?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut ids = Vec::new();
+        for id in rows {
+            ids.push(Uuid::parse_str(&id?).map_err(|e| {
+                DomainError::InternalError(format!("Invalid held notification id: {}", e))
+            })?);
+        }
+        Ok(ids)
+    }
+
+    async fn release(&self, notification_ids: &[Uuid]) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        for id in notification_ids {
+            conn.execute(
+                "DELETE FROM dnd_held_notifications WHERE notification_id = ?1",
+                params![id.to_string()],
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn defer_response(&self, notification_id: Uuid, response: &str) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT INTO dnd_deferred_responses (notification_id, response, deferred_at) VALUES (?1, ?2, ?3)",
+            params![
+                notification_id.to_string(),
+                response,
+                Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true)
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn find_deferred_responses(&self) -> DomainResult<Vec<(i64, Uuid, String)>> {
+        let conn = self.connection.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT id, notification_id, response FROM dnd_deferred_responses ORDER BY id ASC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(id, notification_id, response)| {
+                Uuid::parse_str(&notification_id)
+                    .map(|notification_id| (id, notification_id, response))
+                    .map_err(|e| {
+                        DomainError::InternalError(format!("Invalid deferred response id: {}", e))
+                    })
+            })
+            .collect()
+    }
+
+    async fn remove_deferred_response(&self, id: i64) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "DELETE FROM dnd_deferred_responses WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::NotificationPriority;
+
+    #[tokio::test]
+    async fn test_sqlite_dnd_repository() {
+        let repo = SqliteDndRepository::new(":memory:").unwrap();
+        assert_eq!(
+            repo.get_settings().await.unwrap().threshold,
+            NotificationPriority::High
+        );
+
+        let mut settings = DndSettings {
+            manual_enabled: true,
+            vip_senders: vec!["boss@example.com".to_string()],
+            ..DndSettings::default()
+        };
+        repo.save_settings(&mut settings).await.unwrap();
+        assert_eq!(repo.get_settings().await.unwrap(), settings);
+
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        repo.hold(first).await.unwrap();
+        repo.hold(second).await.unwrap();
+        repo.hold(first).await.unwrap();
+        assert_eq!(repo.find_held().await.unwrap(), vec![first, second]);
+        assert!(repo.is_held(second).await.unwrap());
+
+        repo.release(&[first]).await.unwrap();
+        assert_eq!(repo.find_held().await.unwrap(), vec![second]);
+        assert!(!repo.is_held(first).await.unwrap());
+
+        repo.defer_response(second, "On it").await.unwrap();
+        repo.defer_response(first, "Thanks").await.unwrap();
+        let deferred = repo.find_deferred_responses().await.unwrap();
+        assert_eq!(
+            deferred
+                .iter()
+                .map(|(_, id, response)| (*id, response.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(second, "On it"), (first, "Thanks")]
+        );
+        repo.remove_deferred_response(deferred[0].0).await.unwrap();
+        let remaining = repo.find_deferred_responses().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].1, first);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::domain::{
+    entities::EscalationPolicy,
+    error::{DomainError, DomainResult},
+    repositories::EscalationPolicyRepository,
+};
+use crate::infrastructure::repositories::sqlite_base::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqliteEscalationPolicyRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteEscalationPolicyRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS escalation_policies (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                enabled BOOLEAN NOT NULL DEFAULT 1,
+                position INTEGER NOT NULL,
+                conditions TEXT NOT NULL,
+                steps TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+                [],
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+}
+
+impl SqliteRepository<EscalationPolicy> for SqliteEscalationPolicyRepository {
+    fn table_name(&self) -> &str {
+        "escalation_policies"
+    }
+
+    fn column_names(&self) -> Vec<&str> {
+        vec![
+            "id",
+            "name",
+            "enabled",
+            "position",
+            "conditions",
+            "steps",
+            "created_at",
+            "updated_at",
+        ]
+    }
+
+    fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.connection
+    }
+
+    fn map_row(&self, row: &Row) -> rusqlite::Result<EscalationPolicy> {
+        Ok(EscalationPolicy {
+            id: Uuid::parse_str(&row.get::<_, String>("id")?).unwrap(),
+            name: row.get("name")?,
+            enabled: row.get("enabled")?,
+            position: row.get("position")?,
+            conditions: serde_json::from_str(&row.get::<_, String>("conditions")?).unwrap(),
+            steps: serde_json::from_str(&row.get::<_, String>("steps")?).unwrap(),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("updated_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    fn map_entity_to_params(
+        &self,
+        policy: &EscalationPolicy,
+    ) -> Vec<Box<dyn rusqlite::ToSql + Send>> {
+        vec![
+            Box::new(policy.id.to_string()),
+            Box::new(policy.name.clone()),
+            Box::new(policy.enabled),
+            Box::new(policy.position),
+            Box::new(serde_json::to_string(&policy.conditions).unwrap()),
+            Box::new(serde_json::to_string(&policy.steps).unwrap()),
+            Box::new(policy.created_at.to_rfc3339()),
+            Box::new(policy.updated_at.to_rfc3339()),
+        ]
+    }
+}
+
+#[async_trait]
+impl EscalationPolicyRepository for SqliteEscalationPolicyRepository {
+    async fn save(&self, policy: &mut EscalationPolicy) -> DomainResult<()> {
+        policy.updated_at = Utc::now();
+        <Self as SqliteRepository<EscalationPolicy>>::save(self, policy).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<EscalationPolicy>> {
+        <Self as SqliteRepository<EscalationPolicy>>::find_by_id(self, id).await
+    }
+
+    async fn find_all(&self) -> DomainResult<Vec<EscalationPolicy>> {
+        let conn = self.connection().lock().await;
+        let query = format!(
+            "SELECT * FROM {} ORDER BY position ASC, created_at ASC",
+            self.table_name()
+        );
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map([], |row| self.map_row(row))?;
+
+        let mut policies = Vec::new();
+        for policy in rows {
+            policies.push(policy?);
+        }
+        Ok(policies)
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        <Self as SqliteRepository<EscalationPolicy>>::delete(self, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        EscalationAction, EscalationStep, NotificationPriority, RuleCondition,
+    };
+
+    #[tokio::test]
+    async fn test_sqlite_escalation_policy_repository() {
+        let repo = SqliteEscalationPolicyRepository::new(":memory:").unwrap();
+
+        let mut policy = EscalationPolicy::new(
+            "Urgent".to_string(),
+            vec![RuleCondition::PriorityAtLeast {
+                priority: NotificationPriority::High,
+            }],
+            vec![EscalationStep {
+                after_minutes: 60,
+                actions: vec![EscalationAction::Forward {
+                    to: "oncall@example.com".to_string(),
+                }],
+            }],
+        );
+        policy.position = 1;
+        let mut catch_all = EscalationPolicy::new(
+            "Everything else".to_string(),
+            Vec::new(),
+            vec![EscalationStep {
+                after_minutes: 240,
+                actions: vec![EscalationAction::Realert],
+            }],
+        );
+        catch_all.position = 2;
+
+        EscalationPolicyRepository::save(&repo, &mut catch_all)
+            .await
+            .unwrap();
+        EscalationPolicyRepository::save(&repo, &mut policy)
+            .await
+            .unwrap();
+
+        let policies = EscalationPolicyRepository::find_all(&repo).await.unwrap();
+        assert_eq!(
+            policies.iter().map(|p| p.id).collect::<Vec<_>>(),
+            vec![policy.id, catch_all.id]
+        );
+        assert_eq!(policies[0].conditions, policy.conditions);
+        assert_eq!(policies[0].steps, policy.steps);
+
+        EscalationPolicyRepository::delete(&repo, policy.id)
+            .await
+            .unwrap();
+        assert!(EscalationPolicyRepository::find_by_id(&repo, policy.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
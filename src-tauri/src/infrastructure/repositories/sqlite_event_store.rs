@@ -0,0 +1,250 @@
+use crate::domain::{
+    error::{DomainError, DomainResult},
+    events::{DomainEvent, EventEnvelope},
+    repositories::EventStore,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, types::Type, Connection, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+
+pub struct SqliteEventStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteEventStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        // Rows are immutable once written: the triggers reject updates and deletes.
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS events (
+                sequence INTEGER PRIMARY KEY,
+                category TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                occurred_at TEXT NOT NULL,
+                payload TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_events_occurred_at ON events (occurred_at);
+            CREATE TRIGGER IF NOT EXISTS events_no_update BEFORE UPDATE ON events
+            BEGIN
+                SELECT RAISE(ABORT, 'events are append-only');
+            END;
+            CREATE TRIGGER IF NOT EXISTS events_no_delete BEFORE DELETE ON events
+            BEGIN
+                SELECT RAISE(ABORT, 'events are append-only');
+            END;",
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Fixed-width UTC timestamps so that text comparison matches time order
+    fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+        timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+
+    fn conversion_failure(
+        row: &Row,
+        column: &str,
+        error: impl std::error::Error + Send + Sync + 'static,
+    ) -> rusqlite::Error {
+        rusqlite::Error::FromSqlConversionFailure(
+            row.as_ref().column_index(column).unwrap_or_default(),
+            Type::Text,
+            Box::new(error),
+        )
+    }
+
+    fn map_row(row: &Row) -> rusqlite::Result<EventEnvelope> {
+        let event: DomainEvent = serde_json::from_str(&row.get::<_, String>("payload")?)
+            .map_err(|e| Self::conversion_failure(row, "payload", e))?;
+        let occurred_at = DateTime::parse_from_rfc3339(&row.get::<_, String>("occurred_at")?)
+            .map_err(|e| Self::conversion_failure(row, "occurred_at", e))?
+            .with_timezone(&Utc);
+
+        Ok(EventEnvelope {
+            sequence: row.get::<_, i64>("sequence")? as u64,
+            category: event.category(),
+            occurred_at,
+            event,
+        })
+    }
+
+    fn collect(
+        rows: impl Iterator<Item = rusqlite::Result<EventEnvelope>>,
+    ) -> DomainResult<Vec<EventEnvelope>> {
+        let mut envelopes = Vec::new();
+        for envelope in rows {
+            envelopes.push(envelope?);
+        }
+        Ok(envelopes)
+    }
+}
+
+#[async_trait]
+impl EventStore for SqliteEventStore {
+    async fn append(&self, envelope: &EventEnvelope) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT INTO events (sequence, category, event_type, occurred_at, payload)
+             VALUES (?, ?, ?, ?, ?)",
+            params![
+                envelope.sequence as i64,
+                envelope.category.as_str(),
+                envelope.event.event_type(),
+                Self::format_timestamp(&envelope.occurred_at),
+                serde_json::to_string(&envelope.event)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn latest_sequence(&self) -> DomainResult<u64> {
+        let conn = self.connection.lock().await;
+        let sequence: Option<i64> =
+            conn.query_row("SELECT MAX(sequence) FROM events", [], |row| row.get(0))?;
+        Ok(sequence.unwrap_or(0) as u64)
+    }
+
+    async fn find_after(
+        &self,
+        after_sequence: u64,
+        limit: usize,
+    ) -> DomainResult<Vec<EventEnvelope>> {
+        let conn = self.connection.lock().await;
+        let mut stmt =
+            conn.prepare("SELECT * FROM events WHERE sequence > ? ORDER BY sequence ASC LIMIT ?")?;
+        let rows = stmt.query_map(params![after_sequence as i64, limit as i64], Self::map_row)?;
+        Self::collect(rows)
+    }
+
+    async fn find_from(
+        &self,
+        from: DateTime<Utc>,
+        after_sequence: u64,
+        limit: usize,
+    ) -> DomainResult<Vec<EventEnvelope>> {
+        let conn = self.connection.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT * FROM events WHERE occurred_at >= ? AND sequence > ?
+             ORDER BY sequence ASC LIMIT ?",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                Self::format_timestamp(&from),
+                after_sequence as i64,
+                limit as i64
+            ],
+            Self::map_row,
+        )?;
+        Self::collect(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::{NotificationEvent, ServiceEvent};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_append_and_read_back() {
+        let store = SqliteEventStore::new(":memory:").unwrap();
+        assert_eq!(store.latest_sequence().await.unwrap(), 0);
+
+        for sequence in 1..=3 {
+            store
+                .append(&EventEnvelope::new(
+                    sequence,
+                    NotificationEvent::notification_read(Uuid::new_v4()).into(),
+                ))
+                .await
+                .unwrap();
+        }
+        store
+            .append(&EventEnvelope::new(
+                4,
+                ServiceEvent::sync_completed(None, 2, 2, 0).into(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(store.latest_sequence().await.unwrap(), 4);
+
+        let events = store.find_after(1, 2).await.unwrap();
+        let sequences: Vec<u64> = events.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![2, 3]);
+
+        let last = store.find_after(3, 10).await.unwrap();
+        assert!(matches!(
+            last[0].event,
+            DomainEvent::Service(ServiceEvent::SyncCompleted {
+                created_count: 2,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_find_from_filters_by_time() {
+        let store = SqliteEventStore::new(":memory:").unwrap();
+
+        let mut old = EventEnvelope::new(1, ServiceEvent::sync_started(None).into());
+        old.occurred_at = Utc::now() - chrono::Duration::days(2);
+        store.append(&old).await.unwrap();
+        store
+            .append(&EventEnvelope::new(
+                2,
+                ServiceEvent::sync_started(None).into(),
+            ))
+            .await
+            .unwrap();
+
+        let from = Utc::now() - chrono::Duration::days(1);
+        let events = store.find_from(from, 0, 10).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].sequence, 2);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_row_is_an_error() {
+        let store = SqliteEventStore::new(":memory:").unwrap();
+        let payload =
+            serde_json::to_string(&DomainEvent::from(ServiceEvent::sync_started(None))).unwrap();
+        store
+            .connection
+            .lock()
+            .await
+            .execute(
+                "INSERT INTO events (sequence, category, event_type, occurred_at, payload)
+                 VALUES (1, 'Service', 'SyncStarted', 'yesterday', ?)",
+                params![payload],
+            )
+            .unwrap();
+
+        assert!(store.find_after(0, 10).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_events_are_append_only() {
+        let store = SqliteEventStore::new(":memory:").unwrap();
+        let envelope = EventEnvelope::new(1, ServiceEvent::sync_started(None).into());
+        store.append(&envelope).await.unwrap();
+
+        assert!(store.append(&envelope).await.is_err());
+
+        let conn = store.connection.lock().await;
+        assert!(conn.execute("DELETE FROM events", []).is_err());
+        assert!(conn
+            .execute("UPDATE events SET event_type = 'Tampered'", [])
+            .is_err());
+    }
+}
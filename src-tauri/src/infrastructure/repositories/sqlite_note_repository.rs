@@ -0,0 +1,263 @@
+use crate::domain::{
+    entities::{Note, NoteTarget, Notification},
+    error::{DomainError, DomainResult},
+    repositories::NoteRepository,
+};
+use crate::infrastructure::repositories::sqlite_base::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params_from_iter, Connection, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqliteNoteRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteNoteRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        // Exactly one of notification_id and thread_key is set
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS notes (
+                id TEXT PRIMARY KEY,
+                notification_id TEXT,
+                thread_key TEXT,
+                body TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_notes_notification_id ON notes (notification_id);
+            CREATE INDEX IF NOT EXISTS idx_notes_thread_key ON notes (thread_key);",
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    async fn query(&self, sql: &str, params: Vec<String>) -> DomainResult<Vec<Note>> {
+        let conn = self.connection().lock().await;
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(params_from_iter(params), |row| self.map_row(row))?;
+
+        let mut notes = Vec::new();
+        for note in rows {
+            notes.push(note?);
+        }
+        Ok(notes)
+    }
+}
+
+/// Escapes the LIKE wildcards of a search term
+fn like_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+impl SqliteRepository<Note> for SqliteNoteRepository {
+    fn table_name(&self) -> &str {
+        "notes"
+    }
+
+    fn column_names(&self) -> Vec<&str> {
+        vec![
+            "id",
+            "notification_id",
+            "thread_key",
+            "body",
+            "created_at",
+            "updated_at",
+        ]
+    }
+
+    fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.connection
+    }
+
+    fn map_row(&self, row: &Row) -> rusqlite::Result<Note> {
+        let target = match row.get::<_, Option<String>>("notification_id")? {
+            Some(id) => NoteTarget::Notification {
+                notification_id: Uuid::parse_str(&id).unwrap(),
+            },
+            None => NoteTarget::Thread {
+                thread_key: row.get("thread_key")?,
+            },
+        };
+
+        Ok(Note {
+            id: Uuid::parse_str(&row.get::<_, String>("id")?).unwrap(),
+            target,
+            body: row.get("body")?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("updated_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    fn map_entity_to_params(&self, note: &Note) -> Vec<Box<dyn rusqlite::ToSql + Send>> {
+        let (notification_id, thread_key) = match &note.target {
+            NoteTarget::Notification { notification_id } => {
+                (Some(notification_id.to_string()), None)
+            }
+            NoteTarget::Thread { thread_key } => (None, Some(thread_key.clone())),
+        };
+        vec![
+            Box::new(note.id.to_string()),
+            Box::new(notification_id),
+            Box::new(thread_key),
+            Box::new(note.body.clone()),
+            Box::new(note.created_at.to_rfc3339()),
+            Box::new(note.updated_at.to_rfc3339()),
+        ]
+    }
+}
+
+#[async_trait]
+impl NoteRepository for SqliteNoteRepository {
+    async fn save(&self, note: &mut Note) -> DomainResult<()> {
+        note.updated_at = Utc::now();
+        <Self as SqliteRepository<Note>>::save(self, note).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Note>> {
+        <Self as SqliteRepository<Note>>::find_by_id(self, id).await
+    }
+
+    async fn find_by_target(&self, target: &NoteTarget) -> DomainResult<Vec<Note>> {
+        match target {
+            NoteTarget::Notification { notification_id } => {
+                self.query(
+                    "SELECT * FROM notes WHERE notification_id = ?1 ORDER BY created_at",
+                    vec![notification_id.to_string()],
+                )
+                .await
+            }
+            NoteTarget::Thread { thread_key } => {
+                self.query(
+                    "SELECT * FROM notes WHERE thread_key = ?1 ORDER BY created_at",
+                    vec![thread_key.clone()],
+                )
+                .await
+            }
+        }
+    }
+
+    async fn find_for_notification(&self, notification: &Notification) -> DomainResult<Vec<Note>> {
+        self.query(
+            "SELECT * FROM notes WHERE notification_id = ?1 OR thread_key = ?2
+             ORDER BY created_at",
+            vec![notification.id.to_string(), notification.thread_key()],
+        )
+        .await
+    }
+
+    async fn search(&self, query: &str) -> DomainResult<Vec<Note>> {
+        let patterns: Vec<String> = query.split_whitespace().map(like_pattern).collect();
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conditions = vec!["body LIKE ? ESCAPE '\\'"; patterns.len()].join(" AND ");
+        let sql = format!(
+            "SELECT * FROM notes WHERE {} ORDER BY updated_at DESC",
+            conditions
+        );
+        self.query(&sql, patterns).await
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        <Self as SqliteRepository<Note>>::delete(self, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{NotificationMetadata, NotificationPriority, NotificationSource},
+        repositories::DynNoteRepository,
+    };
+
+    #[tokio::test]
+    async fn test_sqlite_note_repository() {
+        let repo: DynNoteRepository = Arc::new(SqliteNoteRepository::new(":memory:").unwrap());
+        let notification = Notification::new(
+            "Re: outage".to_string(),
+            "Still failing".to_string(),
+            NotificationPriority::High,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                thread_key: Some("outage".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let mut waiting = Note::new(
+            NoteTarget::Notification {
+                notification_id: notification.id,
+            },
+            "Waiting on **Alice**".to_string(),
+        );
+        let mut incident = Note::new(
+            NoteTarget::Thread {
+                thread_key: "outage".to_string(),
+            },
+            "Relates to INC-42, 100% of requests fail".to_string(),
+        );
+        let mut unrelated = Note::new(
+            NoteTarget::Thread {
+                thread_key: "billing".to_string(),
+            },
+            "Ask Alice about the invoice".to_string(),
+        );
+        for note in [&mut waiting, &mut incident, &mut unrelated] {
+            repo.save(note).await.unwrap();
+        }
+
+        assert_eq!(
+            repo.find_by_id(waiting.id).await.unwrap(),
+            Some(waiting.clone())
+        );
+        let ids: Vec<Uuid> = repo
+            .find_for_notification(&notification)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|note| note.id)
+            .collect();
+        assert_eq!(ids, vec![waiting.id, incident.id]);
+        assert_eq!(
+            repo.find_by_target(&unrelated.target).await.unwrap().len(),
+            1
+        );
+
+        let found = repo.search("alice").await.unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            repo.search("inc-42 100%").await.unwrap(),
+            vec![incident.clone()]
+        );
+        assert!(repo.search("10_%").await.unwrap().is_empty());
+        assert!(repo.search("  ").await.unwrap().is_empty());
+
+        repo.delete(waiting.id).await.unwrap();
+        assert!(repo.find_by_id(waiting.id).await.unwrap().is_none());
+    }
+}
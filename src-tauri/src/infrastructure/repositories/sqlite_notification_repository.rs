@@ -1,15 +1,20 @@
 use crate::domain::{
-    entities::{Notification, NotificationMetadata, NotificationSource, NotificationStatus},
+    entities::{
+        rule::{custom_data_matches, regex_matches},
+        thread::STANDALONE_THREAD_PREFIX,
+        Notification, NotificationMetadata, NotificationPriority, NotificationSource,
+        NotificationStatus, RuleCondition, SavedView,
+    },
     error::DomainError,
-    repositories::NotificationRepository,
+    repositories::{CoalescingMerge, NotificationRepository},
 };
 use crate::infrastructure::repositories::{
     cached_repository::{CachedRepository, Repository},
-    sqlite_base::SqliteRepository,
+    sqlite_base::{ensure_column, SqliteRepository},
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, Row};
+use rusqlite::{functions::FunctionFlags, params, Connection, Row, ToSql};
 use serde_json::Value;
 use std::path::Path;
 use std::sync::Arc;
@@ -70,6 +75,40 @@ impl NotificationRepository for CachedSqliteNotificationRepository {
         self.base_repo.find_by_source(source).await
     }
 
+    async fn find_by_thread_key(&self, thread_key: &str) -> Result<Vec<Notification>, DomainError> {
+        self.base_repo.find_by_thread_key(thread_key).await
+    }
+
+    async fn find_by_coalescing_key(&self, key: &str) -> Result<Vec<Notification>, DomainError> {
+        self.base_repo.find_by_coalescing_key(key).await
+    }
+
+    async fn save_coalesced(
+        &self,
+        key: &str,
+        merge: CoalescingMerge,
+    ) -> Result<Notification, DomainError> {
+        let notification = self.base_repo.save_coalesced(key, merge).await?;
+        self.inner.invalidate(notification.id).await;
+        Ok(notification)
+    }
+
+    async fn find_by_tag(&self, tag: &str) -> Result<Vec<Notification>, DomainError> {
+        self.base_repo.find_by_tag(tag).await
+    }
+
+    async fn count_by_tag(&self) -> Result<Vec<(String, usize)>, DomainError> {
+        self.base_repo.count_by_tag().await
+    }
+
+    async fn find_in_view(&self, view: &SavedView) -> Result<Vec<Notification>, DomainError> {
+        self.base_repo.find_in_view(view).await
+    }
+
+    async fn count_in_view(&self, view: &SavedView) -> Result<(usize, usize), DomainError> {
+        self.base_repo.count_in_view(view).await
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
         let result = NotificationRepository::delete(&*self.base_repo, id).await;
         if result.is_ok() {
@@ -90,7 +129,7 @@ impl NotificationRepository for CachedSqliteNotificationRepository {
 #[async_trait]
 impl Repository<Notification> for SqliteNotificationRepository {
     async fn save(&self, entity: &mut Notification) -> Result<(), DomainError> {
-        <Self as SqliteRepository<Notification>>::save(self, entity).await
+        self.save_with_tags(entity).await
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Notification>, DomainError> {
@@ -102,7 +141,7 @@ impl Repository<Notification> for SqliteNotificationRepository {
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        <Self as SqliteRepository<Notification>>::delete(self, id).await
+        self.delete_with_tags(id).await
     }
 }
 
@@ -127,15 +166,176 @@ impl SqliteNotificationRepository {
                     created_at TEXT NOT NULL,
                     updated_at TEXT NOT NULL,
                     read_at TEXT,
-                    action_taken_at TEXT
+                    action_taken_at TEXT,
+                    snoozed_until TEXT,
+                    snoozed_from TEXT,
+                    thread_key TEXT,
+                    aggregate TEXT,
+                    coalescing_key TEXT,
+                    contact TEXT,
+                    contact_id TEXT,
+                    priority_score TEXT,
+                    score REAL,
+                    body TEXT,
+                    attachments TEXT,
+                    escalation TEXT,
+                    sender TEXT,
+                    search_text TEXT
                 )",
                 [],
             )
             .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
 
-        Ok(Self {
+        for (column, definition) in [
+            ("snoozed_until", "TEXT"),
+            ("snoozed_from", "TEXT"),
+            ("thread_key", "TEXT"),
+            ("aggregate", "TEXT"),
+            ("coalescing_key", "TEXT"),
+            ("contact", "TEXT"),
+            ("contact_id", "TEXT"),
+            ("priority_score", "TEXT"),
+            ("score", "REAL"),
+            ("body", "TEXT"),
+            ("attachments", "TEXT"),
+            ("escalation", "TEXT"),
+            ("sender", "TEXT"),
+            ("search_text", "TEXT"),
+        ] {
+            ensure_column(&connection, "notifications", column, definition).map_err(|e| {
+                DomainError::InternalError(format!("Failed to migrate table: {}", e))
+            })?;
+        }
+
+        for index in [
+            "CREATE INDEX IF NOT EXISTS idx_notifications_thread_key
+                ON notifications(thread_key)",
+            "CREATE INDEX IF NOT EXISTS idx_notifications_coalescing_key
+                ON notifications(coalescing_key)",
+            "CREATE INDEX IF NOT EXISTS idx_notifications_contact_id
+                ON notifications(contact_id)",
+            "CREATE INDEX IF NOT EXISTS idx_notifications_score
+                ON notifications(score)",
+        ] {
+            connection.execute(index, []).map_err(|e| {
+                DomainError::InternalError(format!("Failed to create index: {}", e))
+            })?;
+        }
+
+        // Tags are kept as JSON on the notification and mirrored here so
+        // they can be looked up and counted through an index
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS notification_tags (
+                    notification_id TEXT NOT NULL,
+                    tag TEXT NOT NULL COLLATE NOCASE,
+                    PRIMARY KEY (notification_id, tag)
+                );
+                CREATE INDEX IF NOT EXISTS idx_notification_tags_tag
+                    ON notification_tags(tag);
+                INSERT OR IGNORE INTO notification_tags (notification_id, tag)
+                    SELECT n.id, json_each.value FROM notifications n, json_each(n.tags)
+                    WHERE json_valid(n.tags);",
+            )
+            .map_err(|e| {
+                DomainError::InternalError(format!("Failed to create tag index: {}", e))
+            })?;
+
+        register_functions(&connection).map_err(|e| {
+            DomainError::InternalError(format!("Failed to register functions: {}", e))
+        })?;
+
+        let repository = Self {
             connection: Arc::new(Mutex::new(connection)),
-        })
+        };
+        repository.fill_search_columns()?;
+        Ok(repository)
+    }
+
+    /// Derives the sender and search text of rows stored before those
+    /// columns existed
+    fn fill_search_columns(&self) -> Result<(), DomainError> {
+        let mut conn = self.connection.try_lock().map_err(|e| {
+            DomainError::InternalError(format!("Failed to lock the database: {}", e))
+        })?;
+        let tx = conn.transaction()?;
+        let stale = {
+            let query = format!(
+                "SELECT * FROM {} WHERE search_text IS NULL",
+                self.table_name()
+            );
+            let mut stmt = tx.prepare(&query)?;
+            let rows = stmt.query_map([], |row| self.map_row(row))?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        for notification in stale {
+            tx.execute(
+                &format!(
+                    "UPDATE {} SET sender = ?, search_text = ? WHERE id = ?",
+                    self.table_name()
+                ),
+                params![
+                    notification.sender(),
+                    notification.search_text(),
+                    notification.id.to_string()
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    async fn save_with_tags(&self, notification: &Notification) -> Result<(), DomainError> {
+        let mut conn = self.connection().lock().await;
+        let tx = conn.transaction()?;
+        self.write(&tx, notification)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Writes the notification row and its tags on `conn`, which may be a transaction
+    fn write(&self, conn: &Connection, notification: &Notification) -> Result<(), DomainError> {
+        let columns = self.column_names();
+        let query = format!(
+            "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+            self.table_name(),
+            columns.join(", "),
+            std::iter::repeat_n("?", columns.len())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        conn.execute(
+            &query,
+            rusqlite::params_from_iter(self.map_entity_to_params(notification)),
+        )?;
+
+        let id = notification.id.to_string();
+        conn.execute(
+            "DELETE FROM notification_tags WHERE notification_id = ?",
+            params![id],
+        )?;
+        for tag in &notification.metadata.tags {
+            conn.execute(
+                "INSERT OR IGNORE INTO notification_tags (notification_id, tag) VALUES (?, ?)",
+                params![id, tag],
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn delete_with_tags(&self, id: Uuid) -> Result<(), DomainError> {
+        let mut conn = self.connection().lock().await;
+        let tx = conn.transaction()?;
+        tx.execute(
+            &format!("DELETE FROM {} WHERE id = ?", self.table_name()),
+            params![id.to_string()],
+        )?;
+        tx.execute(
+            "DELETE FROM notification_tags WHERE notification_id = ?",
+            params![id.to_string()],
+        )?;
+        tx.commit()?;
+        Ok(())
     }
 }
 
@@ -160,6 +360,20 @@ impl SqliteRepository<Notification> for SqliteNotificationRepository {
             "updated_at",
             "read_at",
             "action_taken_at",
+            "snoozed_until",
+            "snoozed_from",
+            "thread_key",
+            "aggregate",
+            "coalescing_key",
+            "contact",
+            "contact_id",
+            "priority_score",
+            "score",
+            "body",
+            "attachments",
+            "escalation",
+            "sender",
+            "search_text",
         ]
     }
 
@@ -187,6 +401,23 @@ impl SqliteRepository<Notification> for SqliteNotificationRepository {
                 url: row.get("url")?,
                 tags,
                 custom_data,
+                thread_key: row.get("thread_key")?,
+                aggregate: row
+                    .get::<_, Option<String>>("aggregate")?
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                contact: row
+                    .get::<_, Option<String>>("contact")?
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                score: row
+                    .get::<_, Option<String>>("priority_score")?
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                body: row
+                    .get::<_, Option<String>>("body")?
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                attachments: row
+                    .get::<_, Option<String>>("attachments")?
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
             },
             created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
                 .unwrap()
@@ -204,6 +435,17 @@ impl SqliteRepository<Notification> for SqliteNotificationRepository {
                     .unwrap()
                     .with_timezone(&Utc)
             }),
+            snoozed_until: row.get::<_, Option<String>>("snoozed_until")?.map(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .unwrap()
+                    .with_timezone(&Utc)
+            }),
+            snoozed_from: row
+                .get::<_, Option<String>>("snoozed_from")?
+                .and_then(|s| serde_json::from_str(&s).ok()),
+            escalation: row
+                .get::<_, Option<String>>("escalation")?
+                .and_then(|s| serde_json::from_str(&s).ok()),
         })
     }
 
@@ -232,6 +474,72 @@ impl SqliteRepository<Notification> for SqliteNotificationRepository {
             Box::new(notification.updated_at.to_rfc3339()),
             Box::new(notification.read_at.map(|dt| dt.to_rfc3339())),
             Box::new(notification.action_taken_at.map(|dt| dt.to_rfc3339())),
+            Box::new(notification.snoozed_until.map(|dt| dt.to_rfc3339())),
+            Box::new(
+                notification
+                    .snoozed_from
+                    .as_ref()
+                    .map(|status| serde_json::to_string(status).unwrap()),
+            ),
+            Box::new(notification.metadata.thread_key.clone()),
+            Box::new(
+                notification
+                    .metadata
+                    .aggregate
+                    .as_ref()
+                    .map(|aggregate| serde_json::to_string(aggregate).unwrap()),
+            ),
+            Box::new(
+                notification
+                    .metadata
+                    .aggregate
+                    .as_ref()
+                    .map(|aggregate| aggregate.key.clone()),
+            ),
+            Box::new(
+                notification
+                    .metadata
+                    .contact
+                    .as_ref()
+                    .map(|contact| serde_json::to_string(contact).unwrap()),
+            ),
+            Box::new(
+                notification
+                    .metadata
+                    .contact
+                    .as_ref()
+                    .map(|contact| contact.contact_id.to_string()),
+            ),
+            Box::new(
+                notification
+                    .metadata
+                    .score
+                    .as_ref()
+                    .map(|score| serde_json::to_string(score).unwrap()),
+            ),
+            Box::new(
+                notification
+                    .metadata
+                    .score
+                    .as_ref()
+                    .map(|score| score.score),
+            ),
+            Box::new(
+                notification
+                    .metadata
+                    .body
+                    .as_ref()
+                    .map(|body| serde_json::to_string(body).unwrap()),
+            ),
+            Box::new(serde_json::to_string(&notification.metadata.attachments).unwrap()),
+            Box::new(
+                notification
+                    .escalation
+                    .as_ref()
+                    .map(|escalation| serde_json::to_string(escalation).unwrap()),
+            ),
+            Box::new(notification.sender()),
+            Box::new(notification.search_text()),
         ]
     }
 }
@@ -239,7 +547,7 @@ impl SqliteRepository<Notification> for SqliteNotificationRepository {
 #[async_trait]
 impl NotificationRepository for SqliteNotificationRepository {
     async fn save(&self, notification: &mut Notification) -> Result<(), DomainError> {
-        <Self as SqliteRepository<Notification>>::save(self, notification).await
+        self.save_with_tags(notification).await
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Notification>, DomainError> {
@@ -286,8 +594,131 @@ impl NotificationRepository for SqliteNotificationRepository {
         Ok(notifications)
     }
 
+    async fn find_by_thread_key(&self, thread_key: &str) -> Result<Vec<Notification>, DomainError> {
+        let conn = self.connection().lock().await;
+        let query = format!("SELECT * FROM {} WHERE thread_key = ?", self.table_name());
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(params![thread_key], |row| self.map_row(row))?;
+
+        let mut notifications = Vec::new();
+        for notification in rows {
+            notifications.push(notification?);
+        }
+        Ok(notifications)
+    }
+
+    async fn find_by_coalescing_key(&self, key: &str) -> Result<Vec<Notification>, DomainError> {
+        let conn = self.connection().lock().await;
+        let query = format!(
+            "SELECT * FROM {} WHERE coalescing_key = ?",
+            self.table_name()
+        );
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(params![key], |row| self.map_row(row))?;
+
+        let mut notifications = Vec::new();
+        for notification in rows {
+            notifications.push(notification?);
+        }
+        Ok(notifications)
+    }
+
+    async fn save_coalesced(
+        &self,
+        key: &str,
+        merge: CoalescingMerge,
+    ) -> Result<Notification, DomainError> {
+        let mut conn = self.connection().lock().await;
+        let tx = conn.transaction()?;
+        let aggregates = {
+            let query = format!(
+                "SELECT * FROM {} WHERE coalescing_key = ?",
+                self.table_name()
+            );
+            let mut stmt = tx.prepare(&query)?;
+            let rows = stmt.query_map(params![key], |row| self.map_row(row))?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+
+        let notification = merge(aggregates);
+        self.write(&tx, &notification)?;
+        tx.commit()?;
+        Ok(notification)
+    }
+
+    async fn find_by_tag(&self, tag: &str) -> Result<Vec<Notification>, DomainError> {
+        let conn = self.connection().lock().await;
+        let query = format!(
+            "SELECT n.* FROM {} n
+             JOIN notification_tags t ON t.notification_id = n.id
+             WHERE t.tag = ?",
+            self.table_name()
+        );
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(params![tag.trim()], |row| self.map_row(row))?;
+
+        let mut notifications = Vec::new();
+        for notification in rows {
+            notifications.push(notification?);
+        }
+        Ok(notifications)
+    }
+
+    async fn count_by_tag(&self) -> Result<Vec<(String, usize)>, DomainError> {
+        let conn = self.connection().lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT MIN(tag), COUNT(*) FROM notification_tags GROUP BY tag ORDER BY tag",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+        })?;
+
+        let mut counts = Vec::new();
+        for count in rows {
+            counts.push(count?);
+        }
+        Ok(counts)
+    }
+
+    async fn find_in_view(&self, view: &SavedView) -> Result<Vec<Notification>, DomainError> {
+        let conn = self.connection().lock().await;
+        let (filter, values) = view_filter(&conn, view)?;
+        let query = format!("SELECT n.* FROM {} n WHERE {}", self.table_name(), filter);
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(values.iter().map(|v| v.as_ref())),
+            |row| self.map_row(row),
+        )?;
+
+        let mut notifications = Vec::new();
+        for notification in rows {
+            notifications.push(notification?);
+        }
+        Ok(notifications)
+    }
+
+    async fn count_in_view(&self, view: &SavedView) -> Result<(usize, usize), DomainError> {
+        let conn = self.connection().lock().await;
+        let (filter, mut values) = view_filter(&conn, view)?;
+        let query = format!(
+            "SELECT COUNT(*), COALESCE(SUM(n.status = ?), 0) FROM {} n WHERE {}",
+            self.table_name(),
+            filter
+        );
+        values.insert(
+            0,
+            Box::new(serde_json::to_string(&NotificationStatus::New)?),
+        );
+        let (total, unread) = conn.query_row(
+            &query,
+            rusqlite::params_from_iter(values.iter().map(|v| v.as_ref())),
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+        )?;
+        Ok((total as usize, unread as usize))
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        <Self as SqliteRepository<Notification>>::delete(self, id).await
+        self.delete_with_tags(id).await
     }
 
     async fn update_status(&self, id: Uuid, status: NotificationStatus) -> Result<(), DomainError> {
@@ -308,9 +739,153 @@ impl NotificationRepository for SqliteNotificationRepository {
     }
 }
 
+/// `REGEXP`, which SQLite leaves to the application, and the custom data
+/// lookup of rule conditions
+fn register_functions(connection: &Connection) -> rusqlite::Result<()> {
+    let flags = || FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    // `text REGEXP pattern` calls regexp(pattern, text)
+    connection.create_scalar_function("regexp", 2, flags(), |ctx| {
+        let pattern: String = ctx.get(0)?;
+        let text: Option<String> = ctx.get(1)?;
+        Ok(text.is_some_and(|text| regex_matches(&pattern, &text)))
+    })?;
+    connection.create_scalar_function("custom_data_matches", 3, flags(), |ctx| {
+        let data: Option<Value> = ctx
+            .get::<Option<String>>(0)?
+            .and_then(|data| serde_json::from_str(&data).ok());
+        let path: String = ctx.get(1)?;
+        let equals: Option<Value> = ctx
+            .get::<Option<String>>(2)?
+            .and_then(|equals| serde_json::from_str(&equals).ok());
+        Ok(custom_data_matches(data.as_ref(), &path, equals.as_ref()))
+    })
+}
+
+type SqlValues = Vec<Box<dyn ToSql>>;
+
+/// The `WHERE` clause selecting the notifications `n` shown in the view. A
+/// query word matches the notification or, when the notes share the
+/// database, a note on it or its thread.
+fn view_filter(conn: &Connection, view: &SavedView) -> Result<(String, SqlValues), DomainError> {
+    let mut clauses = Vec::new();
+    let mut values: SqlValues = Vec::new();
+
+    if view.statuses.is_empty() {
+        clauses.push("n.status != ?".to_string());
+        values.push(Box::new(serde_json::to_string(
+            &NotificationStatus::Deleted,
+        )?));
+    } else {
+        clauses.push(format!(
+            "n.status IN ({})",
+            vec!["?"; view.statuses.len()].join(", ")
+        ));
+        for status in &view.statuses {
+            values.push(Box::new(serde_json::to_string(status)?));
+        }
+    }
+
+    for condition in &view.conditions {
+        match condition {
+            RuleCondition::Source { source } => {
+                clauses.push("n.source = ?".to_string());
+                values.push(Box::new(serde_json::to_string(source)?));
+            }
+            RuleCondition::Title { pattern } => {
+                clauses.push("n.title REGEXP ?".to_string());
+                values.push(Box::new(pattern.clone()));
+            }
+            RuleCondition::Content { pattern } => {
+                clauses.push(
+                    "COALESCE(json_extract(n.body, '$.text'), n.content) REGEXP ?".to_string(),
+                );
+                values.push(Box::new(pattern.clone()));
+            }
+            RuleCondition::Tag { tag } => {
+                clauses.push(
+                    "EXISTS (SELECT 1 FROM notification_tags t
+                     WHERE t.notification_id = n.id AND t.tag = ?)"
+                        .to_string(),
+                );
+                values.push(Box::new(tag.clone()));
+            }
+            RuleCondition::PriorityAtLeast { priority } => {
+                let priorities: Vec<NotificationPriority> = [
+                    NotificationPriority::Low,
+                    NotificationPriority::Medium,
+                    NotificationPriority::High,
+                    NotificationPriority::Critical,
+                ]
+                .into_iter()
+                .filter(|p| p.rank() >= priority.rank())
+                .collect();
+                clauses.push(format!(
+                    "n.priority IN ({})",
+                    vec!["?"; priorities.len()].join(", ")
+                ));
+                for priority in priorities {
+                    values.push(Box::new(serde_json::to_string(&priority)?));
+                }
+            }
+            RuleCondition::Sender { pattern } => {
+                clauses.push(
+                    "(n.sender REGEXP ? OR json_extract(n.contact, '$.name') REGEXP ?)".to_string(),
+                );
+                values.push(Box::new(pattern.clone()));
+                values.push(Box::new(pattern.clone()));
+            }
+            RuleCondition::Vip => {
+                clauses.push("json_extract(n.contact, '$.vip') = 1".to_string());
+            }
+            RuleCondition::Contact { contact_id } => {
+                clauses.push("n.contact_id = ?".to_string());
+                values.push(Box::new(contact_id.to_string()));
+            }
+            RuleCondition::CustomData { path, equals } => {
+                clauses.push("custom_data_matches(n.custom_data, ?, ?)".to_string());
+                values.push(Box::new(path.clone()));
+                values.push(Box::new(
+                    equals.as_ref().map(serde_json::to_string).transpose()?,
+                ));
+            }
+        }
+    }
+
+    let terms = view.query_terms();
+    if !terms.is_empty() {
+        let notes: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'notes')",
+            [],
+            |row| row.get(0),
+        )?;
+        for term in terms {
+            let mut clause = "(instr(n.search_text, ?) > 0
+                OR instr(lower(json_extract(n.contact, '$.name')), ?) > 0"
+                .to_string();
+            values.push(Box::new(term.clone()));
+            values.push(Box::new(term.clone()));
+            if notes {
+                clause.push_str(
+                    "
+                OR EXISTS (SELECT 1 FROM notes
+                    WHERE (notes.notification_id = n.id
+                        OR notes.thread_key = COALESCE(n.thread_key, ? || n.id))
+                    AND instr(lower(notes.body), ?) > 0)",
+                );
+                values.push(Box::new(STANDALONE_THREAD_PREFIX));
+                values.push(Box::new(term));
+            }
+            clause.push(')');
+            clauses.push(clause);
+        }
+    }
+
+    Ok((clauses.join(" AND "), values))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::domain::NotificationPriority;
+    use crate::domain::{entities::SenderContact, NotificationPriority};
     use std::time::Duration;
 
     use super::*;
@@ -328,11 +903,15 @@ mod tests {
                 url: Some("https://example.com".to_string()),
                 tags: vec!["test".to_string()],
                 custom_data: Some(serde_json::json!({ "key": "value" })),
+                ..Default::default()
             },
             created_at: Utc::now(),
             updated_at: Utc::now(),
             read_at: None,
             action_taken_at: None,
+            snoozed_until: None,
+            snoozed_from: None,
+            escalation: None,
         }
     }
 
@@ -379,6 +958,7 @@ mod tests {
     async fn test_sqlite_repository() {
         let repo = SqliteNotificationRepository::new(":memory:").unwrap();
         let mut notification = create_test_notification().await;
+        notification.metadata.thread_key = Some("google:thread-1".to_string());
 
         // Test save
         NotificationRepository::save(&repo, &mut notification)
@@ -406,6 +986,65 @@ mod tests {
         assert_eq!(email.len(), 1);
         assert_eq!(email[0].id, notification.id);
 
+        // Test find by thread key
+        let thread = repo.find_by_thread_key("google:thread-1").await.unwrap();
+        assert_eq!(thread.len(), 1);
+        assert_eq!(
+            thread[0].metadata.thread_key.as_deref(),
+            Some("google:thread-1")
+        );
+        assert!(repo
+            .find_by_thread_key("google:thread-2")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Test find by coalescing key
+        let mut burst = create_test_notification().await;
+        burst.start_aggregate("github:octo/repo:PushEvent".to_string());
+        NotificationRepository::save(&repo, &mut burst)
+            .await
+            .unwrap();
+        let aggregates = repo
+            .find_by_coalescing_key("github:octo/repo:PushEvent")
+            .await
+            .unwrap();
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(aggregates[0].metadata.aggregate, burst.metadata.aggregate);
+        NotificationRepository::delete(&repo, burst.id)
+            .await
+            .unwrap();
+
+        // Test find by tag, through the notification_tags index
+        let mut tagged = create_test_notification().await;
+        tagged.metadata.tags = vec!["Release".to_string(), "ci".to_string()];
+        NotificationRepository::save(&repo, &mut tagged)
+            .await
+            .unwrap();
+        let releases = repo.find_by_tag("release").await.unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].id, tagged.id);
+        assert_eq!(
+            repo.count_by_tag().await.unwrap(),
+            vec![
+                ("ci".to_string(), 1),
+                ("Release".to_string(), 1),
+                ("test".to_string(), 1)
+            ]
+        );
+        tagged.metadata.tags = vec!["ci".to_string()];
+        NotificationRepository::save(&repo, &mut tagged)
+            .await
+            .unwrap();
+        assert!(repo.find_by_tag("release").await.unwrap().is_empty());
+        NotificationRepository::delete(&repo, tagged.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.count_by_tag().await.unwrap(),
+            vec![("test".to_string(), 1)]
+        );
+
         // Test update status
         repo.update_status(notification.id, NotificationStatus::Read)
             .await
@@ -425,4 +1064,110 @@ mod tests {
             .unwrap();
         assert!(deleted.is_none());
     }
+
+    #[tokio::test]
+    async fn test_find_in_view_matches_filters_and_query() {
+        let repo = SqliteNotificationRepository::new(":memory:").unwrap();
+        let mut notification = Notification::new(
+            "Review requested on #42".to_string(),
+            "Please look at the parser".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source: NotificationSource::Github,
+                tags: vec!["Review".to_string()],
+                contact: Some(SenderContact {
+                    contact_id: Uuid::new_v4(),
+                    name: "Grace Hopper".to_string(),
+                    vip: true,
+                    auto_reply: true,
+                }),
+                ..Default::default()
+            },
+        );
+        NotificationRepository::save(&repo, &mut notification)
+            .await
+            .unwrap();
+
+        // Unread GitHub with tag review from VIPs
+        let mut view = SavedView::new("VIP reviews".to_string());
+        view.statuses = vec![NotificationStatus::New];
+        view.conditions = vec![
+            RuleCondition::Source {
+                source: NotificationSource::Github,
+            },
+            RuleCondition::Tag {
+                tag: "review".to_string(),
+            },
+            RuleCondition::Vip,
+        ];
+        view.query = Some("PARSER grace".to_string());
+        let ids = |notifications: Vec<Notification>| -> Vec<Uuid> {
+            notifications.into_iter().map(|n| n.id).collect()
+        };
+        assert_eq!(
+            ids(repo.find_in_view(&view).await.unwrap()),
+            vec![notification.id]
+        );
+        view.query = Some("lexer".to_string());
+        assert!(repo.find_in_view(&view).await.unwrap().is_empty());
+        view.query = None;
+
+        notification.mark_as_read();
+        NotificationRepository::save(&repo, &mut notification)
+            .await
+            .unwrap();
+        assert!(repo.find_in_view(&view).await.unwrap().is_empty());
+
+        // Any status but deleted by default
+        view.statuses.clear();
+        assert_eq!(repo.count_in_view(&view).await.unwrap(), (1, 0));
+        repo.update_status(notification.id, NotificationStatus::Deleted)
+            .await
+            .unwrap();
+        assert_eq!(repo.count_in_view(&view).await.unwrap(), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_snooze_fields_survive_migration_of_old_schema() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        Connection::open(file.path())
+            .unwrap()
+            .execute(
+                "CREATE TABLE notifications (
+                    id TEXT PRIMARY KEY,
+                    title TEXT NOT NULL,
+                    content TEXT NOT NULL,
+                    priority TEXT NOT NULL,
+                    status TEXT NOT NULL,
+                    source TEXT NOT NULL,
+                    external_id TEXT,
+                    url TEXT,
+                    tags TEXT,
+                    custom_data TEXT,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    read_at TEXT,
+                    action_taken_at TEXT
+                )",
+                [],
+            )
+            .unwrap();
+
+        let repo = SqliteNotificationRepository::new(file.path()).unwrap();
+        let mut notification = create_test_notification().await;
+        notification.mark_action_required();
+        let until = Utc::now() + chrono::Duration::hours(2);
+        notification.snooze(until);
+        NotificationRepository::save(&repo, &mut notification)
+            .await
+            .unwrap();
+
+        let found = NotificationRepository::find_by_id(&repo, notification.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.status, NotificationStatus::Snoozed);
+        assert_eq!(found.snoozed_until.unwrap().timestamp(), until.timestamp());
+        assert_eq!(found.snoozed_from, Some(NotificationStatus::ActionRequired));
+    }
 }
@@ -0,0 +1,183 @@
+use crate::domain::{
+    entities::Rule,
+    error::{DomainError, DomainResult},
+    repositories::RuleRepository,
+};
+use crate::infrastructure::repositories::sqlite_base::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqliteRuleRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteRuleRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS rules (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                enabled BOOLEAN NOT NULL DEFAULT 1,
+                position INTEGER NOT NULL,
+                stop_processing BOOLEAN NOT NULL DEFAULT 0,
+                conditions TEXT NOT NULL,
+                actions TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+                [],
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+}
+
+impl SqliteRepository<Rule> for SqliteRuleRepository {
+    fn table_name(&self) -> &str {
+        "rules"
+    }
+
+    fn column_names(&self) -> Vec<&str> {
+        vec![
+            "id",
+            "name",
+            "enabled",
+            "position",
+            "stop_processing",
+            "conditions",
+            "actions",
+            "created_at",
+            "updated_at",
+        ]
+    }
+
+    fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.connection
+    }
+
+    fn map_row(&self, row: &Row) -> rusqlite::Result<Rule> {
+        Ok(Rule {
+            id: Uuid::parse_str(&row.get::<_, String>("id")?).unwrap(),
+            name: row.get("name")?,
+            enabled: row.get("enabled")?,
+            position: row.get("position")?,
+            stop_processing: row.get("stop_processing")?,
+            conditions: serde_json::from_str(&row.get::<_, String>("conditions")?).unwrap(),
+            actions: serde_json::from_str(&row.get::<_, String>("actions")?).unwrap(),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("updated_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    fn map_entity_to_params(&self, rule: &Rule) -> Vec<Box<dyn rusqlite::ToSql + Send>> {
+        vec![
+            Box::new(rule.id.to_string()),
+            Box::new(rule.name.clone()),
+            Box::new(rule.enabled),
+            Box::new(rule.position),
+            Box::new(rule.stop_processing),
+            Box::new(serde_json::to_string(&rule.conditions).unwrap()),
+            Box::new(serde_json::to_string(&rule.actions).unwrap()),
+            Box::new(rule.created_at.to_rfc3339()),
+            Box::new(rule.updated_at.to_rfc3339()),
+        ]
+    }
+}
+
+#[async_trait]
+impl RuleRepository for SqliteRuleRepository {
+    async fn save(&self, rule: &mut Rule) -> DomainResult<()> {
+        rule.updated_at = Utc::now();
+        <Self as SqliteRepository<Rule>>::save(self, rule).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Rule>> {
+        <Self as SqliteRepository<Rule>>::find_by_id(self, id).await
+    }
+
+    async fn find_all(&self) -> DomainResult<Vec<Rule>> {
+        let conn = self.connection().lock().await;
+        let query = format!(
+            "SELECT * FROM {} ORDER BY position ASC, created_at ASC",
+            self.table_name()
+        );
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map([], |row| self.map_row(row))?;
+
+        let mut rules = Vec::new();
+        for rule in rows {
+            rules.push(rule?);
+        }
+        Ok(rules)
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        <Self as SqliteRepository<Rule>>::delete(self, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{NotificationSource, RuleAction, RuleCondition};
+
+    #[tokio::test]
+    async fn test_sqlite_rule_repository() {
+        let repo = SqliteRuleRepository::new(":memory:").unwrap();
+
+        let mut second = Rule::new(
+            "Archive dependabot".to_string(),
+            vec![RuleCondition::Sender {
+                pattern: "dependabot".to_string(),
+            }],
+            vec![RuleAction::SkipAi],
+        );
+        second.position = 2;
+        let mut first = Rule::new(
+            "GitHub first".to_string(),
+            vec![RuleCondition::Source {
+                source: NotificationSource::Github,
+            }],
+            vec![RuleAction::AddTags {
+                tags: vec!["code".to_string()],
+            }],
+        );
+        first.position = 1;
+        first.stop_processing = true;
+
+        RuleRepository::save(&repo, &mut second).await.unwrap();
+        RuleRepository::save(&repo, &mut first).await.unwrap();
+
+        let rules = RuleRepository::find_all(&repo).await.unwrap();
+        assert_eq!(
+            rules.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![first.id, second.id]
+        );
+        assert!(rules[0].stop_processing);
+        assert_eq!(rules[1].conditions, second.conditions);
+        assert_eq!(rules[1].actions, second.actions);
+
+        RuleRepository::delete(&repo, first.id).await.unwrap();
+        assert!(RuleRepository::find_by_id(&repo, first.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(RuleRepository::find_all(&repo).await.unwrap().len(), 1);
+    }
+}
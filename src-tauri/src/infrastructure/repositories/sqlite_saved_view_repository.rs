@@ -0,0 +1,217 @@
+use crate::domain::{
+    entities::SavedView,
+    error::{DomainError, DomainResult},
+    repositories::SavedViewRepository,
+};
+use crate::infrastructure::repositories::sqlite_base::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params_from_iter, Connection, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const COLUMNS: [&str; 12] = [
+    "id",
+    "name",
+    "icon",
+    "pinned",
+    "position",
+    "statuses",
+    "query",
+    "conditions",
+    "sort",
+    "built_in",
+    "created_at",
+    "updated_at",
+];
+
+pub struct SqliteSavedViewRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteSavedViewRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS saved_views (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                icon TEXT,
+                pinned BOOLEAN NOT NULL DEFAULT 0,
+                position INTEGER NOT NULL DEFAULT 0,
+                statuses TEXT NOT NULL,
+                query TEXT,
+                conditions TEXT NOT NULL,
+                sort TEXT NOT NULL,
+                built_in BOOLEAN NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+                [],
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        // Built-in views are created once, then belong to the user like any other
+        for view in SavedView::built_in_views() {
+            connection
+                .execute(
+                    &format!(
+                        "INSERT OR IGNORE INTO saved_views ({}) VALUES ({})",
+                        COLUMNS.join(", "),
+                        vec!["?"; COLUMNS.len()].join(", ")
+                    ),
+                    params_from_iter(to_params(&view)),
+                )
+                .map_err(|e| {
+                    DomainError::InternalError(format!("Failed to create built-in views: {}", e))
+                })?;
+        }
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+}
+
+fn to_params(view: &SavedView) -> Vec<Box<dyn rusqlite::ToSql + Send>> {
+    vec![
+        Box::new(view.id.to_string()),
+        Box::new(view.name.clone()),
+        Box::new(view.icon.clone()),
+        Box::new(view.pinned),
+        Box::new(view.position),
+        Box::new(serde_json::to_string(&view.statuses).unwrap()),
+        Box::new(view.query.clone()),
+        Box::new(serde_json::to_string(&view.conditions).unwrap()),
+        Box::new(serde_json::to_string(&view.sort).unwrap()),
+        Box::new(view.built_in),
+        Box::new(view.created_at.to_rfc3339()),
+        Box::new(view.updated_at.to_rfc3339()),
+    ]
+}
+
+impl SqliteRepository<SavedView> for SqliteSavedViewRepository {
+    fn table_name(&self) -> &str {
+        "saved_views"
+    }
+
+    fn column_names(&self) -> Vec<&str> {
+        COLUMNS.to_vec()
+    }
+
+    fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.connection
+    }
+
+    fn map_row(&self, row: &Row) -> rusqlite::Result<SavedView> {
+        Ok(SavedView {
+            id: Uuid::parse_str(&row.get::<_, String>("id")?).unwrap(),
+            name: row.get("name")?,
+            icon: row.get("icon")?,
+            pinned: row.get("pinned")?,
+            position: row.get("position")?,
+            statuses: serde_json::from_str(&row.get::<_, String>("statuses")?).unwrap_or_default(),
+            query: row.get("query")?,
+            conditions: serde_json::from_str(&row.get::<_, String>("conditions")?)
+                .unwrap_or_default(),
+            sort: serde_json::from_str(&row.get::<_, String>("sort")?).unwrap_or_default(),
+            built_in: row.get("built_in")?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("updated_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    fn map_entity_to_params(&self, view: &SavedView) -> Vec<Box<dyn rusqlite::ToSql + Send>> {
+        to_params(view)
+    }
+}
+
+#[async_trait]
+impl SavedViewRepository for SqliteSavedViewRepository {
+    async fn save(&self, view: &mut SavedView) -> DomainResult<()> {
+        view.updated_at = Utc::now();
+        <Self as SqliteRepository<SavedView>>::save(self, view).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<SavedView>> {
+        <Self as SqliteRepository<SavedView>>::find_by_id(self, id).await
+    }
+
+    async fn find_all(&self) -> DomainResult<Vec<SavedView>> {
+        let conn = self.connection().lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT * FROM saved_views ORDER BY pinned DESC, position ASC, name COLLATE NOCASE ASC",
+        )?;
+        let rows = stmt.query_map([], |row| self.map_row(row))?;
+
+        let mut views = Vec::new();
+        for view in rows {
+            views.push(view?);
+        }
+        Ok(views)
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        <Self as SqliteRepository<SavedView>>::delete(self, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{NotificationSort, NotificationStatus, RuleCondition, NEEDS_REPLY_VIEW_ID},
+        repositories::DynSavedViewRepository,
+    };
+
+    #[tokio::test]
+    async fn test_sqlite_saved_view_repository() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let repo: DynSavedViewRepository =
+            Arc::new(SqliteSavedViewRepository::new(file.path()).unwrap());
+
+        let names: Vec<String> = repo
+            .find_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|view| view.name)
+            .collect();
+        assert_eq!(names, vec!["Needs reply", "Waiting on others", "Snoozed"]);
+
+        let mut reviews = SavedView::new("Reviews".to_string());
+        reviews.statuses = vec![NotificationStatus::New];
+        reviews.query = Some("parser".to_string());
+        reviews.conditions = vec![RuleCondition::Vip];
+        reviews.sort = NotificationSort::Score;
+        repo.save(&mut reviews).await.unwrap();
+        assert_eq!(repo.find_by_id(reviews.id).await.unwrap(), Some(reviews));
+
+        // Edits to built-in views survive reopening the database
+        let mut needs_reply = repo.find_by_id(NEEDS_REPLY_VIEW_ID).await.unwrap().unwrap();
+        needs_reply.name = "To answer".to_string();
+        repo.save(&mut needs_reply).await.unwrap();
+        let reopened = SqliteSavedViewRepository::new(file.path()).unwrap();
+        let needs_reply = SavedViewRepository::find_by_id(&reopened, NEEDS_REPLY_VIEW_ID)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(needs_reply.name, "To answer");
+        assert_eq!(
+            SavedViewRepository::find_all(&reopened)
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::domain::{
+    error::{DomainError, DomainResult},
+    repositories::SettingsRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Stores each group of settings as a JSON row keyed by feature
+pub struct SqliteSettingsRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteSettingsRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS app_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );",
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+}
+
+#[async_trait]
+impl SettingsRepository for SqliteSettingsRepository {
+    async fn get(&self, key: &str) -> DomainResult<Option<Value>> {
+        let conn = self.connection.lock().await;
+        let value: Option<String> = conn
+            .query_row(
+                "SELECT value FROM app_settings WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        value
+            .map(|value| {
+                serde_json::from_str(&value).map_err(|e| {
+                    DomainError::InternalError(format!("Failed to read {} settings: {}", key, e))
+                })
+            })
+            .transpose()
+    }
+
+    async fn save(&self, key: &str, value: &Value) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            params![key, value.to_string(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{load_settings, store_settings};
+    use crate::domain::services::coalescing::CoalescingConfig;
+
+    #[tokio::test]
+    async fn test_sqlite_settings_repository() {
+        let repo = SqliteSettingsRepository::new(":memory:").unwrap();
+        assert_eq!(repo.get("coalescing").await.unwrap(), None);
+        assert_eq!(
+            load_settings::<CoalescingConfig>(&repo, "coalescing")
+                .await
+                .unwrap(),
+            CoalescingConfig::default()
+        );
+
+        let config = CoalescingConfig {
+            window_minutes: 15,
+            ..CoalescingConfig::default()
+        };
+        store_settings(&repo, "coalescing", &config).await.unwrap();
+        assert_eq!(
+            load_settings::<CoalescingConfig>(&repo, "coalescing")
+                .await
+                .unwrap(),
+            config
+        );
+        assert_eq!(repo.get("scoring").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,422 @@
+use crate::domain::{
+    entities::{AiStatistics, BucketStatistics, NotificationStatistics, TimeBucket},
+    error::{DomainError, DomainResult},
+    repositories::StatisticsRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{named_params, Connection};
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Read-only aggregates over the `notifications` and `events` tables, which
+/// must live in the same database file
+pub struct SqliteStatisticsRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+/// Notifications received in the period, `julianday` so that the stored
+/// `+00:00` offsets compare with the bounds
+const IN_RANGE: &str =
+    "julianday(created_at) >= julianday(:from) AND julianday(created_at) < julianday(:to)";
+
+/// Events recorded in the period
+const EVENTS_IN_RANGE: &str =
+    "julianday(occurred_at) >= julianday(:from) AND julianday(occurred_at) < julianday(:to)";
+
+impl SqliteStatisticsRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Start of the bucket a timestamp column falls into, as RFC 3339
+    fn bucket_start(bucket: TimeBucket) -> &'static str {
+        match bucket {
+            TimeBucket::Hour => "strftime('%Y-%m-%dT%H:00:00Z', created_at)",
+            TimeBucket::Day => "strftime('%Y-%m-%dT00:00:00Z', created_at)",
+            // Next Sunday, then back to the Monday before it
+            TimeBucket::Week => {
+                "strftime('%Y-%m-%dT00:00:00Z', created_at, 'weekday 0', '-6 days')"
+            }
+            TimeBucket::Month => "strftime('%Y-%m-01T00:00:00Z', created_at)",
+        }
+    }
+
+    fn table_exists(conn: &Connection, table: &str) -> DomainResult<bool> {
+        Ok(conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table],
+            |row| row.get::<_, i64>(0),
+        )? > 0)
+    }
+
+    fn buckets(
+        conn: &Connection,
+        from: &str,
+        to: &str,
+        bucket: TimeBucket,
+    ) -> DomainResult<Vec<BucketStatistics>> {
+        // Custom sources are stored as {"Custom": name}, the others as a string
+        let sql = format!(
+            "SELECT {} AS bucket,
+                    COALESCE(json_extract(source, '$.Custom'), json_extract(source, '$')),
+                    json_extract(status, '$'),
+                    json_extract(priority, '$'),
+                    COUNT(*)
+             FROM notifications
+             WHERE {}
+             GROUP BY 1, 2, 3, 4
+             ORDER BY 1",
+            Self::bucket_start(bucket),
+            IN_RANGE
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(named_params! {":from": from, ":to": to}, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)? as usize,
+            ))
+        })?;
+
+        let mut buckets: Vec<BucketStatistics> = Vec::new();
+        for row in rows {
+            let (start, source, status, priority, count) = row?;
+            let start = DateTime::parse_from_rfc3339(&start)
+                .map_err(|e| DomainError::InternalError(format!("Invalid bucket {}", e)))?
+                .with_timezone(&Utc);
+            if buckets.last().map(|b| b.start) != Some(start) {
+                buckets.push(BucketStatistics {
+                    start,
+                    total: 0,
+                    by_source: BTreeMap::new(),
+                    by_status: BTreeMap::new(),
+                    by_priority: BTreeMap::new(),
+                });
+            }
+            let current = buckets.last_mut().unwrap();
+            current.total += count;
+            *current.by_source.entry(source).or_default() += count;
+            *current.by_status.entry(status).or_default() += count;
+            *current.by_priority.entry(priority).or_default() += count;
+        }
+        Ok(buckets)
+    }
+
+    /// Median of `column - created_at` in seconds, averaging the two middle
+    /// values when the count is even
+    fn median_seconds(
+        conn: &Connection,
+        column: &str,
+        from: &str,
+        to: &str,
+    ) -> DomainResult<Option<f64>> {
+        let sql = format!(
+            "WITH delays AS (
+                SELECT (julianday({column}) - julianday(created_at)) * 86400.0 AS seconds
+                FROM notifications
+                WHERE {column} IS NOT NULL AND {range}
+             )
+             SELECT AVG(seconds) FROM (
+                SELECT seconds FROM delays ORDER BY seconds
+                LIMIT 2 - (SELECT COUNT(*) FROM delays) % 2
+                OFFSET ((SELECT COUNT(*) FROM delays) - 1) / 2
+             )",
+            column = column,
+            range = IN_RANGE
+        );
+        Ok(
+            conn.query_row(&sql, named_params! {":from": from, ":to": to}, |row| {
+                row.get::<_, Option<f64>>(0)
+            })?,
+        )
+    }
+
+    /// Compares the AI verdict of each analysed notification with what the
+    /// user did with it afterwards
+    fn ai_statistics(conn: &Connection, from: &str, to: &str) -> DomainResult<AiStatistics> {
+        let sql = format!(
+            "WITH processed AS (
+                SELECT sequence,
+                       json_extract(payload, '$.notification_id') AS notification_id,
+                       json_extract(payload, '$.requires_action') AS flagged
+                FROM events
+                WHERE event_type = 'NotificationProcessed' AND {range}
+             ),
+             later AS (
+                SELECT p.notification_id, p.flagged,
+                       EXISTS (SELECT 1 FROM events e
+                               WHERE e.event_type = 'NotificationActionRequired'
+                                 AND json_extract(e.payload, '$.notification_id') = p.notification_id
+                                 AND e.sequence > p.sequence) AS marked,
+                       EXISTS (SELECT 1 FROM events e
+                               WHERE e.event_type IN ('NotificationRead', 'NotificationArchived')
+                                 AND json_extract(e.payload, '$.notification_id') = p.notification_id
+                                 AND e.sequence > p.sequence) AS put_away,
+                       EXISTS (SELECT 1 FROM events e
+                               WHERE e.event_type = 'NotificationActionTaken'
+                                 AND json_extract(e.payload, '$.notification_id') = p.notification_id
+                                 AND e.sequence > p.sequence) AS acted
+                FROM processed p
+             )
+             SELECT COUNT(*),
+                    COALESCE(SUM(flagged = 1), 0),
+                    COALESCE(SUM(flagged = 1 AND put_away AND NOT acted), 0),
+                    COALESCE(SUM(flagged = 0 AND marked), 0)
+             FROM later",
+            range = EVENTS_IN_RANGE
+        );
+        Ok(
+            conn.query_row(&sql, named_params! {":from": from, ":to": to}, |row| {
+                Ok(AiStatistics {
+                    processed: row.get::<_, i64>(0)? as usize,
+                    action_required: row.get::<_, i64>(1)? as usize,
+                    dismissed: row.get::<_, i64>(2)? as usize,
+                    escalated: row.get::<_, i64>(3)? as usize,
+                })
+            })?,
+        )
+    }
+
+    /// Replies sent by rules and replies drafted by the AI
+    fn responses(conn: &Connection, from: &str, to: &str) -> DomainResult<(usize, usize)> {
+        let sql = format!(
+            "SELECT COALESCE(SUM(event_type = 'ResponseSent'
+                                 AND json_extract(payload, '$.automatic') = 1
+                                 AND COALESCE(json_extract(payload, '$.success'), 1) = 1), 0),
+                    COALESCE(SUM(event_type = 'ResponseGenerated'), 0)
+             FROM events
+             WHERE {}",
+            EVENTS_IN_RANGE
+        );
+        Ok(
+            conn.query_row(&sql, named_params! {":from": from, ":to": to}, |row| {
+                Ok((
+                    row.get::<_, i64>(0)? as usize,
+                    row.get::<_, i64>(1)? as usize,
+                ))
+            })?,
+        )
+    }
+}
+
+#[async_trait]
+impl StatisticsRepository for SqliteStatisticsRepository {
+    async fn get_statistics(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket: TimeBucket,
+    ) -> DomainResult<NotificationStatistics> {
+        let conn = self.connection.lock().await;
+        let (from_text, to_text) = (from.to_rfc3339(), to.to_rfc3339());
+
+        let mut statistics = NotificationStatistics {
+            from,
+            to,
+            bucket,
+            total: 0,
+            buckets: Vec::new(),
+            median_seconds_to_read: None,
+            median_seconds_to_action: None,
+            ai: AiStatistics::default(),
+            auto_responses: 0,
+            responses_generated: 0,
+        };
+
+        if Self::table_exists(&conn, "notifications")? {
+            statistics.buckets = Self::buckets(&conn, &from_text, &to_text, bucket)?;
+            statistics.total = statistics.buckets.iter().map(|b| b.total).sum();
+            statistics.median_seconds_to_read =
+                Self::median_seconds(&conn, "read_at", &from_text, &to_text)?;
+            statistics.median_seconds_to_action =
+                Self::median_seconds(&conn, "action_taken_at", &from_text, &to_text)?;
+        }
+        if Self::table_exists(&conn, "events")? {
+            statistics.ai = Self::ai_statistics(&conn, &from_text, &to_text)?;
+            let (auto_responses, responses_generated) =
+                Self::responses(&conn, &from_text, &to_text)?;
+            statistics.auto_responses = auto_responses;
+            statistics.responses_generated = responses_generated;
+        }
+
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        entities::{Notification, NotificationMetadata, NotificationPriority, NotificationSource},
+        events::{DomainEvent, EventEnvelope, NotificationEvent},
+        repositories::{EventStore, NotificationRepository},
+    };
+    use crate::infrastructure::repositories::{SqliteEventStore, SqliteNotificationRepository};
+    use chrono::{Duration, TimeZone};
+    use uuid::Uuid;
+
+    fn notification(source: NotificationSource, created_at: DateTime<Utc>) -> Notification {
+        let mut notification = Notification::new(
+            "Review requested".to_string(),
+            "Please have a look".to_string(),
+            NotificationPriority::Medium,
+            NotificationMetadata {
+                source,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        notification.created_at = created_at;
+        notification
+    }
+
+    async fn record(store: &SqliteEventStore, sequence: u64, event: NotificationEvent) {
+        let envelope = EventEnvelope::new(sequence, DomainEvent::Notification(event));
+        store.append(&envelope).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_statistics_over_notifications_and_events() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let notifications = SqliteNotificationRepository::new(file.path()).unwrap();
+        let events = SqliteEventStore::new(file.path()).unwrap();
+        let repo = SqliteStatisticsRepository::new(file.path()).unwrap();
+
+        let monday = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let mut read = notification(NotificationSource::Github, monday);
+        read.mark_as_read();
+        read.read_at = Some(monday + Duration::minutes(10));
+        let mut acted = notification(NotificationSource::Github, monday + Duration::hours(1));
+        acted.mark_action_taken();
+        acted.read_at = Some(acted.created_at + Duration::minutes(30));
+        acted.action_taken_at = Some(acted.created_at + Duration::hours(2));
+        let mut custom = notification(
+            NotificationSource::Custom("Sentry".to_string()),
+            monday + Duration::days(8),
+        );
+        let mut outside = notification(NotificationSource::Email, monday - Duration::days(30));
+        for notification in [&mut read, &mut acted, &mut custom, &mut outside] {
+            NotificationRepository::save(&notifications, notification)
+                .await
+                .unwrap();
+        }
+
+        // The AI flags `acted` and `custom` but not `read`; the user escalates
+        // `read` and dismisses `custom`.
+        record(
+            &events,
+            1,
+            NotificationEvent::notification_processed(read.id, false),
+        )
+        .await;
+        record(&events, 2, NotificationEvent::notification_read(read.id)).await;
+        record(
+            &events,
+            3,
+            NotificationEvent::notification_processed(acted.id, true),
+        )
+        .await;
+        record(
+            &events,
+            4,
+            NotificationEvent::notification_action_required(acted.id),
+        )
+        .await;
+        record(
+            &events,
+            5,
+            NotificationEvent::notification_processed(custom.id, true),
+        )
+        .await;
+        record(
+            &events,
+            6,
+            NotificationEvent::notification_action_required(custom.id),
+        )
+        .await;
+        record(
+            &events,
+            7,
+            NotificationEvent::notification_action_required(read.id),
+        )
+        .await;
+        record(
+            &events,
+            8,
+            NotificationEvent::notification_action_taken(acted.id),
+        )
+        .await;
+        record(
+            &events,
+            9,
+            NotificationEvent::notification_archived(custom.id),
+        )
+        .await;
+        record(
+            &events,
+            10,
+            NotificationEvent::response_sent(acted.id, true, true),
+        )
+        .await;
+        record(
+            &events,
+            11,
+            NotificationEvent::response_sent(acted.id, false, true),
+        )
+        .await;
+        let draft = NotificationEvent::response_generated(Uuid::new_v4(), "On it".to_string());
+        record(&events, 12, draft).await;
+        // Failed deliveries are not counted
+        record(
+            &events,
+            13,
+            NotificationEvent::response_sent(acted.id, true, false),
+        )
+        .await;
+
+        let stats = repo
+            .get_statistics(
+                monday - Duration::days(7),
+                Utc::now() + Duration::days(1),
+                TimeBucket::Week,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.buckets.len(), 2);
+        assert_eq!(stats.buckets[0].start, monday - Duration::hours(9));
+        assert_eq!(stats.buckets[0].by_source["Github"], 2);
+        assert_eq!(stats.buckets[0].by_status["ActionTaken"], 1);
+        assert_eq!(stats.buckets[1].by_source["Sentry"], 1);
+        assert_eq!(stats.buckets[1].by_priority["Medium"], 1);
+
+        // Read after 10 and 30 minutes
+        assert_eq!(stats.median_seconds_to_read.map(f64::round), Some(1200.0));
+        assert_eq!(stats.median_seconds_to_action.map(f64::round), Some(7200.0));
+
+        assert_eq!(
+            stats.ai,
+            AiStatistics {
+                processed: 3,
+                action_required: 2,
+                dismissed: 1,
+                escalated: 1,
+            }
+        );
+        assert_eq!(stats.auto_responses, 1);
+        assert_eq!(stats.responses_generated, 1);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::domain::{
+    entities::Tag,
+    error::{DomainError, DomainResult},
+    repositories::TagRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Stores the tag display settings, keyed by name ignoring case
+pub struct SqliteTagRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteTagRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS tags (
+                name TEXT PRIMARY KEY COLLATE NOCASE,
+                color TEXT,
+                description TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+                [],
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn map_row(row: &Row) -> rusqlite::Result<Tag> {
+        Ok(Tag {
+            name: row.get("name")?,
+            color: row.get("color")?,
+            description: row.get("description")?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("updated_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[async_trait]
+impl TagRepository for SqliteTagRepository {
+    async fn save(&self, tag: &mut Tag) -> DomainResult<()> {
+        tag.updated_at = Utc::now();
+        let conn = self.connection.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO tags (name, color, description, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                tag.name,
+                tag.color,
+                tag.description,
+                tag.created_at.to_rfc3339(),
+                tag.updated_at.to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn find_by_name(&self, name: &str) -> DomainResult<Option<Tag>> {
+        let conn = self.connection.lock().await;
+        Ok(conn
+            .query_row(
+                "SELECT * FROM tags WHERE name = ?1",
+                params![name.trim()],
+                Self::map_row,
+            )
+            .optional()?)
+    }
+
+    async fn find_all(&self) -> DomainResult<Vec<Tag>> {
+        let conn = self.connection.lock().await;
+        let mut stmt = conn.prepare("SELECT * FROM tags ORDER BY name")?;
+        let rows = stmt.query_map([], Self::map_row)?;
+
+        let mut tags = Vec::new();
+        for tag in rows {
+            tags.push(tag?);
+        }
+        Ok(tags)
+    }
+
+    async fn delete(&self, name: &str) -> DomainResult<()> {
+        let conn = self.connection.lock().await;
+        conn.execute("DELETE FROM tags WHERE name = ?1", params![name.trim()])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sqlite_tag_repository() {
+        let repo = SqliteTagRepository::new(":memory:").unwrap();
+
+        let mut release = Tag::new("release".to_string());
+        release.color = Some("#1f883d".to_string());
+        repo.save(&mut release).await.unwrap();
+        repo.save(&mut Tag::new("Bug".to_string())).await.unwrap();
+
+        let found = repo.find_by_name("RELEASE").await.unwrap().unwrap();
+        assert_eq!(found.color.as_deref(), Some("#1f883d"));
+        let names: Vec<_> = repo
+            .find_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+        assert_eq!(names, vec!["Bug", "release"]);
+
+        repo.delete("Release").await.unwrap();
+        assert!(repo.find_by_name("release").await.unwrap().is_none());
+    }
+}
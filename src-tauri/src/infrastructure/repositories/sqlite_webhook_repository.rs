@@ -0,0 +1,283 @@
+use crate::domain::{
+    entities::{WebhookDelivery, WebhookSubscription},
+    error::{DomainError, DomainResult},
+    repositories::WebhookRepository,
+};
+use crate::infrastructure::repositories::sqlite_base::SqliteRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Row};
+use std::{path::Path, sync::Arc};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqliteWebhookRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteWebhookRepository {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, DomainError> {
+        let connection = Connection::open(path).map_err(|e| {
+            DomainError::InternalError(format!("Failed to open database connection: {}", e))
+        })?;
+
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+                id TEXT PRIMARY KEY,
+                url TEXT NOT NULL,
+                event_types TEXT NOT NULL,
+                secret TEXT NOT NULL,
+                enabled BOOLEAN NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id TEXT PRIMARY KEY,
+                subscription_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                event_sequence INTEGER NOT NULL,
+                attempt INTEGER NOT NULL,
+                status_code INTEGER,
+                success BOOLEAN NOT NULL,
+                error TEXT,
+                delivered_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_subscription
+                ON webhook_deliveries (subscription_id, delivered_at);",
+            )
+            .map_err(|e| DomainError::InternalError(format!("Failed to create table: {}", e)))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn map_delivery_row(row: &Row) -> rusqlite::Result<WebhookDelivery> {
+        Ok(WebhookDelivery {
+            id: Uuid::parse_str(&row.get::<_, String>("id")?).unwrap(),
+            subscription_id: Uuid::parse_str(&row.get::<_, String>("subscription_id")?).unwrap(),
+            event_type: row.get("event_type")?,
+            event_sequence: row.get::<_, i64>("event_sequence")? as u64,
+            attempt: row.get("attempt")?,
+            status_code: row.get("status_code")?,
+            success: row.get("success")?,
+            error: row.get("error")?,
+            delivered_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("delivered_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+impl SqliteRepository<WebhookSubscription> for SqliteWebhookRepository {
+    fn table_name(&self) -> &str {
+        "webhook_subscriptions"
+    }
+
+    fn column_names(&self) -> Vec<&str> {
+        vec![
+            "id",
+            "url",
+            "event_types",
+            "secret",
+            "enabled",
+            "created_at",
+            "updated_at",
+        ]
+    }
+
+    fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.connection
+    }
+
+    fn map_row(&self, row: &Row) -> rusqlite::Result<WebhookSubscription> {
+        Ok(WebhookSubscription {
+            id: Uuid::parse_str(&row.get::<_, String>("id")?).unwrap(),
+            url: row.get("url")?,
+            event_types: serde_json::from_str(&row.get::<_, String>("event_types")?).unwrap(),
+            secret: row.get("secret")?,
+            enabled: row.get("enabled")?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("created_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>("updated_at")?)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    fn map_entity_to_params(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Vec<Box<dyn rusqlite::ToSql + Send>> {
+        vec![
+            Box::new(subscription.id.to_string()),
+            Box::new(subscription.url.clone()),
+            Box::new(serde_json::to_string(&subscription.event_types).unwrap()),
+            Box::new(subscription.secret.clone()),
+            Box::new(subscription.enabled),
+            Box::new(subscription.created_at.to_rfc3339()),
+            Box::new(subscription.updated_at.to_rfc3339()),
+        ]
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for SqliteWebhookRepository {
+    async fn save(&self, subscription: &mut WebhookSubscription) -> DomainResult<()> {
+        subscription.updated_at = Utc::now();
+        <Self as SqliteRepository<WebhookSubscription>>::save(self, subscription).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<WebhookSubscription>> {
+        <Self as SqliteRepository<WebhookSubscription>>::find_by_id(self, id).await
+    }
+
+    async fn find_all(&self) -> DomainResult<Vec<WebhookSubscription>> {
+        <Self as SqliteRepository<WebhookSubscription>>::find_all(self).await
+    }
+
+    async fn find_enabled(&self) -> DomainResult<Vec<WebhookSubscription>> {
+        let conn = self.connection().lock().await;
+        let query = format!("SELECT * FROM {} WHERE enabled = 1", self.table_name());
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map([], |row| self.map_row(row))?;
+
+        let mut subscriptions = Vec::new();
+        for subscription in rows {
+            subscriptions.push(subscription?);
+        }
+        Ok(subscriptions)
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        let conn = self.connection().lock().await;
+        conn.execute(
+            "DELETE FROM webhook_deliveries WHERE subscription_id = ?",
+            params![id.to_string()],
+        )?;
+        conn.execute(
+            "DELETE FROM webhook_subscriptions WHERE id = ?",
+            params![id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    async fn record_delivery(&self, delivery: &WebhookDelivery) -> DomainResult<()> {
+        let conn = self.connection().lock().await;
+        conn.execute(
+            "INSERT INTO webhook_deliveries (
+                id, subscription_id, event_type, event_sequence, attempt,
+                status_code, success, error, delivered_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                delivery.id.to_string(),
+                delivery.subscription_id.to_string(),
+                delivery.event_type,
+                delivery.event_sequence as i64,
+                delivery.attempt,
+                delivery.status_code,
+                delivery.success,
+                delivery.error,
+                delivery.delivered_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn find_deliveries(
+        &self,
+        subscription_id: Uuid,
+        limit: usize,
+    ) -> DomainResult<Vec<WebhookDelivery>> {
+        let conn = self.connection().lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT * FROM webhook_deliveries WHERE subscription_id = ?
+             ORDER BY delivered_at DESC, attempt DESC LIMIT ?",
+        )?;
+        let rows = stmt.query_map(
+            params![subscription_id.to_string(), limit as i64],
+            Self::map_delivery_row,
+        )?;
+
+        let mut deliveries = Vec::new();
+        for delivery in rows {
+            deliveries.push(delivery?);
+        }
+        Ok(deliveries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sqlite_webhook_repository() {
+        let repo = SqliteWebhookRepository::new(":memory:").unwrap();
+        let mut subscription = WebhookSubscription::new(
+            "http://localhost:9000/hook".to_string(),
+            vec!["NotificationCreated".to_string()],
+            "secret".to_string(),
+        );
+
+        WebhookRepository::save(&repo, &mut subscription)
+            .await
+            .unwrap();
+
+        let found = WebhookRepository::find_by_id(&repo, subscription.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.url, subscription.url);
+        assert_eq!(found.event_types, subscription.event_types);
+        assert_eq!(repo.find_enabled().await.unwrap().len(), 1);
+
+        subscription.set_enabled(false);
+        WebhookRepository::save(&repo, &mut subscription)
+            .await
+            .unwrap();
+        assert!(repo.find_enabled().await.unwrap().is_empty());
+
+        repo.record_delivery(&WebhookDelivery::new(
+            subscription.id,
+            "NotificationCreated".to_string(),
+            1,
+            1,
+            Some(500),
+            None,
+        ))
+        .await
+        .unwrap();
+        repo.record_delivery(&WebhookDelivery::new(
+            subscription.id,
+            "NotificationCreated".to_string(),
+            1,
+            2,
+            Some(200),
+            None,
+        ))
+        .await
+        .unwrap();
+
+        let deliveries = repo.find_deliveries(subscription.id, 10).await.unwrap();
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].attempt, 2);
+        assert!(deliveries[0].success);
+        assert_eq!(deliveries[1].status_code, Some(500));
+
+        WebhookRepository::delete(&repo, subscription.id)
+            .await
+            .unwrap();
+        assert!(WebhookRepository::find_by_id(&repo, subscription.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo
+            .find_deliveries(subscription.id, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
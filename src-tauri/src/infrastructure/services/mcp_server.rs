@@ -268,6 +268,7 @@ mod tests {
                     priority_level: PriorityLevel::High,
                     summary: "Test summary".to_string(),
                     suggested_actions: vec!["Action 1".to_string()],
+                    ..Default::default()
                 })
             });
 
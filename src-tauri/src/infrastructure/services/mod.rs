@@ -4,3 +4,5 @@
 
 pub mod mcp_server;
 pub mod oauth;
+pub mod response_streams;
+pub mod tauri_event_forwarder;
@@ -0,0 +1,191 @@
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::domain::services::ResponseStream;
+
+/// Tauri event name the chunks of streamed responses are emitted on.
+pub const RESPONSE_STREAM_CHANNEL: &str = "response-stream";
+
+/// What the webview receives while a response is generated. Every stream
+/// ends with exactly one `done`, `cancelled` or `failed` event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseStreamEvent {
+    Chunk { stream_id: Uuid, text: String },
+    Done { stream_id: Uuid, response: String },
+    Cancelled { stream_id: Uuid },
+    Failed { stream_id: Uuid, error: String },
+}
+
+/// Responses being streamed to the webview, so they can be cancelled
+#[derive(Debug, Default)]
+pub struct ResponseStreams {
+    running: Mutex<HashMap<Uuid, CancellationToken>>,
+}
+
+impl ResponseStreams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emits the chunks of `stream` in the background, returning the id
+    /// its events carry
+    pub fn start<R: Runtime>(
+        self: &std::sync::Arc<Self>,
+        app_handle: AppHandle<R>,
+        stream: ResponseStream,
+    ) -> Uuid {
+        let stream_id = Uuid::new_v4();
+        let token = CancellationToken::new();
+        self.running
+            .lock()
+            .unwrap()
+            .insert(stream_id, token.clone());
+
+        let streams = self.clone();
+        tauri::async_runtime::spawn(async move {
+            pump(stream_id, stream, token, |event| {
+                if let Err(e) = app_handle.emit(RESPONSE_STREAM_CHANNEL, event) {
+                    warn!("Failed to emit response chunk: {}", e);
+                }
+            })
+            .await;
+            streams.running.lock().unwrap().remove(&stream_id);
+        });
+        stream_id
+    }
+
+    /// Stops the generation, false when it already ended
+    pub fn cancel(&self, stream_id: Uuid) -> bool {
+        match self.running.lock().unwrap().remove(&stream_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Hands every chunk to `emit` until the stream ends, fails or is cancelled.
+/// Cancelling drops the stream, which stops the generation.
+async fn pump(
+    stream_id: Uuid,
+    mut stream: ResponseStream,
+    token: CancellationToken,
+    emit: impl Fn(ResponseStreamEvent),
+) {
+    let mut response = String::new();
+    loop {
+        let next = tokio::select! {
+            _ = token.cancelled() => {
+                emit(ResponseStreamEvent::Cancelled { stream_id });
+                return;
+            }
+            next = stream.next() => next,
+        };
+        match next {
+            Some(Ok(text)) => {
+                response.push_str(&text);
+                emit(ResponseStreamEvent::Chunk { stream_id, text });
+            }
+            Some(Err(e)) => {
+                emit(ResponseStreamEvent::Failed {
+                    stream_id,
+                    error: e.to_string(),
+                });
+                return;
+            }
+            None => {
+                emit(ResponseStreamEvent::Done {
+                    stream_id,
+                    response,
+                });
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::sync::Arc;
+
+    fn collect() -> (
+        Arc<Mutex<Vec<ResponseStreamEvent>>>,
+        impl Fn(ResponseStreamEvent),
+    ) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        (events, move |event| sink.lock().unwrap().push(event))
+    }
+
+    #[tokio::test]
+    async fn test_pump_emits_chunks_then_the_outcome() {
+        let stream_id = Uuid::new_v4();
+        let (events, emit) = collect();
+        let stream: ResponseStream = Box::pin(futures::stream::iter(vec![
+            Ok("Sure, ".to_string()),
+            Ok("Friday works.".to_string()),
+        ]));
+        pump(stream_id, stream, CancellationToken::new(), emit).await;
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                ResponseStreamEvent::Chunk {
+                    stream_id,
+                    text: "Sure, ".to_string()
+                },
+                ResponseStreamEvent::Chunk {
+                    stream_id,
+                    text: "Friday works.".to_string()
+                },
+                ResponseStreamEvent::Done {
+                    stream_id,
+                    response: "Sure, Friday works.".to_string()
+                },
+            ]
+        );
+
+        let (events, emit) = collect();
+        let stream: ResponseStream = Box::pin(futures::stream::iter(vec![Err(
+            DomainError::ExternalServiceError("model not found".to_string()),
+        )]));
+        pump(stream_id, stream, CancellationToken::new(), emit).await;
+        assert!(matches!(
+            events.lock().unwrap()[0],
+            ResponseStreamEvent::Failed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_a_running_generation() {
+        let streams = Arc::new(ResponseStreams::new());
+        let app = tauri::test::mock_app();
+
+        // A model that never finishes
+        let stream: ResponseStream = Box::pin(futures::stream::pending());
+        let stream_id = streams.start(app.handle().clone(), stream);
+
+        assert!(streams.cancel(stream_id));
+        assert!(!streams.cancel(stream_id));
+        assert!(!streams.cancel(Uuid::new_v4()));
+
+        let (events, emit) = collect();
+        let token = CancellationToken::new();
+        token.cancel();
+        pump(stream_id, Box::pin(futures::stream::pending()), token, emit).await;
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![ResponseStreamEvent::Cancelled { stream_id }]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::domain::{
+    error::{DomainError, DomainResult},
+    events::{EventEnvelope, EventSubscriber},
+};
+
+/// Tauri event name every domain event envelope is emitted on.
+pub const DOMAIN_EVENT_CHANNEL: &str = "domain-event";
+
+/// Forwards event bus envelopes to the webview.
+pub struct TauriEventForwarder<R: Runtime> {
+    app_handle: AppHandle<R>,
+}
+
+impl<R: Runtime> TauriEventForwarder<R> {
+    pub fn new(app_handle: AppHandle<R>) -> Self {
+        Self { app_handle }
+    }
+}
+
+#[async_trait]
+impl<R: Runtime> EventSubscriber for TauriEventForwarder<R> {
+    fn name(&self) -> &str {
+        "tauri-event-forwarder"
+    }
+
+    async fn handle_event(&self, envelope: &EventEnvelope) -> DomainResult<()> {
+        self.app_handle
+            .emit(DOMAIN_EVENT_CHANNEL, envelope)
+            .map_err(|e| DomainError::InternalError(format!("Failed to emit event: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::{EventBus, EventPublisher, NotificationEvent};
+    use std::sync::{Arc, Mutex};
+    use tauri::Listener;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_forwards_envelopes_to_webview() {
+        let app = tauri::test::mock_app();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = received.clone();
+        app.listen_any(DOMAIN_EVENT_CHANNEL, move |event| {
+            sink.lock().unwrap().push(event.payload().to_string());
+        });
+
+        let bus = EventBus::new();
+        bus.subscribe(Arc::new(TauriEventForwarder::new(app.handle().clone())));
+
+        let notification_id = Uuid::new_v4();
+        bus.publish_event(NotificationEvent::notification_read(notification_id))
+            .await
+            .unwrap();
+
+        let payloads = received.lock().unwrap();
+        assert_eq!(payloads.len(), 1);
+        let json: serde_json::Value = serde_json::from_str(&payloads[0]).unwrap();
+        assert_eq!(json["sequence"], 1);
+        assert_eq!(json["category"], "notification");
+        assert_eq!(json["event"]["type"], "NotificationRead");
+        assert_eq!(
+            json["event"]["notification_id"],
+            notification_id.to_string()
+        );
+    }
+}
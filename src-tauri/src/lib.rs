@@ -7,23 +7,77 @@ pub mod presentation;
 pub mod test_utils;
 
 use application::{use_cases::MCPServerUseCases, NotificationUseCases, ServiceConfigUseCases};
+use commands::attachments::{download_attachment, get_attachments};
+use commands::coalescing::{get_coalescing_config, save_coalescing_config};
+use commands::contacts::{delete_contact, get_contact, get_contacts, merge_contacts, save_contact};
+use commands::digests::{
+    generate_digest, get_digest, get_digest_settings, get_digests, save_digest_settings,
+};
+use commands::dnd::{get_dnd_status, save_dnd_settings, set_dnd_manual};
+use commands::escalation::{
+    delete_escalation_policy, get_escalation_policies, save_escalation_policy,
+};
+use commands::events::{get_event_log, get_events_since};
+use commands::notes::{delete_note, get_notes, save_note, search_notes};
 use commands::oauth::{
     delete_oauth_service_config, get_service_configs, handle_oauth_callback, save_oauth_config,
     start_oauth_flow,
 };
+use commands::responses::{
+    cancel_response_stream, clear_conversation_memory, get_ai_provider_calls, get_ai_router_config,
+    get_conversation_memory, save_ai_router_config, stream_response,
+};
+use commands::rules::{delete_rule, get_rules, reorder_rules, save_rule, test_rules};
+use commands::scoring::{get_scoring_config, save_scoring_config};
+use commands::statistics::get_statistics;
+use commands::tags::{delete_tag, get_tags, merge_tags, rename_tag, save_tag};
+use commands::threads::{
+    archive_thread, get_thread, get_thread_notifications, get_threads, mark_thread_read,
+};
+use commands::views::{delete_view, get_view_notifications, get_views, save_view};
+use commands::webhooks::{
+    create_webhook, delete_webhook, get_webhook_deliveries, get_webhooks, set_webhook_enabled,
+};
+use domain::repositories::{
+    ContactRepository, DigestRepository, DndRepository, EscalationPolicyRepository, EventStore,
+    NoteRepository, RuleRepository, SavedViewRepository, SettingsRepository, TagRepository,
+    WebhookRepository,
+};
 use domain::{
+    events::EventBus,
     services::{
         actions::ActionExecutor,
-        ai::{AIConfig, MCPConfig, OllamaService},
-        background::{manager::BackgroundJobManagerTrait, BackgroundJobManager},
-        DefaultNotificationService, DefaultServiceConfigService, NotificationService,
-        ServiceConfigService,
+        ai::{AIRouter, AIService, ConversationMemories, MCPConfig},
+        background::{
+            digest_job::DIGEST_CHECK_INTERVAL, dnd_release::DND_CHECK_INTERVAL,
+            escalation_check::ESCALATION_CHECK_INTERVAL, manager::BackgroundJobManagerTrait,
+            score_refresh::SCORE_REFRESH_INTERVAL, snooze_wakeup::SNOOZE_CHECK_INTERVAL,
+            BackgroundJobManager, DigestJobHandler, DndReleaseHandler, EscalationCheckHandler,
+            JobScheduler, NotificationProcessor, ScoreRefreshHandler, SnoozeWakeupHandler,
+        },
+        integrations::{manager::IntegrationManager, service_bridge::ServiceBridge},
+        webhooks::{WebhookDeliveryHandler, WebhookDispatcher, WebhookForwarder},
+        AttachmentService, ContactService, DefaultAttachmentService, DefaultContactService,
+        DefaultDigestService, DefaultNoteService, DefaultNotificationService,
+        DefaultSavedViewService, DefaultServiceConfigService, DefaultStatisticsService,
+        DefaultTagService, DefaultThreadService, DigestService, DndAlertFilter, DoNotDisturb,
+        Escalator, NoteService, NotificationCoalescer, NotificationService, PriorityScorer,
+        SavedViewService, ServiceConfigService, SnoozeUntil, StatisticsService, TagService,
+        ThreadService,
     },
     NotificationRepository, ServiceConfigRepository,
 };
-use infrastructure::repositories::{SqliteNotificationRepository, SqliteServiceConfigRepository};
+use infrastructure::repositories::{
+    FileAttachmentStore, SqliteContactRepository, SqliteConversationMemoryRepository,
+    SqliteDigestRepository, SqliteDndRepository, SqliteEscalationPolicyRepository,
+    SqliteEventStore, SqliteNoteRepository, SqliteNotificationRepository, SqliteRuleRepository,
+    SqliteSavedViewRepository, SqliteServiceConfigRepository, SqliteSettingsRepository,
+    SqliteStatisticsRepository, SqliteTagRepository, SqliteWebhookRepository,
+};
 use infrastructure::services::oauth::DefaultOAuthService;
 use infrastructure::services::oauth::OAuthService;
+use infrastructure::services::response_streams::ResponseStreams;
+use infrastructure::services::tauri_event_forwarder::TauriEventForwarder;
 use presentation::{
     controllers::{NotificationController, ServiceConfigController},
     dtos::{
@@ -35,7 +89,7 @@ use presentation::{
     ServiceConfigListResponse,
 };
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::domain::services::background::mcp_server_job::MCPServerJobBuilder;
 
@@ -205,6 +259,24 @@ async fn delete_notification(
     state.delete_notification(id).await
 }
 
+#[tauri::command(rename_all = "snake_case")]
+async fn snooze_notification(
+    state: tauri::State<'_, NotificationController>,
+    id: String,
+    until: SnoozeUntil,
+    timezone: Option<String>,
+) -> Result<NotificationResponse, NotificationError> {
+    state.snooze_notification(id, until, timezone).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn unsnooze_notification(
+    state: tauri::State<'_, NotificationController>,
+    id: String,
+) -> Result<(), NotificationError> {
+    state.unsnooze_notification(id).await
+}
+
 #[tauri::command(rename_all = "snake_case")]
 async fn mark_all_notifications_read(
     state: tauri::State<'_, NotificationController>,
@@ -225,8 +297,12 @@ async fn archive_all_read_notifications(
         source: None,
         priority: None,
         tags: None,
+        sender: None,
+        contact_id: None,
+        vip: None,
         from_date: None,
         to_date: None,
+        sort_by: None,
         page: None,
         per_page: None,
     };
@@ -253,23 +329,94 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
             .expect("Failed to create service config repository"),
     ) as Arc<dyn ServiceConfigRepository>;
 
+    let webhook_repository = Arc::new(
+        SqliteWebhookRepository::new(db_path.clone()).expect("Failed to create webhook repository"),
+    ) as Arc<dyn WebhookRepository>;
+
+    let rule_repository = Arc::new(
+        SqliteRuleRepository::new(db_path.clone()).expect("Failed to create rule repository"),
+    ) as Arc<dyn RuleRepository>;
+
+    let escalation_policy_repository = Arc::new(
+        SqliteEscalationPolicyRepository::new(db_path.clone())
+            .expect("Failed to create escalation policy repository"),
+    ) as Arc<dyn EscalationPolicyRepository>;
+
+    // Each conversation the AI replies to keeps its own memory
+    let conversation_memory_repository = Arc::new(
+        SqliteConversationMemoryRepository::new(db_path.clone())
+            .expect("Failed to create conversation memory repository"),
+    );
+
+    let dnd_repository = Arc::new(
+        SqliteDndRepository::new(db_path.clone()).expect("Failed to create DND repository"),
+    ) as Arc<dyn DndRepository>;
+
+    let digest_repository = Arc::new(
+        SqliteDigestRepository::new(db_path.clone()).expect("Failed to create digest repository"),
+    ) as Arc<dyn DigestRepository>;
+
+    let contact_repository = Arc::new(
+        SqliteContactRepository::new(db_path.clone()).expect("Failed to create contact repository"),
+    ) as Arc<dyn ContactRepository>;
+
+    let tag_repository = Arc::new(
+        SqliteTagRepository::new(db_path.clone()).expect("Failed to create tag repository"),
+    ) as Arc<dyn TagRepository>;
+
+    let note_repository = Arc::new(
+        SqliteNoteRepository::new(db_path.clone()).expect("Failed to create note repository"),
+    ) as Arc<dyn NoteRepository>;
+
+    let saved_view_repository = Arc::new(
+        SqliteSavedViewRepository::new(db_path.clone())
+            .expect("Failed to create saved view repository"),
+    ) as Arc<dyn SavedViewRepository>;
+
     let notification_repository = Arc::new(
-        SqliteNotificationRepository::new(db_path)
+        SqliteNotificationRepository::new(db_path.clone())
             .expect("Failed to create notification repository"),
     ) as Arc<dyn NotificationRepository>;
 
+    // Settings of the features without a table of their own
+    let settings_repository = Arc::new(
+        SqliteSettingsRepository::new(db_path.clone())
+            .expect("Failed to create settings repository"),
+    ) as Arc<dyn SettingsRepository>;
+
+    // Initialize event bus backed by the persisted event log
+    let event_store =
+        Arc::new(SqliteEventStore::new(db_path.clone()).expect("Failed to create event store"))
+            as Arc<dyn EventStore>;
+
+    // Statistics read both the notifications table and the event log
+    let statistics_service = Arc::new(DefaultStatisticsService::new(Arc::new(
+        SqliteStatisticsRepository::new(db_path).expect("Failed to create statistics repository"),
+    ))) as Arc<dyn StatisticsService>;
+    let event_bus = Arc::new(
+        EventBus::persistent(event_store.clone())
+            .await
+            .expect("Failed to initialize event bus"),
+    );
+
     // Initialize background job manager
-    let job_manager = Arc::new(BackgroundJobManager::new());
+    let job_manager = Arc::new(BackgroundJobManager::new().with_event_publisher(event_bus.clone()));
 
     // Initialize services
     let oauth_service = Arc::new(DefaultOAuthService::new(service_config_repository.clone()));
-    let service_config_service = Arc::new(DefaultServiceConfigService::new(
-        service_config_repository.clone(),
-    )) as Arc<dyn ServiceConfigService>;
 
     // Initialize AI service
-    let ai_config = AIConfig::default();
-    let ai_service = Arc::new(OllamaService::new(ai_config.clone()));
+    let ai_router = Arc::new(
+        AIRouter::from_settings(
+            settings_repository.clone(),
+            Arc::new(
+                ConversationMemories::default().with_repository(conversation_memory_repository),
+            ),
+        )
+        .await
+        .expect("Failed to load AI router config"),
+    );
+    let ai_service: Arc<dyn AIService> = ai_router.clone();
 
     // Initialize MCP server
     let mcp_use_cases = Arc::new(MCPServerUseCases::new(
@@ -299,12 +446,207 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize action executor
     let action_executor = Arc::new(ActionExecutor::new());
 
-    let notification_service = Arc::new(DefaultNotificationService::new(
+    let contact_service =
+        Arc::new(DefaultContactService::new(contact_repository)) as Arc<dyn ContactService>;
+
+    let note_service = Arc::new(DefaultNoteService::new(
+        note_repository.clone(),
+        notification_repository.clone(),
+    )) as Arc<dyn NoteService>;
+
+    let view_service = Arc::new(DefaultSavedViewService::new(
+        saved_view_repository,
+        notification_repository.clone(),
+    )) as Arc<dyn SavedViewService>;
+
+    let tag_service = Arc::new(DefaultTagService::new(
+        tag_repository,
+        notification_repository.clone(),
+    )) as Arc<dyn TagService>;
+
+    let scorer = Arc::new(
+        PriorityScorer::from_settings(notification_repository.clone(), settings_repository.clone())
+            .await
+            .expect("Failed to load scoring config"),
+    );
+
+    let coalescer = Arc::new(
+        NotificationCoalescer::from_settings(
+            notification_repository.clone(),
+            settings_repository.clone(),
+        )
+        .await
+        .expect("Failed to load coalescing config"),
+    );
+
+    // The bridge holds the notification service, which replies through the
+    // integrations of the bridge and forwards through the webhooks relaying
+    // forwards. Do Not Disturb decides which new notifications are held and
+    // sends its deferred auto-responses through the notification service.
+    let integration_manager =
+        Arc::new(IntegrationManager::new().with_event_publisher(event_bus.clone()));
+    let mut bridged_notification_service = None;
+    let mut dnd = None;
+    let service_bridge = Arc::new_cyclic(|service_bridge| {
+        let notification_service =
+            Arc::<DefaultNotificationService>::new_cyclic(|notification_service| {
+                let do_not_disturb = Arc::new(
+                    DoNotDisturb::new(dnd_repository)
+                        .with_event_publisher(event_bus.clone())
+                        .with_notification_service(notification_service.clone()),
+                );
+                dnd = Some(do_not_disturb.clone());
+                DefaultNotificationService::new(
+                    notification_repository.clone(),
+                    job_manager.clone(),
+                    action_executor,
+                    ai_service.clone(),
+                )
+                .with_service_bridge(service_bridge.clone())
+                .with_event_publisher(event_bus.clone())
+                .with_contacts(contact_service.clone())
+                .with_coalescer(coalescer.clone())
+                .with_scorer(scorer.clone())
+                .with_notes(note_repository)
+                .with_do_not_disturb(do_not_disturb)
+                .with_forwarder(Arc::new(WebhookForwarder::new(
+                    webhook_repository.clone(),
+                    event_bus.clone(),
+                )))
+            }) as Arc<dyn NotificationService>;
+        bridged_notification_service = Some(notification_service.clone());
+        ServiceBridge::new(integration_manager.clone(), notification_service)
+            .with_event_publisher(event_bus.clone())
+    });
+    let notification_service =
+        bridged_notification_service.expect("Notification service is built with the bridge");
+    let dnd = dnd.expect("Do Not Disturb is built with the notification service");
+
+    // Connect the integrations configured in earlier runs
+    match service_config_repository.find_enabled().await {
+        Ok(configs) => {
+            for config in configs {
+                if let Err(e) = service_bridge.initialize_service(config.clone()).await {
+                    warn!("Failed to connect {}: {}", config.name, e);
+                }
+            }
+        }
+        Err(e) => warn!("Failed to load service configs: {}", e),
+    }
+
+    let service_config_service = Arc::new(
+        DefaultServiceConfigService::new(service_config_repository.clone())
+            .with_service_bridge(service_bridge.clone()),
+    ) as Arc<dyn ServiceConfigService>;
+
+    // Attachments are downloaded through the integration they came from
+    let attachment_service = Arc::new(
+        DefaultAttachmentService::new(
+            notification_repository.clone(),
+            Arc::new(
+                FileAttachmentStore::new(app_dir.join("attachments"))
+                    .expect("Failed to create attachment cache"),
+            ),
+        )
+        .with_downloader(service_bridge.clone()),
+    ) as Arc<dyn AttachmentService>;
+
+    let thread_service = Arc::new(DefaultThreadService::new(
         notification_repository.clone(),
+        notification_service.clone(),
+    )) as Arc<dyn ThreadService>;
+
+    // Register the notification processing job handler
+    let notification_processor = Arc::new(
+        NotificationProcessor::new(
+            notification_service.clone(),
+            notification_repository.clone(),
+            event_bus.clone(),
+        )
+        .with_rule_repository(rule_repository.clone())
+        .with_do_not_disturb(dnd.clone())
+        .with_scorer(scorer.clone()),
+    );
+    job_manager
+        .register_handler(notification_processor)
+        .await
+        .expect("Failed to register notification processor");
+
+    // Deliver notification events to webhook subscriptions
+    job_manager
+        .register_handler(Arc::new(WebhookDeliveryHandler::new(
+            webhook_repository.clone(),
+        )))
+        .await
+        .expect("Failed to register webhook delivery handler");
+    event_bus.subscribe(Arc::new(WebhookDispatcher::new(
+        webhook_repository.clone(),
         job_manager.clone(),
-        action_executor,
-        ai_service,
-    )) as Arc<dyn NotificationService>;
+    )));
+
+    // Wake snoozed notifications when their snooze time has passed
+    job_manager
+        .register_handler(Arc::new(SnoozeWakeupHandler::new(
+            notification_service.clone(),
+        )))
+        .await
+        .expect("Failed to register snooze wake-up handler");
+    let job_scheduler = Arc::new(JobScheduler::new(job_manager.clone()));
+    job_scheduler
+        .schedule_every(SNOOZE_CHECK_INTERVAL, SnoozeWakeupHandler::job)
+        .await;
+
+    // Release the notifications held by Do Not Disturb once it ends
+    job_manager
+        .register_handler(Arc::new(DndReleaseHandler::new(dnd.clone())))
+        .await
+        .expect("Failed to register DND release handler");
+    job_scheduler
+        .schedule_every(DND_CHECK_INTERVAL, DndReleaseHandler::job)
+        .await;
+
+    // Generate the scheduled digests
+    let digest_service = Arc::new(
+        DefaultDigestService::new(
+            notification_repository.clone(),
+            digest_repository,
+            ai_service.clone(),
+        )
+        .with_event_publisher(event_bus.clone()),
+    ) as Arc<dyn DigestService>;
+    job_manager
+        .register_handler(Arc::new(DigestJobHandler::new(digest_service.clone())))
+        .await
+        .expect("Failed to register digest handler");
+    job_scheduler
+        .schedule_every(DIGEST_CHECK_INTERVAL, DigestJobHandler::job)
+        .await;
+
+    // Keep the scores of waiting notifications up to date as they age
+    job_manager
+        .register_handler(Arc::new(ScoreRefreshHandler::new(scorer.clone())))
+        .await
+        .expect("Failed to register score refresh handler");
+    job_scheduler
+        .schedule_every(SCORE_REFRESH_INTERVAL, ScoreRefreshHandler::job)
+        .await;
+
+    // Escalate notifications left ActionRequired for too long
+    let escalator = Arc::new(
+        Escalator::new(
+            notification_repository.clone(),
+            escalation_policy_repository.clone(),
+            notification_service.clone(),
+        )
+        .with_event_publisher(event_bus.clone()),
+    );
+    job_manager
+        .register_handler(Arc::new(EscalationCheckHandler::new(escalator)))
+        .await
+        .expect("Failed to register escalation check handler");
+    job_scheduler
+        .schedule_every(ESCALATION_CHECK_INTERVAL, EscalationCheckHandler::job)
+        .await;
 
     // Initialize use cases
     let service_config_use_cases =
@@ -313,19 +655,55 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize controllers
     let service_config_controller = ServiceConfigController::new(service_config_service);
-    let notification_controller = NotificationController::new(notification_service);
+    let notification_controller = NotificationController::new(notification_service.clone());
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
+        .setup({
+            let event_bus = event_bus.clone();
+            let dnd = dnd.clone();
+            move |app| {
+                let forwarder = Arc::new(DndAlertFilter::new(
+                    Arc::new(TauriEventForwarder::new(app.handle().clone())),
+                    dnd,
+                ));
+                // Subscribed before setup returns so no event published
+                // once the app runs is missed
+                event_bus.subscribe(forwarder);
+                Ok(())
+            }
+        })
         .manage(service_config_controller)
         .manage(notification_controller)
+        .manage(notification_service)
+        .manage(Arc::new(ResponseStreams::new()))
+        .manage(ai_service.clone())
+        .manage(ai_router)
         .manage(service_config_use_cases)
         .manage(notification_use_cases)
         .manage(mcp_use_cases)
         .manage(service_config_repository.clone() as Arc<dyn ServiceConfigRepository>)
         .manage(oauth_service as Arc<dyn OAuthService>)
+        .manage(event_bus)
+        .manage(event_store)
+        .manage(webhook_repository)
+        .manage(job_scheduler)
+        .manage(thread_service)
+        .manage(rule_repository)
+        .manage(escalation_policy_repository)
+        .manage(notification_repository)
+        .manage(dnd)
+        .manage(coalescer)
+        .manage(scorer)
+        .manage(digest_service)
+        .manage(contact_service)
+        .manage(tag_service)
+        .manage(note_service)
+        .manage(attachment_service)
+        .manage(view_service)
+        .manage(statistics_service)
         .invoke_handler(tauri::generate_handler![
             // Service Config Commands
             create_service_config,
@@ -350,8 +728,86 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
             mark_action_taken,
             archive_notification,
             delete_notification,
+            snooze_notification,
+            unsnooze_notification,
             mark_all_notifications_read,
             archive_all_read_notifications,
+            // Response Commands
+            stream_response,
+            cancel_response_stream,
+            get_conversation_memory,
+            clear_conversation_memory,
+            get_ai_provider_calls,
+            get_ai_router_config,
+            save_ai_router_config,
+            // Rule Commands
+            get_rules,
+            save_rule,
+            delete_rule,
+            reorder_rules,
+            test_rules,
+            // Escalation Commands
+            get_escalation_policies,
+            save_escalation_policy,
+            delete_escalation_policy,
+            // Do Not Disturb Commands
+            get_dnd_status,
+            save_dnd_settings,
+            set_dnd_manual,
+            // Coalescing Commands
+            get_coalescing_config,
+            save_coalescing_config,
+            // Digest Commands
+            get_digest_settings,
+            save_digest_settings,
+            generate_digest,
+            get_digests,
+            get_digest,
+            // Contact Commands
+            get_contacts,
+            get_contact,
+            save_contact,
+            delete_contact,
+            merge_contacts,
+            // Tag Commands
+            get_tags,
+            save_tag,
+            rename_tag,
+            merge_tags,
+            delete_tag,
+            // Note Commands
+            get_notes,
+            save_note,
+            delete_note,
+            search_notes,
+            // Attachment Commands
+            get_attachments,
+            download_attachment,
+            // Saved View Commands
+            get_views,
+            save_view,
+            delete_view,
+            get_view_notifications,
+            // Scoring Commands
+            get_scoring_config,
+            save_scoring_config,
+            // Statistics Commands
+            get_statistics,
+            // Thread Commands
+            get_threads,
+            get_thread,
+            get_thread_notifications,
+            archive_thread,
+            mark_thread_read,
+            // Event Commands
+            get_events_since,
+            get_event_log,
+            // Webhook Commands
+            create_webhook,
+            get_webhooks,
+            set_webhook_enabled,
+            delete_webhook,
+            get_webhook_deliveries,
         ])
         .run(tauri::generate_context!())
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
@@ -1,6 +1,6 @@
 use crate::{
     domain::entities::NotificationMetadata,
-    domain::services::NotificationService,
+    domain::services::{snooze, NotificationService, SnoozeUntil},
     presentation::dtos::{
         CreateNotificationRequest, NotificationError, NotificationFilterRequest,
         NotificationListResponse, NotificationResponse,
@@ -28,6 +28,7 @@ impl NotificationController {
             url: request.url,
             tags: request.tags,
             custom_data: request.custom_data,
+            ..Default::default()
         };
 
         let notification = self
@@ -62,11 +63,15 @@ impl NotificationController {
         &self,
         filter: Option<NotificationFilterRequest>,
     ) -> Result<NotificationListResponse, NotificationError> {
-        let mut notifications = self
-            .service
-            .get_all_notifications()
-            .await
-            .map_err(NotificationError::from)?;
+        // The first tag narrows the notifications down through the tag index
+        let first_tag = filter
+            .as_ref()
+            .and_then(|filter| filter.tags.as_ref()?.first());
+        let mut notifications = match first_tag {
+            Some(tag) => self.service.get_notifications_by_tag(tag).await,
+            None => self.service.get_all_notifications().await,
+        }
+        .map_err(NotificationError::from)?;
 
         // Apply filters if provided
         let mut total = notifications.len();
@@ -84,7 +89,32 @@ impl NotificationController {
                 notifications.retain(|n| &n.priority == priority);
             }
             if let Some(ref tags) = filter.tags {
-                notifications.retain(|n| tags.iter().all(|tag| n.metadata.tags.contains(tag)));
+                notifications.retain(|n| {
+                    tags.iter()
+                        .all(|tag| n.metadata.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+                });
+            }
+            if let Some(ref sender) = filter.sender {
+                let sender = sender.to_lowercase();
+                notifications.retain(|n| {
+                    n.sender()
+                        .is_some_and(|address| address.to_lowercase().contains(&sender))
+                        || n.metadata
+                            .contact
+                            .as_ref()
+                            .is_some_and(|contact| contact.name.to_lowercase().contains(&sender))
+                });
+            }
+            if let Some(contact_id) = filter.contact_id {
+                notifications.retain(|n| {
+                    n.metadata
+                        .contact
+                        .as_ref()
+                        .is_some_and(|contact| contact.contact_id == contact_id)
+                });
+            }
+            if let Some(vip) = filter.vip {
+                notifications.retain(|n| n.is_from_vip() == vip);
             }
             if let Some(from_date) = filter.from_date {
                 notifications.retain(|n| n.created_at >= from_date);
@@ -92,6 +122,9 @@ impl NotificationController {
             if let Some(to_date) = filter.to_date {
                 notifications.retain(|n| n.created_at <= to_date);
             }
+            if let Some(sort) = filter.sort_by {
+                sort.sort(&mut notifications);
+            }
             page = filter.page.unwrap_or(1);
             per_page = filter.per_page.unwrap_or(20);
             total = notifications.len();
@@ -178,13 +211,58 @@ impl NotificationController {
             .await
             .map_err(NotificationError::from)
     }
+
+    /// Snoozes until an absolute time or a preset resolved in `timezone`
+    /// (an IANA name, UTC when omitted)
+    pub async fn snooze_notification(
+        &self,
+        id: String,
+        until: SnoozeUntil,
+        timezone: Option<String>,
+    ) -> Result<NotificationResponse, NotificationError> {
+        let id = Uuid::parse_str(&id).map_err(|e| NotificationError {
+            code: "INVALID_ID".to_string(),
+            message: e.to_string(),
+            details: vec![],
+        })?;
+
+        let timezone = match timezone {
+            Some(name) => snooze::parse_timezone(&name).map_err(NotificationError::from)?,
+            None => chrono_tz::UTC,
+        };
+        let until = until
+            .resolve(chrono::Utc::now(), timezone)
+            .map_err(NotificationError::from)?;
+
+        self.service
+            .snooze_notification(id, until)
+            .await
+            .map_err(NotificationError::from)?;
+        self.get_notification(id.to_string()).await
+    }
+
+    pub async fn unsnooze_notification(&self, id: String) -> Result<(), NotificationError> {
+        let id = Uuid::parse_str(&id).map_err(|e| NotificationError {
+            code: "INVALID_ID".to_string(),
+            message: e.to_string(),
+            details: vec![],
+        })?;
+
+        self.service
+            .unsnooze_notification(id)
+            .await
+            .map_err(NotificationError::from)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::{
-        entities::{NotificationPriority, NotificationSource},
+        entities::{
+            NotificationPriority, NotificationSort, NotificationSource, PriorityScore, ScoreFactor,
+            ScoreFactorKind, SenderContact,
+        },
         services::MockNotificationService,
     };
     use mockall::predicate;
@@ -237,6 +315,196 @@ mod tests {
         assert!(matches!(response.priority, NotificationPriority::Medium));
     }
 
+    #[tokio::test]
+    async fn test_filters_by_sender_and_vip() {
+        let contact = SenderContact {
+            contact_id: Uuid::new_v4(),
+            name: "Grace Hopper".to_string(),
+            vip: true,
+            auto_reply: true,
+        };
+        let notification = |from: &str, contact: Option<SenderContact>| {
+            crate::domain::entities::Notification::new(
+                format!("Mail from {}", from),
+                "Content".to_string(),
+                NotificationPriority::Low,
+                NotificationMetadata {
+                    source: NotificationSource::Email,
+                    external_id: None,
+                    url: None,
+                    tags: vec![],
+                    custom_data: Some(serde_json::json!({ "from": from })),
+                    contact,
+                    ..Default::default()
+                },
+            )
+        };
+        let notifications = vec![
+            notification("grace@navy.example", Some(contact.clone())),
+            notification("alan@example.com", None),
+        ];
+
+        let mut mock_service = MockNotificationService::new();
+        mock_service
+            .expect_get_all_notifications()
+            .returning(move || Ok(notifications.clone()));
+        let controller = NotificationController::new(Arc::new(mock_service));
+
+        let filter = |sender: Option<&str>, contact_id: Option<Uuid>, vip: Option<bool>| {
+            NotificationFilterRequest {
+                source: None,
+                status: None,
+                priority: None,
+                tags: None,
+                sender: sender.map(str::to_string),
+                contact_id,
+                vip,
+                from_date: None,
+                to_date: None,
+                sort_by: None,
+                page: None,
+                per_page: None,
+            }
+        };
+        for (request, expected) in [
+            (
+                filter(Some("NAVY"), None, None),
+                "Mail from grace@navy.example",
+            ),
+            (
+                filter(Some("hopper"), None, None),
+                "Mail from grace@navy.example",
+            ),
+            (
+                filter(None, Some(contact.contact_id), None),
+                "Mail from grace@navy.example",
+            ),
+            (
+                filter(None, None, Some(false)),
+                "Mail from alan@example.com",
+            ),
+        ] {
+            let result = controller
+                .get_all_notifications(Some(request))
+                .await
+                .unwrap();
+            assert_eq!(result.total, 1);
+            assert_eq!(result.notifications[0].title, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sorts_by_score() {
+        let scored = |title: &str, score: Option<f64>| {
+            let mut notification = crate::domain::entities::Notification::new(
+                title.to_string(),
+                "Content".to_string(),
+                NotificationPriority::Medium,
+                NotificationMetadata {
+                    source: NotificationSource::Email,
+                    external_id: None,
+                    url: None,
+                    tags: vec![],
+                    custom_data: None,
+                    ..Default::default()
+                },
+            );
+            notification.metadata.score = score.map(|points| {
+                PriorityScore::new(
+                    vec![ScoreFactor {
+                        kind: ScoreFactorKind::SourcePriority,
+                        points,
+                        detail: String::new(),
+                    }],
+                    None,
+                    chrono::Utc::now(),
+                )
+            });
+            notification
+        };
+        let notifications = vec![
+            scored("Unscored", None),
+            scored("Low", Some(12.5)),
+            scored("High", Some(80.0)),
+        ];
+
+        let mut mock_service = MockNotificationService::new();
+        mock_service
+            .expect_get_all_notifications()
+            .returning(move || Ok(notifications.clone()));
+        let controller = NotificationController::new(Arc::new(mock_service));
+
+        let result = controller
+            .get_all_notifications(Some(NotificationFilterRequest {
+                source: None,
+                status: None,
+                priority: None,
+                tags: None,
+                sender: None,
+                contact_id: None,
+                vip: None,
+                from_date: None,
+                to_date: None,
+                sort_by: Some(NotificationSort::Score),
+                page: None,
+                per_page: None,
+            }))
+            .await
+            .unwrap();
+        let titles: Vec<_> = result
+            .notifications
+            .iter()
+            .map(|n| n.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["High", "Low", "Unscored"]);
+        assert_eq!(result.notifications[0].score.as_ref().unwrap().score, 80.0);
+    }
+
+    #[tokio::test]
+    async fn test_snooze_resolves_preset_before_calling_service() {
+        let mut mock_service = MockNotificationService::new();
+        let notification = crate::domain::entities::Notification::new(
+            "Test".to_string(),
+            "Content".to_string(),
+            NotificationPriority::Low,
+            NotificationMetadata {
+                source: NotificationSource::Email,
+                external_id: None,
+                url: None,
+                tags: vec![],
+                custom_data: None,
+                ..Default::default()
+            },
+        );
+        let id = notification.id;
+
+        mock_service
+            .expect_snooze_notification()
+            .withf(move |snoozed_id, until| *snoozed_id == id && *until > chrono::Utc::now())
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_service
+            .expect_get_notification()
+            .returning(move |_| Ok(notification.clone()));
+
+        let controller = NotificationController::new(Arc::new(mock_service));
+        let until = SnoozeUntil::Preset {
+            preset: crate::domain::services::SnoozePreset::Tomorrow,
+        };
+
+        let invalid_timezone = controller
+            .snooze_notification(id.to_string(), until.clone(), Some("Nowhere".to_string()))
+            .await;
+        assert!(
+            matches!(invalid_timezone, Err(NotificationError { code, .. }) if code == "VALIDATION_ERROR")
+        );
+
+        let result = controller
+            .snooze_notification(id.to_string(), until, Some("America/New_York".to_string()))
+            .await;
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     async fn test_notification_not_found() {
         let mut mock_service = MockNotificationService::new();
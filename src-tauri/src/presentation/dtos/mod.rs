@@ -9,7 +9,7 @@ pub use service_config::{
 
 pub use notification::{
     CreateNotificationRequest, NotificationError, NotificationFilterRequest,
-    NotificationListResponse, NotificationResponse, UpdateNotificationRequest,
+    NotificationListResponse, NotificationResponse, NotificationSort, UpdateNotificationRequest,
 };
 
 pub use validation::ValidationError;
@@ -1,7 +1,12 @@
-use crate::domain::entities::{NotificationPriority, NotificationSource, NotificationStatus};
+pub use crate::domain::entities::NotificationSort;
+use crate::domain::entities::{
+    EscalationState, NotificationAggregate, NotificationPriority, NotificationSource,
+    NotificationStatus, PriorityScore, RichContent, SenderContact,
+};
 use crate::presentation::middleware::ValidatedCommand;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -64,10 +69,24 @@ pub struct NotificationResponse {
     pub url: Option<String>,
     pub tags: Vec<String>,
     pub custom_data: Option<serde_json::Value>,
+    /// Key of the thread the notification belongs to
+    pub thread_key: String,
+    /// Count and items of a coalesced burst
+    pub aggregate: Option<NotificationAggregate>,
+    pub sender: Option<String>,
+    /// Contact the sender was resolved to
+    pub contact: Option<SenderContact>,
+    /// Priority score and the factors it is made of
+    pub score: Option<PriorityScore>,
+    /// Original body with its sanitized HTML rendering, `content` is its text
+    pub body: Option<RichContent>,
     pub created_at: String,
     pub updated_at: String,
     pub read_at: Option<String>,
     pub action_taken_at: Option<String>,
+    pub snoozed_until: Option<String>,
+    /// How far the notification went through its escalation policy
+    pub escalation: Option<EscalationState>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -84,8 +103,15 @@ pub struct NotificationFilterRequest {
     pub priority: Option<NotificationPriority>,
     #[validate(length(max = 10, message = "Maximum 10 tags allowed for filtering"))]
     pub tags: Option<Vec<String>>,
+    /// Part of the sender's address or contact name, ignoring case
+    pub sender: Option<String>,
+    pub contact_id: Option<Uuid>,
+    /// Only notifications from VIP contacts when true, none of them when false
+    pub vip: Option<bool>,
     pub from_date: Option<DateTime<Utc>>,
     pub to_date: Option<DateTime<Utc>>,
+    /// Order of the results, as stored when not set
+    pub sort_by: Option<NotificationSort>,
     #[validate(range(min = 1, message = "Page must be greater than 0"))]
     pub page: Option<u32>,
     #[validate(range(
@@ -164,6 +190,8 @@ impl From<crate::domain::error::DomainError> for NotificationError {
 
 impl From<crate::domain::entities::Notification> for NotificationResponse {
     fn from(notification: crate::domain::entities::Notification) -> Self {
+        let thread_key = notification.thread_key();
+        let sender = notification.sender();
         Self {
             id: notification.id.to_string(),
             title: notification.title,
@@ -175,10 +203,18 @@ impl From<crate::domain::entities::Notification> for NotificationResponse {
             url: notification.metadata.url,
             tags: notification.metadata.tags,
             custom_data: notification.metadata.custom_data,
+            thread_key,
+            aggregate: notification.metadata.aggregate,
+            sender,
+            contact: notification.metadata.contact,
+            score: notification.metadata.score,
+            body: notification.metadata.body,
             created_at: notification.created_at.to_rfc3339(),
             updated_at: notification.updated_at.to_rfc3339(),
             read_at: notification.read_at.map(|dt| dt.to_rfc3339()),
             action_taken_at: notification.action_taken_at.map(|dt| dt.to_rfc3339()),
+            snoozed_until: notification.snoozed_until.map(|dt| dt.to_rfc3339()),
+            escalation: notification.escalation,
         }
     }
 }
@@ -29,22 +29,34 @@ mockall::mock! {
         async fn get_all_notifications(&self) -> DomainResult<Vec<Notification>>;
         async fn get_notifications_by_status(&self, status: NotificationStatus) -> DomainResult<Vec<Notification>>;
         async fn get_notifications_by_source(&self, source: NotificationSource) -> DomainResult<Vec<Notification>>;
+        async fn get_notifications_by_tag(&self, tag: &str) -> DomainResult<Vec<Notification>>;
         async fn mark_as_read(&self, id: Uuid) -> DomainResult<()>;
         async fn mark_action_required(&self, id: Uuid) -> DomainResult<()>;
         async fn mark_action_taken(&self, id: Uuid) -> DomainResult<()>;
         async fn archive_notification(&self, id: Uuid) -> DomainResult<()>;
         async fn delete_notification(&self, id: Uuid) -> DomainResult<()>;
-        async fn analyze_notification_content(&self, notification: &Notification) -> DomainResult<bool>;
+        async fn snooze_notification(&self, id: Uuid, until: chrono::DateTime<chrono::Utc>) -> DomainResult<()>;
+        async fn unsnooze_notification(&self, id: Uuid) -> DomainResult<()>;
+        async fn analyze_notification_content(&self, notification: &Notification) -> DomainResult<AIAnalysis>;
         async fn generate_response(&self, notification: &Notification) -> DomainResult<String>;
         async fn execute_action(&self, notification: &Notification) -> DomainResult<()>;
+        async fn send_response(&self, notification: &Notification, response: &str) -> DomainResult<()>;
+        async fn forward_notification(&self, notification: &Notification, recipient: &str) -> DomainResult<()>;
     }
 }
 
 impl MockNotificationService {
     pub fn new_with_defaults() -> Self {
         let mut mock = Self::new();
-        mock.expect_analyze_notification_content()
-            .returning(|_| Ok(true));
+        mock.expect_analyze_notification_content().returning(|_| {
+            Ok(AIAnalysis {
+                requires_action: true,
+                priority_level: PriorityLevel::Medium,
+                summary: "Test summary".to_string(),
+                suggested_actions: vec![],
+                ..Default::default()
+            })
+        });
         mock.expect_generate_response()
             .returning(|_| Ok("Test response".to_string()));
         mock.expect_execute_action().returning(|_| Ok(()));
@@ -72,6 +84,7 @@ impl MockAIService {
                 priority_level: PriorityLevel::Medium,
                 summary: "Test summary".to_string(),
                 suggested_actions: vec!["Test action".to_string()],
+                ..Default::default()
             })
         });
         mock.expect_generate_response()
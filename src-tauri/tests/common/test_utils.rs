@@ -92,6 +92,7 @@ pub fn create_test_notification(
             url: Some("http://test.com".to_string()),
             tags: vec!["test".to_string()],
             custom_data: None,
+            ..Default::default()
         },
     )
 }
@@ -64,6 +64,7 @@ async fn setup_test_service() -> Arc<dyn NotificationService> {
             priority_level: PriorityLevel::High,
             summary: "Test summary".to_string(),
             suggested_actions: vec!["Test action".to_string()],
+            ..Default::default()
         })
     });
     mock_ai
@@ -96,6 +97,7 @@ async fn test_email_notification_workflow() {
                 "subject": "Test Subject",
                 "recipient": "test@example.com"
             })),
+            ..Default::default()
         },
     );
 
@@ -134,6 +136,7 @@ async fn test_github_notification_workflow() {
                 "pr_number": 123,
                 "action": "review_requested"
             })),
+            ..Default::default()
         },
     );
 
@@ -204,6 +207,7 @@ async fn test_jira_notification_chain() {
                 url: Some("https://jira.company.com/browse/PROJ-123".to_string()),
                 tags: vec!["jira".to_string()],
                 custom_data: data,
+                ..Default::default()
             },
         );
 
@@ -226,10 +230,13 @@ async fn test_jira_notification_chain() {
     // Verify all notifications were processed
     for id in created_ids {
         let notification = service.get_notification(id).await.unwrap();
-        assert!(service
-            .analyze_notification_content(&notification)
-            .await
-            .unwrap());
+        assert!(
+            service
+                .analyze_notification_content(&notification)
+                .await
+                .unwrap()
+                .requires_action
+        );
     }
 }
 
@@ -250,6 +257,7 @@ async fn test_notification_error_handling() {
             custom_data: Some(serde_json::json!({
                 "invalid_field": "value"
             })),
+            ..Default::default()
         },
     );
 
@@ -4,7 +4,10 @@ use autoresponse_lib::domain::{
         NotificationStatus,
     },
     error::DomainResult,
-    services::{actions::executor::ActionExecutorTrait, notification_service::NotificationService},
+    services::{
+        actions::executor::ActionExecutorTrait, ai::AIAnalysis,
+        notification_service::NotificationService,
+    },
 };
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -78,6 +81,22 @@ impl NotificationService for TestNotificationService {
         self.action_executor.execute(notification).await
     }
 
+    async fn send_response(
+        &self,
+        _notification: &Notification,
+        _response: &str,
+    ) -> DomainResult<()> {
+        unimplemented!()
+    }
+
+    async fn forward_notification(
+        &self,
+        _notification: &Notification,
+        _recipient: &str,
+    ) -> DomainResult<()> {
+        unimplemented!()
+    }
+
     async fn create_notification(
         &self,
         title: String,
@@ -110,6 +129,10 @@ impl NotificationService for TestNotificationService {
         unimplemented!("Not needed for these tests")
     }
 
+    async fn get_notifications_by_tag(&self, _tag: &str) -> DomainResult<Vec<Notification>> {
+        unimplemented!("Not needed for these tests")
+    }
+
     async fn mark_as_read(&self, _id: uuid::Uuid) -> DomainResult<()> {
         unimplemented!("Not needed for these tests")
     }
@@ -130,10 +153,22 @@ impl NotificationService for TestNotificationService {
         unimplemented!("Not needed for these tests")
     }
 
+    async fn snooze_notification(
+        &self,
+        _id: uuid::Uuid,
+        _until: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()> {
+        unimplemented!("Not needed for these tests")
+    }
+
+    async fn unsnooze_notification(&self, _id: uuid::Uuid) -> DomainResult<()> {
+        unimplemented!("Not needed for these tests")
+    }
+
     async fn analyze_notification_content(
         &self,
         _notification: &Notification,
-    ) -> DomainResult<bool> {
+    ) -> DomainResult<AIAnalysis> {
         unimplemented!("Not needed for these tests")
     }
 
@@ -156,6 +191,7 @@ async fn test_action_execution() {
             url: Some("http://test.com".to_string()),
             tags: vec!["test".to_string()],
             custom_data: None,
+            ..Default::default()
         },
     );
 
@@ -188,6 +224,7 @@ async fn test_multiple_action_sources() {
                 url: Some("http://test.com".to_string()),
                 tags: vec!["test".to_string()],
                 custom_data: None,
+                ..Default::default()
             },
         );
 
@@ -219,6 +256,7 @@ async fn test_concurrent_action_execution() {
                     url: Some("http://test.com".to_string()),
                     tags: vec!["test".to_string()],
                     custom_data: None,
+                    ..Default::default()
                 },
             )
         })
@@ -27,6 +27,7 @@ async fn test_cache_hit_and_miss() -> Result<()> {
             url: None,
             tags: vec!["test".to_string()],
             custom_data: None,
+            ..Default::default()
         },
     );
 
@@ -61,6 +62,7 @@ async fn test_cache_invalidation() -> Result<()> {
             url: None,
             tags: vec!["test".to_string()],
             custom_data: None,
+            ..Default::default()
         },
     );
 
@@ -102,6 +104,7 @@ async fn test_cache_ttl() -> Result<()> {
             url: None,
             tags: vec!["test".to_string()],
             custom_data: None,
+            ..Default::default()
         },
     );
 
@@ -138,6 +141,7 @@ async fn test_bulk_operations() -> Result<()> {
                 url: None,
                 tags: vec!["bulk".to_string()],
                 custom_data: None,
+                ..Default::default()
             },
         );
         NotificationRepository::save(&repo, &mut notification).await?;
@@ -49,6 +49,7 @@ async fn test_notification_creation_event() -> Result<(), DomainError> {
         priority: NotificationPriority::High,
         source: NotificationSource::Email,
         created_at: now,
+        held: false,
     };
 
     publisher.publish_event(event.clone()).await?;
@@ -63,6 +64,7 @@ async fn test_notification_creation_event() -> Result<(), DomainError> {
         priority,
         source,
         created_at,
+        ..
     } = &events[0]
     {
         assert_eq!(*id, notification_id);
@@ -213,6 +215,7 @@ async fn test_concurrent_event_publishing() -> Result<(), DomainError> {
                 priority: NotificationPriority::Medium,
                 source: NotificationSource::Email,
                 created_at: Utc::now(),
+                held: false,
             };
             publisher.publish_event(event).await
         });
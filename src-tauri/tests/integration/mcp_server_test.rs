@@ -48,6 +48,7 @@ impl TestContext {
                 priority_level: PriorityLevel::High,
                 summary: "Test summary".to_string(),
                 suggested_actions: vec!["Action 1".to_string()],
+                ..Default::default()
             })
         });
         mock_ai
@@ -205,6 +205,7 @@ fn create_test_notification(
             url: None,
             tags: vec![],
             custom_data: None,
+            ..Default::default()
         },
     );
 
@@ -214,6 +215,9 @@ fn create_test_notification(
         NotificationStatus::ActionTaken => notification.mark_action_taken(),
         NotificationStatus::Archived => notification.archive(),
         NotificationStatus::Deleted => notification.delete(),
+        NotificationStatus::Snoozed => {
+            notification.snooze(chrono::Utc::now() + chrono::Duration::hours(1))
+        }
         NotificationStatus::New => {}
     }
 
@@ -1,10 +1,13 @@
 use anyhow::Result;
 use autoresponse_lib::domain::{
-    entities::{Notification, NotificationPriority, NotificationSource, NotificationStatus},
+    entities::{
+        Notification, NotificationPriority, NotificationSource, NotificationStatus, SavedView,
+    },
     error::DomainError,
     events::{notification_events::NotificationEvent, publisher::EventPublisher},
-    repositories::notification_repository::NotificationRepository,
+    repositories::notification_repository::{CoalescingMerge, NotificationRepository},
     services::{
+        ai::{AIAnalysis, PriorityLevel},
         Job, JobHandler, JobType, NotificationActionType, NotificationProcessor,
         NotificationService,
     },
@@ -70,6 +73,41 @@ impl NotificationRepository for TestNotificationRepository {
             .collect())
     }
 
+    async fn find_by_thread_key(
+        &self,
+        _thread_key: &str,
+    ) -> Result<Vec<Notification>, DomainError> {
+        unimplemented!("Not needed for these tests")
+    }
+
+    async fn find_by_coalescing_key(&self, _key: &str) -> Result<Vec<Notification>, DomainError> {
+        unimplemented!("Not needed for these tests")
+    }
+
+    async fn save_coalesced(
+        &self,
+        _key: &str,
+        _merge: CoalescingMerge,
+    ) -> Result<Notification, DomainError> {
+        unimplemented!("Not needed for these tests")
+    }
+
+    async fn find_by_tag(&self, _tag: &str) -> Result<Vec<Notification>, DomainError> {
+        unimplemented!("Not needed for these tests")
+    }
+
+    async fn count_by_tag(&self) -> Result<Vec<(String, usize)>, DomainError> {
+        unimplemented!("Not needed for these tests")
+    }
+
+    async fn find_in_view(&self, _view: &SavedView) -> Result<Vec<Notification>, DomainError> {
+        unimplemented!("Not needed for these tests")
+    }
+
+    async fn count_in_view(&self, _view: &SavedView) -> Result<(usize, usize), DomainError> {
+        unimplemented!("Not needed for these tests")
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
         let mut notifications = self.notifications.lock().await;
         notifications.retain(|n| n.id != id);
@@ -98,8 +136,14 @@ impl NotificationService for TestNotificationService {
     async fn analyze_notification_content(
         &self,
         _notification: &Notification,
-    ) -> Result<bool, DomainError> {
-        Ok(true)
+    ) -> Result<AIAnalysis, DomainError> {
+        Ok(AIAnalysis {
+            requires_action: true,
+            priority_level: PriorityLevel::High,
+            summary: "Needs a reply".to_string(),
+            suggested_actions: vec![],
+            ..Default::default()
+        })
     }
 
     async fn generate_response(&self, _notification: &Notification) -> Result<String, DomainError> {
@@ -110,6 +154,22 @@ impl NotificationService for TestNotificationService {
         Ok(())
     }
 
+    async fn send_response(
+        &self,
+        _notification: &Notification,
+        _response: &str,
+    ) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn forward_notification(
+        &self,
+        _notification: &Notification,
+        _recipient: &str,
+    ) -> Result<(), DomainError> {
+        Ok(())
+    }
+
     // Implement required methods with minimal functionality
     async fn create_notification(
         &self,
@@ -145,6 +205,10 @@ impl NotificationService for TestNotificationService {
         Ok(vec![])
     }
 
+    async fn get_notifications_by_tag(&self, _tag: &str) -> Result<Vec<Notification>, DomainError> {
+        Ok(vec![])
+    }
+
     async fn mark_as_read(&self, _id: Uuid) -> Result<(), DomainError> {
         Ok(())
     }
@@ -164,6 +228,18 @@ impl NotificationService for TestNotificationService {
     async fn delete_notification(&self, _id: Uuid) -> Result<(), DomainError> {
         Ok(())
     }
+
+    async fn snooze_notification(
+        &self,
+        _id: Uuid,
+        _until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn unsnooze_notification(&self, _id: Uuid) -> Result<(), DomainError> {
+        Ok(())
+    }
 }
 
 struct TestEventPublisher {
@@ -376,6 +452,7 @@ fn create_test_notification() -> Notification {
             url: None,
             tags: vec!["test".to_string()],
             custom_data: None,
+            ..Default::default()
         },
     )
 }